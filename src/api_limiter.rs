@@ -0,0 +1,143 @@
+//! Request spacing shared by API clients that use the same key.
+//!
+//! The translator and the name scout each wait their own configured delay
+//! between requests. When `scout_api` points at the same endpoint and key as
+//! `api`, those delays don't see each other, and with novels processed in
+//! parallel (`--jobs`) scouting one novel while translating another easily
+//! trips the provider's rate limit. Clients given the same [`ApiLimiter`]
+//! reserve their request starts through it instead, so every request on one
+//! key starts at least the requesting client's delay after the previous one.
+
+use crate::config::{ApiConfig, normalize_base_url};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// When the next request may start, per endpoint and key.
+///
+/// Clones share the same state; give every client of a run a clone of one
+/// limiter so they count against the same spacing.
+#[derive(Clone, Default)]
+pub struct ApiLimiter {
+    next_at: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl ApiLimiter {
+    /// Creates a limiter with no requests made yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits until `api` may be sent another request, then holds the key for
+    /// `delay_sec` before the next one.
+    pub async fn pace(&self, api: &ApiConfig, delay_sec: f64) {
+        let slot = {
+            let mut next_at = self.next_at.lock().unwrap_or_else(|e| e.into_inner());
+            reserve_slot(
+                &mut next_at,
+                &limiter_key(api),
+                Instant::now(),
+                Duration::from_secs_f64(delay_sec.max(0.0)),
+            )
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// Whether two API configs send requests to the same endpoint with the same
+/// key, so they share one rate limit.
+pub fn same_key(a: &ApiConfig, b: &ApiConfig) -> bool {
+    limiter_key(a) == limiter_key(b)
+}
+
+fn limiter_key(api: &ApiConfig) -> String {
+    format!("{}\n{}", normalize_base_url(&api.base_url), api.key)
+}
+
+/// Reserves the key's next request slot and returns when it starts.
+fn reserve_slot(
+    next_at: &mut HashMap<String, Instant>,
+    key: &str,
+    now: Instant,
+    delay: Duration,
+) -> Instant {
+    let slot = next_at
+        .get(key)
+        .copied()
+        .filter(|t| *t > now)
+        .unwrap_or(now);
+    next_at.insert(key.to_string(), slot + delay);
+    slot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_slot_per_key() {
+        let mut next_at = HashMap::new();
+        let now = Instant::now();
+        let second = Duration::from_secs(1);
+
+        assert_eq!(reserve_slot(&mut next_at, "a", now, second), now);
+        // A client with a longer delay holds the key longer
+        assert_eq!(
+            reserve_slot(&mut next_at, "a", now, second * 3),
+            now + second
+        );
+        assert_eq!(
+            reserve_slot(&mut next_at, "a", now, second),
+            now + second * 4
+        );
+        // Other keys aren't held up
+        assert_eq!(reserve_slot(&mut next_at, "b", now, second), now);
+        // Idle keys start again immediately
+        let later = now + second * 10;
+        assert_eq!(reserve_slot(&mut next_at, "a", later, second), later);
+    }
+
+    #[tokio::test]
+    async fn test_limiters_are_independent() {
+        let api = ApiConfig::default();
+        let limiter = ApiLimiter::new();
+        limiter.pace(&api, 60.0).await;
+
+        // Clones share the key's spacing; other limiters don't
+        let shared = limiter.clone();
+        let wait = Duration::from_millis(50);
+        assert!(
+            tokio::time::timeout(wait, shared.pace(&api, 60.0))
+                .await
+                .is_err()
+        );
+        let separate = ApiLimiter::new();
+        assert!(
+            tokio::time::timeout(wait, separate.pace(&api, 60.0))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_same_key() {
+        let api = ApiConfig {
+            base_url: "https://api.example.com/v1".to_string(),
+            key: "sk-one".to_string(),
+            ..Default::default()
+        };
+        let scout = ApiConfig {
+            base_url: "https://api.example.com/v1/".to_string(),
+            model: "other-model".to_string(),
+            ..api.clone()
+        };
+        assert!(same_key(&api, &scout));
+
+        let other_key = ApiConfig {
+            key: "sk-two".to_string(),
+            ..scout.clone()
+        };
+        assert!(!same_key(&api, &other_key));
+    }
+}
@@ -0,0 +1,242 @@
+//! Compressed archive of a story's original chapter texts.
+//!
+//! With `output.archive_originals` enabled, originals are appended to
+//! `Original.zip` in the story folder instead of being written as loose files
+//! under `Original/`. Entries keep the relative paths recorded in the
+//! manifest, so readers fall back to the archive transparently.
+
+use crate::error::ArchiveError;
+use crate::manifest::content_hash;
+use crate::utils::join_relative;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Archive file name inside the story folder.
+pub const ORIGINALS_ARCHIVE: &str = "Original.zip";
+
+/// Folder holding loose originals.
+const ORIGINALS_DIR: &str = "Original";
+
+/// Reads a file recorded relative to the story folder, from disk or, failing
+/// that, from the originals archive. Returns `None` if it is in neither.
+pub fn read_story_file(
+    story_dir: &Path,
+    relative_path: &str,
+) -> Result<Option<String>, ArchiveError> {
+    let path = join_relative(story_dir, relative_path);
+    if path.is_file() {
+        return Ok(Some(fs::read_to_string(path)?));
+    }
+
+    let archive_path = story_dir.join(ORIGINALS_ARCHIVE);
+    if !archive_path.is_file() {
+        return Ok(None);
+    }
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+    let mut entry = match archive.by_name(relative_path) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(Some(content))
+}
+
+/// Adds a file to the originals archive, creating the archive if needed.
+///
+/// New entries are appended in place; an existing entry with the same path
+/// is replaced.
+pub fn append(story_dir: &Path, relative_path: &str, content: &str) -> Result<(), ArchiveError> {
+    let archive_path = story_dir.join(ORIGINALS_ARCHIVE);
+    let mut writer = if archive_path.is_file() {
+        remove_entry(&archive_path, relative_path)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&archive_path)?;
+        ZipWriter::new_append(file)?
+    } else {
+        ZipWriter::new(File::create(&archive_path)?)
+    };
+
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    writer.start_file(relative_path, options)?;
+    writer.write_all(content.as_bytes())?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Saves an original loose or into the archive, unless the stored copy's
+/// content hash already matches.
+///
+/// Leaving unchanged files alone keeps their timestamps, so sync tools and
+/// change detection don't see a re-download as an edit. Returns whether
+/// anything was written.
+pub fn save_original(
+    story_dir: &Path,
+    relative_path: &str,
+    content: &str,
+    archived: bool,
+) -> Result<bool, ArchiveError> {
+    let unchanged = read_story_file(story_dir, relative_path)?
+        .is_some_and(|existing| content_hash(&existing) == content_hash(content));
+    if unchanged {
+        return Ok(false);
+    }
+
+    if archived {
+        append(story_dir, relative_path, content)?;
+    } else {
+        fs::write(join_relative(story_dir, relative_path), content)?;
+    }
+    Ok(true)
+}
+
+/// Moves loose files from `Original/` into the archive, removing the folder
+/// once it is empty. Returns the number of files moved.
+pub fn archive_original_dir(story_dir: &Path) -> Result<usize, ArchiveError> {
+    let dir = story_dir.join(ORIGINALS_DIR);
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+
+    let mut names: Vec<String> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+
+    for name in &names {
+        let path = dir.join(name);
+        let content = fs::read_to_string(&path)?;
+        append(story_dir, &format!("{}/{}", ORIGINALS_DIR, name), &content)?;
+        fs::remove_file(path)?;
+    }
+    // Anything left behind (e.g. subfolders) keeps the folder around
+    let _ = fs::remove_dir(&dir);
+
+    Ok(names.len())
+}
+
+/// Paths of every entry in the story's originals archive (empty without
+/// one).
+pub fn entry_names(story_dir: &Path) -> Result<Vec<String>, ArchiveError> {
+    let archive_path = story_dir.join(ORIGINALS_ARCHIVE);
+    if !archive_path.is_file() {
+        return Ok(Vec::new());
+    }
+    let archive = ZipArchive::new(File::open(archive_path)?)?;
+    Ok(archive.file_names().map(str::to_string).collect())
+}
+
+/// Renames archive entries in a single rewrite, so swaps and shifts between
+/// entries never collide. Each `(old, new)` pair names entry paths.
+pub fn rename_entries(story_dir: &Path, renames: &[(String, String)]) -> Result<(), ArchiveError> {
+    if renames.is_empty() {
+        return Ok(());
+    }
+    let archive_path = story_dir.join(ORIGINALS_ARCHIVE);
+    let mut archive = ZipArchive::new(File::open(&archive_path)?)?;
+
+    let temp_path = archive_path.with_extension("zip.tmp");
+    let mut writer = ZipWriter::new(File::create(&temp_path)?);
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        match renames.iter().find(|(old, _)| old == entry.name()) {
+            Some((_, new)) => writer.raw_copy_file_rename(entry, new)?,
+            None => writer.raw_copy_file(entry)?,
+        }
+    }
+    writer.finish()?;
+    fs::rename(temp_path, archive_path)?;
+    Ok(())
+}
+
+/// Rewrites the archive without `relative_path`, if it is present.
+fn remove_entry(archive_path: &Path, relative_path: &str) -> Result<(), ArchiveError> {
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+    if archive.index_for_name(relative_path).is_none() {
+        return Ok(());
+    }
+
+    let temp_path = archive_path.with_extension("zip.tmp");
+    let mut writer = ZipWriter::new(File::create(&temp_path)?);
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        if entry.name() != relative_path {
+            writer.raw_copy_file(entry)?;
+        }
+    }
+    writer.finish()?;
+    fs::rename(temp_path, archive_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_read_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+
+        append(story_dir, "Original/1 - A.txt", "本文一").unwrap();
+        append(story_dir, "Original/2 - B.txt", "本文二").unwrap();
+        append(story_dir, "Original/1 - A.txt", "改稿").unwrap();
+
+        let read = |path| read_story_file(story_dir, path).unwrap();
+        assert_eq!(read("Original/1 - A.txt").as_deref(), Some("改稿"));
+        assert_eq!(read("Original/2 - B.txt").as_deref(), Some("本文二"));
+        assert_eq!(read("Original/3 - C.txt"), None);
+
+        // Loose files take precedence
+        fs::write(story_dir.join("note.txt"), "loose").unwrap();
+        assert_eq!(read("note.txt").as_deref(), Some("loose"));
+    }
+
+    #[test]
+    fn test_save_original_skips_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+        fs::create_dir(story_dir.join("Original")).unwrap();
+
+        assert!(save_original(story_dir, "Original/1 - A.txt", "本文", false).unwrap());
+        let path = story_dir.join("Original/1 - A.txt");
+        let written = fs::metadata(&path).unwrap().modified().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(!save_original(story_dir, "Original/1 - A.txt", "本文", false).unwrap());
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), written);
+        assert!(save_original(story_dir, "Original/1 - A.txt", "改稿", false).unwrap());
+
+        assert!(save_original(story_dir, "Original/2 - B.txt", "本文二", true).unwrap());
+        assert!(!save_original(story_dir, "Original/2 - B.txt", "本文二", true).unwrap());
+        assert!(!path.with_file_name("2 - B.txt").exists());
+    }
+
+    #[test]
+    fn test_archive_original_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+        fs::create_dir(story_dir.join("Original")).unwrap();
+        fs::write(story_dir.join("Original/1 - A.txt"), "本文一").unwrap();
+        fs::write(story_dir.join("Original/2 - B.txt"), "本文二").unwrap();
+
+        assert_eq!(archive_original_dir(story_dir).unwrap(), 2);
+        assert!(!story_dir.join("Original").exists());
+        assert_eq!(
+            read_story_file(story_dir, "Original/2 - B.txt")
+                .unwrap()
+                .as_deref(),
+            Some("本文二")
+        );
+        assert_eq!(archive_original_dir(story_dir).unwrap(), 0);
+    }
+}
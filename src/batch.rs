@@ -0,0 +1,149 @@
+//! Batch (URL list) files.
+//!
+//! Each non-empty line is `URL [--chapters RANGE] [--profile NAME]`; lines
+//! starting with `#` are comments. Ranges are `N`, `N-M`, `N-`, or `-M`.
+
+use crate::error::BatchError;
+use std::path::Path;
+
+/// One novel to process in a batch run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchJob {
+    /// 1-based line number in the batch file.
+    pub line: usize,
+    /// Novel URL.
+    pub url: String,
+    /// First chapter to process (inclusive).
+    pub start: Option<u32>,
+    /// Last chapter to process (inclusive).
+    pub end: Option<u32>,
+    /// Config profile to apply for this novel.
+    pub profile: Option<String>,
+}
+
+/// Reads and parses a batch file.
+pub fn load_batch_file(path: &Path) -> Result<Vec<BatchJob>, BatchError> {
+    let content = std::fs::read_to_string(path)?;
+    parse_batch(&content)
+}
+
+/// Parses batch file content into jobs.
+pub fn parse_batch(content: &str) -> Result<Vec<BatchJob>, BatchError> {
+    let mut jobs = Vec::new();
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = idx + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |message: String| BatchError::InvalidLine { line, message };
+        let mut tokens = trimmed.split_whitespace();
+        let url = tokens.next().unwrap_or_default().to_string();
+        if url.starts_with("--") {
+            return Err(invalid("line must start with a URL".to_string()));
+        }
+
+        let mut job = BatchJob {
+            line,
+            url,
+            start: None,
+            end: None,
+            profile: None,
+        };
+
+        while let Some(option) = tokens.next() {
+            let value = tokens
+                .next()
+                .ok_or_else(|| invalid(format!("{} needs a value", option)))?;
+            match option {
+                "--chapters" => {
+                    let (start, end) = parse_range(value).map_err(invalid)?;
+                    job.start = start;
+                    job.end = end;
+                }
+                "--profile" => job.profile = Some(value.to_string()),
+                _ => return Err(invalid(format!("unknown option '{}'", option))),
+            }
+        }
+
+        jobs.push(job);
+    }
+
+    Ok(jobs)
+}
+
+/// Parses a chapter range (`N`, `N-M`, `N-`, or `-M`).
+pub fn parse_range(value: &str) -> Result<(Option<u32>, Option<u32>), String> {
+    let parse = |s: &str| -> Result<Option<u32>, String> {
+        if s.is_empty() {
+            return Ok(None);
+        }
+        match s.parse::<u32>() {
+            Ok(n) if n >= 1 => Ok(Some(n)),
+            _ => Err(format!("invalid chapter number '{}'", s)),
+        }
+    };
+
+    let (start, end) = match value.split_once('-') {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => {
+            let n = parse(value)?;
+            (n, n)
+        }
+    };
+
+    if start.is_none() && end.is_none() {
+        return Err(format!("invalid chapter range '{}'", value));
+    }
+    if let (Some(s), Some(e)) = (start, end)
+        && s > e
+    {
+        return Err(format!("chapter range '{}' ends before it starts", value));
+    }
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch() {
+        let content = "\
+# my queue
+https://ncode.syosetu.com/n1234ab/ --chapters 1-50 --profile cheap
+
+https://kakuyomu.jp/works/1111 --chapters 10-
+https://www.pixiv.net/novel/show.php?id=1
+";
+        let jobs = parse_batch(content).unwrap();
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].line, 2);
+        assert_eq!(jobs[0].start, Some(1));
+        assert_eq!(jobs[0].end, Some(50));
+        assert_eq!(jobs[0].profile.as_deref(), Some("cheap"));
+        assert_eq!(jobs[1].start, Some(10));
+        assert_eq!(jobs[1].end, None);
+        assert_eq!(jobs[2].profile, None);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("7").unwrap(), (Some(7), Some(7)));
+        assert_eq!(parse_range("-5").unwrap(), (None, Some(5)));
+        assert!(parse_range("5-2").is_err());
+        assert!(parse_range("0-2").is_err());
+        assert!(parse_range("-").is_err());
+    }
+
+    #[test]
+    fn test_parse_batch_errors() {
+        let err = parse_batch("https://a/ --chapters").unwrap_err();
+        assert!(err.to_string().starts_with("Line 1:"));
+        assert!(parse_batch("https://a/ --model x").is_err());
+        assert!(parse_batch("--chapters 1-2").is_err());
+    }
+}
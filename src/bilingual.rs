@@ -0,0 +1,195 @@
+//! Paragraph-aligned bilingual export.
+//!
+//! Produces (source paragraph, translated paragraph) pairs from translated
+//! chunks, for building translation memories or fine-tuning data, and
+//! annotated text that interleaves each translated paragraph with its source
+//! for proofreading.
+
+use crate::config::BilingualFormat;
+use crate::translator::TranslatedChunk;
+use serde::Serialize;
+
+/// An aligned source/translation pair.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlignedPair {
+    /// Chapter number the pair came from.
+    pub chapter: u32,
+    /// Source paragraph (or whole chunk when paragraphs don't line up).
+    pub source: String,
+    /// Translated paragraph (or whole chunk when paragraphs don't line up).
+    pub translation: String,
+}
+
+/// Aligns paragraphs within each chunk.
+///
+/// When a chunk's source and translation have the same number of non-empty
+/// paragraphs they are paired one-to-one; otherwise the whole chunk becomes a
+/// single pair so nothing is misaligned. Failed chunks are skipped.
+pub fn align_paragraphs(chapter: u32, chunks: &[TranslatedChunk]) -> Vec<AlignedPair> {
+    chunks
+        .iter()
+        .filter(|c| !c.failed)
+        .flat_map(|chunk| align_chunk(chapter, chunk))
+        .collect()
+}
+
+/// Aligns the paragraphs of one chunk (see [`align_paragraphs`]).
+fn align_chunk(chapter: u32, chunk: &TranslatedChunk) -> Vec<AlignedPair> {
+    let source = paragraphs(&chunk.source);
+    let translation = paragraphs(&chunk.translated);
+
+    if source.len() == translation.len() {
+        source
+            .into_iter()
+            .zip(translation)
+            .map(|(s, t)| AlignedPair {
+                chapter,
+                source: s.to_string(),
+                translation: t.to_string(),
+            })
+            .collect()
+    } else {
+        vec![AlignedPair {
+            chapter,
+            source: source.join("\n"),
+            translation: translation.join("\n"),
+        }]
+    }
+}
+
+/// Renders translated text with each paragraph preceded by its source, every
+/// source line prefixed with `marker` (e.g. `"> "`).
+///
+/// Failed chunks are written as-is, since they already hold the source.
+pub fn format_annotated(chunks: &[TranslatedChunk], marker: &str) -> String {
+    let mut blocks = Vec::new();
+
+    for chunk in chunks {
+        if chunk.failed {
+            blocks.push(chunk.translated.clone());
+            continue;
+        }
+        for pair in align_chunk(0, chunk) {
+            let source: Vec<String> = pair
+                .source
+                .lines()
+                .map(|line| format!("{}{}", marker, line))
+                .collect();
+            blocks.push(format!("{}\n{}", source.join("\n"), pair.translation));
+        }
+    }
+
+    blocks.join("\n\n") + "\n"
+}
+
+/// Renders pairs in the requested format (empty for [`BilingualFormat::None`]).
+pub fn format_pairs(pairs: &[AlignedPair], format: BilingualFormat) -> String {
+    match format {
+        BilingualFormat::None => String::new(),
+        BilingualFormat::Tsv => pairs
+            .iter()
+            .map(|p| {
+                format!(
+                    "{}\t{}\n",
+                    escape_tsv(&p.source),
+                    escape_tsv(&p.translation)
+                )
+            })
+            .collect(),
+        BilingualFormat::Jsonl => pairs
+            .iter()
+            .filter_map(|p| serde_json::to_string(p).ok())
+            .map(|line| line + "\n")
+            .collect(),
+    }
+}
+
+/// Splits text into trimmed, non-empty paragraphs.
+fn paragraphs(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Escapes tabs and newlines so each pair stays on one TSV line.
+fn escape_tsv(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(source: &str, translated: &str) -> TranslatedChunk {
+        TranslatedChunk {
+            source: source.to_string(),
+            translated: translated.to_string(),
+            failed: false,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_align_matching_paragraphs() {
+        let chunks = vec![chunk("一行目\n\n二行目", "Line one\n\nLine two")];
+        let pairs = align_paragraphs(3, &chunks);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].source, "一行目");
+        assert_eq!(pairs[0].translation, "Line one");
+        assert_eq!(pairs[1].chapter, 3);
+    }
+
+    #[test]
+    fn test_align_mismatch_falls_back_to_chunk() {
+        let chunks = vec![chunk("一行目\n二行目", "Lines one and two")];
+        let pairs = align_paragraphs(1, &chunks);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].source, "一行目\n二行目");
+    }
+
+    #[test]
+    fn test_failed_chunks_skipped() {
+        let mut failed = chunk("一行目", "[TRANSLATION FAILED]\n一行目");
+        failed.failed = true;
+        assert!(align_paragraphs(1, &[failed]).is_empty());
+    }
+
+    #[test]
+    fn test_format_annotated() {
+        let mut failed = chunk("三行目", "[TRANSLATION FAILED]\n三行目");
+        failed.failed = true;
+        let chunks = vec![
+            chunk("一行目\n\n二行目", "Line one\n\nLine two"),
+            chunk("四行目\n五行目", "Lines four and five"),
+            failed,
+        ];
+
+        assert_eq!(
+            format_annotated(&chunks, "> "),
+            "> 一行目\nLine one\n\n\
+             > 二行目\nLine two\n\n\
+             > 四行目\n> 五行目\nLines four and five\n\n\
+             [TRANSLATION FAILED]\n三行目\n"
+        );
+    }
+
+    #[test]
+    fn test_format_tsv_and_jsonl() {
+        let pairs = vec![AlignedPair {
+            chapter: 1,
+            source: "a\tb".to_string(),
+            translation: "c".to_string(),
+        }];
+        assert_eq!(format_pairs(&pairs, BilingualFormat::Tsv), "a\\tb\tc\n");
+
+        let jsonl = format_pairs(&pairs, BilingualFormat::Jsonl);
+        assert_eq!(
+            jsonl,
+            "{\"chapter\":1,\"source\":\"a\\tb\",\"translation\":\"c\"}\n"
+        );
+        assert!(format_pairs(&pairs, BilingualFormat::None).is_empty());
+    }
+}
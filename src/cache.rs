@@ -0,0 +1,89 @@
+//! Response caches for LLM requests.
+//!
+//! Library consumers can plug a cache into [`Translator`](crate::Translator) or
+//! [`NameScout`](crate::NameScout) via their builders so identical requests
+//! (same model, system prompt, and input text) are answered without an API call.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A cache for LLM responses keyed by model, system prompt, and input text.
+pub trait ResponseCache: Send + Sync {
+    /// Returns the cached response for this request, if any.
+    fn get(&self, model: &str, system_prompt: &str, input: &str) -> Option<String>;
+
+    /// Stores a successful response for this request.
+    fn put(&self, model: &str, system_prompt: &str, input: &str, response: &str);
+}
+
+/// Simple in-memory cache, useful for tests and single-run deduplication.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<(String, String, String), String>>,
+}
+
+impl MemoryCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    /// Returns true if nothing has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get(&self, model: &str, system_prompt: &str, input: &str) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&(
+                model.to_string(),
+                system_prompt.to_string(),
+                input.to_string(),
+            ))
+            .cloned()
+    }
+
+    fn put(&self, model: &str, system_prompt: &str, input: &str, response: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                (
+                    model.to_string(),
+                    system_prompt.to_string(),
+                    input.to_string(),
+                ),
+                response.to_string(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_cache_round_trip() {
+        let cache = MemoryCache::new();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get("model", "prompt", "テキスト"), None);
+
+        cache.put("model", "prompt", "テキスト", "Text");
+        assert_eq!(
+            cache.get("model", "prompt", "テキスト"),
+            Some("Text".to_string())
+        );
+        assert_eq!(cache.len(), 1);
+
+        // Different model or prompt must not hit
+        assert_eq!(cache.get("other", "prompt", "テキスト"), None);
+        assert_eq!(cache.get("model", "other", "テキスト"), None);
+    }
+}
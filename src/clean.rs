@@ -0,0 +1,182 @@
+//! Finds stale files in a story folder.
+//!
+//! Candidates are temporary files left behind by an interrupted run and
+//! chapter files (translations, originals, bilingual and annotated exports,
+//! metadata sidecars) that no chapter in the manifest refers to any more.
+
+use crate::manifest::Manifest;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Chapter file names as written by Tsundoku ("012 - Title.txt").
+static CHAPTER_FILE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d+ - .+\.[a-z]+$").unwrap());
+
+/// Extensions of temporary files written during atomic saves and renames.
+const TEMP_EXTENSIONS: &[&str] = &["tmp", "remap"];
+
+/// Folders scanned, relative to the story folder ("" is the folder itself).
+const SCANNED_DIRS: &[&str] = &["", "Original", "Bilingual", "Annotated", "Metadata"];
+
+/// Folders of per-chapter exports named after the translated file.
+const EXPORT_DIRS: &[&str] = &["Bilingual", "Annotated", "Metadata"];
+
+/// Why a file is a clean-up candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanReason {
+    /// Left behind by an interrupted save or rename.
+    Temporary,
+    /// A chapter file no manifest record refers to.
+    Orphaned,
+}
+
+impl fmt::Display for CleanReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            CleanReason::Temporary => "temporary",
+            CleanReason::Orphaned => "orphaned",
+        };
+        f.write_str(label)
+    }
+}
+
+/// A file that can be removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanCandidate {
+    /// Path relative to the story folder.
+    pub path: String,
+    /// Why it can be removed.
+    pub reason: CleanReason,
+}
+
+/// Lists the removable files in a story folder, sorted by path.
+pub fn find_candidates(
+    story_dir: &Path,
+    manifest: &Manifest,
+) -> std::io::Result<Vec<CleanCandidate>> {
+    let chapters = &manifest.data().chapters;
+    let referenced: HashSet<&str> = chapters
+        .iter()
+        .flat_map(|c| [c.original_file.as_deref(), c.translated_file.as_deref()])
+        .flatten()
+        .collect();
+    let translated_stems: HashSet<&str> = chapters
+        .iter()
+        .filter_map(|c| c.translated_file.as_deref())
+        .filter_map(|f| Path::new(f).file_stem()?.to_str())
+        .collect();
+
+    let mut candidates = Vec::new();
+    for folder in SCANNED_DIRS {
+        for name in file_names(&story_dir.join(folder))? {
+            let path = if folder.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", folder, name)
+            };
+
+            let reason = if is_temporary(&name) {
+                CleanReason::Temporary
+            } else if !CHAPTER_FILE_REGEX.is_match(&name) {
+                continue;
+            } else if EXPORT_DIRS.contains(folder) {
+                let stem = Path::new(&name).file_stem().and_then(|s| s.to_str());
+                if stem.is_some_and(|s| translated_stems.contains(s)) {
+                    continue;
+                }
+                CleanReason::Orphaned
+            } else if referenced.contains(path.as_str()) {
+                continue;
+            } else {
+                CleanReason::Orphaned
+            };
+            candidates.push(CleanCandidate { path, reason });
+        }
+    }
+
+    candidates.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(candidates)
+}
+
+fn is_temporary(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| TEMP_EXTENSIONS.contains(&e))
+}
+
+/// Names of the files directly inside `dir` (empty if it doesn't exist).
+fn file_names(dir: &Path) -> std::io::Result<Vec<String>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    Ok(std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scrapers::ChapterInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_candidates() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+        for dir in ["Original", "Bilingual", "Annotated"] {
+            std::fs::create_dir(story_dir.join(dir)).unwrap();
+        }
+        for file in [
+            "1 - One.txt",
+            "2 - Old.txt",
+            "about.txt",
+            "manifest.json.tmp",
+            "Original/1 - One.txt",
+            "Original/2 - Old.txt",
+            "Original/1 - One.txt.remap",
+            "Bilingual/1 - One.tsv",
+            "Bilingual/2 - Old.tsv",
+            "Annotated/2 - Old.txt",
+        ] {
+            std::fs::write(story_dir.join(file), "x").unwrap();
+        }
+
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        manifest.sync_chapters(&[ChapterInfo {
+            title: "One".to_string(),
+            url: "https://example.com/1".to_string(),
+            number: 1,
+            episode_id: "1".to_string(),
+        }]);
+        manifest.mark_downloaded("1", "Original/1 - One.txt", "x");
+        manifest.mark_translated("1", "1 - One.txt", "x");
+
+        let found: Vec<(String, CleanReason)> = find_candidates(story_dir, &manifest)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.path, c.reason))
+            .collect();
+        let expected = [
+            ("2 - Old.txt", CleanReason::Orphaned),
+            ("Annotated/2 - Old.txt", CleanReason::Orphaned),
+            ("Bilingual/2 - Old.tsv", CleanReason::Orphaned),
+            ("Original/1 - One.txt.remap", CleanReason::Temporary),
+            ("Original/2 - Old.txt", CleanReason::Orphaned),
+            ("manifest.json.tmp", CleanReason::Temporary),
+        ];
+        assert_eq!(
+            found,
+            expected
+                .iter()
+                .map(|(p, r)| (p.to_string(), *r))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Subcommand handlers and the download/translate pipeline, plus helpers
+//! they share for loading configuration and finding story folders.
+
+pub(crate) mod compare;
+pub(crate) mod cookies;
+pub(crate) mod import;
+pub(crate) mod info;
+pub(crate) mod library;
+pub(crate) mod models;
+pub(crate) mod names;
+pub(crate) mod process;
+pub(crate) mod review;
+pub(crate) mod story;
+pub(crate) mod sync;
+pub(crate) mod translate;
+pub(crate) mod update;
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tsundoku::config::{Config, normalize_base_url};
+use tsundoku::console::Console;
+use tsundoku::manifest::{MANIFEST_FILENAME, Manifest};
+use tsundoku::translator::{preflight, verify_endpoint};
+use tsundoku::utils::{MAX_FILENAME_BYTES, long_path, sanitize_filename};
+
+/// Loads and validates configuration for a download/translate run.
+///
+/// Returns `None` (after telling the user what to fix) if API keys are unset.
+pub(crate) fn load_run_config(
+    console: &Console,
+    debug: bool,
+    no_name_scout: bool,
+) -> Result<Option<Config>> {
+    // Load configuration
+    console.step("Loading configuration...");
+    let mut config = Config::load().context("Failed to load configuration")?;
+    config.scraping.debug = debug;
+
+    // Check if this is first run (API key not configured)
+    if !config.api.is_configured() {
+        let config_path = Config::config_path()?;
+        console.warning(&format!(
+            "API key not configured. Please edit: {}",
+            config_path.display()
+        ));
+        console.info("Set your OpenAI-compatible API key in the config file and run again.");
+        return Ok(None);
+    }
+
+    if !no_name_scout
+        && config
+            .scout_api
+            .as_ref()
+            .filter(|api| api.is_configured())
+            .is_none()
+    {
+        let config_path = Config::config_path()?;
+        console.warning(&format!(
+            "Name scout API key not configured. Please edit: {}",
+            config_path.display()
+        ));
+        console.info("Set scout_api.key in the config file and run again.");
+        return Ok(None);
+    }
+
+    config
+        .validate_with_options(!no_name_scout)
+        .context("Invalid configuration")?;
+    console.success("Configuration loaded");
+
+    Ok(Some(config))
+}
+
+/// Checks that the translation endpoint (and the name scout's, with
+/// `include_scout`) answers, skipping any with `verify_endpoint` off, then
+/// sends the pre-flight requests if `translation.preflight` is set.
+pub(crate) async fn verify_endpoints(
+    console: &Console,
+    config: &Config,
+    include_scout: bool,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let scout_api = config
+        .scout_api
+        .as_ref()
+        .filter(|api| include_scout && api.is_configured())
+        .filter(|api| api.base_url != config.api.base_url);
+    for (label, api) in [("API", Some(&config.api)), ("Name scout API", scout_api)] {
+        let Some(api) = api.filter(|api| api.verify_endpoint) else {
+            continue;
+        };
+        let found = verify_endpoint(&client, api)
+            .await
+            .with_context(|| format!("{} endpoint check failed", label))?;
+        if found {
+            console.success(&format!("{} reachable at {}", label, api.base_url));
+        } else {
+            console.warning(&format!(
+                "{} answered HTTP 404 for /models at {}; if requests fail, check base_url, which usually ends in /v1",
+                label,
+                normalize_base_url(&api.base_url)
+            ));
+        }
+    }
+    if include_scout && config.scout_shares_api() {
+        console.warning(&format!(
+            "Name scout and translation share the API key for {}; their requests will be \
+             spaced together to stay under its rate limit",
+            config.api.base_url
+        ));
+    }
+
+    if !config.translation.preflight {
+        return Ok(());
+    }
+    let translation = &config.translation;
+    let mut checks = vec![
+        (
+            "API",
+            &config.api,
+            translation
+                .title_model
+                .as_ref()
+                .unwrap_or(&config.api.model),
+        ),
+        (
+            "API",
+            &config.api,
+            translation
+                .content_model
+                .as_ref()
+                .unwrap_or(&config.api.model),
+        ),
+    ];
+    if let Some(api) = config
+        .scout_api
+        .as_ref()
+        .filter(|api| include_scout && api.is_configured())
+    {
+        let model = config.name_scout.model.as_ref().unwrap_or(&api.model);
+        checks.push(("Name scout API", api, model));
+    }
+    let mut checked = HashSet::new();
+    for (label, api, model) in checks {
+        if !checked.insert((&api.base_url, &api.key, model)) {
+            continue;
+        }
+        console.step(&format!("Pre-flight request to {} ({})...", label, model));
+        preflight(&client, api, model).await.with_context(|| {
+            format!(
+                "Pre-flight request to {} with model {} failed",
+                label, model
+            )
+        })?;
+        console.success(&format!("{} answered with {}", label, model));
+    }
+    Ok(())
+}
+
+/// Opens a story folder's manifest, failing if the folder has none yet.
+pub(crate) fn open_existing_manifest(story_dir: &Path) -> Result<Manifest> {
+    let manifest = Manifest::open(story_dir)?;
+    if !manifest.existed() {
+        anyhow::bail!(
+            "No {} in {}; process the novel once to create it",
+            MANIFEST_FILENAME,
+            story_dir.display()
+        );
+    }
+    Ok(manifest)
+}
+
+/// Resolves a story folder from a path or a novel ID in the output directory.
+pub(crate) fn resolve_story_dir(config: &Config, novel: &str) -> Result<PathBuf> {
+    let path = Path::new(novel);
+    if path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+
+    // Folder names are "[site: id] Title" (or "[id] Title" for old folders)
+    let output_dir = expand_path(&config.paths.output_directory);
+    let new_format_suffix = format!(": {}", novel);
+    let old_format_prefix = format!("[{}]", novel);
+    let entries = std::fs::read_dir(&output_dir)
+        .with_context(|| format!("Failed to read {}", output_dir.display()))?;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let matches = name.starts_with(&old_format_prefix)
+            || name
+                .split_once(']')
+                .is_some_and(|(head, _)| head.ends_with(&new_format_suffix));
+        if matches && entry.path().is_dir() {
+            return Ok(entry.path());
+        }
+    }
+
+    anyhow::bail!(
+        "No story folder for '{}' in {}",
+        novel,
+        output_dir.display()
+    )
+}
+
+/// Finds a novel's story folder name in the output directory.
+pub(crate) fn existing_story_folder(
+    output_dir: &Path,
+    site: &str,
+    novel_id: &str,
+) -> Option<String> {
+    let new_format_prefix = format!("[{}: {}]", site, novel_id);
+    let old_format_prefix = format!("[{}]", novel_id);
+
+    std::fs::read_dir(output_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .find(|name| name.starts_with(&new_format_prefix) || name.starts_with(&old_format_prefix))
+}
+
+/// Room left after a title for the longest extension a chapter file gets
+/// (`.jsonl`, `.json`, ...).
+const EXTENSION_RESERVE_BYTES: usize = 16;
+
+/// Makes a title safe to follow `prefix` in a file or folder name, short
+/// enough to leave room for an extension.
+pub(crate) fn safe_title(config: &Config, prefix: &str, title: &str) -> String {
+    let max_bytes = MAX_FILENAME_BYTES.saturating_sub(prefix.len() + EXTENSION_RESERVE_BYTES);
+    sanitize_filename(title, config.output.filename_replacement, max_bytes)
+}
+
+/// Expands ~ in paths to the home directory.
+///
+/// On Windows the result is an extended-length path, so files deep in
+/// story folders aren't limited to MAX_PATH.
+pub(crate) fn expand_path(path: &Path) -> PathBuf {
+    let path_str = path.to_string_lossy();
+    if let Some(stripped) = path_str.strip_prefix("~/")
+        && let Some(home) = dirs::home_dir()
+    {
+        return long_path(&home.join(stripped));
+    }
+    long_path(path)
+}
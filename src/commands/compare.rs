@@ -0,0 +1,167 @@
+//! `tsundoku compare`: side-by-side translations of a chapter.
+
+use crate::CompareArgs;
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use std::path::PathBuf;
+use tsundoku::compare::{self, CompareSide};
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::name_mapping::NameMappingStore;
+use tsundoku::scrapers::{self, ChapterList, ScraperRegistry};
+use tsundoku::translator::{TranslationEvent, Translator};
+
+/// Handles `tsundoku compare`.
+pub(crate) async fn run_compare(console: &Console, args: CompareArgs) -> Result<()> {
+    console.section("Tsundoku - Model Comparison");
+
+    let config = Config::load().context("Failed to load configuration")?;
+    config
+        .validate_with_options(false)
+        .context("Invalid configuration")?;
+
+    let content_model = config
+        .translation
+        .content_model
+        .clone()
+        .unwrap_or_else(|| config.api.model.clone());
+    let model_a = args.model_a.unwrap_or_else(|| content_model.clone());
+    let model_b = args.model_b.unwrap_or(content_model);
+    if model_a == model_b && args.prompt_a.is_none() && args.prompt_b.is_none() {
+        anyhow::bail!("Both sides would be identical; pass --model-b or a prompt file");
+    }
+
+    let registry = ScraperRegistry::new(&config.scraping, Config::config_dir().ok().as_deref());
+    let scraper = registry
+        .find_for_url(&args.novel_url)
+        .ok_or_else(|| anyhow::anyhow!("No scraper found for URL: {}", args.novel_url))?;
+
+    console.step("Fetching novel information...");
+    let novel_info = scraper
+        .get_novel_info(&args.novel_url)
+        .await
+        .context("Failed to fetch novel info")?;
+    let chapter_list = scraper
+        .get_chapter_list(&novel_info.base_url)
+        .await
+        .context("Failed to fetch chapter list")?;
+
+    let (chapter_title, chapter_url) = match &chapter_list {
+        ChapterList::OneShot => (novel_info.title.clone(), novel_info.base_url.clone()),
+        ChapterList::Chapters(chapters) => {
+            let chapter = chapters
+                .iter()
+                .find(|c| c.number == args.chapter)
+                .with_context(|| {
+                    format!(
+                        "Chapter {} not found ({} chapters available)",
+                        args.chapter,
+                        chapters.len()
+                    )
+                })?;
+            (chapter.title.clone(), chapter.url.clone())
+        }
+    };
+
+    let load_prompt = |path: &Option<PathBuf>| -> Result<(String, String)> {
+        match path {
+            Some(path) => {
+                let prompt = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read prompt file: {}", path.display()))?;
+                Ok((prompt, path.display().to_string()))
+            }
+            None => Ok((
+                config.content_prompt(scraper.id(), &novel_info.novel_id),
+                "configured".to_string(),
+            )),
+        }
+    };
+    let (prompt_a, prompt_a_source) = load_prompt(&args.prompt_a)?;
+    let (prompt_b, prompt_b_source) = load_prompt(&args.prompt_b)?;
+
+    console.step(&format!("Downloading chapter: {}", chapter_title));
+    let content = scraper
+        .download_chapter(&chapter_url)
+        .await
+        .context("Failed to download chapter")?;
+    scrapers::validate_chapter(&content, config.scraping.min_chapter_chars)
+        .context("Downloaded chapter can't be compared")?;
+
+    // Use the existing name mapping (if any) so both sides see the same names
+    let mut name_mapping =
+        NameMappingStore::new(&config.names_dir()?, scraper.id(), &novel_info.novel_id)
+            .context("Failed to load name mapping")?;
+    name_mapping.set_romanize_fallback(config.translation.romanize_fallback);
+    let mapped_content = name_mapping.apply_to_text(&content);
+
+    let mut sides = Vec::new();
+    for (label, model, prompt, prompt_source) in [
+        ("A", model_a, prompt_a, prompt_a_source),
+        ("B", model_b, prompt_b, prompt_b_source),
+    ] {
+        console.step(&format!("Translating with {} ({})", model, label));
+        let mut api = config.api.clone();
+        api.model = model.clone();
+        let mut translation = config.translation.clone();
+        translation.content_model = None;
+        let translator = Translator::builder(api, translation)
+            .content_prompt(prompt)
+            .stat_block_prompt(config.prompts.stat_block_translation.clone())
+            .refusal_nudge(
+                config.prompts.refusal_nudge.clone(),
+                config.prompts.refusal_nudge_role,
+            )
+            .build();
+
+        let started = std::time::Instant::now();
+        let mut chunks: Vec<String> = Vec::new();
+        let mut events = std::pin::pin!(translator.translate_stream(&mapped_content, None));
+        while let Some(event) = events.next().await {
+            if let TranslationEvent::ChunkDone {
+                chunk_index, text, ..
+            } = event.with_context(|| format!("Translation with {} failed", model))?
+            {
+                chunks.resize(chunks.len().max(chunk_index + 1), String::new());
+                chunks[chunk_index] = text;
+            }
+        }
+        let elapsed = started.elapsed();
+        console.success(&format!(
+            "{}: {} chars in {:.1}s",
+            model,
+            chunks.iter().map(|c| c.chars().count()).sum::<usize>(),
+            elapsed.as_secs_f64()
+        ));
+
+        sides.push(CompareSide {
+            label: label.to_string(),
+            model,
+            prompt_source,
+            chunks,
+            elapsed,
+        });
+    }
+
+    // Both sides use the same chunking configuration, so chunks line up
+    let source_chunks = Translator::new(
+        config.api.clone(),
+        config.translation.clone(),
+        String::new(),
+        String::new(),
+    )
+    .split_text_into_chunks(&mapped_content);
+
+    let report =
+        compare::format_comparison(&novel_info.title, &chapter_title, &source_chunks, &sides);
+    let output = args.output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "compare-{}-{}.md",
+            novel_info.novel_id, args.chapter
+        ))
+    });
+    std::fs::write(&output, report)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    console.success(&format!("Comparison written to {}", output.display()));
+
+    Ok(())
+}
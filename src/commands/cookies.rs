@@ -0,0 +1,79 @@
+//! `tsundoku cookies`: managing saved login cookies.
+
+use crate::CookiesCommand;
+use anyhow::{Context, Result};
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::cookies;
+use tsundoku::utils::now_unix;
+
+/// Handles `tsundoku cookies ...`.
+pub(crate) fn run_cookies_command(console: &Console, command: CookiesCommand) -> Result<()> {
+    let config_dir = Config::config_dir()?;
+
+    match command {
+        CookiesCommand::List => {
+            let files = cookies::list_cookie_files(&config_dir)
+                .context("Failed to scan config directory for cookie files")?;
+            if files.is_empty() {
+                console.info(&format!(
+                    "No cookie files found in {}",
+                    config_dir.display()
+                ));
+                return Ok(());
+            }
+            for file in files {
+                console.info(&format!(
+                    "{} ({} cookies: {})",
+                    file.path.display(),
+                    file.cookie_count,
+                    file.domains.join(", ")
+                ));
+            }
+        }
+        CookiesCommand::Check { site } => {
+            let path = cookies::find_cookie_file(&config_dir, &[site.as_str()])
+                .context("Failed to scan config directory for cookie files")?
+                .with_context(|| {
+                    format!(
+                        "No cookie file containing '{}' in its name found in {}",
+                        site,
+                        config_dir.display()
+                    )
+                })?;
+            console.info(&format!("Cookie file: {}", path.display()));
+
+            let parsed = cookies::parse_netscape_cookie_file(&path)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            let now = now_unix();
+            let check = cookies::check_cookies(&parsed, &site, now);
+
+            console.info(&format!("{} cookies loaded", check.total));
+            for name in &check.expired {
+                console.warning(&format!("Cookie '{}' has expired", name));
+            }
+            for (name, expires) in &check.expiring_soon {
+                let days = expires.saturating_sub(now) / 86_400;
+                console.warning(&format!("Cookie '{}' expires in {} days", name, days));
+            }
+            for name in &check.missing_login {
+                console.error(&format!(
+                    "Login cookie '{}' is missing or expired; re-export cookies while logged in",
+                    name
+                ));
+            }
+
+            if !check.is_ok() {
+                anyhow::bail!("Cookies for {} need attention", site);
+            }
+            console.success(&format!("Cookies for {} look valid", site));
+        }
+        CookiesCommand::Import { file, site } => {
+            let target = cookies::import_cookies(&file, &config_dir, &site)
+                .with_context(|| format!("Failed to import cookies from {}", file.display()))?;
+            console.success(&format!("Imported cookies to {}", target.display()));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! `tsundoku import`: adopting translations made elsewhere.
+
+use super::review::run_name_scout;
+use super::{expand_path, load_run_config, safe_title, verify_endpoints};
+use crate::ImportArgs;
+use anyhow::{Context, Result};
+use tsundoku::archive;
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::import;
+use tsundoku::manifest::{Manifest, content_hash};
+use tsundoku::name_mapping::NameMappingStore;
+use tsundoku::name_scout::NameScout;
+use tsundoku::scrapers::ChapterInfo;
+
+/// Handles `tsundoku import`: copies raw chapters into a new story folder and
+/// writes its manifest, so `--translate-only` can pick it up.
+pub(crate) async fn run_import(console: &Console, args: ImportArgs) -> Result<()> {
+    console.section("Tsundoku - Import");
+
+    let chapters = import::scan_chapter_files(&args.dir)
+        .with_context(|| format!("Failed to read {}", args.dir.display()))?;
+    if chapters.is_empty() {
+        anyhow::bail!("No .txt chapter files in {}", args.dir.display());
+    }
+    let title = match args.title {
+        Some(title) => title,
+        None => args
+            .dir
+            .canonicalize()?
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .context("Cannot take a title from the folder name; pass --title")?,
+    };
+    // IDs end up in folder and file names
+    let novel_id = args
+        .id
+        .unwrap_or_else(|| content_hash(&title)[..12].to_string());
+    if novel_id.is_empty()
+        || !novel_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Novel ID may only contain letters, digits, '-' and '_'");
+    }
+
+    let config = Config::load().context("Failed to load configuration")?;
+    let output_dir = expand_path(&config.paths.output_directory);
+    let prefix = format!("[{}: {}]", import::IMPORT_SITE, novel_id);
+    if let Ok(entries) = std::fs::read_dir(&output_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(&prefix) {
+                anyhow::bail!("A story folder for '{}' already exists: {}", novel_id, name);
+            }
+        }
+    }
+
+    let folder_name = format!("{} {}", prefix, safe_title(&config, &prefix, &title));
+    let story_dir = output_dir.join(&folder_name);
+    let archive_originals = config.output.archive_originals;
+    std::fs::create_dir_all(&story_dir)?;
+    if !archive_originals {
+        std::fs::create_dir_all(story_dir.join("Original"))?;
+    }
+
+    let infos: Vec<ChapterInfo> = chapters
+        .iter()
+        .map(|c| ChapterInfo {
+            title: c.title.clone(),
+            url: String::new(),
+            number: c.number,
+            episode_id: c.number.to_string(),
+        })
+        .collect();
+    let padding = chapters.len().to_string().len();
+    let mut manifest = Manifest::open(&story_dir)?;
+    manifest.set_novel(import::IMPORT_SITE, &novel_id, &title);
+    manifest.sync_chapters(&infos);
+
+    let mut texts = Vec::new();
+    for chapter in &chapters {
+        let content = std::fs::read_to_string(&chapter.path)
+            .with_context(|| format!("Failed to read {}", chapter.path.display()))?;
+        let chapter_num_str = format!("{:0width$} - ", chapter.number, width = padding);
+        let filename = format!(
+            "{}{}.txt",
+            chapter_num_str,
+            safe_title(&config, &chapter_num_str, &chapter.title)
+        );
+        let original_file = format!("Original/{}", filename);
+        archive::save_original(&story_dir, &original_file, &content, archive_originals)?;
+        manifest.mark_downloaded(&chapter.number.to_string(), &original_file, &content);
+        texts.push(content);
+    }
+    manifest.save()?;
+    console.success(&format!(
+        "Imported {} chapter(s) into {}",
+        chapters.len(),
+        folder_name
+    ));
+
+    if args.scout {
+        let Some(config) = load_run_config(console, false, false)? else {
+            return Ok(());
+        };
+        verify_endpoints(console, &config, true).await?;
+        let scout_api = config
+            .scout_api_config()
+            .context("Name scout API not configured")?
+            .clone();
+        let name_scout = NameScout::builder(scout_api, config.name_scout.clone())
+            .prompt(config.prompts.name_scout.clone())
+            .refusal_nudge(
+                config.prompts.scout_refusal_nudge.clone(),
+                config.prompts.refusal_nudge_role,
+            )
+            .build();
+        let mut name_mapping =
+            NameMappingStore::new(&config.names_dir()?, import::IMPORT_SITE, &novel_id)
+                .context("Failed to initialize name mapping store")?;
+        let scout_input: Vec<(u32, &str, &str)> = chapters
+            .iter()
+            .zip(&texts)
+            .map(|(c, text)| (c.number, c.title.as_str(), text.as_str()))
+            .collect();
+        run_name_scout(console, &name_scout, &mut name_mapping, &scout_input).await?;
+        for chapter in &chapters {
+            manifest.mark_scouted(&chapter.number.to_string());
+        }
+        manifest.save()?;
+    }
+
+    console.info(&format!(
+        "Translate it with: tsundoku --translate-only {}",
+        novel_id
+    ));
+    Ok(())
+}
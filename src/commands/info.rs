@@ -0,0 +1,351 @@
+//! Read-only lookups: `info`, `diff`, `history`, and `search`.
+
+use super::{existing_story_folder, expand_path, open_existing_manifest};
+use anyhow::{Context, Result};
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::diff;
+use tsundoku::estimate;
+use tsundoku::history::{self, NovelRun, RunRecord};
+use tsundoku::library::Library;
+use tsundoku::manifest::Manifest;
+use tsundoku::metadata;
+use tsundoku::scrapers::{ChapterList, ScraperRegistry};
+use tsundoku::search::{self, Query};
+use tsundoku::stats;
+use tsundoku::translator::TokenUsage;
+use tsundoku::utils::join_relative;
+
+/// Handles `tsundoku info`.
+pub(crate) async fn run_info(console: &Console, url: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let registry = ScraperRegistry::new(&config.scraping, Config::config_dir().ok().as_deref());
+    let scraper = registry
+        .find_for_url(url)
+        .ok_or_else(|| anyhow::anyhow!("No scraper found for URL: {}", url))?;
+
+    console.step("Fetching novel information...");
+    let novel_info = scraper
+        .get_novel_info(url)
+        .await
+        .context("Failed to fetch novel info")?;
+    console.step("Fetching chapter list...");
+    let chapter_list = scraper
+        .get_chapter_list(&novel_info.base_url)
+        .await
+        .context("Failed to fetch chapter list")?;
+
+    let (chapter_count, sample_urls) = match &chapter_list {
+        ChapterList::OneShot => (1, vec![novel_info.base_url.clone()]),
+        ChapterList::Chapters(chapters) => (
+            chapters.len(),
+            stats::sample_indices(chapters.len(), INFO_SAMPLE_CHAPTERS)
+                .into_iter()
+                .map(|i| chapters[i].url.clone())
+                .collect(),
+        ),
+    };
+    console.step(&format!("Sampling {} chapter(s)...", sample_urls.len()));
+    let mut samples = Vec::new();
+    for sample_url in &sample_urls {
+        match scraper.download_chapter(sample_url).await {
+            Ok(content) => samples.push(content),
+            Err(e) => console.warning(&format!("Could not sample {}: {}", sample_url, e)),
+        }
+    }
+
+    console.section(&format!("Info: {}", novel_info.title));
+    let author = if novel_info.author.is_empty() {
+        "-"
+    } else {
+        &novel_info.author
+    };
+    let status = match (&chapter_list, novel_info.completed) {
+        (ChapterList::OneShot, _) => "One-shot",
+        (_, Some(true)) => "Complete",
+        (_, Some(false)) => "Ongoing",
+        (_, None) => "Unknown",
+    };
+    println!("{:>9}  {}", "Author", author);
+    println!(
+        "{:>9}  {} ({})",
+        "Site",
+        scraper.name(),
+        novel_info.novel_id
+    );
+    println!("{:>9}  {}", "Status", status);
+    println!("{:>9}  {}", "Chapters", chapter_count);
+    match estimate::estimate_run(&samples, chapter_count, &config) {
+        Some(projected) => {
+            // Exact when every chapter was sampled
+            let approx = if samples.len() < chapter_count {
+                "~"
+            } else {
+                ""
+            };
+            println!(
+                "{:>9}  {}{} chars (from {} sampled chapter(s))",
+                "Length",
+                approx,
+                projected.chars,
+                samples.len()
+            );
+            println!(
+                "{:>9}  ~{} chunks, {} requests",
+                "Work", projected.chunks, projected.requests
+            );
+            println!(
+                "{:>9}  ~{} prompt, ~{} completion",
+                "Tokens", projected.input_tokens, projected.output_tokens
+            );
+            if let Some(cost) = projected.cost {
+                println!("{:>9}  ~{:.2}", "Cost", cost);
+            }
+            println!(
+                "{:>9}  ~{}",
+                "Time",
+                history::format_duration(projected.duration_sec)
+            );
+        }
+        None => println!("{:>9}  -", "Length"),
+    }
+    if !novel_info.tags.is_empty() {
+        println!("{:>9}  {}", "Tags", novel_info.tags.join(", "));
+    }
+    if !novel_info.synopsis.is_empty() {
+        println!();
+        println!("{}", novel_info.synopsis);
+    }
+    Ok(())
+}
+
+/// Chapters downloaded to estimate a novel's length in `tsundoku info`.
+const INFO_SAMPLE_CHAPTERS: usize = 3;
+
+/// Handles `tsundoku diff`: fetches the chapter list and reports how it
+/// differs from the manifest, without downloading or saving anything.
+pub(crate) async fn run_diff(console: &Console, url: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let registry = ScraperRegistry::new(&config.scraping, Config::config_dir().ok().as_deref());
+    let scraper = registry
+        .find_for_url(url)
+        .ok_or_else(|| anyhow::anyhow!("No scraper found for URL: {}", url))?;
+
+    console.step("Fetching novel information...");
+    let novel_info = scraper
+        .get_novel_info(url)
+        .await
+        .context("Failed to fetch novel info")?;
+    let output_dir = expand_path(&config.paths.output_directory);
+    let folder = existing_story_folder(&output_dir, scraper.id(), &novel_info.novel_id)
+        .with_context(|| {
+            format!(
+                "No story folder for {} in {}; process the novel once to create it",
+                novel_info.novel_id,
+                output_dir.display()
+            )
+        })?;
+    let manifest = open_existing_manifest(&output_dir.join(&folder))?;
+    console.section(&format!("Diff: {}", manifest.data().title));
+
+    console.step("Fetching chapter list...");
+    let chapter_list = scraper
+        .get_chapter_list(&novel_info.base_url)
+        .await
+        .context("Failed to fetch chapter list")?;
+    let ChapterList::Chapters(chapters) = &chapter_list else {
+        console.success("One-shot story; nothing to compare");
+        return Ok(());
+    };
+
+    let diff = diff::diff_chapters(&manifest.data().chapters, chapters);
+    if diff.is_empty() {
+        console.success(&format!(
+            "Up to date: {} chapters, as recorded",
+            chapters.len()
+        ));
+        return Ok(());
+    }
+
+    if !diff.added.is_empty() {
+        console.info(&format!("{} new chapter(s):", diff.added.len()));
+        for chapter in &diff.added {
+            println!("  + {}: {}", chapter.number, chapter.title);
+        }
+    }
+    if !diff.removed.is_empty() {
+        console.warning(&format!(
+            "{} chapter(s) no longer listed:",
+            diff.removed.len()
+        ));
+        for record in &diff.removed {
+            println!("  - {}: {}", record.number, record.title);
+        }
+    }
+    if !diff.retitled.is_empty() {
+        console.info(&format!("{} chapter(s) retitled:", diff.retitled.len()));
+        for retitled in &diff.retitled {
+            println!(
+                "  ~ {}: {} -> {}",
+                retitled.number, retitled.old_title, retitled.new_title
+            );
+        }
+    }
+    if !diff.renumbered.is_empty() {
+        console.info(&format!("{} chapter(s) renumbered:", diff.renumbered.len()));
+        for r in &diff.renumbered {
+            println!("  {} -> {}: {}", r.old_number, r.new_number, r.title);
+        }
+    }
+    Ok(())
+}
+
+/// Handles `tsundoku history`.
+pub(crate) fn run_history(console: &Console, novel: Option<&str>, limit: usize) -> Result<()> {
+    let path = Config::history_path()?;
+    let runs =
+        history::load(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let query = novel.map(Query::new);
+    let matches = |n: &NovelRun| {
+        query
+            .as_ref()
+            .is_none_or(|q| q.matches(&n.title) || q.matches(&n.novel_id) || q.matches(&n.source))
+    };
+    let runs: Vec<(&RunRecord, Vec<&NovelRun>)> = runs
+        .iter()
+        .map(|run| (run, run.novels.iter().filter(|n| matches(n)).collect()))
+        .filter(|(_, novels): &(_, Vec<_>)| query.is_none() || !novels.is_empty())
+        .collect();
+    if runs.is_empty() {
+        console.info(&format!("No runs recorded in {}", path.display()));
+        return Ok(());
+    }
+    console.section("Run History");
+
+    let skip = if limit == 0 {
+        0
+    } else {
+        runs.len().saturating_sub(limit)
+    };
+    for (run, novels) in &runs[skip..] {
+        let status = if run.error.is_some() { "failed" } else { "ok" };
+        println!(
+            "{}  {:>7}  {:<6}  tsundoku {}",
+            run.started_at,
+            history::format_duration(run.duration_sec),
+            status,
+            run.args.join(" ")
+        );
+        for novel in novels {
+            let name = if novel.title.is_empty() {
+                &novel.source
+            } else {
+                &novel.title
+            };
+            let mut line = format!(
+                "    {}: {} downloaded, {} translated, {} failed, {} tokens, {}",
+                name,
+                novel.chapters_downloaded,
+                novel.chapters_translated,
+                novel.chapters_failed,
+                novel.usage.total_tokens,
+                history::format_duration(novel.duration_sec)
+            );
+            if let Some(error) = &novel.error {
+                line.push_str(&format!(" ({})", error));
+            }
+            println!("{}", line);
+        }
+    }
+
+    let mut usage = TokenUsage::default();
+    let (mut translated, mut failed) = (0, 0);
+    for novel in runs.iter().flat_map(|(_, novels)| novels) {
+        usage.add(novel.usage);
+        translated += novel.chapters_translated;
+        failed += novel.chapters_failed;
+    }
+    console.info(&format!(
+        "{} run(s) ({} shown): {} chapter(s) translated, {} failed, {} tokens ({} prompt, {} completion)",
+        runs.len(),
+        runs.len() - skip,
+        translated,
+        failed,
+        usage.total_tokens,
+        usage.prompt_tokens,
+        usage.completion_tokens
+    ));
+    Ok(())
+}
+
+/// Handles `tsundoku search`.
+pub(crate) fn run_search(console: &Console, text: &str, full_text: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let library = Library::open(&Config::library_path()?)?;
+    let output_dir = expand_path(&config.paths.output_directory);
+    if text.trim().is_empty() {
+        anyhow::bail!("Search query is empty");
+    }
+    let query = Query::new(text);
+    console.section(&format!("Search: {}", text.trim()));
+
+    let mut novel_hits = 0;
+    let mut chapter_hits = 0;
+    for entry in library.entries() {
+        let folder = existing_story_folder(&output_dir, &entry.site, &entry.novel_id);
+        let translated_title = folder.as_deref().and_then(search::folder_title);
+        if query.matches(&entry.title) || translated_title.is_some_and(|t| query.matches(t)) {
+            novel_hits += 1;
+            let title = match translated_title {
+                Some(translated) if translated != entry.title => {
+                    format!("{} / {}", entry.title, translated)
+                }
+                _ => entry.title.clone(),
+            };
+            println!("[{}: {}] {}", entry.site, entry.novel_id, title);
+            match &folder {
+                Some(folder) => println!("    {}", output_dir.join(folder).display()),
+                None => println!("    {} (not downloaded)", entry.url),
+            }
+        }
+
+        let Some(folder) = folder.filter(|_| full_text) else {
+            continue;
+        };
+        let story_dir = output_dir.join(folder);
+        let manifest = Manifest::open(&story_dir)?;
+        if !manifest.existed() {
+            continue;
+        }
+        for record in manifest.data().chapters.iter().filter(|c| c.translated) {
+            let Some(relative_path) = &record.translated_file else {
+                continue;
+            };
+            let path = join_relative(&story_dir, relative_path);
+            let Ok(chapter_text) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let (_, body) = metadata::split_front_matter(&chapter_text);
+            let found = query.find_in_text(body);
+            if found.is_empty() {
+                continue;
+            }
+            chapter_hits += 1;
+            println!("{}", path.display());
+            for hit in &found {
+                println!("    {}: {}", hit.line, hit.snippet);
+            }
+        }
+    }
+
+    let summary = if full_text {
+        format!(
+            "{} novel(s) and {} chapter(s) matched",
+            novel_hits, chapter_hits
+        )
+    } else {
+        format!("{} novel(s) matched", novel_hits)
+    };
+    console.info(&summary);
+    Ok(())
+}
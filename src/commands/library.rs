@@ -0,0 +1,275 @@
+//! `tsundoku library`, `tsundoku read`, and `tsundoku chapter-profile`.
+
+use super::{existing_story_folder, expand_path, open_existing_manifest, resolve_story_dir};
+use crate::LibraryCommand;
+use anyhow::{Context, Result};
+use tsundoku::batch;
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::library::{Library, LibraryEntry};
+use tsundoku::manifest::Manifest;
+use tsundoku::scrapers::{
+    BookmarkedNovel, KakuyomuScraper, Scraper, ScraperRegistry, SyosetuScraper, site_cookie_jar,
+};
+use tsundoku::utils::now_unix;
+
+/// Handles `tsundoku library ...`.
+pub(crate) async fn run_library_command(console: &Console, command: LibraryCommand) -> Result<()> {
+    let library_path = Config::library_path()?;
+    let mut library = Library::open(&library_path)?;
+
+    match command {
+        LibraryCommand::List => {
+            if library.is_empty() {
+                console.info(&format!("Library is empty ({})", library_path.display()));
+                return Ok(());
+            }
+            // Unread counts need the output directory; list without them if
+            // the config can't be loaded
+            let output_dir = Config::load()
+                .ok()
+                .map(|config| expand_path(&config.paths.output_directory));
+            for entry in library.entries() {
+                let marker = if entry.tracked { "*" } else { " " };
+                let mut schedule = Vec::new();
+                if entry.priority != 0 {
+                    schedule.push(format!("priority {}", entry.priority));
+                }
+                if let Some(hours) = entry.check_interval_hours {
+                    schedule.push(format!("every {}h", hours));
+                }
+                let schedule = if schedule.is_empty() {
+                    String::new()
+                } else {
+                    format!("  ({})", schedule.join(", "))
+                };
+                let unread = output_dir
+                    .as_deref()
+                    .and_then(|dir| {
+                        let folder = existing_story_folder(dir, &entry.site, &entry.novel_id)?;
+                        Manifest::open(&dir.join(folder)).ok()
+                    })
+                    .map(|manifest| entry.unread_count(&manifest.data().chapters))
+                    .filter(|&count| count > 0)
+                    .map_or_else(String::new, |count| format!("  [{} unread]", count));
+                println!(
+                    "{} [{}: {}] {}  {}{}{}",
+                    marker, entry.site, entry.novel_id, entry.title, entry.url, schedule, unread
+                );
+            }
+            console.info(&format!(
+                "{} novel(s), {} tracked (*)",
+                library.len(),
+                library.tracked().count()
+            ));
+        }
+        LibraryCommand::Add { url } => {
+            let config = Config::load().context("Failed to load configuration")?;
+            let registry =
+                ScraperRegistry::new(&config.scraping, Config::config_dir().ok().as_deref());
+            let scraper = registry
+                .find_for_url(&url)
+                .ok_or_else(|| anyhow::anyhow!("No scraper found for URL: {}", url))?;
+            let info = scraper
+                .get_novel_info(&url)
+                .await
+                .context("Failed to fetch novel info")?;
+
+            library.upsert(LibraryEntry {
+                site: scraper.id().to_string(),
+                novel_id: info.novel_id,
+                url,
+                title: info.title.clone(),
+                tracked: true,
+                added_unix: now_unix(),
+                ..Default::default()
+            });
+            library.save()?;
+            console.success(&format!("Tracking: {}", info.title));
+        }
+        LibraryCommand::Untrack { site, novel_id } => {
+            let entry = library
+                .find_mut(&site, &novel_id)
+                .ok_or_else(|| anyhow::anyhow!("Not in library: {}: {}", site, novel_id))?;
+            entry.tracked = false;
+            let title = entry.title.clone();
+            library.save()?;
+            console.success(&format!("No longer tracking: {}", title));
+        }
+        LibraryCommand::Schedule {
+            site,
+            novel_id,
+            priority,
+            interval,
+        } => {
+            let entry = library
+                .find_mut(&site, &novel_id)
+                .ok_or_else(|| anyhow::anyhow!("Not in library: {}: {}", site, novel_id))?;
+            if let Some(priority) = priority {
+                entry.priority = priority;
+            }
+            if let Some(hours) = interval {
+                entry.check_interval_hours = (hours > 0).then_some(hours);
+            }
+            let summary =
+                format!(
+                    "{}: priority {}, {}",
+                    entry.title,
+                    entry.priority,
+                    entry.check_interval_hours.map_or(
+                        "checked every run".to_string(),
+                        |h| format!("checked every {}h", h)
+                    )
+                );
+            library.save()?;
+            console.success(&summary);
+        }
+        LibraryCommand::ImportSyosetu => {
+            let config = Config::load().context("Failed to load configuration")?;
+            console.step("Fetching Syosetu bookmarks...");
+            let jar = site_cookie_jar(
+                Config::config_dir().ok().as_deref(),
+                "syosetu",
+                config.scraping.debug,
+            );
+            let scraper = SyosetuScraper::new(config.scraping.clone(), jar);
+            let bookmarks = scraper
+                .fetch_bookmarks()
+                .await
+                .context("Failed to fetch Syosetu bookmarks")?;
+            import_bookmarks(console, &mut library, scraper.id(), bookmarks)?;
+        }
+        LibraryCommand::ImportKakuyomu => {
+            let config = Config::load().context("Failed to load configuration")?;
+            console.step("Fetching Kakuyomu follows...");
+            let jar = site_cookie_jar(
+                Config::config_dir().ok().as_deref(),
+                "kakuyomu",
+                config.scraping.debug,
+            );
+            let scraper = KakuyomuScraper::new(config.scraping.clone(), jar);
+            let works = scraper
+                .fetch_followed_works()
+                .await
+                .context("Failed to fetch Kakuyomu follows")?;
+            import_bookmarks(console, &mut library, scraper.id(), works)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers bookmarked/followed novels as tracked library entries.
+fn import_bookmarks(
+    console: &Console,
+    library: &mut Library,
+    site: &str,
+    novels: Vec<BookmarkedNovel>,
+) -> Result<()> {
+    let total = novels.len();
+    let mut added = 0;
+    for novel in novels {
+        let is_new = library.upsert(LibraryEntry {
+            site: site.to_string(),
+            novel_id: novel.novel_id,
+            url: novel.url,
+            title: novel.title,
+            tracked: true,
+            added_unix: now_unix(),
+            ..Default::default()
+        });
+        if is_new {
+            added += 1;
+        }
+    }
+    library.save()?;
+
+    console.success(&format!(
+        "Imported {} novel(s): {} new, {} already in library",
+        total,
+        added,
+        total - added
+    ));
+    Ok(())
+}
+
+/// Handles `tsundoku read`.
+pub(crate) fn run_read(console: &Console, novel: &str, number: u32, read: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+    let data = manifest.data();
+    let chapter = data
+        .chapters
+        .iter()
+        .find(|c| c.number == number && !c.removed)
+        .or_else(|| data.chapters.iter().find(|c| c.number == number))
+        .ok_or_else(|| anyhow::anyhow!("No chapter {} in {}", number, data.title))?;
+    if read && !chapter.translated {
+        anyhow::bail!("Chapter {} hasn't been translated yet", number);
+    }
+
+    let mut library = Library::open(&Config::library_path()?)?;
+    let entry = library
+        .find_mut(&data.site, &data.novel_id)
+        .ok_or_else(|| anyhow::anyhow!("Not in library: {}: {}", data.site, data.novel_id))?;
+    let changed = entry.set_read(chapter, read);
+    let unread = entry.unread_count(&data.chapters);
+    library.save()?;
+
+    let state = if read { "read" } else { "unread" };
+    if changed {
+        console.success(&format!(
+            "Marked chapter {} as {}: {}",
+            number, state, chapter.title
+        ));
+    } else {
+        console.info(&format!("Chapter {} was already {}", number, state));
+    }
+    console.info(&format!("{} unread chapter(s) in {}", unread, data.title));
+    Ok(())
+}
+
+/// Handles `tsundoku chapter-profile`.
+pub(crate) fn run_chapter_profile(
+    console: &Console,
+    novel: &str,
+    chapters: &str,
+    profile: Option<&str>,
+) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    if let Some(profile) = profile
+        && !config.profiles.contains_key(profile)
+    {
+        anyhow::bail!("No [profiles.{}] section in config", profile);
+    }
+    let (start, end) = batch::parse_range(chapters).map_err(anyhow::Error::msg)?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let mut manifest = open_existing_manifest(&story_dir)?;
+
+    let numbers = start.unwrap_or(1)..=end.unwrap_or(u32::MAX);
+    let changed = manifest.set_profile(numbers.clone(), profile);
+    manifest.save()?;
+
+    let Some(profile) = profile else {
+        console.success(&format!("Cleared the profile of {} chapter(s)", changed));
+        return Ok(());
+    };
+    console.success(&format!(
+        "Marked {} chapter(s) to translate with profile '{}'",
+        changed, profile
+    ));
+    let translated = manifest
+        .data()
+        .chapters
+        .iter()
+        .filter(|c| numbers.contains(&c.number) && c.translated)
+        .count();
+    if translated > 0 {
+        console.warning(&format!(
+            "{} of the chapters are already translated; their translations are kept",
+            translated
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! `tsundoku models`: listing and choosing API models.
+
+use crate::ModelsArgs;
+use anyhow::{Context, Result};
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::search::Query;
+use tsundoku::translator::list_models;
+
+/// Handles `tsundoku models`.
+pub(crate) async fn run_models(console: &Console, args: ModelsArgs) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let config_path = Config::config_path()?;
+    if !config.api.is_configured() {
+        anyhow::bail!(
+            "API key not configured. Please edit: {}",
+            config_path.display()
+        );
+    }
+    let client = reqwest::Client::new();
+    let filter = args.filter.as_deref().map(Query::new);
+
+    let main_models =
+        list_endpoint_models(console, &client, "Main API", &config.api, &filter).await?;
+    let scout_api = config
+        .scout_api
+        .as_ref()
+        .filter(|api| api.is_configured())
+        .cloned();
+    let scout_models = match &scout_api {
+        Some(api) if api.base_url == config.api.base_url && api.key == config.api.key => {
+            console.info(&format!(
+                "Name scout API uses the same endpoint (model: {})",
+                api.model
+            ));
+            Some(main_models.clone())
+        }
+        Some(api) => {
+            Some(list_endpoint_models(console, &client, "Name Scout API", api, &filter).await?)
+        }
+        None => None,
+    };
+
+    // Only the model key is rewritten, so comments in the file survive
+    if let Some(model) = &args.set {
+        check_model_available(model, &main_models, "main API")?;
+        Config::set_value_in(&config_path, "api", "model", model)
+            .context("Failed to save configuration")?;
+        console.success(&format!("Main API model set to {}", model));
+    }
+    if let Some(model) = &args.set_scout {
+        let Some(models) = &scout_models else {
+            anyhow::bail!("Name scout API not configured; set scout_api in the config first");
+        };
+        check_model_available(model, models, "name scout API")?;
+        Config::set_value_in(&config_path, "scout_api", "model", model)
+            .context("Failed to save configuration")?;
+        console.success(&format!("Name scout API model set to {}", model));
+    }
+    if args.set.is_some() || args.set_scout.is_some() {
+        console.info(&format!("Saved {}", config_path.display()));
+    }
+    Ok(())
+}
+
+/// Prints an endpoint's models, marking the configured one, and returns
+/// the full (unfiltered) list.
+async fn list_endpoint_models(
+    console: &Console,
+    client: &reqwest::Client,
+    label: &str,
+    api: &tsundoku::config::ApiConfig,
+    filter: &Option<Query>,
+) -> Result<Vec<String>> {
+    console.section(&format!("{}: {}", label, api.base_url));
+    let models = list_models(client, api)
+        .await
+        .with_context(|| format!("Failed to list models from {}", api.base_url))?;
+    let shown: Vec<&String> = models
+        .iter()
+        .filter(|m| filter.as_ref().is_none_or(|q| q.matches(m)))
+        .collect();
+    for model in &shown {
+        let marker = if **model == api.model { "*" } else { " " };
+        println!("{} {}", marker, model);
+    }
+    console.info(&format!(
+        "{} model(s) shown of {}; current model: {}",
+        shown.len(),
+        models.len(),
+        api.model
+    ));
+    if !models.contains(&api.model) {
+        console.warning(&format!(
+            "Configured model '{}' is not offered by this endpoint",
+            api.model
+        ));
+    }
+    Ok(models)
+}
+
+/// Fails with near matches if `model` isn't in the endpoint's list.
+fn check_model_available(model: &str, models: &[String], label: &str) -> Result<()> {
+    if models.iter().any(|m| m == model) {
+        return Ok(());
+    }
+    let query = Query::new(model);
+    let similar: Vec<&str> = models
+        .iter()
+        .filter(|m| query.matches(m) || Query::new(m).matches(model))
+        .map(String::as_str)
+        .take(5)
+        .collect();
+    if similar.is_empty() {
+        anyhow::bail!("Model '{}' is not offered by the {}", model, label)
+    } else {
+        anyhow::bail!(
+            "Model '{}' is not offered by the {}; did you mean: {}?",
+            model,
+            label,
+            similar.join(", ")
+        )
+    }
+}
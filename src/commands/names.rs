@@ -0,0 +1,318 @@
+//! `tsundoku names` and `tsundoku terms`.
+
+use super::{open_existing_manifest, resolve_story_dir};
+use crate::NamesCommand;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::glossary::{self, GlossaryFormat};
+use tsundoku::manifest::ChapterRecord;
+use tsundoku::name_mapping::{NameMappingStore, ReplacementReport};
+use tsundoku::name_scout::{NameScout, build_chapter_payload};
+use tsundoku::terminology::{self, ReportFormat, TermReport};
+
+/// Handles `tsundoku names ...`.
+pub(crate) fn run_names_command(console: &Console, command: NamesCommand) -> Result<()> {
+    match command {
+        NamesCommand::Merge {
+            file1,
+            file2,
+            output,
+        } => {
+            let mut store = NameMappingStore::open(&file1)
+                .with_context(|| format!("Failed to load {}", file1.display()))?;
+            let other = NameMappingStore::open(&file2)
+                .with_context(|| format!("Failed to load {}", file2.display()))?;
+
+            let summary = store.merge(other.data());
+            for original in &summary.conflicts {
+                console.warning(&format!(
+                    "{} is locked to different names in each file; kept {}",
+                    original,
+                    store.data().names[original]
+                        .english
+                        .as_deref()
+                        .unwrap_or("?")
+                ));
+            }
+
+            let target = output.unwrap_or(file1);
+            store
+                .save_to(&target)
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            console.success(&format!(
+                "Merged {} shared and {} new name(s) into {} ({} names, {} chapters covered)",
+                summary.merged,
+                summary.added,
+                target.display(),
+                store.len(),
+                store.coverage().len()
+            ));
+        }
+        NamesCommand::Import {
+            novel,
+            file,
+            format,
+            lock,
+        } => {
+            let (site, novel_id) = novel
+                .split_once(':')
+                .filter(|(site, id)| !site.is_empty() && !id.is_empty())
+                .with_context(|| format!("Expected \"site:novel_id\", got \"{}\"", novel))?;
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let format = format.unwrap_or_else(|| GlossaryFormat::detect(&file, &content));
+            let glossary = glossary::parse(&content, format)
+                .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+            let config = Config::load().context("Failed to load configuration")?;
+            let mut store = NameMappingStore::new(&config.names_dir()?, site, novel_id)
+                .context("Failed to load name mapping")?;
+            let before = store.len();
+
+            if !glossary.skipped_lines.is_empty() {
+                console.warning(&format!(
+                    "Skipped {} unreadable line(s): {}",
+                    glossary.skipped_lines.len(),
+                    glossary
+                        .skipped_lines
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            if let Some(mapping) = &glossary.mapping {
+                if lock {
+                    console.warning("--lock has no effect on a mapping file; its locks are kept");
+                }
+                let summary = store.merge(mapping);
+                console.info(&format!(
+                    "Merged mapping file: {} shared and {} new name(s)",
+                    summary.merged, summary.added
+                ));
+            }
+            let accepted = store.import_entries(&glossary.entries, lock);
+            let rejected = glossary.entries.len() - accepted;
+            if rejected > 0 {
+                console.warning(&format!(
+                    "Left out {} of {} entries a name mapping can't hold (multi-word renderings, honorifics, punctuation)",
+                    rejected,
+                    glossary.entries.len()
+                ));
+            }
+
+            store.save().context("Failed to save name mapping")?;
+            console.success(&format!(
+                "Imported {} entries; {} now has {} names ({} new)",
+                accepted,
+                store.filepath().display(),
+                store.len(),
+                store.len().saturating_sub(before)
+            ));
+        }
+        NamesCommand::Report { novel } => {
+            let config = Config::load().context("Failed to load configuration")?;
+            let story_dir = resolve_story_dir(&config, &novel)?;
+            let manifest = open_existing_manifest(&story_dir)?;
+            let data = manifest.data();
+            let store = NameMappingStore::new(&config.names_dir()?, &data.site, &data.novel_id)
+                .context("Failed to load name mapping")?;
+            print_name_report(console, &store, &data.chapters);
+        }
+        NamesCommand::Reset { novel, keep } => {
+            if !(0.0..=1.0).contains(&keep) {
+                anyhow::bail!("--keep must be between 0 and 1");
+            }
+            let config = Config::load().context("Failed to load configuration")?;
+            let story_dir = resolve_story_dir(&config, &novel)?;
+            let manifest = open_existing_manifest(&story_dir)?;
+            let data = manifest.data();
+            let mut store = NameMappingStore::new(&config.names_dir()?, &data.site, &data.novel_id)
+                .context("Failed to load name mapping")?;
+            store
+                .write_backup()
+                .context("Failed to back up name mapping")?;
+
+            let removed = store.decay_votes(keep);
+            store.clear_coverage();
+            store.save()?;
+            if keep > 0.0 {
+                console.success(&format!(
+                    "Scaled votes to {:.0}%; dropped {} name(s) left without votes",
+                    keep * 100.0,
+                    removed
+                ));
+            } else {
+                console.success(&format!(
+                    "Cleared votes; dropped {} name(s) left without votes",
+                    removed
+                ));
+            }
+            console.info(&format!(
+                "{} name(s) remain; every chapter will be scouted again on the next run",
+                store.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles `tsundoku terms`.
+pub(crate) async fn run_terms(
+    console: &Console,
+    novel: &str,
+    (start, end): (Option<u32>, Option<u32>),
+    format: ReportFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let scout_api = config
+        .scout_api_config()
+        .context("Name scout API not configured")?
+        .clone();
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+    let data = manifest.data();
+    console.section(&format!("Terminology: {}", data.title));
+
+    let chapters: Vec<(&ChapterRecord, String)> = data
+        .chapters
+        .iter()
+        .filter(|c| start.is_none_or(|s| c.number >= s) && end.is_none_or(|e| c.number <= e))
+        .filter_map(|c| Some((c, manifest.downloaded_text(&story_dir, &c.episode_id)?)))
+        .collect();
+    if chapters.is_empty() {
+        anyhow::bail!("No downloaded chapters to scan");
+    }
+
+    let scout = NameScout::builder(scout_api, config.name_scout.clone())
+        .prompt(config.prompts.terminology.clone())
+        .refusal_nudge(
+            config.prompts.scout_refusal_nudge.clone(),
+            config.prompts.refusal_nudge_role,
+        )
+        .build();
+    let mut report = TermReport::default();
+    for (chapter, text) in &chapters {
+        console.step(&format!(
+            "Scanning chapter {}: {}",
+            chapter.number, chapter.title
+        ));
+        let payload = build_chapter_payload(chapter.number, &chapter.title, text);
+        for terms in scout.collect_terms(&payload).await {
+            report.add(chapter.number, &terms);
+        }
+    }
+    if report.is_empty() {
+        console.warning("No terms found");
+        return Ok(());
+    }
+
+    let mapping = NameMappingStore::new(&config.names_dir()?, &data.site, &data.novel_id)
+        .context("Failed to load name mapping")?;
+    let texts: Vec<&str> = chapters.iter().map(|(_, text)| text.as_str()).collect();
+    let rows = report.rows(&texts, &mapping);
+    let content = match format {
+        ReportFormat::Markdown => terminology::to_markdown(&data.title, &rows),
+        ReportFormat::Csv => terminology::to_csv(&rows),
+    };
+    let path =
+        output.unwrap_or_else(|| story_dir.join(format!("Terminology.{}", format.extension())));
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    console.success(&format!(
+        "{} term(s) from {} chapter(s) written to {}",
+        rows.len(),
+        chapters.len(),
+        path.display()
+    ));
+    Ok(())
+}
+
+/// Prints the name substitutions recorded in a story's manifest.
+fn print_name_report(console: &Console, store: &NameMappingStore, chapters: &[ChapterRecord]) {
+    let reported: Vec<&ChapterRecord> = chapters
+        .iter()
+        .filter(|c| !c.names_replaced.is_empty() || !c.names_unmapped.is_empty())
+        .collect();
+    if reported.is_empty() {
+        console.info("No name substitutions recorded yet; translate some chapters first.");
+        return;
+    }
+    console.info(&format!(
+        "Name substitutions recorded for {} chapter(s)",
+        reported.len()
+    ));
+
+    let mut replaced: HashMap<&str, u32> = HashMap::new();
+    let mut unmapped: HashMap<&str, u32> = HashMap::new();
+    for chapter in &reported {
+        for (name, count) in &chapter.names_replaced {
+            *replaced.entry(name).or_default() += count;
+        }
+        for (word, count) in &chapter.names_unmapped {
+            *unmapped.entry(word).or_default() += count;
+        }
+    }
+
+    let mut unused: Vec<(&str, &str)> = store
+        .names()
+        .filter(|(original, _)| !replaced.contains_key(original))
+        .filter_map(|(original, info)| Some((original, info.english.as_deref()?)))
+        .collect();
+    unused.sort();
+    if unused.is_empty() {
+        console.success("Every mapped name was substituted at least once");
+    } else {
+        console.warning(&format!(
+            "{} mapped name(s) never substituted:",
+            unused.len()
+        ));
+        for (original, english) in unused {
+            console.info(&format!("  {} → {}", original, english));
+        }
+    }
+
+    let dominated: Vec<(&ChapterRecord, ReplacementReport)> = reported
+        .iter()
+        .map(|c| {
+            let report = ReplacementReport {
+                replaced: c.names_replaced.clone(),
+                unmapped: c.names_unmapped.clone(),
+            };
+            (*c, report)
+        })
+        .filter(|(_, report)| report.dominated_by_unmapped())
+        .collect();
+    if !dominated.is_empty() {
+        console.warning(&format!(
+            "{} chapter(s) with more unmapped katakana words than mapped names:",
+            dominated.len()
+        ));
+        for (chapter, report) in dominated {
+            console.info(&format!(
+                "  Chapter {} ({}): {} unmapped, {} mapped",
+                chapter.number,
+                chapter.title,
+                report.unmapped_total(),
+                report.replaced_total()
+            ));
+        }
+    }
+
+    let mut frequent: Vec<(&str, u32)> = unmapped.into_iter().collect();
+    frequent.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    if !frequent.is_empty() {
+        console.info("Most frequent unmapped katakana words:");
+        for (word, count) in frequent.into_iter().take(UNMAPPED_REPORT_LIMIT) {
+            console.info(&format!("  {} ({})", word, count));
+        }
+    }
+}
+
+/// Unmapped words listed by `tsundoku names report`.
+const UNMAPPED_REPORT_LIMIT: usize = 10;
@@ -0,0 +1,1345 @@
+//! The download/translate pipeline for one novel: folder setup, manifest
+//! bookkeeping, downloading, and failure reporting.
+
+use super::review::{check_external_edit, manual_name_review, run_name_scout};
+use super::translate::{
+    chapter_body, chapter_metadata, failed_chunk_indices, name_prompt_context,
+    translate_marked_chapter, translation_provenance, warn_unmapped_names, write_aligned_exports,
+    write_metadata,
+};
+use super::{existing_story_folder, expand_path, safe_title};
+use anyhow::{Context, Result};
+use inquire::{InquireError, MultiSelect};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::sync::Arc;
+use tsundoku::api_limiter::ApiLimiter;
+use tsundoku::archive;
+use tsundoku::config::{Config, FolderTitle};
+use tsundoku::console::Console;
+use tsundoku::error::{ScraperError, TranslationError};
+use tsundoku::export::ABOUT_FILENAME;
+use tsundoku::failures::{ChapterFailure, ERROR_LOG_FILENAME, FailurePhase, FailureReport};
+use tsundoku::header::{HeaderFields, with_header};
+use tsundoku::history::NovelRun;
+use tsundoku::latest::{self, LATEST_FILENAME};
+use tsundoku::library::{Library, LibraryEntry};
+use tsundoku::manifest::{AboutInfo, ChapterRecord, MANIFEST_FILENAME, Manifest, Renumbered};
+use tsundoku::name_mapping::NameMappingStore;
+use tsundoku::name_scout::NameScout;
+use tsundoku::postprocess::PostProcessor;
+use tsundoku::romaji;
+use tsundoku::scrapers::{self, ChapterInfo, ChapterList, HostLimiter, Scraper, ScraperRegistry};
+use tsundoku::translator::{NoProgress, ProgressInfo, Translator};
+use tsundoku::utils::{format_unix_date, join_relative, now_unix};
+
+/// Per-novel options for a download/translate run.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct JobOptions {
+    pub(crate) start: Option<u32>,
+    pub(crate) end: Option<u32>,
+    pub(crate) pick: bool,
+    pub(crate) no_name_pause: bool,
+    pub(crate) no_name_scout: bool,
+    pub(crate) no_progress: bool,
+}
+
+/// Rate limiters shared by every novel in a run.
+#[derive(Clone, Default)]
+pub(crate) struct Limiters {
+    /// Per-host scraping budget.
+    pub(crate) hosts: HostLimiter,
+    /// Request spacing per API endpoint and key.
+    pub(crate) api: ApiLimiter,
+}
+
+/// Downloaded chapter data.
+#[allow(dead_code)]
+pub(crate) struct ChapterData {
+    pub(crate) number: u32,
+    pub(crate) episode_id: String,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) content: String,
+    pub(crate) filename: String,
+}
+
+/// Original file of a one-shot story, relative to its folder.
+const ONESHOT_ORIGINAL: &str = "original.txt";
+
+/// Parameters for processing novels.
+pub(crate) struct ProcessParams<'a> {
+    pub(crate) console: &'a Console,
+    /// Site ID used in folder names, the manifest, and name mappings.
+    pub(crate) site: &'a str,
+    /// `None` in translate-only runs, which never touch the network.
+    pub(crate) scraper: Option<&'a dyn Scraper>,
+    pub(crate) novel_info: &'a tsundoku::scrapers::NovelInfo,
+    pub(crate) output_dir: &'a Path,
+    pub(crate) translator: &'a Translator,
+    /// Translators for chapters marked with a profile, by profile name.
+    pub(crate) profile_translators: &'a HashMap<String, Translator>,
+    /// Profile the current chapter is translated with, if it has its own.
+    pub(crate) chapter_profile: Option<&'a str>,
+    pub(crate) name_scout: &'a NameScout,
+    pub(crate) name_mapping: &'a mut NameMappingStore,
+    pub(crate) no_name_pause: bool,
+    pub(crate) no_name_scout: bool,
+    pub(crate) config: &'a Config,
+    pub(crate) postprocessor: &'a PostProcessor,
+    /// Counts for the run history.
+    pub(crate) run: &'a mut NovelRun,
+}
+
+/// Downloads, scouts, and translates one novel, then records it in the library.
+///
+/// Returns the chapters newly found to be deleted or privated upstream.
+pub(crate) async fn process_novel(
+    console: &Console,
+    config: &Config,
+    novel_url: &str,
+    options: JobOptions,
+    limiters: &Limiters,
+    run: &mut NovelRun,
+) -> Result<Vec<ChapterRecord>> {
+    // Find appropriate scraper
+    console.step("Finding scraper for URL...");
+    let registry = ScraperRegistry::with_limiter(
+        &config.scraping,
+        Config::config_dir().ok().as_deref(),
+        limiters.hosts.clone(),
+    );
+    let scraper = registry
+        .find_for_url(novel_url)
+        .ok_or_else(|| anyhow::anyhow!("No scraper found for URL: {}", novel_url))?;
+
+    console.success(&format!("Using {} scraper", scraper.name()));
+
+    // Fetch novel info
+    console.step("Fetching novel information...");
+    let novel_info = scraper
+        .get_novel_info(novel_url)
+        .await
+        .context("Failed to fetch novel info")?;
+
+    console.success(&format!("Found: {}", novel_info.title));
+    console.info(&format!("Novel ID: {}", novel_info.novel_id));
+
+    // Fetch chapter list
+    console.step("Fetching chapter list...");
+    let chapter_list = scraper
+        .get_chapter_list(&novel_info.base_url)
+        .await
+        .context("Failed to fetch chapter list")?;
+
+    match &chapter_list {
+        ChapterList::OneShot => {
+            console.success("This is a one-shot story");
+        }
+        ChapterList::Chapters(chapters) => {
+            console.success(&format!("Found {} chapters", chapters.len()));
+        }
+    }
+    let missing = chapter_list.missing_numbers();
+    if !missing.is_empty() {
+        let numbers: Vec<String> = missing.iter().map(|n| n.to_string()).collect();
+        console.warning(&format!(
+            "Chapter(s) {} are missing from the listing (deleted or login-only); later chapters keep their numbers",
+            numbers.join(", ")
+        ));
+    }
+
+    let output_dir = expand_path(&config.paths.output_directory);
+    let removed = process_story(
+        console,
+        config,
+        &output_dir,
+        scraper.id(),
+        Some(scraper),
+        &novel_info,
+        &chapter_list,
+        options,
+        limiters,
+        run,
+    )
+    .await?;
+
+    // Serials are tracked for updates by default; one-shots can't change
+    let entry = LibraryEntry {
+        site: scraper.id().to_string(),
+        novel_id: novel_info.novel_id.clone(),
+        url: novel_url.to_string(),
+        title: novel_info.title.clone(),
+        tracked: !chapter_list.is_oneshot(),
+        added_unix: now_unix(),
+        ..Default::default()
+    };
+    if let Err(e) = record_in_library(entry) {
+        console.warning(&format!("Could not update library: {:#}", e));
+    }
+
+    Ok(removed)
+}
+
+/// Translates an existing story folder from its manifest, without network access.
+///
+/// Chapters whose originals aren't on disk are skipped.
+pub(crate) async fn process_offline(
+    console: &Console,
+    config: &Config,
+    story_dir: &Path,
+    options: JobOptions,
+    limiters: &Limiters,
+    run: &mut NovelRun,
+) -> Result<Vec<ChapterRecord>> {
+    let manifest_path = story_dir.join(MANIFEST_FILENAME);
+    if !manifest_path.is_file() {
+        anyhow::bail!(
+            "{} has no {}; run a normal download or `tsundoku import` first",
+            story_dir.display(),
+            MANIFEST_FILENAME
+        );
+    }
+    let manifest = Manifest::open(story_dir)
+        .with_context(|| format!("Failed to open manifest in {}", story_dir.display()))?;
+    let data = manifest.data();
+
+    // Folder lookup goes by the "[site: id]" prefix from the output directory
+    let folder_name = story_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let prefix = format!("[{}: {}]", data.site, data.novel_id);
+    let old_prefix = format!("[{}]", data.novel_id);
+    if !folder_name.starts_with(&prefix) && !folder_name.starts_with(&old_prefix) {
+        anyhow::bail!(
+            "Story folder name must start with \"{}\" to match its manifest",
+            prefix
+        );
+    }
+    let output_dir = story_dir.parent().unwrap_or(Path::new("."));
+
+    // A one-shot's only record is keyed by the work ID and saved as original.txt
+    let oneshot = match data.chapters.as_slice() {
+        [record] => {
+            record.episode_id == data.novel_id
+                && record.original_file.as_deref() == Some(ONESHOT_ORIGINAL)
+        }
+        _ => false,
+    };
+    let chapter_list = if oneshot {
+        ChapterList::OneShot
+    } else {
+        ChapterList::Chapters(manifest.listed_chapters())
+    };
+    let novel_info = tsundoku::scrapers::NovelInfo {
+        title: data.title.clone(),
+        base_url: data
+            .chapters
+            .first()
+            .filter(|_| oneshot)
+            .map(|c| c.url.clone())
+            .unwrap_or_default(),
+        novel_id: data.novel_id.clone(),
+        synopsis: String::new(),
+        tags: Vec::new(),
+        author: String::new(),
+        completed: None,
+    };
+    let site = data.site.clone();
+
+    console.success(&format!("Found: {}", novel_info.title));
+    match &chapter_list {
+        ChapterList::OneShot => console.success("This is a one-shot story"),
+        ChapterList::Chapters(chapters) => {
+            console.success(&format!("{} chapters in manifest", chapters.len()))
+        }
+    }
+
+    process_story(
+        console,
+        config,
+        output_dir,
+        &site,
+        None,
+        &novel_info,
+        &chapter_list,
+        options,
+        limiters,
+        run,
+    )
+    .await
+}
+
+/// Scouts and translates one novel's chapters, downloading originals when a
+/// scraper is given.
+///
+/// Returns the chapters newly found to be deleted or privated upstream.
+#[allow(clippy::too_many_arguments)]
+async fn process_story(
+    console: &Console,
+    config: &Config,
+    output_dir: &Path,
+    site: &str,
+    scraper: Option<&dyn Scraper>,
+    novel_info: &tsundoku::scrapers::NovelInfo,
+    chapter_list: &ChapterList,
+    options: JobOptions,
+    limiters: &Limiters,
+    run: &mut NovelRun,
+) -> Result<Vec<ChapterRecord>> {
+    run.site = site.to_string();
+    run.novel_id = novel_info.novel_id.clone();
+    run.title = novel_info.title.clone();
+
+    let existing_manifest = existing_story_folder(output_dir, site, &novel_info.novel_id)
+        .and_then(|folder| Manifest::open(&output_dir.join(folder)).ok());
+    // A folder already laid out as chapters keeps that layout if the site
+    // starts reporting a one-shot (e.g. a single-episode work being finished);
+    // a one-shot's only record is keyed by the work's own ID
+    let kept_layout = match (chapter_list, &existing_manifest) {
+        (ChapterList::OneShot, Some(manifest))
+            if manifest
+                .data()
+                .chapters
+                .iter()
+                .any(|c| c.episode_id != novel_info.novel_id) =>
+        {
+            console.info("The site now lists this as a one-shot; keeping the folder's chapters");
+            Some(ChapterList::Chapters(manifest.listed_chapters()))
+        }
+        _ => None,
+    };
+    let chapter_list = kept_layout.as_ref().unwrap_or(chapter_list);
+
+    // Validate chapter range
+    let (start_chapter, end_chapter) =
+        validate_chapter_range(options.start, options.end, chapter_list, console)?;
+    let picked = match chapter_list {
+        ChapterList::Chapters(chapters) if options.pick => Some(pick_chapters(console, chapters)?),
+        ChapterList::OneShot if options.pick => {
+            console.warning("One-shot story has a single chapter; ignoring --pick");
+            None
+        }
+        _ => None,
+    };
+
+    // Initialize name mapping store
+    let names_dir = config.names_dir()?;
+    let mut name_mapping = NameMappingStore::new(&names_dir, site, &novel_info.novel_id)
+        .context("Failed to initialize name mapping store")?;
+    name_mapping.set_romanize_fallback(config.translation.romanize_fallback);
+    check_external_edit(console, &name_mapping)?;
+
+    console.info(&format!(
+        "Name mapping: {} names loaded, {} chapters covered",
+        name_mapping.len(),
+        name_mapping.coverage().len()
+    ));
+
+    // Initialize translators, with one per profile chapters are marked with
+    let show_progress = !options.no_progress;
+    let translator = build_translator(
+        config,
+        site,
+        &novel_info.novel_id,
+        show_progress,
+        &limiters.api,
+    );
+    let marked_profiles: BTreeSet<String> = existing_manifest
+        .iter()
+        .flat_map(|manifest| manifest.data().chapters.iter())
+        .filter_map(|c| c.profile.clone())
+        .collect();
+    let mut profile_translators = HashMap::new();
+    for name in marked_profiles {
+        let mut profile_config = config.clone();
+        if let Err(e) = profile_config.apply_profile(&name) {
+            // Its chapters fall back to the run's settings
+            console.warning(&format!("Can't use profile '{}': {}", name, e));
+            continue;
+        }
+        profile_translators.insert(
+            name,
+            build_translator(
+                &profile_config,
+                site,
+                &novel_info.novel_id,
+                show_progress,
+                &limiters.api,
+            ),
+        );
+    }
+
+    // Initialize name scout
+    let scout_api = if options.no_name_scout {
+        config.api.clone()
+    } else {
+        config
+            .scout_api_config()
+            .context("Name scout API not configured")?
+            .clone()
+    };
+    let mut name_scout = NameScout::builder(scout_api, config.name_scout.clone())
+        .prompt(config.prompts.name_scout.clone())
+        .refusal_nudge(
+            config.prompts.scout_refusal_nudge.clone(),
+            config.prompts.refusal_nudge_role,
+        );
+    if !options.no_name_scout && config.scout_shares_api() {
+        name_scout = name_scout.api_limiter(limiters.api.clone());
+    }
+    let name_scout = name_scout.build();
+
+    let postprocessor = PostProcessor::from_config(config, site, &novel_info.novel_id)
+        .context("Invalid post-processing rule")?;
+
+    // Create process params
+    let mut params = ProcessParams {
+        console,
+        site,
+        scraper,
+        novel_info,
+        output_dir,
+        translator: &translator,
+        profile_translators: &profile_translators,
+        chapter_profile: None,
+        name_scout: &name_scout,
+        name_mapping: &mut name_mapping,
+        no_name_pause: options.no_name_pause,
+        no_name_scout: options.no_name_scout,
+        config,
+        postprocessor: &postprocessor,
+        run,
+    };
+
+    // Process based on chapter type
+    let result = match chapter_list {
+        ChapterList::OneShot => process_oneshot(&mut params).await.map(|_| Vec::new()),
+        ChapterList::Chapters(chapters) => {
+            process_chapters(
+                &mut params,
+                chapters,
+                start_chapter,
+                end_chapter,
+                picked.as_ref(),
+            )
+            .await
+        }
+    };
+    let mut usage = translator.total_usage();
+    for profile_translator in profile_translators.values() {
+        usage.add(profile_translator.total_usage());
+    }
+    params.run.usage = usage;
+
+    result
+}
+
+/// Builds the content and title translator for a novel from `config`,
+/// drawing the live progress line unless `show_progress` is off. Requests are
+/// spaced through `api_limiter` when the name scout shares the API key.
+pub(crate) fn build_translator(
+    config: &Config,
+    site: &str,
+    novel_id: &str,
+    show_progress: bool,
+    api_limiter: &ApiLimiter,
+) -> Translator {
+    let mut builder = Translator::builder(config.api.clone(), config.translation.clone())
+        .title_prompt(config.prompts.title_translation.clone())
+        .content_prompt(config.content_prompt(site, novel_id))
+        .stat_block_prompt(config.prompts.stat_block_translation.clone())
+        .refusal_nudge(
+            config.prompts.refusal_nudge.clone(),
+            config.prompts.refusal_nudge_role,
+        );
+    if !show_progress {
+        builder = builder.progress_sink(Arc::new(NoProgress));
+    }
+    if config.scout_shares_api() {
+        builder = builder.api_limiter(api_limiter.clone());
+    }
+    builder.build()
+}
+
+/// Adds or refreshes a novel in the library database.
+fn record_in_library(entry: LibraryEntry) -> Result<()> {
+    let mut library = Library::open(&Config::library_path()?)?;
+    library.upsert(entry);
+    library.save()?;
+    Ok(())
+}
+
+/// Processes a one-shot story.
+async fn process_oneshot(params: &mut ProcessParams<'_>) -> Result<()> {
+    params.console.section("Processing One-Shot Story");
+
+    // Find or create story folder
+    let folder_name = find_or_create_folder(params).await?;
+
+    let story_dir = params.output_dir.join(&folder_name);
+    std::fs::create_dir_all(&story_dir)?;
+
+    let original_file = ONESHOT_ORIGINAL;
+    let translated_file = "oneshot.txt";
+
+    // A one-shot's only chapter is keyed by the work's own ID
+    let episode_id = params.novel_info.novel_id.as_str();
+
+    let mut manifest = open_manifest(params, &story_dir)?;
+    write_about_file(params, &mut manifest, &story_dir).await;
+    let record = manifest.chapter_mut(episode_id, 1);
+    record.title = params.novel_info.title.clone();
+    record.url = params.novel_info.base_url.clone();
+    if !manifest.existed() {
+        // Folder may predate manifests; adopt any existing files once
+        adopt_existing_file(&mut manifest, &story_dir, episode_id, original_file, false)?;
+        adopt_existing_file(&mut manifest, &story_dir, episode_id, translated_file, true)?;
+    }
+
+    // Download original content if not already recorded
+    let content = if let Some(content) = manifest.downloaded_text(&story_dir, episode_id) {
+        params
+            .console
+            .info("Original content already exists, loading...");
+        content
+    } else {
+        let Some(scraper) = params.scraper else {
+            anyhow::bail!(
+                "{} is missing; cannot download in translate-only mode",
+                original_file
+            );
+        };
+        params.console.step("Downloading original content...");
+        let content = scraper
+            .download_chapter(&params.novel_info.base_url)
+            .await
+            .context("Failed to download content")?;
+        scrapers::validate_chapter(&content, params.config.scraping.min_chapter_chars)
+            .context("Downloaded content was not saved")?;
+        archive::save_original(
+            &story_dir,
+            original_file,
+            &content,
+            params.config.output.archive_originals,
+        )?;
+        manifest.mark_downloaded(episode_id, original_file, &content);
+        params.run.chapters_downloaded += 1;
+        params.console.success(&format!(
+            "Saved original ({} chars)",
+            content.chars().count()
+        ));
+        content
+    };
+    manifest.save()?;
+
+    // Run name scout
+    let scouted = if params.no_name_scout {
+        params.console.info("Name scout disabled; skipping");
+        false
+    } else {
+        let scouted = run_name_scout(
+            params.console,
+            params.name_scout,
+            params.name_mapping,
+            &[(1, &params.novel_info.title, &content)],
+        )
+        .await?;
+        manifest.mark_scouted(episode_id);
+        manifest.save()?;
+        scouted
+    };
+
+    // Manual review (only if scouting was performed)
+    if !params.no_name_pause && scouted {
+        manual_name_review(params.console, params.name_mapping, params.config)?;
+    }
+
+    // Translate content
+    let translated_path = story_dir.join(translated_file);
+    if manifest.translated_file(&story_dir, episode_id).is_some() {
+        params
+            .console
+            .info("Translation already exists, skipping...");
+    } else {
+        params.console.step("Translating content...");
+        params.translator.take_usage();
+
+        let mapped_title = params.name_mapping.apply_to_text(&params.novel_info.title);
+        let translated_title = match params.translator.translate(&mapped_title, true, None).await {
+            Ok(title) => title,
+            Err(e) => {
+                params
+                    .console
+                    .warning(&format!("Title translation failed: {}", e));
+                params.novel_info.title.clone()
+            }
+        };
+
+        // Apply name mapping
+        let (mapped_content, name_report) = params.name_mapping.apply_with_report(&content);
+        warn_unmapped_names(params.console, 1, &name_report);
+        let name_list = name_prompt_context(params.config, params.name_mapping);
+
+        let progress = ProgressInfo {
+            chapter: 1,
+            ..Default::default()
+        };
+
+        let mut chunks = params
+            .translator
+            .translate_chunked(&mapped_content, name_list.as_deref(), None, Some(progress))
+            .await
+            .context("Failed to translate content")?;
+        params.postprocessor.apply_to_chunks(&mut chunks);
+
+        let fields = HeaderFields {
+            novel_title: &params.novel_info.title,
+            chapter_number: 1,
+            chapter_title: &translated_title,
+            chapter_title_original: &params.novel_info.title,
+            url: &params.novel_info.base_url,
+            date: &format_unix_date(now_unix()),
+            model: params.translator.model(),
+        };
+        let translated = with_header(
+            &params.config.output.header_template,
+            &fields,
+            &chapter_body(params, &chunks),
+        );
+        let metadata = chapter_metadata(params, manifest.chapter(episode_id), &translated_title);
+        let translated = write_metadata(params, &story_dir, "oneshot", &metadata, translated)?;
+        std::fs::write(&translated_path, &translated)?;
+        manifest.record_names(episode_id, &name_report);
+        manifest.set_failed_chunks(episode_id, failed_chunk_indices(&chunks));
+        manifest.mark_translated(episode_id, translated_file, &translated);
+        manifest.record_provenance(episode_id, translation_provenance(params));
+        manifest.save()?;
+        params.console.success("Translation saved");
+        if chunks.iter().any(|c| c.failed) {
+            params.run.chapters_failed += 1;
+        } else {
+            params.run.chapters_translated += 1;
+        }
+
+        write_aligned_exports(params, &story_dir, "oneshot", 1, &chunks)?;
+    }
+
+    Ok(())
+}
+
+/// Processes multi-chapter stories.
+///
+/// Only chapters in the range (and in `picked`, if given) are downloaded and
+/// translated.
+async fn process_chapters(
+    params: &mut ProcessParams<'_>,
+    chapters: &[ChapterInfo],
+    start_chapter: u32,
+    end_chapter: u32,
+    picked: Option<&HashSet<u32>>,
+) -> Result<Vec<ChapterRecord>> {
+    params.console.section("Processing Multi-Chapter Story");
+
+    // Find or create story folder
+    let folder_name = find_or_create_folder(params).await?;
+
+    let story_dir = params.output_dir.join(&folder_name);
+    let original_dir = story_dir.join("Original");
+    let archive_originals = params.config.output.archive_originals;
+    if archive_originals {
+        let moved = archive::archive_original_dir(&story_dir)
+            .context("Failed to archive existing originals")?;
+        if moved > 0 {
+            params.console.info(&format!(
+                "Moved {} original(s) into {}",
+                moved,
+                archive::ORIGINALS_ARCHIVE
+            ));
+        }
+    } else {
+        std::fs::create_dir_all(&original_dir)?;
+    }
+
+    // Calculate padding for chapter numbers (gaps keep later numbers high)
+    let last_number = chapters.iter().map(|c| c.number).max().unwrap_or(0);
+    let padding = params
+        .config
+        .output
+        .file_number(last_number)
+        .to_string()
+        .len();
+
+    let mut manifest = open_manifest(params, &story_dir)?;
+    write_about_file(params, &mut manifest, &story_dir).await;
+    let positional_ids = params.scraper.is_some_and(|s| s.positional_episode_ids());
+    let previously_listed = manifest.listed_chapters().len();
+    let renumbered = manifest.sync_chapters(chapters);
+    let mut removed = manifest.mark_missing(chapters, positional_ids);
+    if positional_ids && chapters.len() < previously_listed {
+        params.console.warning(&format!(
+            "The site lists {} chapter(s), down from {}; it numbers chapters by position, so the removed ones can't be identified",
+            chapters.len(),
+            previously_listed
+        ));
+    }
+    for record in &removed {
+        params.console.warning(&format!(
+            "Chapter {} ({}) is no longer listed on the site",
+            record.number, record.title
+        ));
+    }
+    if !manifest.existed() {
+        // Folder may predate manifests; adopt any existing files once
+        // Files from before manifests were always numbered from 1
+        let legacy_padding = last_number.to_string().len();
+        adopt_legacy_chapters(
+            params.config,
+            &mut manifest,
+            &story_dir,
+            chapters,
+            legacy_padding,
+        )?;
+    } else if !renumbered.is_empty() {
+        handle_renumbering(params, &mut manifest, &story_dir, &renumbered, padding)?;
+    }
+    manifest.save()?;
+
+    // Download phase
+    params.console.section("Download Phase");
+
+    let mut report = FailureReport::new();
+    let mut downloaded_chapters: Vec<ChapterData> = Vec::new();
+
+    for chapter in chapters.iter() {
+        if chapter.number < start_chapter
+            || chapter.number > end_chapter
+            || picked.is_some_and(|p| !p.contains(&chapter.number))
+        {
+            continue;
+        }
+
+        let file_number = params.config.output.file_number(chapter.number);
+        let chapter_num_str = format!("{:0width$}", file_number, width = padding);
+        let filename = format!(
+            "{} - {}.txt",
+            chapter_num_str,
+            safe_title(params.config, &chapter_num_str, &chapter.title)
+        );
+        let original_file = format!("Original/{}", filename);
+
+        let content = if let Some(content) =
+            manifest.downloaded_text(&story_dir, &chapter.episode_id)
+        {
+            params
+                .console
+                .info(&format!("Chapter {} already downloaded", chapter.number));
+            content
+        } else {
+            let Some(scraper) = params.scraper else {
+                params.console.warning(&format!(
+                    "Chapter {} has no original; skipping (translate-only)",
+                    chapter.number
+                ));
+                continue;
+            };
+            params.console.step(&format!(
+                "Downloading chapter {}: {}",
+                chapter.number, chapter.title
+            ));
+
+            let downloaded = scraper
+                .download_chapter(&chapter.url)
+                .await
+                .and_then(|content| {
+                    scrapers::validate_chapter(&content, params.config.scraping.min_chapter_chars)?;
+                    Ok(content)
+                });
+            let content = match downloaded {
+                Ok(content) => content,
+                Err(e) if e.is_not_found() => {
+                    // Listed but gone (privated or mid-deletion); keep going
+                    params.console.warning(&format!(
+                        "Chapter {} returned not found; marking as removed",
+                        chapter.number
+                    ));
+                    manifest.mark_removed(&chapter.episode_id);
+                    manifest.save()?;
+                    if let Some(record) = manifest.chapter(&chapter.episode_id) {
+                        removed.push(record.clone());
+                    }
+                    report.record(download_failure(
+                        chapter,
+                        vec![format!("{} (marked as removed)", e)],
+                    ));
+                    continue;
+                }
+                Err(e @ ScraperError::InvalidContent(_)) => {
+                    // Placeholder or broken page; don't save it, try the rest
+                    params
+                        .console
+                        .warning(&format!("Chapter {} not saved: {}", chapter.number, e));
+                    report.record(download_failure(chapter, vec![e.to_string()]));
+                    continue;
+                }
+                Err(e) => {
+                    let e = anyhow::Error::from(e)
+                        .context(format!("Failed to download chapter {}", chapter.number));
+                    report.record(download_failure(chapter, error_chain(&e)));
+                    save_failure_report(params, &mut report, &story_dir)?;
+                    return Err(e);
+                }
+            };
+
+            let written =
+                archive::save_original(&story_dir, &original_file, &content, archive_originals)?;
+            manifest.mark_downloaded(&chapter.episode_id, &original_file, &content);
+            params.run.chapters_downloaded += 1;
+            let status = if written { "Saved" } else { "Unchanged" };
+            params
+                .console
+                .success(&format!("{} ({} chars)", status, content.chars().count()));
+            content
+        };
+        manifest.save()?;
+
+        downloaded_chapters.push(ChapterData {
+            number: chapter.number,
+            episode_id: chapter.episode_id.clone(),
+            title: chapter.title.clone(),
+            url: chapter.url.clone(),
+            content,
+            filename,
+        });
+    }
+
+    if downloaded_chapters.is_empty() {
+        params.console.warning("No chapters downloaded");
+        save_failure_report(params, &mut report, &story_dir)?;
+        refresh_latest_link(params, &manifest, &story_dir);
+        return Ok(removed);
+    }
+
+    // Name scout phase
+    let scout_data: Vec<(u32, &str, &str)> = downloaded_chapters
+        .iter()
+        .map(|c| (c.number, c.title.as_str(), c.content.as_str()))
+        .collect();
+
+    let scouted = if params.no_name_scout {
+        params.console.info("Name scout disabled; skipping");
+        false
+    } else {
+        let scouted = run_name_scout(
+            params.console,
+            params.name_scout,
+            params.name_mapping,
+            &scout_data,
+        )
+        .await?;
+        for chapter_data in &downloaded_chapters {
+            manifest.mark_scouted(&chapter_data.episode_id);
+        }
+        manifest.save()?;
+        scouted
+    };
+
+    // Manual review (only if scouting was performed)
+    if !params.no_name_pause && scouted {
+        manual_name_review(params.console, params.name_mapping, params.config)?;
+    }
+
+    // Translation phase
+    params.console.section("Translation Phase");
+
+    let mut failed = Vec::new();
+    for chapter_data in &downloaded_chapters {
+        // Check if translation already exists
+        if manifest
+            .translated_file(&story_dir, &chapter_data.episode_id)
+            .is_some()
+        {
+            params.console.info(&format!(
+                "Chapter {} already translated, skipping",
+                chapter_data.number
+            ));
+            continue;
+        }
+
+        let result = translate_marked_chapter(
+            params,
+            &mut manifest,
+            &story_dir,
+            chapter_data,
+            padding,
+            false,
+        )
+        .await;
+        let Some(errors) = chapter_failure(chapter_data, result)? else {
+            params.run.chapters_translated += 1;
+            continue;
+        };
+        params.console.warning(&format!(
+            "Chapter {} failed ({}); will retry at the end of the run",
+            chapter_data.number,
+            errors.join("; ")
+        ));
+        failed.push(chapter_data);
+        report.record(ChapterFailure {
+            chapter: chapter_data.number,
+            title: chapter_data.title.clone(),
+            phase: FailurePhase::Translate,
+            errors,
+            attempts: 1,
+            resolved: false,
+        });
+    }
+
+    // One more attempt for chapters that exhausted their retries; whatever
+    // still fails is saved with failure markers
+    if !failed.is_empty() {
+        params.console.section("Retrying Failed Chapters");
+        for chapter_data in failed {
+            let result = translate_marked_chapter(
+                params,
+                &mut manifest,
+                &story_dir,
+                chapter_data,
+                padding,
+                true,
+            )
+            .await;
+            let errors = chapter_failure(chapter_data, result)?;
+            if errors.is_none() {
+                params.run.chapters_translated += 1;
+            }
+            report.reattempted(chapter_data.number, FailurePhase::Translate, errors);
+        }
+    }
+
+    save_failure_report(params, &mut report, &story_dir)?;
+    refresh_latest_link(params, &manifest, &story_dir);
+    let unresolved: Vec<&ChapterFailure> = report
+        .unresolved()
+        .filter(|f| f.phase == FailurePhase::Translate)
+        .collect();
+    if !unresolved.is_empty() {
+        params.console.section("Failed Chapters");
+        for failure in &unresolved {
+            params.console.error(&format!(
+                "Chapter {}: {}",
+                failure.chapter,
+                failure.errors.join("; ")
+            ));
+        }
+        params.console.info(&format!(
+            "Details saved to {}",
+            story_dir.join(ERROR_LOG_FILENAME).display()
+        ));
+        anyhow::bail!(
+            "{} chapter(s) failed to translate after retrying",
+            unresolved.len()
+        );
+    }
+
+    Ok(removed)
+}
+
+/// Sorts a chapter translation result into success, a failure worth
+/// retrying (returned as its error messages), or a fatal error that ends
+/// the run.
+fn chapter_failure(
+    chapter_data: &ChapterData,
+    result: Result<Vec<String>>,
+) -> Result<Option<Vec<String>>> {
+    match result {
+        Ok(failures) if failures.is_empty() => Ok(None),
+        Ok(failures) => Ok(Some(failures)),
+        Err(e) if is_fatal_translation_error(&e) => Err(e),
+        Err(e) => Ok(Some(error_chain(
+            &e.context(format!("Chapter {}", chapter_data.number)),
+        ))),
+    }
+}
+
+/// Writes the story's failure report and counts its unresolved failures for
+/// the run history.
+fn save_failure_report(
+    params: &mut ProcessParams<'_>,
+    report: &mut FailureReport,
+    story_dir: &Path,
+) -> Result<()> {
+    params.run.chapters_failed = report.unresolved().count() as u32;
+    report.write(story_dir, now_unix())?;
+    Ok(())
+}
+
+/// Points the story's `latest.txt` at its newest translation, if
+/// `output.latest_link` is set. Problems are only warned about.
+fn refresh_latest_link(params: &ProcessParams<'_>, manifest: &Manifest, story_dir: &Path) {
+    if !params.config.output.latest_link {
+        return;
+    }
+    if let Err(e) = latest::refresh(story_dir, manifest) {
+        params
+            .console
+            .warning(&format!("Could not update {}: {}", LATEST_FILENAME, e));
+    }
+}
+
+/// Failure report entry for a chapter that couldn't be downloaded.
+fn download_failure(chapter: &ChapterInfo, errors: Vec<String>) -> ChapterFailure {
+    ChapterFailure {
+        chapter: chapter.number,
+        title: chapter.title.clone(),
+        phase: FailurePhase::Download,
+        errors,
+        attempts: 1,
+        resolved: false,
+    }
+}
+
+/// An error and its causes, outermost first.
+fn error_chain(error: &anyhow::Error) -> Vec<String> {
+    error.chain().map(|e| e.to_string()).collect()
+}
+
+/// Errors no retry can fix: bad credentials and cancellation.
+fn is_fatal_translation_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<TranslationError>()
+        .is_some_and(|e| e.is_auth() || matches!(e, TranslationError::Cancelled))
+}
+
+/// Writes `about.txt` with the translated synopsis and tags, once per story,
+/// and records them in the manifest for exports.
+///
+/// Problems are only warned about; the synopsis isn't worth failing a run.
+async fn write_about_file(params: &ProcessParams<'_>, manifest: &mut Manifest, story_dir: &Path) {
+    let info = params.novel_info;
+    let about_path = story_dir.join(ABOUT_FILENAME);
+    let nothing_to_translate = info.synopsis.is_empty() && info.tags.is_empty();
+    if manifest.data().about.is_some() && (about_path.exists() || nothing_to_translate) {
+        return;
+    }
+    if nothing_to_translate {
+        manifest.set_about(AboutInfo {
+            url: info.base_url.clone(),
+            author: info.author.clone(),
+            ..Default::default()
+        });
+        return;
+    }
+
+    params.console.step("Translating synopsis and tags...");
+    let translate = |text: String| async move {
+        if text.is_empty() {
+            return text;
+        }
+        let mapped = params.name_mapping.apply_to_text(&text);
+        params
+            .translator
+            .translate(&mapped, true, None)
+            .await
+            .unwrap_or_else(|e| {
+                params
+                    .console
+                    .warning(&format!("Could not translate synopsis/tags: {}", e));
+                text
+            })
+    };
+
+    let synopsis = translate(info.synopsis.clone()).await;
+    // Tags go in one request, one per line, and are kept as-is if the
+    // model merges or splits lines
+    let translated_tags = translate(info.tags.join("\n")).await;
+    let tag_lines: Vec<String> = translated_tags
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect();
+    let tags = if tag_lines.len() == info.tags.len() {
+        tag_lines
+    } else {
+        info.tags.clone()
+    };
+
+    let mut about = format!("{}\n{}\n", info.title, info.base_url);
+    if !info.author.is_empty() {
+        about.push_str(&format!("Author: {}\n", info.author));
+    }
+    if !synopsis.is_empty() {
+        about.push_str(&format!("\nSynopsis:\n{}\n", synopsis));
+    }
+    if !tags.is_empty() {
+        about.push_str(&format!("\nTags: {}\n", tags.join(", ")));
+    }
+    about.push_str("\n--- Original ---\n");
+    if !info.synopsis.is_empty() {
+        about.push_str(&format!("\n{}\n", info.synopsis));
+    }
+    if !info.tags.is_empty() {
+        about.push_str(&format!("\n{}\n", info.tags.join(" ")));
+    }
+
+    manifest.set_about(AboutInfo {
+        url: info.base_url.clone(),
+        author: info.author.clone(),
+        synopsis,
+        tags,
+    });
+    match std::fs::write(&about_path, about) {
+        Ok(()) => params.console.success(&format!("Saved {}", ABOUT_FILENAME)),
+        Err(e) => params
+            .console
+            .warning(&format!("Could not write {}: {}", ABOUT_FILENAME, e)),
+    }
+}
+
+/// Opens a story folder's manifest and records the novel's identity.
+fn open_manifest(params: &ProcessParams<'_>, story_dir: &Path) -> Result<Manifest> {
+    let mut manifest = Manifest::open(story_dir)
+        .with_context(|| format!("Failed to open manifest in {}", story_dir.display()))?;
+    manifest.set_novel(
+        params.site,
+        &params.novel_info.novel_id,
+        &params.novel_info.title,
+    );
+    Ok(manifest)
+}
+
+/// Reports chapters the author renumbered and offers to rename their files.
+///
+/// The manifest already follows chapters by episode ID, so skipping stays
+/// correct either way; renaming just keeps filename numbers in order.
+fn handle_renumbering(
+    params: &ProcessParams<'_>,
+    manifest: &mut Manifest,
+    story_dir: &Path,
+    renumbered: &[Renumbered],
+    padding: usize,
+) -> Result<()> {
+    params.console.warning(&format!(
+        "{} chapter(s) were renumbered by the author:",
+        renumbered.len()
+    ));
+    for r in renumbered {
+        params.console.info(&format!(
+            "  {} -> {}: {}",
+            r.old_number, r.new_number, r.title
+        ));
+    }
+
+    if params.no_name_pause {
+        params
+            .console
+            .info("Existing files keep their old numbers (run interactively to rename them)");
+        return Ok(());
+    }
+
+    print!("Rename existing files to match the new numbering? [y/N] ");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if !input.trim().eq_ignore_ascii_case("y") {
+        return Ok(());
+    }
+
+    let first_number = params.config.output.first_chapter_number;
+    let renamed = manifest.remap_files(story_dir, padding, first_number)?;
+    for (old, new) in &renamed {
+        params.console.info(&format!("Renamed: {} -> {}", old, new));
+    }
+    params
+        .console
+        .success(&format!("Renamed {} file(s)", renamed.len()));
+    Ok(())
+}
+
+/// Records chapter files written before manifests existed: originals by
+/// their expected name and translations by their "<number> - " prefix.
+fn adopt_legacy_chapters(
+    config: &Config,
+    manifest: &mut Manifest,
+    story_dir: &Path,
+    chapters: &[ChapterInfo],
+    padding: usize,
+) -> Result<()> {
+    let files: Vec<String> = std::fs::read_dir(story_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    for chapter in chapters {
+        let chapter_num_str = format!("{:0width$}", chapter.number, width = padding);
+        let original_file = format!(
+            "Original/{} - {}.txt",
+            chapter_num_str,
+            safe_title(config, &chapter_num_str, &chapter.title)
+        );
+        adopt_existing_file(
+            manifest,
+            story_dir,
+            &chapter.episode_id,
+            &original_file,
+            false,
+        )?;
+
+        let pattern = format!("{} - ", chapter_num_str);
+        if let Some(translated_file) = files
+            .iter()
+            .find(|name| name.starts_with(&pattern) && name.ends_with(".txt"))
+        {
+            adopt_existing_file(
+                manifest,
+                story_dir,
+                &chapter.episode_id,
+                translated_file,
+                true,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Marks an existing file as a chapter's original or translation.
+fn adopt_existing_file(
+    manifest: &mut Manifest,
+    story_dir: &Path,
+    episode_id: &str,
+    relative_path: &str,
+    translated: bool,
+) -> Result<()> {
+    let path = join_relative(story_dir, relative_path);
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    if translated {
+        manifest.mark_translated(episode_id, relative_path, &content);
+    } else {
+        manifest.mark_downloaded(episode_id, relative_path, &content);
+    }
+    Ok(())
+}
+
+/// Finds an existing folder or creates a new one named after the title
+/// chosen by `paths.folder_title`.
+async fn find_or_create_folder(params: &ProcessParams<'_>) -> Result<String> {
+    let console = params.console;
+    let novel_id = &params.novel_info.novel_id;
+    if let Some(name) = existing_story_folder(params.output_dir, params.site, novel_id) {
+        console.info(&format!("Using existing folder: {}", name));
+        return Ok(name);
+    }
+
+    let original_title = &params.novel_info.title;
+    let title = match params.config.paths.folder_title {
+        FolderTitle::Translated => {
+            console.step("Translating title for folder name...");
+            params
+                .translator
+                .translate(original_title, true, None)
+                .await
+                .unwrap_or_else(|_| original_title.to_string())
+        }
+        FolderTitle::Romaji => romaji::romanize_title(original_title).unwrap_or_else(|| {
+            console.info("Title isn't all kana, so it can't be romanized; using the original");
+            original_title.to_string()
+        }),
+        FolderTitle::Original => original_title.to_string(),
+    };
+
+    let prefix = format!("[{}: {}]", params.site, novel_id);
+    let folder_name = format!("{} {}", prefix, safe_title(params.config, &prefix, &title));
+
+    console.success(&format!("Creating folder: {}", folder_name));
+
+    Ok(folder_name)
+}
+
+/// Asks the user to choose chapters from a fuzzy-searchable list.
+fn pick_chapters(console: &Console, chapters: &[ChapterInfo]) -> Result<HashSet<u32>> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("--pick needs an interactive terminal");
+    }
+
+    let width = chapters
+        .iter()
+        .map(|c| c.number)
+        .max()
+        .unwrap_or(0)
+        .to_string()
+        .len();
+    let labels: Vec<String> = chapters
+        .iter()
+        .map(|c| format!("{:>width$}. {}", c.number, c.title, width = width))
+        .collect();
+    let selected = MultiSelect::new("Chapters to process:", labels)
+        .with_page_size(15)
+        .with_help_message("type to search, space to toggle, → all, ← none, enter to confirm")
+        .raw_prompt()
+        .map_err(|e| match e {
+            InquireError::OperationCanceled | InquireError::OperationInterrupted => {
+                anyhow::anyhow!("Chapter selection cancelled")
+            }
+            e => anyhow::Error::from(e).context("Chapter picker failed"),
+        })?;
+    if selected.is_empty() {
+        anyhow::bail!("No chapters selected");
+    }
+
+    console.info(&format!("Selected {} chapter(s)", selected.len()));
+    Ok(selected.iter().map(|o| chapters[o.index].number).collect())
+}
+
+/// Validates the chapter range arguments.
+fn validate_chapter_range(
+    start: Option<u32>,
+    end: Option<u32>,
+    chapter_list: &ChapterList,
+    console: &Console,
+) -> Result<(u32, u32)> {
+    let total_chapters = chapter_list.last_number();
+
+    // One-shots cannot use range
+    if chapter_list.is_oneshot() {
+        if start.is_some() || end.is_some() {
+            anyhow::bail!("Cannot use --start or --end with one-shot stories");
+        }
+        return Ok((1, 1));
+    }
+
+    let start_chapter = start.unwrap_or(1);
+    let end_chapter = end.unwrap_or(total_chapters);
+
+    // Validate range
+    if start_chapter > end_chapter {
+        anyhow::bail!(
+            "Start chapter ({}) cannot be greater than end chapter ({})",
+            start_chapter,
+            end_chapter
+        );
+    }
+
+    if end_chapter > total_chapters {
+        anyhow::bail!(
+            "End chapter ({}) exceeds total chapters ({})",
+            end_chapter,
+            total_chapters
+        );
+    }
+
+    console.info(&format!(
+        "Processing chapters {} to {} of {}",
+        start_chapter, end_chapter, total_chapters
+    ));
+
+    Ok((start_chapter, end_chapter))
+}
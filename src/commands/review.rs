@@ -0,0 +1,328 @@
+//! Name scouting during a run and the pause for reviewing the mapping.
+
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::error::NameMappingError;
+use tsundoku::name_mapping::{self, NameMappingStore};
+use tsundoku::name_scout::{NameScout, build_chapter_payload};
+
+/// Runs name scout on chapters that haven't been covered.
+/// Returns true if any scouting was performed, false if all chapters were already covered.
+pub(crate) async fn run_name_scout(
+    console: &Console,
+    name_scout: &NameScout,
+    name_mapping: &mut NameMappingStore,
+    chapters: &[(u32, &str, &str)], // (number, title, content)
+) -> Result<bool> {
+    console.section("Name Scout Phase");
+
+    let uncovered: Vec<_> = chapters
+        .iter()
+        .filter(|(num, _, _)| !name_mapping.is_chapter_covered(*num))
+        .collect();
+
+    if uncovered.is_empty() {
+        console.info("All chapters already scouted for names");
+        return Ok(false);
+    }
+
+    console.info(&format!(
+        "Scouting {} chapters for character names",
+        uncovered.len()
+    ));
+
+    for (number, title, content) in uncovered {
+        console.step(&format!("Scouting chapter {}: {}", number, title));
+
+        let payload = build_chapter_payload(*number, title, content);
+        let chunks = name_scout.name_chunks(&payload);
+        let resumed = name_mapping.scouted_chunks(*number, &chunks);
+        if resumed > 0 {
+            console.info(&format!(
+                "Resuming after chunk {}/{} from an interrupted run",
+                resumed,
+                chunks.len()
+            ));
+        }
+
+        // Record votes and progress as each chunk finishes
+        let mut total_names = 0;
+        let finished = name_scout
+            .scout_names_each(&chunks, resumed, |index, entries| {
+                total_names += entries.len();
+                name_mapping.record_votes(&entries);
+                name_mapping.record_scouted_chunks(*number, &chunks, index + 1);
+                name_mapping.save()
+            })
+            .await?;
+        console.info(&format!(
+            "Found {} names in chapter {}",
+            total_names, number
+        ));
+        if !finished {
+            anyhow::bail!("Name scouting of chapter {} was interrupted", number);
+        }
+
+        // Mark chapter as covered
+        name_mapping.add_coverage(&[*number]);
+        name_mapping.save()?;
+    }
+
+    console.success(&format!(
+        "Name mapping now has {} names",
+        name_mapping.len()
+    ));
+
+    Ok(true)
+}
+
+/// Prompts user to review and edit name mappings.
+pub(crate) fn manual_name_review(
+    console: &Console,
+    name_mapping: &mut NameMappingStore,
+    config: &Config,
+) -> Result<()> {
+    console.section("Name Mapping Review");
+
+    // Keep the pre-edit mapping so a broken edit can be undone
+    name_mapping
+        .write_backup()
+        .context("Failed to back up name mapping")?;
+
+    let filepath = name_mapping.filepath();
+    console.info(&format!("Name mapping file: {}", filepath.display()));
+    let low_confidence = name_mapping.low_confidence_count();
+    if low_confidence > 0 {
+        console.warning(&format!(
+            "{} name(s) the scout was unsure of are listed first",
+            low_confidence
+        ));
+    }
+
+    // Try to open in editor
+    let editor_opened = if let Some(ref editor_cmd) = config.paths.editor_command {
+        // Use configured editor
+        match std::process::Command::new(editor_cmd).arg(filepath).spawn() {
+            Ok(_) => {
+                console.info(&format!("Opening in {}...", editor_cmd));
+                true
+            }
+            Err(e) => {
+                console.warning(&format!("Failed to launch {}: {}", editor_cmd, e));
+                false
+            }
+        }
+    } else {
+        // Auto-detect editor
+        let editors = if cfg!(target_os = "windows") {
+            vec!["notepad", "code", "notepad++"]
+        } else if cfg!(target_os = "macos") {
+            vec!["open", "code", "vim", "nano"]
+        } else {
+            // Linux and other Unix-like systems
+            vec!["kate", "gedit", "code", "vim", "nano", "emacs"]
+        };
+
+        let mut opened = false;
+        for editor in editors {
+            if let Ok(editor_path) = which::which(editor) {
+                match std::process::Command::new(&editor_path)
+                    .arg(filepath)
+                    .spawn()
+                {
+                    Ok(_) => {
+                        console.info(&format!("Opening in {}...", editor));
+                        opened = true;
+                        break;
+                    }
+                    Err(_) => continue,
+                }
+            }
+        }
+        opened
+    };
+
+    if !editor_opened {
+        console.info(&format!(
+            "Could not auto-detect editor. Please open the file manually: {}",
+            filepath.display()
+        ));
+    }
+
+    // Reload whenever the file is saved; Enter finishes the review (and is
+    // all there is if the file can't be watched)
+    let (tx, rx) = mpsc::channel();
+    let watcher = match watch_mapping_file(name_mapping.filepath(), tx.clone()) {
+        Ok(watcher) => {
+            console.info("Changes are picked up each time you save the file.");
+            Some(watcher)
+        }
+        Err(e) => {
+            console.warning(&format!("Can't watch the name mapping file: {}", e));
+            None
+        }
+    };
+
+    let mut prompt = "Review the name mappings and press Enter when done.";
+    loop {
+        console.info(prompt);
+        print!("> ");
+        io::stdout().flush()?;
+        spawn_line_reader(tx.clone());
+
+        // Wait for the line, reloading on every save in the meantime
+        let input = loop {
+            match rx.recv().context("Name review input stopped")? {
+                ReviewEvent::Line(input) => break input,
+                ReviewEvent::FileChanged => {
+                    // Editors write a file in several steps; let them finish
+                    while rx
+                        .recv_timeout(WATCH_SETTLE)
+                        .is_ok_and(|event| matches!(event, ReviewEvent::FileChanged))
+                    {
+                    }
+                    reload_reviewed_mapping(console, name_mapping);
+                }
+            }
+        };
+        let Some(input) = input else {
+            // Input closed; keep the edit if it's valid, as Enter would
+            name_mapping
+                .reload_from_disk()
+                .context("Input closed during name review with an invalid mapping file")?;
+            break;
+        };
+
+        if input.trim().eq_ignore_ascii_case("restore") {
+            name_mapping
+                .restore_backup()
+                .context("Failed to restore name mapping backup")?;
+            console.success("Restored the name mapping from before your edits");
+            break;
+        }
+
+        // Reload and validate
+        if reload_reviewed_mapping(console, name_mapping) {
+            console.success("Name mapping reloaded successfully");
+            break;
+        }
+        prompt = "Fix the file and press Enter to retry, or type \"restore\" to undo your edits.";
+    }
+    drop(watcher);
+
+    // Record the reviewed file so its edits aren't reported as external
+    name_mapping.save().context("Failed to save name mapping")?;
+    name_mapping
+        .discard_backup()
+        .context("Failed to remove name mapping backup")?;
+    Ok(())
+}
+
+/// How long to wait for more file events before reloading.
+const WATCH_SETTLE: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Something that happened during the name review.
+enum ReviewEvent {
+    /// The mapping file (or its folder) changed on disk.
+    FileChanged,
+    /// A line was entered; None if input is closed.
+    Line(Option<String>),
+}
+
+/// Watches the mapping file's folder (editors often save by replacing the
+/// file) and reports changes to the file.
+fn watch_mapping_file(
+    path: &Path,
+    tx: mpsc::Sender<ReviewEvent>,
+) -> notify::Result<notify::RecommendedWatcher> {
+    use notify::Watcher;
+
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        let relevant = (event.kind.is_modify() || event.kind.is_create())
+            && event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+        if relevant {
+            let _ = tx.send(ReviewEvent::FileChanged);
+        }
+    })?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    watcher.watch(dir, notify::RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// Reads one line of input on a background thread.
+fn spawn_line_reader(tx: mpsc::Sender<ReviewEvent>) {
+    std::thread::spawn(move || {
+        let mut input = String::new();
+        let line = match io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(input),
+        };
+        let _ = tx.send(ReviewEvent::Line(line));
+    });
+}
+
+/// Reloads the mapping under review, printing what changed or why the file
+/// can't be read. Returns whether it loaded.
+fn reload_reviewed_mapping(console: &Console, name_mapping: &mut NameMappingStore) -> bool {
+    let before = name_mapping.data().clone();
+    match name_mapping.reload_from_disk() {
+        Ok(()) => {
+            let changes = name_mapping::diff(&before, name_mapping.data());
+            if !changes.is_empty() {
+                console.info(&format!("{} name(s) changed:", changes.len()));
+                for change in changes {
+                    console.info(&format!("  {}", change));
+                }
+            }
+            for name in name_mapping.external_edit().unwrap_or_default() {
+                console.warning(&format!("Removed {}", name));
+            }
+            true
+        }
+        Err(e) => {
+            console.error(&format!("Failed to reload name mapping: {}", e));
+            if let NameMappingError::InvalidJson {
+                line, source_line, ..
+            } = &e
+            {
+                console.info(&format!("  {} | {}", line, source_line.trim_end()));
+            }
+            false
+        }
+    }
+}
+
+/// Warns when the name mapping was edited outside Tsundoku since it last
+/// saved it, listing the names the load purged and why, then saves the
+/// cleaned-up file so the warning isn't repeated.
+pub(crate) fn check_external_edit(
+    console: &Console,
+    name_mapping: &NameMappingStore,
+) -> Result<()> {
+    let Some(purged) = name_mapping.external_edit() else {
+        return Ok(());
+    };
+    console.info(&format!(
+        "{} was edited outside Tsundoku; re-checked it",
+        name_mapping.filepath().display()
+    ));
+    if !purged.is_empty() {
+        console.warning(&format!(
+            "Removed {} name(s) that break the mapping rules:",
+            purged.len()
+        ));
+        for name in purged {
+            console.info(&format!("  {}", name));
+        }
+    }
+    name_mapping.save().context("Failed to save name mapping")
+}
@@ -0,0 +1,441 @@
+//! Subcommands that work on an existing story folder: stats, lint, clean,
+//! export, verify, repair, and postprocess.
+
+use super::process::build_translator;
+use super::review::check_external_edit;
+use super::translate::{chunk_error, name_prompt_context, refresh_aligned_exports};
+use super::{load_run_config, open_existing_manifest, resolve_story_dir, verify_endpoints};
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tsundoku::api_limiter::ApiLimiter;
+use tsundoku::clean;
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::export::{self, ExportFormat};
+use tsundoku::lint::{self, LintKind};
+use tsundoku::manifest::ChapterRecord;
+use tsundoku::metadata;
+use tsundoku::name_mapping::NameMappingStore;
+use tsundoku::postprocess::{self, PostProcessor};
+use tsundoku::stats;
+use tsundoku::translator::{FAILED_CHUNK_MARKER, ProgressInfo, ProgressPhase};
+use tsundoku::utils::join_relative;
+use tsundoku::verify;
+
+/// Handles `tsundoku stats`.
+pub(crate) fn run_stats(console: &Console, novel: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+
+    let stats = stats::collect_stats(
+        &story_dir,
+        &manifest,
+        config.translation.chunk_size_chars,
+        config.translation.chunk_size_unit,
+    );
+    let title = &manifest.data().title;
+    console.section(&format!("Stats: {}", title));
+
+    println!(
+        "{:>5}  {:>8}  {:>6}  {:>8}  {:>5}  Title",
+        "Ch", "Original", "Chunks", "English", "Ratio"
+    );
+    for chapter in &stats.chapters {
+        let flag = if chapter.suspicious { " (short?)" } else { "" };
+        println!(
+            "{:>5}  {:>8}  {:>6}  {:>8}  {:>5}  {}{}",
+            chapter.number,
+            format_count(chapter.original_chars),
+            chapter.estimated_chunks,
+            format_count(chapter.translated_chars),
+            format_ratio(chapter.ratio()),
+            chapter.title,
+            flag
+        );
+    }
+
+    console.info(&format!(
+        "{} chapter(s): {} downloaded, {} translated",
+        stats.chapters.len(),
+        stats.downloaded(),
+        stats.translated()
+    ));
+    console.info(&format!(
+        "{} original chars, ~{} chunks, {} translated chars (ratio {})",
+        stats.original_chars(),
+        stats.estimated_chunks(),
+        stats.translated_chars(),
+        format_ratio(stats.ratio())
+    ));
+    for chapter in stats.suspicious() {
+        console.warning(&format!(
+            "Chapter {} is much shorter than usual ({} chars); the download may be truncated",
+            chapter.number,
+            format_count(chapter.original_chars)
+        ));
+    }
+
+    Ok(())
+}
+
+fn format_count(count: Option<usize>) -> String {
+    count.map_or_else(|| "-".to_string(), |c| c.to_string())
+}
+
+fn format_ratio(ratio: Option<f64>) -> String {
+    ratio.map_or_else(|| "-".to_string(), |r| format!("{:.2}", r))
+}
+
+/// Handles `tsundoku lint`.
+pub(crate) fn run_lint(console: &Console, novel: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+    let data = manifest.data();
+    console.section(&format!("Lint: {}", data.title));
+
+    let name_mapping = NameMappingStore::new(&config.names_dir()?, &data.site, &data.novel_id)
+        .context("Failed to load name mapping")?;
+    let names = lint::name_renderings(name_mapping.data());
+
+    let mut issues = Vec::new();
+    let mut checked = 0;
+    for record in data.chapters.iter().filter(|c| c.translated) {
+        let Some(relative_path) = &record.translated_file else {
+            continue;
+        };
+        let Ok(text) = std::fs::read_to_string(join_relative(&story_dir, relative_path)) else {
+            console.warning(&format!("Missing translation: {}", relative_path));
+            continue;
+        };
+        let (_, body) = metadata::split_front_matter(&text);
+        issues.extend(lint::lint_text(relative_path, body, &names));
+        checked += 1;
+    }
+
+    for issue in &issues {
+        println!("{}", issue);
+    }
+
+    let count = |kind| issues.iter().filter(|i| i.kind == kind).count();
+    console.info(&format!(
+        "{} chapter(s) checked: {} name inconsistencies, {} leftover Japanese, {} untranslated chunks",
+        checked,
+        count(LintKind::InconsistentName),
+        count(LintKind::LeftoverJapanese),
+        count(LintKind::UntranslatedChunk)
+    ));
+
+    if issues.is_empty() {
+        console.success("No issues found");
+        Ok(())
+    } else {
+        anyhow::bail!("{} issue(s) found", issues.len())
+    }
+}
+
+/// Handles `tsundoku clean`.
+pub(crate) fn run_clean(console: &Console, novel: &str, yes: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+    console.section(&format!("Clean: {}", manifest.data().title));
+
+    let candidates = clean::find_candidates(&story_dir, &manifest)?;
+    if candidates.is_empty() {
+        console.success("Nothing to clean");
+        return Ok(());
+    }
+    for candidate in &candidates {
+        console.info(&format!("[{}] {}", candidate.reason, candidate.path));
+    }
+
+    if !yes {
+        print!("Delete {} file(s)? [y/N] ", candidates.len());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            console.info("Nothing deleted");
+            return Ok(());
+        }
+    }
+
+    for candidate in &candidates {
+        std::fs::remove_file(join_relative(&story_dir, &candidate.path))
+            .with_context(|| format!("Failed to delete {}", candidate.path))?;
+    }
+    console.success(&format!("Deleted {} file(s)", candidates.len()));
+    Ok(())
+}
+
+/// Handles `tsundoku export`.
+pub(crate) fn run_export(
+    console: &Console,
+    novel: &str,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+    console.section(&format!("Export: {}", manifest.data().title));
+
+    let mut book = export::load_book(&story_dir, &manifest)?;
+    book.furigana = config.scraping.furigana;
+    // Not `with_extension`, which would cut titles containing a dot
+    let output = output.unwrap_or_else(|| {
+        let mut path = story_dir.clone().into_os_string();
+        path.push(format!(".{}", format.extension()));
+        PathBuf::from(path)
+    });
+    export::export_book(&book, format, &output)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    console.success(&format!(
+        "Exported {} chapter(s) to {}",
+        book.chapters.len(),
+        output.display()
+    ));
+    Ok(())
+}
+
+/// Handles `tsundoku verify`.
+pub(crate) fn run_verify(console: &Console, novel: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let manifest = open_existing_manifest(&story_dir)?;
+    console.section(&format!("Verify: {}", manifest.data().title));
+
+    let report = verify::verify_story(&story_dir, &manifest);
+    for issue in &report.issues {
+        console.error(&issue.to_string());
+    }
+    if !report.not_downloaded.is_empty() {
+        console.warning(&format!(
+            "{} listed chapter(s) not downloaded yet: {}",
+            report.not_downloaded.len(),
+            report
+                .not_downloaded
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    console.info(&format!(
+        "{} file(s) checked, {} problem(s)",
+        report.checked,
+        report.issues.len()
+    ));
+
+    if report.issues.is_empty() {
+        console.success("All recorded files are intact");
+        Ok(())
+    } else {
+        anyhow::bail!("{} problem(s) found", report.issues.len())
+    }
+}
+
+/// Handles `tsundoku repair`: re-translates the chunks recorded as failed
+/// and splices the results into the chapter files.
+pub(crate) async fn run_repair(console: &Console, novel: &str) -> Result<()> {
+    let Some(config) = load_run_config(console, false, true)? else {
+        return Ok(());
+    };
+    verify_endpoints(console, &config, false).await?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let mut manifest = open_existing_manifest(&story_dir)?;
+    let (site, novel_id) = (
+        manifest.data().site.clone(),
+        manifest.data().novel_id.clone(),
+    );
+    console.section(&format!("Repair: {}", manifest.data().title));
+
+    let damaged: Vec<ChapterRecord> = manifest
+        .data()
+        .chapters
+        .iter()
+        .filter(|c| c.translated && !c.failed_chunks.is_empty())
+        .cloned()
+        .collect();
+    if damaged.is_empty() {
+        console.success("No failed chunks recorded");
+        return Ok(());
+    }
+
+    let mut name_mapping = NameMappingStore::new(&config.names_dir()?, &site, &novel_id)
+        .context("Failed to load name mapping")?;
+    name_mapping.set_romanize_fallback(config.translation.romanize_fallback);
+    check_external_edit(console, &name_mapping)?;
+    let translator = build_translator(&config, &site, &novel_id, true, &ApiLimiter::new());
+    let name_list = name_prompt_context(&config, &name_mapping);
+    let postprocessor = PostProcessor::from_config(&config, &site, &novel_id)
+        .context("Invalid post-processing rule")?;
+    let output = &config.output;
+    let layout =
+        |text: &str| postprocess::apply_layout(text, output.paragraph_spacing, output.wrap_columns);
+
+    let (mut repaired, mut remaining) = (0, 0);
+    for record in damaged {
+        let (Some(relative_path), Some(original)) = (
+            record.translated_file.as_deref(),
+            manifest.downloaded_text(&story_dir, &record.episode_id),
+        ) else {
+            console.warning(&format!(
+                "Chapter {}: original or translation missing; skipping",
+                record.number
+            ));
+            remaining += record.failed_chunks.len();
+            continue;
+        };
+        let path = join_relative(&story_dir, relative_path);
+        let mut text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        console.step(&format!(
+            "Chapter {}: re-translating {} chunk(s)",
+            record.number,
+            record.failed_chunks.len()
+        ));
+        let mapped = name_mapping.apply_to_text(&original);
+        let progress = ProgressInfo {
+            phase: ProgressPhase::Repair,
+            chapter: record.number,
+            ..Default::default()
+        };
+        let results = translator
+            .translate_chunks_at(
+                &mapped,
+                &record.failed_chunks,
+                name_list.as_deref(),
+                Some(progress),
+            )
+            .await
+            .with_context(|| format!("Failed to repair chapter {}", record.number))?;
+
+        let mut errors = Vec::new();
+        let mut fixed = Vec::new();
+        let mut still_failed: Vec<usize> = record
+            .failed_chunks
+            .iter()
+            .copied()
+            .filter(|i| !results.iter().any(|(index, _)| index == i))
+            .collect();
+        for (index, mut chunk) in results {
+            if chunk.failed {
+                let error = format!("chunk {}: {}", index + 1, chunk_error(&chunk));
+                console.warning(&format!("Chapter {} {}", record.number, error));
+                errors.push(error);
+                still_failed.push(index);
+                continue;
+            }
+
+            // The failed block as saved, before or after `postprocess` ran
+            let block = format!("{}\n{}", FAILED_CHUNK_MARKER, chunk.source);
+            let saved = [layout(&block), layout(&postprocessor.apply(&block))]
+                .into_iter()
+                .find(|saved| text.contains(saved.as_str()));
+            let Some(saved) = saved else {
+                console.warning(&format!(
+                    "Chapter {} chunk {}: the saved chunk no longer matches the original \
+                     (edited, or the chunk size or name mapping changed); re-translate the chapter instead",
+                    record.number,
+                    index + 1
+                ));
+                still_failed.push(index);
+                continue;
+            };
+            postprocessor.apply_to_chunks(std::slice::from_mut(&mut chunk));
+            text = text.replacen(&saved, &layout(&chunk.translated), 1);
+            fixed.push(chunk);
+            repaired += 1;
+        }
+
+        still_failed.sort_unstable();
+        remaining += still_failed.len();
+        if still_failed.len() < record.failed_chunks.len() {
+            std::fs::write(&path, &text)?;
+            manifest.mark_translated(&record.episode_id, relative_path, &text);
+            console.success(&format!("Updated: {}", relative_path));
+            if let Some(stem) = Path::new(relative_path).file_stem() {
+                refresh_aligned_exports(
+                    console,
+                    output,
+                    &story_dir,
+                    &stem.to_string_lossy(),
+                    record.number,
+                    &fixed,
+                )?;
+            }
+        }
+        if still_failed.is_empty() {
+            manifest.set_last_error(&record.episode_id, None);
+        } else if !errors.is_empty() {
+            manifest.set_last_error(&record.episode_id, Some(errors.join("; ")));
+        }
+        manifest.set_failed_chunks(&record.episode_id, still_failed);
+        manifest.save()?;
+    }
+
+    console.info(&format!(
+        "{} chunk(s) repaired, {} still failed",
+        repaired, remaining
+    ));
+    if remaining > 0 {
+        anyhow::bail!("{} chunk(s) could not be repaired", remaining);
+    }
+    Ok(())
+}
+
+/// Handles `tsundoku postprocess`.
+pub(crate) fn run_postprocess(console: &Console, novel: &str) -> Result<()> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let story_dir = resolve_story_dir(&config, novel)?;
+    let mut manifest = open_existing_manifest(&story_dir)?;
+    console.section(&format!("Post-process: {}", manifest.data().title));
+
+    let postprocessor =
+        PostProcessor::from_config(&config, &manifest.data().site, &manifest.data().novel_id)
+            .context("Invalid post-processing rule")?;
+    if postprocessor.is_empty() {
+        console.info("No post-processing configured");
+        return Ok(());
+    }
+
+    let translated: Vec<(String, String)> = manifest
+        .data()
+        .chapters
+        .iter()
+        .filter(|c| c.translated)
+        .filter_map(|c| Some((c.episode_id.clone(), c.translated_file.clone()?)))
+        .collect();
+
+    let mut changed = 0;
+    for (episode_id, relative_path) in &translated {
+        let path = join_relative(&story_dir, relative_path);
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            console.warning(&format!("Missing translation: {}", relative_path));
+            continue;
+        };
+        // Front matter is provenance, not prose
+        let (front_matter, body) = metadata::split_front_matter(&text);
+        let processed = format!("{}{}", front_matter, postprocessor.apply(body));
+        if processed != text {
+            std::fs::write(&path, &processed)?;
+            manifest.mark_translated(episode_id, relative_path, &processed);
+            console.success(&format!("Updated: {}", relative_path));
+            changed += 1;
+        }
+    }
+    manifest.save()?;
+
+    console.info(&format!(
+        "{} of {} translated chapter(s) changed",
+        changed,
+        translated.len()
+    ));
+    Ok(())
+}
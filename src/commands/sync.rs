@@ -0,0 +1,46 @@
+//! `tsundoku sync` and the sync after a run.
+
+use super::expand_path;
+use anyhow::{Context, Result};
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::sync;
+
+/// Handles `tsundoku sync`: uploads new and changed output files.
+pub(crate) async fn run_sync(console: &Console, config: &Config) -> Result<()> {
+    console.section("Sync");
+    let output_dir = expand_path(&config.paths.output_directory);
+    let report = sync::sync_output(&config.sync, &output_dir)
+        .await
+        .context("Sync failed")?;
+
+    for path in &report.uploaded {
+        console.success(&format!("Uploaded: {}", path));
+    }
+    for (path, e) in &report.failed {
+        console.error(&format!("{}: {}", path, e));
+    }
+    console.info(&format!(
+        "{} uploaded, {} unchanged, {} failed",
+        report.uploaded.len(),
+        report.unchanged,
+        report.failed.len()
+    ));
+
+    if report.failed.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!("{} file(s) failed to upload", report.failed.len())
+    }
+}
+
+/// Runs the post-run sync if `sync.after_run` is set. Failures only warn,
+/// since the downloads themselves succeeded.
+pub(crate) async fn sync_after_run(console: &Console, config: &Config) {
+    if !config.sync.after_run {
+        return;
+    }
+    if let Err(e) = run_sync(console, config).await {
+        console.warning(&format!("{:#}", e));
+    }
+}
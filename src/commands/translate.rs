@@ -0,0 +1,463 @@
+//! Translating a downloaded chapter and writing its outputs (metadata,
+//! aligned exports, provenance).
+
+use super::process::{ChapterData, ProcessParams};
+use super::safe_title;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use tsundoku::bilingual;
+use tsundoku::config::{Config, MetadataFormat, OutputConfig};
+use tsundoku::console::Console;
+use tsundoku::header::{HeaderFields, with_header};
+use tsundoku::manifest::{ChapterRecord, Manifest, TranslationProvenance, content_hash};
+use tsundoku::metadata::{self, ChapterMetadata};
+use tsundoku::name_mapping::{NameMappingStore, ReplacementReport};
+use tsundoku::postprocess;
+use tsundoku::translator::{
+    FAILED_CHUNK_MARKER, ProgressInfo, TranslatedChunk, Translator, join_chunks, last_paragraphs,
+};
+use tsundoku::utils::{format_unix_date, format_unix_timestamp, now_unix};
+
+/// Translates and saves one chapter with the translator of the profile the
+/// manifest marks it with, if any, or the run's.
+pub(crate) async fn translate_marked_chapter<'a>(
+    params: &mut ProcessParams<'a>,
+    manifest: &mut Manifest,
+    story_dir: &Path,
+    chapter_data: &ChapterData,
+    padding: usize,
+    save_partial: bool,
+) -> Result<Vec<String>> {
+    let profile_translators: &'a HashMap<String, Translator> = params.profile_translators;
+    let marked = manifest
+        .chapter(&chapter_data.episode_id)
+        .and_then(|record| record.profile.as_deref());
+    let Some(profile) = marked else {
+        return translate_chapter(
+            params,
+            manifest,
+            story_dir,
+            chapter_data,
+            padding,
+            save_partial,
+        )
+        .await;
+    };
+    let Some((name, translator)) = profile_translators.get_key_value(profile) else {
+        params.console.warning(&format!(
+            "Chapter {} is marked with profile '{}', which isn't in the config; \
+             using the run's settings",
+            chapter_data.number, profile
+        ));
+        return translate_chapter(
+            params,
+            manifest,
+            story_dir,
+            chapter_data,
+            padding,
+            save_partial,
+        )
+        .await;
+    };
+
+    params.console.info(&format!(
+        "Using profile '{}' ({}) for chapter {}",
+        name,
+        translator.model(),
+        chapter_data.number
+    ));
+    let run_translator = std::mem::replace(&mut params.translator, translator);
+    params.chapter_profile = Some(name);
+    let result = translate_chapter(
+        params,
+        manifest,
+        story_dir,
+        chapter_data,
+        padding,
+        save_partial,
+    )
+    .await;
+    params.translator = run_translator;
+    params.chapter_profile = None;
+    result
+}
+
+/// Translates and saves one chapter.
+///
+/// Returns an error message for each part (the title or a content chunk)
+/// that failed every retry. Unless `save_partial` is set, a chapter with
+/// failures isn't saved, so a later attempt starts clean.
+async fn translate_chapter(
+    params: &ProcessParams<'_>,
+    manifest: &mut Manifest,
+    story_dir: &Path,
+    chapter_data: &ChapterData,
+    padding: usize,
+    save_partial: bool,
+) -> Result<Vec<String>> {
+    let file_number = params.config.output.file_number(chapter_data.number);
+    let chapter_num_str = format!("{:0width$}", file_number, width = padding);
+
+    params.console.step(&format!(
+        "Translating chapter {}: {}",
+        chapter_data.number, chapter_data.title
+    ));
+
+    params.translator.take_usage();
+
+    // Translate title
+    let mapped_title = params.name_mapping.apply_to_text(&chapter_data.title);
+    let mut failures = Vec::new();
+    let translated_title = match params.translator.translate(&mapped_title, true, None).await {
+        Ok(title) => title,
+        Err(e) => {
+            failures.push(format!("title: {}", e));
+            format!("{} [TRANSLATION_FAILED]", chapter_data.title)
+        }
+    };
+
+    // Validate translated title for filesystem
+    let safe_title = safe_title(params.config, &chapter_num_str, &translated_title);
+
+    // Apply name mapping to content
+    let (mapped_content, name_report) =
+        params.name_mapping.apply_with_report(&chapter_data.content);
+    warn_unmapped_names(params.console, chapter_data.number, &name_report);
+    let name_list = name_prompt_context(params.config, params.name_mapping);
+    let lead_in = previous_chapter_lead_in(params, manifest, story_dir, chapter_data.number);
+
+    // Translate content
+    // Chunk counts and timing are filled in by the translator
+    let progress = ProgressInfo {
+        chapter: chapter_data.number,
+        ..Default::default()
+    };
+
+    let mut chunks = params
+        .translator
+        .translate_chunked(
+            &mapped_content,
+            name_list.as_deref(),
+            lead_in.as_ref().map(|(s, t)| (s.as_str(), t.as_str())),
+            Some(progress),
+        )
+        .await
+        .context("Failed to translate chapter")?;
+    params.postprocessor.apply_to_chunks(&mut chunks);
+
+    for (i, chunk) in chunks.iter().enumerate().filter(|(_, c)| c.failed) {
+        failures.push(format!("chunk {}: {}", i + 1, chunk_error(chunk)));
+    }
+    let last_error = (!failures.is_empty()).then(|| failures.join("; "));
+    if !failures.is_empty() && !save_partial {
+        manifest.set_last_error(&chapter_data.episode_id, last_error);
+        manifest.save()?;
+        return Ok(failures);
+    }
+
+    // Save translated chapter
+    let translated_stem = format!("{} - {}", chapter_num_str, safe_title);
+    let translated_filename = format!("{}.txt", translated_stem);
+    let translated_path = story_dir.join(&translated_filename);
+    let fields = HeaderFields {
+        novel_title: &params.novel_info.title,
+        chapter_number: file_number,
+        chapter_title: &translated_title,
+        chapter_title_original: &chapter_data.title,
+        url: &chapter_data.url,
+        date: &format_unix_date(now_unix()),
+        model: params.translator.model(),
+    };
+    let translated_content = with_header(
+        &params.config.output.header_template,
+        &fields,
+        &chapter_body(params, &chunks),
+    );
+    let metadata = chapter_metadata(
+        params,
+        manifest.chapter(&chapter_data.episode_id),
+        &translated_title,
+    );
+    let translated_content = write_metadata(
+        params,
+        story_dir,
+        &translated_stem,
+        &metadata,
+        translated_content,
+    )?;
+    std::fs::write(&translated_path, &translated_content)?;
+    manifest.record_names(&chapter_data.episode_id, &name_report);
+    manifest.set_failed_chunks(&chapter_data.episode_id, failed_chunk_indices(&chunks));
+    manifest.set_last_error(&chapter_data.episode_id, last_error);
+    manifest.mark_translated(
+        &chapter_data.episode_id,
+        &translated_filename,
+        &translated_content,
+    );
+    manifest.record_provenance(&chapter_data.episode_id, translation_provenance(params));
+    manifest.save()?;
+
+    params
+        .console
+        .success(&format!("Saved: {}", translated_filename));
+
+    write_aligned_exports(
+        params,
+        story_dir,
+        &translated_stem,
+        chapter_data.number,
+        &chunks,
+    )?;
+
+    Ok(failures)
+}
+
+/// Warns when a chapter's unmapped katakana words outnumber its mapped
+/// names, a sign the scout missed much of the cast.
+pub(crate) fn warn_unmapped_names(console: &Console, chapter: u32, report: &ReplacementReport) {
+    if report.dominated_by_unmapped() {
+        console.warning(&format!(
+            "Chapter {}: {} unmapped katakana word(s) against {} mapped name(s); \
+             the name mapping may be missing names",
+            chapter,
+            report.unmapped_total(),
+            report.replaced_total()
+        ));
+    }
+}
+
+/// The end of the chapter before `number`, source and translation, to open
+/// the translation history with, if `translation.previous_chapter_paragraphs`
+/// is set and that chapter is translated.
+fn previous_chapter_lead_in(
+    params: &ProcessParams<'_>,
+    manifest: &Manifest,
+    story_dir: &Path,
+    number: u32,
+) -> Option<(String, String)> {
+    let count = params.config.translation.previous_chapter_paragraphs;
+    (count > 0)
+        .then(|| {
+            let previous = manifest
+                .data()
+                .chapters
+                .iter()
+                .filter(|c| c.number < number)
+                .max_by_key(|c| c.number)?;
+            let source = manifest.downloaded_text(story_dir, &previous.episode_id)?;
+            let path = manifest.translated_file(story_dir, &previous.episode_id)?;
+            let translated = std::fs::read_to_string(path).ok()?;
+            let (_, body) = metadata::split_front_matter(&translated);
+            let translated = last_paragraphs(body, count);
+            // A failed tail would teach the model to leave text untranslated
+            if translated.contains(FAILED_CHUNK_MARKER) {
+                return None;
+            }
+            let source = params
+                .name_mapping
+                .apply_to_text(&last_paragraphs(&source, count));
+            Some((source, translated))
+        })
+        .flatten()
+}
+
+/// Why a failed chunk failed, for messages and the manifest.
+pub(crate) fn chunk_error(chunk: &TranslatedChunk) -> String {
+    chunk
+        .error
+        .as_ref()
+        .map_or_else(|| "translation failed".to_string(), |e| e.to_string())
+}
+
+/// Indices of the chunks saved with a failure marker.
+pub(crate) fn failed_chunk_indices(chunks: &[TranslatedChunk]) -> Vec<usize> {
+    chunks
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.failed)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Joins a chapter's translated chunks, laid out as `[output]` asks.
+pub(crate) fn chapter_body(params: &ProcessParams<'_>, chunks: &[TranslatedChunk]) -> String {
+    let output = &params.config.output;
+    postprocess::apply_layout(
+        &join_chunks(chunks),
+        output.paragraph_spacing,
+        output.wrap_columns,
+    )
+}
+
+/// Name list and character sheet for the content prompt, each included if
+/// `translation.inject_names` or `translation.inject_character_sheet` is set.
+pub(crate) fn name_prompt_context(
+    config: &Config,
+    name_mapping: &NameMappingStore,
+) -> Option<String> {
+    let translation = &config.translation;
+    let sections: Vec<String> = [
+        translation
+            .inject_names
+            .then(|| name_mapping.prompt_name_list())
+            .flatten(),
+        translation
+            .inject_character_sheet
+            .then(|| name_mapping.character_sheet())
+            .flatten(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!sections.is_empty()).then(|| sections.join("\n\n"))
+}
+
+/// Writes the enabled side-by-side outputs for a chapter: aligned pairs to
+/// `Bilingual/<stem>.<ext>` and annotated text to `Annotated/<stem>.txt`.
+pub(crate) fn write_aligned_exports(
+    params: &ProcessParams<'_>,
+    story_dir: &Path,
+    stem: &str,
+    chapter: u32,
+    chunks: &[TranslatedChunk],
+) -> Result<()> {
+    let output = &params.config.output;
+
+    let format = output.bilingual_export;
+    if let Some(extension) = format.extension() {
+        let pairs = bilingual::align_paragraphs(chapter, chunks);
+        let bilingual_dir = story_dir.join("Bilingual");
+        std::fs::create_dir_all(&bilingual_dir)?;
+        std::fs::write(
+            bilingual_dir.join(format!("{}.{}", stem, extension)),
+            bilingual::format_pairs(&pairs, format),
+        )?;
+    }
+
+    if output.annotated {
+        let annotated_dir = story_dir.join("Annotated");
+        std::fs::create_dir_all(&annotated_dir)?;
+        std::fs::write(
+            annotated_dir.join(format!("{}.txt", stem)),
+            bilingual::format_annotated(chunks, &output.annotation_marker),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Brings a repaired chapter's side-by-side outputs up to date: each repaired
+/// chunk replaces its failed block in `Annotated/<stem>.txt`, and its pairs are
+/// appended to `Bilingual/<stem>.<ext>`, which left the failed chunk out.
+///
+/// Only outputs that were written when the chapter was translated are touched.
+pub(crate) fn refresh_aligned_exports(
+    console: &Console,
+    output: &OutputConfig,
+    story_dir: &Path,
+    stem: &str,
+    chapter: u32,
+    repaired: &[TranslatedChunk],
+) -> Result<()> {
+    if repaired.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(extension) = output.bilingual_export.extension() {
+        let path = story_dir
+            .join("Bilingual")
+            .join(format!("{}.{}", stem, extension));
+        if path.exists() {
+            let pairs = bilingual::align_paragraphs(chapter, repaired);
+            let mut file = std::fs::OpenOptions::new().append(true).open(&path)?;
+            file.write_all(bilingual::format_pairs(&pairs, output.bilingual_export).as_bytes())?;
+        }
+    }
+
+    let path = story_dir.join("Annotated").join(format!("{}.txt", stem));
+    if let Ok(mut annotated) = std::fs::read_to_string(&path) {
+        let mut stale = false;
+        for chunk in repaired {
+            let block = format!("{}\n{}", FAILED_CHUNK_MARKER, chunk.source);
+            if annotated.contains(&block) {
+                let replacement = bilingual::format_annotated(
+                    std::slice::from_ref(chunk),
+                    &output.annotation_marker,
+                );
+                annotated = annotated.replacen(&block, replacement.trim_end_matches('\n'), 1);
+            } else {
+                stale = true;
+            }
+        }
+        std::fs::write(&path, &annotated)?;
+        if stale {
+            console.warning(&format!(
+                "Annotated/{}.txt no longer matches the translation; re-translate the chapter to \
+                 regenerate it",
+                stem
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects a chapter's provenance, including the tokens used since the
+/// translator's usage was last taken.
+pub(crate) fn chapter_metadata(
+    params: &ProcessParams<'_>,
+    record: Option<&ChapterRecord>,
+    translated_title: &str,
+) -> ChapterMetadata {
+    let record = record.cloned().unwrap_or_default();
+    ChapterMetadata {
+        novel_title: params.novel_info.title.clone(),
+        chapter_number: record.number,
+        original_title: record.title,
+        translated_title: translated_title.to_string(),
+        source_url: record.url,
+        model: params.translator.model().to_string(),
+        prompt_hash: content_hash(params.translator.content_prompt()),
+        downloaded_at: (record.downloaded_unix > 0)
+            .then(|| format_unix_timestamp(record.downloaded_unix)),
+        translated_at: format_unix_timestamp(now_unix()),
+        usage: params.translator.take_usage(),
+    }
+}
+
+/// Records the model, prompt, and name mapping a translation was made with.
+pub(crate) fn translation_provenance(params: &ProcessParams<'_>) -> TranslationProvenance {
+    TranslationProvenance {
+        model: params.translator.model().to_string(),
+        prompt_hash: content_hash(params.translator.content_prompt()),
+        names_hash: params.name_mapping.version(),
+        translated_unix: now_unix(),
+        profile: params.chapter_profile.map(str::to_string),
+    }
+}
+
+/// Writes a chapter's metadata as configured: a `Metadata/<stem>.json`
+/// sidecar, or front matter on the returned text.
+pub(crate) fn write_metadata(
+    params: &ProcessParams<'_>,
+    story_dir: &Path,
+    stem: &str,
+    metadata: &ChapterMetadata,
+    translated: String,
+) -> Result<String> {
+    match params.config.output.metadata {
+        MetadataFormat::None => Ok(translated),
+        MetadataFormat::FrontMatter => Ok(metadata::with_front_matter(metadata, &translated)),
+        MetadataFormat::Json => {
+            let metadata_dir = story_dir.join(metadata::METADATA_DIR);
+            std::fs::create_dir_all(&metadata_dir)?;
+            std::fs::write(
+                metadata_dir.join(format!("{}.json", stem)),
+                metadata.to_json(),
+            )?;
+            Ok(translated)
+        }
+    }
+}
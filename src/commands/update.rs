@@ -0,0 +1,291 @@
+//! `tsundoku update` and `tsundoku batch`: runs over several novels.
+
+use super::process::{JobOptions, Limiters, process_novel};
+use super::sync::sync_after_run;
+use super::{load_run_config, verify_endpoints};
+use crate::{BatchArgs, UpdateArgs};
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use std::time::Instant;
+use tsundoku::batch;
+use tsundoku::config::Config;
+use tsundoku::console::Console;
+use tsundoku::history::{self, NovelRun, RunRecord};
+use tsundoku::library::{Library, LibraryEntry};
+use tsundoku::manifest::ChapterRecord;
+use tsundoku::utils::{format_unix_timestamp, now_unix};
+
+/// Handles `tsundoku update`: processes tracked novels that are due for a
+/// check, highest priority first.
+pub(crate) async fn run_update(console: &Console, args: UpdateArgs) -> Result<()> {
+    console.section("Tsundoku - Library Update");
+
+    let Some(config) = load_run_config(console, args.debug, args.no_name_scout)? else {
+        return Ok(());
+    };
+    verify_endpoints(console, &config, !args.no_name_scout).await?;
+
+    let library_path = Config::library_path()?;
+    let library = Library::open(&library_path)?;
+    let started_unix = now_unix();
+    // `--all` ignores intervals by checking as if every interval had elapsed
+    let now = if args.all { u64::MAX } else { started_unix };
+    let tracked: Vec<LibraryEntry> = library.due(now).into_iter().cloned().collect();
+    if tracked.is_empty() {
+        if library.tracked().next().is_none() {
+            console.info("No tracked novels. Add some with `tsundoku library add <url>`.");
+        } else {
+            console.info("No tracked novels are due for a check (use --all to check anyway)");
+        }
+        return Ok(());
+    }
+    let skipped = library.tracked().count() - tracked.len();
+    if skipped > 0 {
+        console.info(&format!(
+            "Skipping {} novel(s) checked within their interval",
+            skipped
+        ));
+    }
+
+    let options = JobOptions {
+        no_name_pause: no_name_pause(console, args.no_name_pause, args.jobs),
+        no_name_scout: args.no_name_scout,
+        no_progress: args.no_progress,
+        ..Default::default()
+    };
+    let started = Instant::now();
+    let limiters = Limiters::default();
+
+    let results: Vec<_> = futures::stream::iter(tracked.iter().enumerate())
+        .map(|(i, entry)| {
+            let config = &config;
+            let limiters = &limiters;
+            let total = tracked.len();
+            async move {
+                console.section(&format!("[{}/{}] {}", i + 1, total, entry.title));
+                let novel_started = Instant::now();
+                let mut run = NovelRun::new(&entry.url);
+                let result =
+                    process_novel(console, config, &entry.url, options, limiters, &mut run).await;
+                run.finish(novel_started.elapsed(), error_message(&result));
+                if let Err(e) = &result {
+                    console.error(&format!("{}: {:#}", entry.title, e));
+                }
+                (result, run)
+            }
+        })
+        .buffered(args.jobs as usize)
+        .collect()
+        .await;
+
+    let mut failed = Vec::new();
+    let mut removed = Vec::new();
+    let mut checked = Vec::new();
+    let mut novels = Vec::new();
+    for (entry, (result, run)) in tracked.iter().zip(results) {
+        novels.push(run);
+        match result {
+            Ok(records) => {
+                removed.extend(records.into_iter().map(|r| (&entry.title, r)));
+                checked.push(entry);
+            }
+            Err(_) => failed.push(&entry.title),
+        }
+    }
+
+    // Reopen, since processing each novel refreshes its library entry
+    let mut library = Library::open(&library_path)?;
+    for entry in checked {
+        library.mark_checked(&entry.site, &entry.novel_id, started_unix);
+    }
+    if let Err(e) = library.save() {
+        console.warning(&format!("Could not record update checks: {:#}", e));
+    }
+    sync_after_run(console, &config).await;
+
+    let summary = report_run_summary(console, "Update Summary", tracked.len(), &failed, &removed);
+    record_run(
+        console,
+        started,
+        started_unix,
+        novels,
+        error_message(&summary),
+    );
+    summary
+}
+
+/// Handles `tsundoku batch`: processes each line of a URL list file in turn.
+pub(crate) async fn run_batch(console: &Console, args: BatchArgs) -> Result<()> {
+    console.section("Tsundoku - Batch Run");
+
+    // Parse the whole file up front so a typo on line 40 fails before any work
+    let jobs = batch::load_batch_file(&args.file)
+        .with_context(|| format!("Failed to load {}", args.file.display()))?;
+    if jobs.is_empty() {
+        console.info("Batch file has no entries");
+        return Ok(());
+    }
+
+    let Some(config) = load_run_config(console, args.debug, args.no_name_scout)? else {
+        return Ok(());
+    };
+    verify_endpoints(console, &config, !args.no_name_scout).await?;
+
+    // Reject unknown profiles before starting
+    for job in &jobs {
+        if let Some(profile) = &job.profile {
+            config
+                .clone()
+                .apply_profile(profile)
+                .with_context(|| format!("Line {}", job.line))?;
+        }
+    }
+
+    let no_name_pause = no_name_pause(console, args.no_name_pause, args.jobs);
+    let started = Instant::now();
+    let started_unix = now_unix();
+    let limiters = Limiters::default();
+    let results: Vec<_> = futures::stream::iter(jobs.iter().enumerate())
+        .map(|(i, job)| {
+            let config = &config;
+            let limiters = &limiters;
+            let total = jobs.len();
+            async move {
+                console.section(&format!("[{}/{}] {}", i + 1, total, job.url));
+
+                let novel_started = Instant::now();
+                let mut run = NovelRun::new(&job.url);
+                let result = async {
+                    let mut job_config = config.clone();
+                    if let Some(profile) = &job.profile {
+                        job_config.apply_profile(profile)?;
+                        console.info(&format!(
+                            "Using profile '{}' ({})",
+                            profile, job_config.api.model
+                        ));
+                    }
+
+                    let options = JobOptions {
+                        start: job.start,
+                        end: job.end,
+                        no_name_pause,
+                        no_name_scout: args.no_name_scout,
+                        no_progress: args.no_progress,
+                        ..Default::default()
+                    };
+                    process_novel(console, &job_config, &job.url, options, limiters, &mut run).await
+                }
+                .await;
+                run.finish(novel_started.elapsed(), error_message(&result));
+                if let Err(e) = &result {
+                    console.error(&format!("{}: {:#}", job.url, e));
+                }
+                (result, run)
+            }
+        })
+        .buffered(args.jobs as usize)
+        .collect()
+        .await;
+
+    let mut failed = Vec::new();
+    let mut removed = Vec::new();
+    let mut novels = Vec::new();
+    for (job, (result, run)) in jobs.iter().zip(results) {
+        novels.push(run);
+        match result {
+            Ok(records) => removed.extend(records.into_iter().map(|r| (&job.url, r))),
+            Err(_) => failed.push(&job.url),
+        }
+    }
+    sync_after_run(console, &config).await;
+
+    let summary = report_run_summary(console, "Batch Summary", jobs.len(), &failed, &removed);
+    record_run(
+        console,
+        started,
+        started_unix,
+        novels,
+        error_message(&summary),
+    );
+    summary
+}
+
+/// Appends a run to the history log; failing to write it only warns.
+pub(crate) fn record_run(
+    console: &Console,
+    started: Instant,
+    started_unix: u64,
+    novels: Vec<NovelRun>,
+    error: Option<String>,
+) {
+    let record = RunRecord {
+        started_at: format_unix_timestamp(started_unix),
+        args: std::env::args().skip(1).collect(),
+        duration_sec: started.elapsed().as_secs_f64(),
+        novels,
+        error,
+    };
+    let result = Config::history_path()
+        .map_err(anyhow::Error::from)
+        .and_then(|path| Ok(history::append(&path, &record)?));
+    if let Err(e) = result {
+        console.warning(&format!("Could not record run history: {:#}", e));
+    }
+}
+
+/// The error of a failed result, with its causes, for the run history.
+pub(crate) fn error_message<T>(result: &Result<T>) -> Option<String> {
+    result.as_ref().err().map(|e| format!("{:#}", e))
+}
+
+/// Resolves `--no-name-pause` for a multi-novel run.
+///
+/// Parallel novels can't share the terminal for interactive review, so the
+/// pause is skipped whenever more than one novel runs at once.
+fn no_name_pause(console: &Console, requested: bool, jobs: u32) -> bool {
+    if jobs > 1 && !requested {
+        console.warning("Running novels in parallel; skipping manual name mapping review");
+        return true;
+    }
+    requested
+}
+
+/// Prints a multi-novel run summary, failing if any novel failed.
+///
+/// Chapters that disappeared upstream are listed per novel so the user knows
+/// which local copies can no longer be re-downloaded.
+fn report_run_summary(
+    console: &Console,
+    heading: &str,
+    total: usize,
+    failed: &[&String],
+    removed: &[(&String, ChapterRecord)],
+) -> Result<()> {
+    console.section(heading);
+    console.info(&format!(
+        "{} novel(s) processed, {} failed",
+        total,
+        failed.len()
+    ));
+    for name in failed {
+        console.warning(&format!("Failed: {}", name));
+    }
+    if !removed.is_empty() {
+        console.warning(&format!(
+            "{} chapter(s) removed upstream; local copies are now the only copies:",
+            removed.len()
+        ));
+        for (name, record) in removed {
+            console.warning(&format!(
+                "  {}: chapter {} ({})",
+                name, record.number, record.title
+            ));
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!("{} novel(s) failed", failed.len())
+    }
+}
@@ -0,0 +1,129 @@
+//! Side-by-side report for `tsundoku compare`.
+//!
+//! The same chapter is translated with two models or prompts, and the
+//! results are laid out chunk by chunk under each source chunk so the two
+//! can be read against each other.
+
+use std::time::Duration;
+
+/// One side of a model comparison.
+#[derive(Debug, Clone)]
+pub struct CompareSide {
+    /// Side name in headings (e.g. "A").
+    pub label: String,
+    /// Model that produced the translation.
+    pub model: String,
+    /// Where the side's prompt came from (the configured prompt or a file).
+    pub prompt_source: String,
+    /// Translated chunks, in the same order as the source chunks.
+    pub chunks: Vec<String>,
+    /// Time the side took to translate.
+    pub elapsed: Duration,
+}
+
+/// Renders a chunk-by-chunk Markdown comparison of two translations.
+pub fn format_comparison(
+    novel_title: &str,
+    chapter_title: &str,
+    source_chunks: &[String],
+    sides: &[CompareSide],
+) -> String {
+    let mut out = format!("# {} — {}\n\n", novel_title, chapter_title);
+    for side in sides {
+        out.push_str(&format!(
+            "- **{}**: `{}` (prompt: {}, {:.1}s)\n",
+            side.label,
+            side.model,
+            side.prompt_source,
+            side.elapsed.as_secs_f64()
+        ));
+    }
+
+    let total = source_chunks.len();
+    for (i, source) in source_chunks.iter().enumerate() {
+        out.push_str(&format!(
+            "\n## Chunk {}/{}\n\n### Source\n\n{}\n",
+            i + 1,
+            total,
+            source
+        ));
+        for side in sides {
+            let text = side.chunks.get(i).map(String::as_str).unwrap_or("");
+            out.push_str(&format!(
+                "\n### {}: {}\n\n{}\n",
+                side.label, side.model, text
+            ));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(label: &str, model: &str, chunks: &[&str]) -> CompareSide {
+        CompareSide {
+            label: label.to_string(),
+            model: model.to_string(),
+            prompt_source: "config".to_string(),
+            chunks: chunks.iter().map(|c| c.to_string()).collect(),
+            elapsed: Duration::from_millis(1500),
+        }
+    }
+
+    #[test]
+    fn test_chunks_aligned_under_source() {
+        let sources = vec!["一つ目".to_string(), "二つ目".to_string()];
+        let sides = [
+            side("A", "cheap", &["First (A)", "Second (A)"]),
+            side("B", "pricey", &["First (B)", "Second (B)"]),
+        ];
+        let report = format_comparison("Novel", "Chapter 1", &sources, &sides);
+
+        assert!(report.starts_with("# Novel — Chapter 1\n\n"));
+        assert!(report.contains("- **A**: `cheap` (prompt: config, 1.5s)\n"));
+        assert!(report.contains("- **B**: `pricey` (prompt: config, 1.5s)\n"));
+
+        // Each chunk's source is followed by both sides, in order
+        let expected = "\n## Chunk 2/2\n\n### Source\n\n二つ目\n\
+                        \n### A: cheap\n\nSecond (A)\n\
+                        \n### B: pricey\n\nSecond (B)\n";
+        assert!(report.ends_with(expected));
+        let first = report.find("First (A)").unwrap();
+        assert!(first < report.find("First (B)").unwrap());
+        assert!(report.find("First (B)").unwrap() < report.find("## Chunk 2/2").unwrap());
+    }
+
+    #[test]
+    fn test_missing_chunks_left_empty() {
+        // A side that stopped early still gets a heading for every chunk
+        let sources = vec!["一".to_string(), "二".to_string(), "三".to_string()];
+        let sides = [
+            side("A", "a", &["one", "two", "three"]),
+            side("B", "b", &["one"]),
+        ];
+        let report = format_comparison("N", "C", &sources, &sides);
+
+        assert_eq!(report.matches("### B: b").count(), 3);
+        assert!(report.ends_with("\n### A: a\n\nthree\n\n### B: b\n\n\n"));
+        // Extra chunks past the source are dropped
+        let sides = [side("A", "a", &["one", "extra"]), side("B", "b", &["one"])];
+        let report = format_comparison("N", "C", &sources[..1], &sides);
+        assert!(!report.contains("extra"));
+    }
+
+    #[test]
+    fn test_text_kept_verbatim() {
+        // Long lines and paragraph breaks are not rewrapped
+        let long = "word ".repeat(60).trim_end().to_string();
+        let translated = format!("{}\n\nSecond paragraph.", long);
+        let sources = vec!["原文\n\n二段落目".to_string()];
+        let sides = [side("A", "a", &[&translated]), side("B", "b", &[""])];
+        let report = format_comparison("N", "C", &sources, &sides);
+
+        assert!(report.contains(&format!("\n\n{}\n\nSecond paragraph.\n", long)));
+        assert!(report.contains("\n\n原文\n\n二段落目\n"));
+    }
+}
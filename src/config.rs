@@ -3,9 +3,14 @@
 //! Handles loading, saving, and validating configuration from
 //! platform-specific config directories.
 
-use crate::error::ConfigError;
+use crate::error::{ConfigError, RetryKind};
+use crate::history::HISTORY_FILENAME;
+use crate::utils::jittered_delay;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
 /// Application name used for config directory.
 const APP_NAME: &str = "Tsundoku";
@@ -40,6 +45,23 @@ pub struct Config {
 
     /// File paths.
     pub paths: PathsConfig,
+
+    /// Output file options.
+    pub output: OutputConfig,
+
+    /// Named overrides selectable per run or per batch line (`--profile NAME`).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+
+    /// Clean-up applied to translated text before saving.
+    pub postprocess: PostprocessConfig,
+
+    /// Per-novel settings keyed by "site:novel_id" (e.g. "syosetu:n1234ab").
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub novels: BTreeMap<String, NovelConfig>,
+
+    /// Uploading the output directory to remote storage.
+    pub sync: SyncConfig,
 }
 
 impl Default for Config {
@@ -52,6 +74,11 @@ impl Default for Config {
             scraping: ScrapingConfig::default(),
             prompts: PromptsConfig::default(),
             paths: PathsConfig::default(),
+            output: OutputConfig::default(),
+            profiles: BTreeMap::new(),
+            postprocess: PostprocessConfig::default(),
+            novels: BTreeMap::new(),
+            sync: SyncConfig::default(),
         }
     }
 }
@@ -63,11 +90,39 @@ pub struct ApiConfig {
     /// API key (required).
     pub key: String,
 
-    /// Base URL for the API.
+    /// Base URL for the API (e.g. `https://api.openai.com/v1`). Trailing
+    /// slashes, a full `/chat/completions` path, and a bare host missing
+    /// `/v1` are corrected.
     pub base_url: String,
 
     /// Model identifier.
     pub model: String,
+
+    /// Whether the endpoint accepts the `system` role. When false, the system
+    /// prompt is folded into the first user message (o1-style models).
+    pub supports_system_role: bool,
+
+    /// Whether the endpoint accepts `stream: true`. When false, responses are
+    /// requested in one piece and progress is shown only on completion.
+    pub supports_streaming: bool,
+
+    /// Extra fields merged into every chat completion request body, for
+    /// provider-specific knobs such as `reasoning_effort` or `provider`.
+    /// Keys here override the fields Tsundoku sets itself.
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra_body: serde_json::Map<String, serde_json::Value>,
+
+    /// Check that the endpoint answers before a run starts, so a rejected
+    /// key fails up front and a likely wrong `base_url` is warned about.
+    pub verify_endpoint: bool,
+
+    /// Price per million prompt tokens, for the cost estimate in
+    /// `tsundoku info` (unset to leave cost out).
+    pub input_price_per_mtok: Option<f64>,
+
+    /// Price per million completion tokens, for the cost estimate in
+    /// `tsundoku info` (unset to leave cost out).
+    pub output_price_per_mtok: Option<f64>,
 }
 
 impl Default for ApiConfig {
@@ -76,6 +131,12 @@ impl Default for ApiConfig {
             key: API_KEY_PLACEHOLDER.to_string(),
             base_url: "https://api.openai.com/v1".to_string(),
             model: "gpt-4o-mini".to_string(),
+            supports_system_role: true,
+            supports_streaming: true,
+            extra_body: serde_json::Map::new(),
+            verify_endpoint: true,
+            input_price_per_mtok: None,
+            output_price_per_mtok: None,
         }
     }
 }
@@ -85,32 +146,253 @@ impl ApiConfig {
     pub fn is_configured(&self) -> bool {
         !self.key.is_empty() && self.key != API_KEY_PLACEHOLDER
     }
+
+    /// URL of an endpoint under the (normalized) base URL, e.g.
+    /// `endpoint_url("chat/completions")`.
+    pub fn endpoint_url(&self, path: &str) -> String {
+        format!("{}/{}", normalize_base_url(&self.base_url), path)
+    }
+
+    /// Serializes a request and merges `extra_body` into it.
+    pub fn request_body<T: Serialize>(&self, request: &T) -> serde_json::Result<serde_json::Value> {
+        let mut body = serde_json::to_value(request)?;
+        if let serde_json::Value::Object(fields) = &mut body {
+            for (key, value) in &self.extra_body {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+        Ok(body)
+    }
+}
+
+/// Corrects common `base_url` mistakes: surrounding whitespace, trailing
+/// slashes, a full endpoint path (`/chat/completions`, `/models`), and a
+/// bare host without `/v1`.
+pub fn normalize_base_url(base_url: &str) -> String {
+    let mut url = base_url.trim().trim_end_matches('/');
+    for endpoint in ["/chat/completions", "/completions", "/models"] {
+        if let Some(base) = url.strip_suffix(endpoint) {
+            url = base.trim_end_matches('/');
+            break;
+        }
+    }
+    let bare_host = reqwest::Url::parse(url).is_ok_and(|parsed| parsed.path() == "/");
+    if bare_host {
+        format!("{}/v1", url)
+    } else {
+        url.to_string()
+    }
 }
 
 /// Translation behavior configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TranslationConfig {
-    /// Maximum characters per translation chunk.
+    /// Maximum size of a translation chunk, in `chunk_size_unit`s.
     pub chunk_size_chars: usize,
 
-    /// Number of retry attempts for failed translations.
+    /// What `chunk_size_chars` counts.
+    pub chunk_size_unit: ChunkSizeUnit,
+
+    /// How much of the end of each chunk, in `chunk_size_unit`s, to send
+    /// again ahead of the next one (0 for none). Its translation is dropped
+    /// from the next chunk's.
+    pub chunk_overlap: usize,
+
+    /// Attempts per chunk before it is marked failed, for each kind of error
+    /// `retry` doesn't set a limit for.
     pub retries: u32,
 
+    /// Backoff between attempts and per-error retry limits.
+    pub retry: RetryPolicy,
+
     /// Delay between API requests in seconds.
     pub delay_between_requests_sec: f64,
 
+    /// Random extra delay added to each request delay, in seconds: a value
+    /// between `jitter_min_sec` and `jitter_max_sec` (0 and 0 for none).
+    pub jitter_min_sec: f64,
+
+    /// Upper bound of the random extra delay, in seconds.
+    pub jitter_max_sec: f64,
+
     /// Number of message pairs to retain in conversation history.
     pub history_length: usize,
+
+    /// Estimated tokens the history (system prompt and retained pairs) may
+    /// take up; the oldest pairs are dropped until it fits (0 for no limit).
+    pub history_max_tokens: usize,
+
+    /// Paragraphs from the end of the previous chapter, source and
+    /// translation, to start each chapter's history with (0 for none), so
+    /// cliffhangers and pronouns carry across chapters.
+    pub previous_chapter_paragraphs: usize,
+
+    /// Continuation requests to issue when a response hits the output token
+    /// limit (`finish_reason: "length"`) before giving up on the rest of it.
+    pub max_continuations: u32,
+
+    /// Append the novel's name mapping to the content prompt, so the model
+    /// renders names the same way where substitution missed them.
+    pub inject_names: bool,
+
+    /// Replace names written in kanji that have no confident English
+    /// rendering with their kana reading in romaji, instead of leaving the
+    /// kanji for the model to guess at in each chunk.
+    pub romanize_fallback: bool,
+
+    /// Append a character sheet (gender, role, relationships noted on
+    /// mapped names) to the content prompt, so pronouns and family terms
+    /// come out right.
+    pub inject_character_sheet: bool,
+
+    /// Before a run, send one tiny request to each model it will use
+    /// (titles, content, and name scouting), so a bad key or model name fails
+    /// in seconds instead of after the downloads.
+    pub preflight: bool,
+
+    /// File to log every API request to (metadata, truncated prompts and
+    /// responses, status, timing, and token usage; the API key is
+    /// redacted). Unset for no log.
+    pub debug_log: Option<PathBuf>,
+
+    /// Model for chapter title requests (unset for `api.model`).
+    pub title_model: Option<String>,
+
+    /// Sampling temperature for title requests (unset for the endpoint's
+    /// default).
+    pub title_temperature: Option<f64>,
+
+    /// Model for content requests (unset for `api.model`).
+    pub content_model: Option<String>,
+
+    /// Sampling temperature for content requests (unset for the endpoint's
+    /// default).
+    pub content_temperature: Option<f64>,
+
+    /// How stat blocks (status screens, tables, boxed system messages) are
+    /// handled: "off" (chunked with the prose), "translate" (sent on their
+    /// own with `prompts.stat_block_translation`), or "keep" (left
+    /// untranslated).
+    pub stat_blocks: StatBlockMode,
+}
+
+impl TranslationConfig {
+    /// Delay before the next API request, with jitter applied.
+    pub fn request_delay_sec(&self) -> f64 {
+        jittered_delay(
+            self.delay_between_requests_sec,
+            self.jitter_min_sec,
+            self.jitter_max_sec,
+        )
+    }
 }
 
 impl Default for TranslationConfig {
     fn default() -> Self {
         Self {
             chunk_size_chars: 4000,
+            chunk_size_unit: ChunkSizeUnit::Chars,
+            chunk_overlap: 0,
             retries: 3,
+            retry: RetryPolicy::default(),
             delay_between_requests_sec: 1.0,
+            jitter_min_sec: 0.0,
+            jitter_max_sec: 0.0,
             history_length: 5,
+            history_max_tokens: 0,
+            previous_chapter_paragraphs: 0,
+            max_continuations: 3,
+            inject_names: false,
+            romanize_fallback: false,
+            inject_character_sheet: false,
+            preflight: false,
+            debug_log: None,
+            title_model: None,
+            title_temperature: None,
+            content_model: None,
+            content_temperature: None,
+            stat_blocks: StatBlockMode::default(),
+        }
+    }
+}
+
+/// Backoff between retries and how many failures of each kind to allow
+/// (`[translation.retry]`, `[name_scout.retry]`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Delay before the first retry, in seconds; it doubles for each retry
+    /// after that.
+    pub base_delay_sec: f64,
+
+    /// Longest delay between retries, in seconds.
+    pub max_delay_sec: f64,
+
+    /// Upper bound of a random extra delay added to each backoff, in
+    /// seconds (0 for none).
+    pub jitter_sec: f64,
+
+    /// Failed attempts allowed for network errors, rate limiting, and server
+    /// errors (unset for the section's default).
+    pub network_retries: Option<u32>,
+
+    /// Failed attempts allowed for refusals and empty responses (unset for
+    /// the section's default).
+    pub refusal_retries: Option<u32>,
+
+    /// Failed attempts allowed for unparseable responses (unset for the
+    /// section's default).
+    pub parse_retries: Option<u32>,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (1 for the first), with jitter
+    /// applied.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let doublings = retry.saturating_sub(1).min(32) as i32;
+        let delay = (self.base_delay_sec * 2f64.powi(doublings)).min(self.max_delay_sec);
+        Duration::from_secs_f64(jittered_delay(delay, 0.0, self.jitter_sec))
+    }
+
+    /// Failed attempts allowed for errors of `kind`, or `default` if the
+    /// policy doesn't set a limit for it.
+    pub fn attempts(&self, kind: RetryKind, default: u32) -> u32 {
+        match kind {
+            RetryKind::Network => self.network_retries,
+            RetryKind::Refusal => self.refusal_retries,
+            RetryKind::Parse => self.parse_retries,
+        }
+        .unwrap_or(default)
+    }
+
+    /// Checks that the delays are non-negative and finite.
+    fn validate(&self, section: &str) -> Result<(), ConfigError> {
+        for (key, value) in [
+            ("base_delay_sec", self.base_delay_sec),
+            ("max_delay_sec", self.max_delay_sec),
+            ("jitter_sec", self.jitter_sec),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(ConfigError::InvalidValue {
+                    key: format!("{}.retry.{}", section, key),
+                    message: "must be a non-negative number of seconds".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay_sec: 2.0,
+            max_delay_sec: 60.0,
+            jitter_sec: 0.0,
+            network_retries: None,
+            refusal_retries: None,
+            parse_retries: None,
         }
     }
 }
@@ -119,26 +401,49 @@ impl Default for TranslationConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct NameScoutConfig {
-    /// Maximum characters per name scout chunk.
+    /// Maximum size of a name scout chunk, in `chunk_size_unit`s.
     pub chunk_size_chars: usize,
 
-    /// Number of retry attempts.
+    /// What `chunk_size_chars` counts.
+    pub chunk_size_unit: ChunkSizeUnit,
+
+    /// Attempts per chunk that may fail with network errors, unless `retry`
+    /// sets a limit.
     pub retries: u32,
 
     /// Delay between requests in seconds.
     pub delay_between_requests_sec: f64,
 
-    /// Number of JSON parsing retry attempts.
+    /// Attempts per chunk that may fail with unparseable JSON or a refusal,
+    /// unless `retry` sets a limit.
     pub json_retries: u32,
+
+    /// Backoff between attempts and per-error retry limits.
+    pub retry: RetryPolicy,
+
+    /// Chunks scouted at once.
+    pub concurrency: usize,
+
+    /// Model for name scout requests (unset for `scout_api.model`).
+    pub model: Option<String>,
+
+    /// Sampling temperature for name scout requests (unset for the
+    /// endpoint's default).
+    pub temperature: Option<f64>,
 }
 
 impl Default for NameScoutConfig {
     fn default() -> Self {
         Self {
             chunk_size_chars: 2500,
+            chunk_size_unit: ChunkSizeUnit::Chars,
             retries: 3,
             delay_between_requests_sec: 1.0,
             json_retries: 3,
+            retry: RetryPolicy::default(),
+            concurrency: 1,
+            model: None,
+            temperature: None,
         }
     }
 }
@@ -149,19 +454,117 @@ impl Default for NameScoutConfig {
 pub struct ScrapingConfig {
     /// Delay between web requests in seconds.
     pub delay_between_requests_sec: f64,
+    /// Random extra delay added to each request delay, in seconds: a value
+    /// between `jitter_min_sec` and `jitter_max_sec` (0 and 0 for none).
+    pub jitter_min_sec: f64,
+    /// Upper bound of the random extra delay, in seconds.
+    pub jitter_max_sec: f64,
+    /// Overall timeout for each web request, in seconds.
+    pub timeout_sec: u64,
+    /// Idle connections kept open per host (unset for no limit).
+    pub pool_max_idle_per_host: Option<usize>,
+    /// TCP keepalive interval in seconds (0 to disable).
+    pub tcp_keepalive_sec: u64,
+    /// HTTP version to use: "auto" (negotiated), "http1", or "http2".
+    pub http_version: HttpVersion,
     /// Enable scraper debug logging.
     pub debug: bool,
+    /// How ruby readings (furigana) are kept in downloaded originals.
+    pub furigana: FuriganaMode,
+    /// Requests allowed in flight to one host at once, across every novel
+    /// in the run.
+    pub max_requests_per_host: usize,
+    /// Delay between requests in seconds for particular hosts (e.g.
+    /// `"ncode.syosetu.com" = 2.0`), replacing `delay_between_requests_sec`
+    /// and its jitter there.
+    pub host_delays: BTreeMap<String, f64>,
+    /// Fetch each site's robots.txt, skip the pages it disallows, and wait
+    /// at least its `Crawl-delay` between requests.
+    pub respect_robots_txt: bool,
+    /// Fewest characters a downloaded chapter may have before it's rejected
+    /// as empty or broken (0 to only reject blank chapters).
+    pub min_chapter_chars: usize,
+}
+
+impl ScrapingConfig {
+    /// Delay before the next web request, with jitter applied.
+    pub fn request_delay_sec(&self) -> f64 {
+        jittered_delay(
+            self.delay_between_requests_sec,
+            self.jitter_min_sec,
+            self.jitter_max_sec,
+        )
+    }
+
+    /// Delay before the next request to `host`, with jitter applied unless
+    /// the host has its own delay.
+    pub fn host_delay_sec(&self, host: &str) -> f64 {
+        self.host_delays
+            .get(host)
+            .copied()
+            .unwrap_or_else(|| self.request_delay_sec())
+    }
 }
 
 impl Default for ScrapingConfig {
     fn default() -> Self {
         Self {
             delay_between_requests_sec: 1.0,
+            jitter_min_sec: 0.0,
+            jitter_max_sec: 0.0,
+            timeout_sec: 30,
+            pool_max_idle_per_host: None,
+            tcp_keepalive_sec: 15,
+            http_version: HttpVersion::Auto,
             debug: false,
+            furigana: FuriganaMode::default(),
+            max_requests_per_host: 1,
+            host_delays: BTreeMap::new(),
+            respect_robots_txt: false,
+            min_chapter_chars: 10,
         }
     }
 }
 
+/// HTTP version preference for scraper connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpVersion {
+    /// HTTP/2 when the server offers it, otherwise HTTP/1.1.
+    #[default]
+    Auto,
+    /// HTTP/1.1 only; some flaky proxies mishandle HTTP/2.
+    Http1,
+    /// HTTP/2 only, without falling back.
+    Http2,
+}
+
+/// Handling of ruby readings (furigana) in chapter text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FuriganaMode {
+    /// Keep only the base text: `漢字`.
+    #[default]
+    Drop,
+    /// Append the reading in parentheses: `漢字(かんじ)`.
+    Parens,
+    /// Keep readings in parentheses in originals, and render them as
+    /// `<ruby>漢字<rt>かんじ</rt></ruby>` in HTML and EPUB exports.
+    Html,
+}
+
+/// How a refusal nudge is added to a retried request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum NudgeRole {
+    /// Prepended to the chunk in the user message.
+    #[default]
+    User,
+    /// A system message just before the chunk (folded into the user message
+    /// for endpoints without the system role).
+    System,
+}
+
 /// LLM system prompts.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -174,6 +577,33 @@ pub struct PromptsConfig {
 
     /// Prompt for name extraction.
     pub name_scout: String,
+
+    /// Prompt for term extraction (`tsundoku terms`).
+    pub terminology: String,
+
+    /// Prompt for stat blocks when `translation.stat_blocks` is "translate".
+    pub stat_block_translation: String,
+
+    /// Sent along when retrying a chunk the model refused to translate
+    /// (empty to retry as-is).
+    pub refusal_nudge: String,
+
+    /// Sent along when retrying a chunk the name scout refused (empty to
+    /// retry as-is).
+    pub scout_refusal_nudge: String,
+
+    /// How refusal nudges are sent.
+    pub refusal_nudge_role: NudgeRole,
+
+    /// Text appended to the content prompt for chapters from a site, keyed
+    /// by scraper ID (e.g. "pixiv").
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub site_suffixes: BTreeMap<String, String>,
+
+    /// Genre preset whose instructions are appended to the content prompt
+    /// for every novel without its own `[novels]` preset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<PromptPreset>,
 }
 
 impl Default for PromptsConfig {
@@ -183,8 +613,81 @@ impl Default for PromptsConfig {
             content_translation: "You are a Japanese to English translator specializing in web novels. Translate the following Japanese text to natural English, preserving the author's style and tone. Character names have already been converted to English - do not change them.".to_string(),
             name_scout: r#"You read Japanese fiction text and extract character name parts.
 Return ONLY JSON with this shape:
-{"names":[{"original":"<exact name characters>","part":"family|given|unknown","english":"<best English rendering>"}]}
-Treat given and family names separately. Use romaji or common English equivalents. No explanations."#.to_string(),
+{"names":[{"original":"<exact name characters>","part":"family|given|unknown","english":"<best English rendering>","reading":"<kana reading if written in kanji>","confidence":<0.0-1.0>}]}
+Treat given and family names separately. Use romaji or common English equivalents. Give the reading in kana for names written in kanji; leave it out for kana names. Set confidence to how sure you are that it is a name and that the rendering is right. No explanations."#.to_string(),
+            terminology: r#"You read Japanese fiction text and list the terms a translator needs to render consistently: character names, places, organizations, skills and spells, items, and other coined or story-specific words.
+Return ONLY JSON with this shape:
+{"terms":[{"original":"<exact characters as written>","english":"<best English rendering>","category":"character|place|organization|skill|item|other"}]}
+Use romaji for names and natural English for descriptive terms. Skip ordinary vocabulary. No explanations."#.to_string(),
+            stat_block_translation: "You are a Japanese to English translator. The following text is a status screen, table, or system message from a game-like web novel. Translate the Japanese in it to English, keeping exactly the same lines in the same order: one output line per input line, with brackets, box-drawing characters, separators, numbers, and alignment unchanged. Use standard game terms for stats and skills (e.g. HP, MP, STR, AGI, Lv). Provide only the translated block.".to_string(),
+            refusal_nudge: "This is an excerpt of published fiction. Translate all of it faithfully, without commentary, warnings, or omissions.".to_string(),
+            scout_refusal_nudge: "This is an excerpt of published fiction. Only list the character and place names it contains; no other response is needed.".to_string(),
+            refusal_nudge_role: NudgeRole::User,
+            site_suffixes: BTreeMap::new(),
+            preset: None,
+        }
+    }
+}
+
+impl PromptsConfig {
+    /// Content prompt for chapters from a site, with its suffix appended.
+    pub fn content_prompt_for(&self, site: &str) -> String {
+        match self.site_suffixes.get(site).map(|s| s.trim()) {
+            Some(suffix) if !suffix.is_empty() => {
+                format!("{}\n\n{}", self.content_translation.trim_end(), suffix)
+            }
+            _ => self.content_translation.clone(),
+        }
+    }
+}
+
+/// Genre-specific instructions added to the content prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptPreset {
+    /// Consistent rendering of invented terms, skills, and place names.
+    Fantasy,
+    /// Emotional nuance, speech styles, and terms of address.
+    Romance,
+    /// Status screens and system messages kept line for line.
+    Litrpg,
+    /// Explicit content translated without softening.
+    R18,
+}
+
+impl PromptPreset {
+    /// Instructions appended to the content prompt.
+    pub fn instructions(self) -> &'static str {
+        match self {
+            PromptPreset::Fantasy => {
+                "This is a fantasy story. Translate invented terms, skills, spells, monsters, and titles the same way every time they appear. Render descriptive names in natural English (e.g. 冒険者ギルド as \"Adventurers' Guild\") and romanize proper names of people and places."
+            }
+            PromptPreset::Romance => {
+                "This is a romance story. Preserve the emotional nuance of dialogue and inner monologue, keep each character's distinct way of speaking, and render terms of address (e.g. 先輩, お兄様) so the relationship between the characters stays clear."
+            }
+            PromptPreset::Litrpg => {
+                "This is a LitRPG story with game-system elements. Status screens, stat blocks, skill lists, and system messages must keep their exact layout: one entry per line, the same brackets (【】, [], 《》), and all numbers unchanged. Translate stat and skill labels the same way every time (e.g. 筋力 as STR, 敏捷 as AGI), and never merge, reorder, or drop lines in these blocks."
+            }
+            PromptPreset::R18 => {
+                "This is adult fiction intended for adult readers. Translate sexual and explicit content completely and faithfully, at the same level of explicitness as the original, without softening, summarizing, censoring, or adding warnings or commentary."
+            }
+        }
+    }
+}
+
+impl FromStr for PromptPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fantasy" => Ok(PromptPreset::Fantasy),
+            "romance" => Ok(PromptPreset::Romance),
+            "litrpg" => Ok(PromptPreset::Litrpg),
+            "r18" => Ok(PromptPreset::R18),
+            _ => Err(format!(
+                "unknown preset '{}' (expected fantasy, romance, litrpg, or r18)",
+                s
+            )),
         }
     }
 }
@@ -203,6 +706,11 @@ pub struct PathsConfig {
     /// If not set, will try to auto-detect a suitable editor.
     /// Examples: "kate", "vim", "nano", "code", "notepad"
     pub editor_command: Option<String>,
+
+    /// Title used when naming a new story folder: "translated" (one API
+    /// call), "romaji" (kana titles only; others keep the original), or
+    /// "original".
+    pub folder_title: FolderTitle,
 }
 
 impl Default for PathsConfig {
@@ -211,11 +719,365 @@ impl Default for PathsConfig {
             output_directory: PathBuf::from("."),
             names_directory: None,
             editor_command: None,
+            folder_title: FolderTitle::default(),
+        }
+    }
+}
+
+/// Which title a new story folder is named after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FolderTitle {
+    /// The title translated by the API.
+    #[default]
+    Translated,
+    /// The title romanized from kana, with no API call.
+    Romaji,
+    /// The title as the site gives it.
+    Original,
+}
+
+/// Format for the optional bilingual paragraph export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BilingualFormat {
+    /// No bilingual export.
+    #[default]
+    None,
+    /// Tab-separated `source<TAB>translation` lines.
+    Tsv,
+    /// One JSON object per line.
+    Jsonl,
+}
+
+impl BilingualFormat {
+    /// File extension for this format, if exporting.
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            BilingualFormat::None => None,
+            BilingualFormat::Tsv => Some("tsv"),
+            BilingualFormat::Jsonl => Some("jsonl"),
+        }
+    }
+}
+
+/// Where per-chapter provenance metadata is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetadataFormat {
+    /// No metadata.
+    #[default]
+    None,
+    /// A JSON sidecar per chapter in a `Metadata/` folder.
+    Json,
+    /// YAML front matter at the top of each translated file.
+    FrontMatter,
+}
+
+/// Handling of stat blocks in chapter text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StatBlockMode {
+    /// No detection; blocks are chunked and translated with the prose.
+    #[default]
+    Off,
+    /// Each block is translated on its own with the stat block prompt, and
+    /// kept untranslated if the translation changes its line count.
+    Translate,
+    /// Blocks are passed through untranslated.
+    Keep,
+}
+
+/// What a chunk size counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChunkSizeUnit {
+    /// Unicode characters.
+    #[default]
+    Chars,
+    /// UTF-8 bytes (three per Japanese character), as chunk sizes were
+    /// counted before units could be chosen.
+    Bytes,
+    /// Estimated model tokens.
+    Tokens,
+}
+
+/// How paragraphs are separated in translated files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ParagraphSpacing {
+    /// Keep the line structure of the translation (which follows the
+    /// source) exactly.
+    #[default]
+    Preserve,
+    /// Every line is a paragraph, with one blank line between paragraphs.
+    BlankLine,
+}
+
+/// Output file configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputConfig {
+    /// Also write aligned source/translation paragraph pairs per chapter
+    /// into a `Bilingual/` folder: "none", "tsv", or "jsonl".
+    pub bilingual_export: BilingualFormat,
+
+    /// Also write each chapter with every translated paragraph preceded by
+    /// its source into an `Annotated/` folder, for proofreading.
+    pub annotated: bool,
+
+    /// Prefix for source lines in annotated output (e.g. "> " or "# ").
+    pub annotation_marker: String,
+
+    /// Header prepended to each translated file; empty for none. Supports
+    /// `{novel_title}`, `{chapter_number}`, `{chapter_title}`,
+    /// `{chapter_title_original}`, `{url}`, `{date}`, and `{model}`.
+    pub header_template: String,
+
+    /// Keep chapter originals in a single compressed `Original.zip` instead
+    /// of one file per chapter under `Original/`.
+    pub archive_originals: bool,
+
+    /// Keep a `latest.txt` in each story folder pointing at the newest
+    /// translated chapter (a symlink, or a copy on Windows).
+    pub latest_link: bool,
+
+    /// Paragraph separation in translated files: "preserve" or "blank-line".
+    pub paragraph_spacing: ParagraphSpacing,
+
+    /// Wrap translated lines longer than this many columns at spaces; 0
+    /// leaves lines unwrapped.
+    pub wrap_columns: usize,
+
+    /// Per-chapter provenance (source URL, original title, model, prompt
+    /// hash, timestamps, token usage): "none", "json" for a sidecar in
+    /// `Metadata/`, or "front-matter" for YAML at the top of each translation.
+    pub metadata: MetadataFormat,
+
+    /// Character substituted for ones filenames can't contain (`/`, `?`,
+    /// `"`, ...) in translated titles.
+    pub filename_replacement: char,
+
+    /// Number given to the first chapter in file names and headers, with
+    /// later chapters counting on from it; 0 for works that number their
+    /// prologue as chapter 0.
+    pub first_chapter_number: u32,
+}
+
+impl OutputConfig {
+    /// Number chapter `number` (1-based, in site order) gets in file names
+    /// and headers.
+    pub fn file_number(&self, number: u32) -> u32 {
+        number.saturating_sub(1) + self.first_chapter_number
+    }
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            bilingual_export: BilingualFormat::None,
+            annotated: false,
+            annotation_marker: "> ".to_string(),
+            header_template: String::new(),
+            archive_originals: false,
+            latest_link: false,
+            paragraph_spacing: ParagraphSpacing::Preserve,
+            wrap_columns: 0,
+            metadata: MetadataFormat::None,
+            filename_replacement: '_',
+            first_chapter_number: 1,
         }
     }
 }
 
+/// Remote copy of the output directory.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Upload new and changed output files after every download run.
+    pub after_run: bool,
+
+    /// WebDAV share to upload to (e.g. for KOReader).
+    pub webdav: Option<WebDavConfig>,
+
+    /// S3 (or S3-compatible) bucket to upload to.
+    pub s3: Option<S3Config>,
+}
+
+/// WebDAV sync target.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct WebDavConfig {
+    /// Collection URL files are uploaded under.
+    pub url: String,
+
+    /// Username for basic authentication; empty for none.
+    pub username: String,
+
+    /// Password for basic authentication.
+    pub password: String,
+}
+
+/// S3 sync target, addressed path-style (`{endpoint}/{bucket}/{key}`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct S3Config {
+    /// Service endpoint, e.g. `https://s3.us-east-1.amazonaws.com` or a
+    /// MinIO/R2 URL.
+    pub endpoint: String,
+
+    /// Bucket name.
+    pub bucket: String,
+
+    /// Region used for request signing.
+    pub region: String,
+
+    /// Key prefix for uploaded objects (e.g. "novels/"); empty for none.
+    pub prefix: String,
+
+    /// Access key ID.
+    pub access_key: String,
+
+    /// Secret access key.
+    pub secret_key: String,
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://s3.amazonaws.com".to_string(),
+            bucket: String::new(),
+            region: "us-east-1".to_string(),
+            prefix: String::new(),
+            access_key: String::new(),
+            secret_key: String::new(),
+        }
+    }
+}
+
+/// A named set of overrides applied on top of the main configuration.
+///
+/// Unset fields keep the main configuration's values.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ProfileConfig {
+    /// Overrides `api.model` (and `translation.content_model`).
+    pub model: Option<String>,
+
+    /// Overrides `api.base_url`.
+    pub base_url: Option<String>,
+
+    /// Overrides `api.key`.
+    pub key: Option<String>,
+
+    /// Overrides `prompts.content_translation`.
+    pub content_translation: Option<String>,
+
+    /// Overrides `translation.chunk_size_chars`.
+    pub chunk_size_chars: Option<usize>,
+}
+
+/// A regex find/replace applied to translated text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaceRule {
+    /// Regular expression to search for.
+    pub find: String,
+
+    /// Replacement text; `$1`, `${name}` refer to capture groups.
+    pub replace: String,
+}
+
+/// Post-processing of translated text.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PostprocessConfig {
+    /// Quote style: "keep", "curly", or "straight".
+    pub quotes: QuoteStyle,
+
+    /// Ellipsis style: "keep", "unicode" (…), or "dots" (...).
+    pub ellipsis: EllipsisStyle,
+
+    /// Normalize `――`, `──`, and `--` to an em dash.
+    pub dashes: bool,
+
+    /// Convert full-width punctuation to ASCII, drop spaces before
+    /// punctuation, and collapse repeated spaces.
+    pub spacing: bool,
+
+    /// Rules applied to every novel, in order, after the normalizations above.
+    pub rules: Vec<ReplaceRule>,
+}
+
+/// Quote normalization for translated text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    /// Leave quotes as the model wrote them.
+    #[default]
+    Keep,
+    /// Typographic quotes: 「」 and "" become “”, 『』 and '' become ‘’.
+    Curly,
+    /// ASCII quotes: 「」 and “” become ", 『』 and ‘’ become '.
+    Straight,
+}
+
+/// Ellipsis normalization for translated text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EllipsisStyle {
+    /// Leave ellipses as the model wrote them.
+    #[default]
+    Keep,
+    /// A single `…` character.
+    Unicode,
+    /// Three ASCII dots.
+    Dots,
+}
+
+/// Settings for a single novel.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NovelConfig {
+    /// Rules applied after the global `postprocess.rules`.
+    pub rules: Vec<ReplaceRule>,
+
+    /// Genre preset for this novel, overriding `prompts.preset`.
+    pub preset: Option<PromptPreset>,
+}
+
 impl Config {
+    /// Applies the named profile's overrides to this configuration.
+    pub fn apply_profile(&mut self, name: &str) -> Result<(), ConfigError> {
+        let profile =
+            self.profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    key: "profile".to_string(),
+                    message: format!("no [profiles.{}] section in config", name),
+                })?;
+
+        if let Some(model) = profile.model {
+            // The profile's model is the one content gets translated with
+            self.api.model = model;
+            self.translation.content_model = None;
+        }
+        if let Some(base_url) = profile.base_url {
+            self.api.base_url = normalize_base_url(&base_url);
+        }
+        if let Some(key) = profile.key {
+            self.api.key = key;
+        }
+        if let Some(prompt) = profile.content_translation {
+            self.prompts.content_translation = prompt;
+        }
+        if let Some(chunk_size) = profile.chunk_size_chars {
+            self.translation.chunk_size_chars = chunk_size;
+        }
+
+        Ok(())
+    }
+
     /// Returns the platform-specific config directory path.
     pub fn config_dir() -> Result<PathBuf, ConfigError> {
         dirs::config_dir()
@@ -246,8 +1108,12 @@ impl Config {
         }
 
         let content = std::fs::read_to_string(path)?;
-        let config: Config =
+        let mut config: Config =
             toml::from_str(&content).map_err(|e| ConfigError::ParseError(e.to_string()))?;
+        config.api.base_url = normalize_base_url(&config.api.base_url);
+        if let Some(scout_api) = &mut config.scout_api {
+            scout_api.base_url = normalize_base_url(&scout_api.base_url);
+        }
 
         Ok(config)
     }
@@ -272,6 +1138,29 @@ impl Config {
         Ok(())
     }
 
+    /// Sets one string value in the config file at `path`, leaving the
+    /// rest of the file (comments, ordering, formatting) as it is.
+    pub fn set_value_in(
+        path: &Path,
+        table: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(), ConfigError> {
+        let content = std::fs::read_to_string(path)?;
+        let mut document: toml_edit::DocumentMut = content
+            .parse()
+            .map_err(|e: toml_edit::TomlError| ConfigError::ParseError(e.to_string()))?;
+        let item = &mut document[table][key];
+        let mut new_value = toml_edit::Value::from(value);
+        // Keep the spacing and any trailing comment of the old value
+        if let Some(old_value) = item.as_value() {
+            *new_value.decor_mut() = old_value.decor().clone();
+        }
+        *item = toml_edit::Item::Value(new_value);
+        std::fs::write(path, document.to_string())?;
+        Ok(())
+    }
+
     /// Validates the configuration.
     pub fn validate(&self) -> Result<(), ConfigError> {
         self.validate_with_options(true)
@@ -296,6 +1185,26 @@ impl Config {
             }
         }
 
+        for (key, api) in [
+            ("api", Some(&self.api)),
+            ("scout_api", self.scout_api.as_ref()),
+        ] {
+            let Some(api) = api else {
+                continue;
+            };
+            let valid = reqwest::Url::parse(&normalize_base_url(&api.base_url))
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+            if !valid {
+                return Err(ConfigError::InvalidValue {
+                    key: format!("{}.base_url", key),
+                    message: format!(
+                        "'{}' is not an http(s) URL (e.g. https://api.openai.com/v1)",
+                        api.base_url
+                    ),
+                });
+            }
+        }
+
         if self.translation.chunk_size_chars == 0 {
             return Err(ConfigError::InvalidValue {
                 key: "translation.chunk_size_chars".to_string(),
@@ -303,7 +1212,171 @@ impl Config {
             });
         }
 
-        Ok(())
+        if self.name_scout.concurrency == 0 {
+            return Err(ConfigError::InvalidValue {
+                key: "name_scout.concurrency".to_string(),
+                message: "must be greater than 0".to_string(),
+            });
+        }
+
+        if self.scraping.timeout_sec == 0 {
+            return Err(ConfigError::InvalidValue {
+                key: "scraping.timeout_sec".to_string(),
+                message: "must be greater than 0".to_string(),
+            });
+        }
+
+        if self.scraping.max_requests_per_host == 0 {
+            return Err(ConfigError::InvalidValue {
+                key: "scraping.max_requests_per_host".to_string(),
+                message: "must be greater than 0".to_string(),
+            });
+        }
+
+        for (host, delay) in &self.scraping.host_delays {
+            if !delay.is_finite() || *delay < 0.0 {
+                return Err(ConfigError::InvalidValue {
+                    key: format!("scraping.host_delays.\"{}\"", host),
+                    message: "must be a non-negative number of seconds".to_string(),
+                });
+            }
+        }
+
+        for (key, min, max) in [
+            (
+                "translation",
+                self.translation.jitter_min_sec,
+                self.translation.jitter_max_sec,
+            ),
+            (
+                "scraping",
+                self.scraping.jitter_min_sec,
+                self.scraping.jitter_max_sec,
+            ),
+        ] {
+            if min < 0.0 || max < min {
+                return Err(ConfigError::InvalidValue {
+                    key: format!("{}.jitter_max_sec", key),
+                    message: "jitter must satisfy 0 <= jitter_min_sec <= jitter_max_sec"
+                        .to_string(),
+                });
+            }
+        }
+
+        self.translation.retry.validate("translation")?;
+        self.name_scout.retry.validate("name_scout")?;
+
+        for (key, temperature) in [
+            (
+                "translation.title_temperature",
+                self.translation.title_temperature,
+            ),
+            (
+                "translation.content_temperature",
+                self.translation.content_temperature,
+            ),
+            ("name_scout.temperature", self.name_scout.temperature),
+        ] {
+            if temperature.is_some_and(|t| !(0.0..=2.0).contains(&t)) {
+                return Err(ConfigError::InvalidValue {
+                    key: key.to_string(),
+                    message: "must be between 0 and 2".to_string(),
+                });
+            }
+        }
+
+        if !crate::utils::is_filename_safe(self.output.filename_replacement) {
+            return Err(ConfigError::InvalidValue {
+                key: "output.filename_replacement".to_string(),
+                message: format!(
+                    "'{}' can't appear in filenames itself",
+                    self.output.filename_replacement.escape_default()
+                ),
+            });
+        }
+
+        let novel_rules = self.novels.iter().flat_map(|(key, novel)| {
+            novel
+                .rules
+                .iter()
+                .map(move |r| (format!("novels.\"{}\".rules", key), r))
+        });
+        let global_rules = self
+            .postprocess
+            .rules
+            .iter()
+            .map(|r| ("postprocess.rules".to_string(), r));
+        for (key, rule) in global_rules.chain(novel_rules) {
+            if let Err(e) = regex::Regex::new(&rule.find) {
+                return Err(ConfigError::InvalidValue {
+                    key,
+                    message: e.to_string(),
+                });
+            }
+        }
+
+        self.validate_sync()
+    }
+
+    /// Validates the `[sync]` settings.
+    pub fn validate_sync(&self) -> Result<(), ConfigError> {
+        let invalid = |key: &str, message: &str| ConfigError::InvalidValue {
+            key: key.to_string(),
+            message: message.to_string(),
+        };
+
+        match (&self.sync.webdav, &self.sync.s3) {
+            (Some(_), Some(_)) => Err(invalid("sync", "configure only one of webdav or s3")),
+            (None, None) if self.sync.after_run => Err(invalid(
+                "sync.after_run",
+                "needs a [sync.webdav] or [sync.s3] target",
+            )),
+            (Some(webdav), None) if url::Url::parse(&webdav.url).is_err() => {
+                Err(invalid("sync.webdav.url", "must be a valid URL"))
+            }
+            (None, Some(s3)) if url::Url::parse(&s3.endpoint).is_err() => {
+                Err(invalid("sync.s3.endpoint", "must be a valid URL"))
+            }
+            (None, Some(s3)) if s3.bucket.is_empty() => {
+                Err(invalid("sync.s3.bucket", "must not be empty"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the post-processing rules for a novel: global rules first,
+    /// then any rules under `[novels."site:novel_id"]`.
+    pub fn postprocess_rules(&self, site: &str, novel_id: &str) -> Vec<ReplaceRule> {
+        let mut rules = self.postprocess.rules.clone();
+        if let Some(novel) = self.novels.get(&format!("{}:{}", site, novel_id)) {
+            rules.extend(novel.rules.iter().cloned());
+        }
+        rules
+    }
+
+    /// Returns the content prompt for a novel: the site's prompt (see
+    /// [`PromptsConfig::content_prompt_for`]) followed by the instructions of
+    /// the novel's preset, or of `prompts.preset` if it has none.
+    pub fn content_prompt(&self, site: &str, novel_id: &str) -> String {
+        let prompt = self.prompts.content_prompt_for(site);
+        let preset = self
+            .novels
+            .get(&format!("{}:{}", site, novel_id))
+            .and_then(|novel| novel.preset)
+            .or(self.prompts.preset);
+        match preset {
+            Some(preset) => format!("{}\n\n{}", prompt.trim_end(), preset.instructions()),
+            None => prompt,
+        }
+    }
+
+    /// Uses `preset` for every novel in this run, overriding presets set
+    /// under `[novels]`.
+    pub fn force_preset(&mut self, preset: PromptPreset) {
+        self.prompts.preset = Some(preset);
+        for novel in self.novels.values_mut() {
+            novel.preset = None;
+        }
     }
 
     /// Returns the effective names directory, using config or default.
@@ -315,6 +1388,16 @@ impl Config {
         }
     }
 
+    /// Returns the path to the library database of known novels.
+    pub fn library_path() -> Result<PathBuf, ConfigError> {
+        Ok(Self::config_dir()?.join("library.json"))
+    }
+
+    /// Returns the path to the run history log.
+    pub fn history_path() -> Result<PathBuf, ConfigError> {
+        Ok(Self::config_dir()?.join(HISTORY_FILENAME))
+    }
+
     /// Returns the API config to use for name scouting.
     pub fn scout_api_config(&self) -> Result<&ApiConfig, ConfigError> {
         self.scout_api
@@ -326,6 +1409,13 @@ impl Config {
                 )
             })
     }
+
+    /// Whether the name scout sends its requests to the same endpoint with
+    /// the same key as translation, so the two share one rate limit.
+    pub fn scout_shares_api(&self) -> bool {
+        self.scout_api_config()
+            .is_ok_and(|scout| crate::api_limiter::same_key(scout, &self.api))
+    }
 }
 
 #[cfg(test)]
@@ -343,6 +1433,41 @@ mod tests {
         assert_eq!(config.scraping.delay_between_requests_sec, 1.0);
     }
 
+    #[test]
+    fn test_normalize_base_url() {
+        for url in [
+            "https://api.openai.com/v1",
+            " https://api.openai.com/v1/ ",
+            "https://api.openai.com/v1/chat/completions",
+            "https://api.openai.com/v1/models/",
+            "https://api.openai.com",
+            "https://api.openai.com/",
+        ] {
+            assert_eq!(
+                normalize_base_url(url),
+                "https://api.openai.com/v1",
+                "{}",
+                url
+            );
+        }
+        // Providers with their own path layout are left alone
+        assert_eq!(
+            normalize_base_url("https://openrouter.ai/api/v1/"),
+            "https://openrouter.ai/api/v1"
+        );
+
+        let mut config = Config::default();
+        config.api.key = "real-key".to_string();
+        config.api.base_url = "localhost:8080".to_string();
+        assert!(config.validate_with_options(false).is_err());
+        config.api.base_url = "http://localhost:8080/v1/".to_string();
+        assert!(config.validate_with_options(false).is_ok());
+        assert_eq!(
+            config.api.endpoint_url("chat/completions"),
+            "http://localhost:8080/v1/chat/completions"
+        );
+    }
+
     #[test]
     fn test_api_configured_check() {
         let mut api = ApiConfig::default();
@@ -367,6 +1492,283 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_set_value_keeps_comments() {
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "# My settings\n[api]\nmodel = \"old\"  # fast one\nkey = \"sk\"\n",
+        )
+        .unwrap();
+
+        Config::set_value_in(file.path(), "api", "model", "new").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(file.path()).unwrap(),
+            "# My settings\n[api]\nmodel = \"new\"  # fast one\nkey = \"sk\"\n"
+        );
+    }
+
+    #[test]
+    fn test_extra_body_merged_into_request() {
+        let config: Config = toml::from_str(
+            "[api]\nmodel = \"m\"\n[api.extra_body]\nreasoning_effort = \"high\"\n[api.extra_body.provider]\norder = [\"a\", \"b\"]\n",
+        )
+        .unwrap();
+
+        let body = config
+            .api
+            .request_body(&serde_json::json!({"model": "m", "stream": true}))
+            .unwrap();
+        assert_eq!(body["reasoning_effort"], "high");
+        assert_eq!(body["provider"]["order"][1], "b");
+        assert_eq!(body["stream"], true);
+    }
+
+    #[test]
+    fn test_apply_profile() {
+        let mut config: Config = toml::from_str(
+            "[api]\nmodel = \"gpt-4o\"\n[profiles.cheap]\nmodel = \"gpt-4o-mini\"\nchunk_size_chars = 2000\n",
+        )
+        .unwrap();
+
+        config.apply_profile("cheap").unwrap();
+        assert_eq!(config.api.model, "gpt-4o-mini");
+        assert_eq!(config.translation.chunk_size_chars, 2000);
+        assert_eq!(config.api.base_url, "https://api.openai.com/v1");
+
+        assert!(config.apply_profile("missing").is_err());
+    }
+
+    #[test]
+    fn test_task_models_and_temperatures() {
+        let mut config: Config = toml::from_str(
+            "[translation]\ntitle_model = \"gpt-4o-mini\"\ntitle_temperature = 0.2\ncontent_model = \"gpt-4o\"\n[name_scout]\ntemperature = 0.0\n[profiles.cheap]\nmodel = \"local\"\n",
+        )
+        .unwrap();
+        config.api.key = "real-key".to_string();
+        assert_eq!(
+            config.translation.title_model.as_deref(),
+            Some("gpt-4o-mini")
+        );
+        assert_eq!(config.translation.title_temperature, Some(0.2));
+        assert_eq!(config.translation.content_temperature, None);
+        assert!(config.validate_with_options(false).is_ok());
+
+        config.translation.content_temperature = Some(3.0);
+        assert!(config.validate_with_options(false).is_err());
+        config.translation.content_temperature = None;
+
+        // A profile's model replaces the content model, not the title model
+        config.apply_profile("cheap").unwrap();
+        assert_eq!(config.translation.content_model, None);
+        assert_eq!(
+            config.translation.title_model.as_deref(),
+            Some("gpt-4o-mini")
+        );
+    }
+
+    #[test]
+    fn test_site_prompt_suffix() {
+        let config: Config = toml::from_str(
+            "[prompts]\ncontent_translation = \"Translate.\\n\"\n[prompts.site_suffixes]\npixiv = \"Keep the tag list as is.\"\nkakuyomu = \" \"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.prompts.content_prompt_for("pixiv"),
+            "Translate.\n\nKeep the tag list as is."
+        );
+        assert_eq!(
+            config.prompts.content_prompt_for("kakuyomu"),
+            "Translate.\n"
+        );
+        assert_eq!(config.prompts.content_prompt_for("syosetu"), "Translate.\n");
+    }
+
+    #[test]
+    fn test_prompt_presets() {
+        let mut config: Config = toml::from_str(
+            "[prompts]\ncontent_translation = \"Translate.\"\npreset = \"fantasy\"\n[novels.\"syosetu:n1234ab\"]\npreset = \"litrpg\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.content_prompt("syosetu", "n9999zz"),
+            format!("Translate.\n\n{}", PromptPreset::Fantasy.instructions())
+        );
+        assert_eq!(
+            config.content_prompt("syosetu", "n1234ab"),
+            format!("Translate.\n\n{}", PromptPreset::Litrpg.instructions())
+        );
+
+        // A forced preset wins over the novel's own
+        config.force_preset("R18".parse().unwrap());
+        assert_eq!(
+            config.content_prompt("syosetu", "n1234ab"),
+            format!("Translate.\n\n{}", PromptPreset::R18.instructions())
+        );
+        assert!("horror".parse::<PromptPreset>().is_err());
+
+        assert_eq!(
+            Config::default().content_prompt("syosetu", "n1234ab"),
+            PromptsConfig::default().content_translation
+        );
+    }
+
+    #[test]
+    fn test_bilingual_format_parsing() {
+        let config: Config = toml::from_str("[output]\nbilingual_export = \"jsonl\"\n").unwrap();
+        assert_eq!(config.output.bilingual_export, BilingualFormat::Jsonl);
+        assert_eq!(config.output.bilingual_export.extension(), Some("jsonl"));
+        assert_eq!(
+            Config::default().output.bilingual_export,
+            BilingualFormat::None
+        );
+    }
+
+    #[test]
+    fn test_http_tuning_parsing() {
+        let config: Config = toml::from_str(
+            "[scraping]\ntimeout_sec = 90\npool_max_idle_per_host = 2\nhttp_version = \"http1\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.scraping.timeout_sec, 90);
+        assert_eq!(config.scraping.pool_max_idle_per_host, Some(2));
+        assert_eq!(config.scraping.http_version, HttpVersion::Http1);
+        assert_eq!(config.scraping.tcp_keepalive_sec, 15);
+        assert_eq!(Config::default().scraping.http_version, HttpVersion::Auto);
+    }
+
+    #[test]
+    fn test_metadata_format_parsing() {
+        let config: Config = toml::from_str("[output]\nmetadata = \"front-matter\"\n").unwrap();
+        assert_eq!(config.output.metadata, MetadataFormat::FrontMatter);
+        assert_eq!(Config::default().output.metadata, MetadataFormat::None);
+    }
+
+    #[test]
+    fn test_refusal_nudge_parsing() {
+        let config: Config =
+            toml::from_str("[prompts]\nrefusal_nudge = \"\"\nrefusal_nudge_role = \"system\"\n")
+                .unwrap();
+        assert_eq!(config.prompts.refusal_nudge, "");
+        assert_eq!(config.prompts.refusal_nudge_role, NudgeRole::System);
+        assert!(!config.prompts.scout_refusal_nudge.is_empty());
+        assert_eq!(
+            Config::default().prompts.refusal_nudge_role,
+            NudgeRole::User
+        );
+    }
+
+    #[test]
+    fn test_output_layout_parsing() {
+        let config: Config =
+            toml::from_str("[output]\nparagraph_spacing = \"blank-line\"\nwrap_columns = 72\n")
+                .unwrap();
+        assert_eq!(config.output.paragraph_spacing, ParagraphSpacing::BlankLine);
+        assert_eq!(config.output.wrap_columns, 72);
+        assert_eq!(
+            Config::default().output.paragraph_spacing,
+            ParagraphSpacing::Preserve
+        );
+    }
+
+    #[test]
+    fn test_filename_replacement() {
+        let config: Config = toml::from_str("[output]\nfilename_replacement = \"-\"\n").unwrap();
+        assert_eq!(config.output.filename_replacement, '-');
+        assert_eq!(Config::default().output.filename_replacement, '_');
+
+        let mut config = Config::default();
+        config.api.key = "sk-test".to_string();
+        assert!(config.validate_with_options(false).is_ok());
+        config.output.filename_replacement = '/';
+        assert!(config.validate_with_options(false).is_err());
+    }
+
+    #[test]
+    fn test_first_chapter_number() {
+        let output = OutputConfig::default();
+        assert_eq!(output.file_number(1), 1);
+        assert_eq!(output.file_number(12), 12);
+
+        let config: Config = toml::from_str("[output]\nfirst_chapter_number = 0\n").unwrap();
+        assert_eq!(config.output.file_number(1), 0);
+        assert_eq!(config.output.file_number(12), 11);
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let config: Config = toml::from_str(
+            "[translation.retry]\nmax_delay_sec = 5\nrefusal_retries = 1\n[name_scout.retry]\nbase_delay_sec = 0.5\n",
+        )
+        .unwrap();
+        let policy = &config.translation.retry;
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(3), Duration::from_secs(5));
+        assert_eq!(policy.backoff(100), Duration::from_secs(5));
+        assert_eq!(policy.attempts(RetryKind::Refusal, 3), 1);
+        assert_eq!(policy.attempts(RetryKind::Network, 3), 3);
+        assert_eq!(config.name_scout.retry.backoff(2), Duration::from_secs(1));
+
+        let mut config = Config::default();
+        config.api.key = "real-key".to_string();
+        assert!(config.validate_with_options(false).is_ok());
+        config.name_scout.retry.jitter_sec = -1.0;
+        assert!(config.validate_with_options(false).is_err());
+    }
+
+    #[test]
+    fn test_postprocess_rules() {
+        let toml_str = r#"
+[[postprocess.rules]]
+find = "\\.{3,}"
+replace = "…"
+
+[[novels."syosetu:n1234ab".rules]]
+find = "[Bb]ig brother"
+replace = "Onii-chan"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        config.api.key = "real-key".to_string();
+        config.scout_api.as_mut().unwrap().key = "scout-key".to_string();
+        assert!(config.validate().is_ok());
+
+        let rules = config.postprocess_rules("syosetu", "n1234ab");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[1].replace, "Onii-chan");
+        assert_eq!(config.postprocess_rules("syosetu", "n9999zz").len(), 1);
+
+        config.postprocess.rules[0].find = "(".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_furigana_mode_parsing() {
+        let config: Config = toml::from_str("[scraping]\nfurigana = \"parens\"\n").unwrap();
+        assert_eq!(config.scraping.furigana, FuriganaMode::Parens);
+        assert_eq!(Config::default().scraping.furigana, FuriganaMode::Drop);
+        let config: Config = toml::from_str("[scraping]\nfurigana = \"html\"\n").unwrap();
+        assert_eq!(config.scraping.furigana, FuriganaMode::Html);
+    }
+
+    #[test]
+    fn test_sync_validation() {
+        let config: Config =
+            toml::from_str("[sync.s3]\nbucket = \"novels\"\nprefix = \"jp/\"\n").unwrap();
+        let s3 = config.sync.s3.as_ref().unwrap();
+        assert_eq!(s3.region, "us-east-1");
+        assert!(config.validate_sync().is_ok());
+        assert!(Config::default().validate_sync().is_ok());
+
+        let both: Config =
+            toml::from_str("[sync.s3]\nbucket = \"b\"\n[sync.webdav]\nurl = \"https://dav\"\n")
+                .unwrap();
+        assert!(both.validate_sync().is_err());
+
+        let no_target: Config = toml::from_str("[sync]\nafter_run = true\n").unwrap();
+        assert!(no_target.validate_sync().is_err());
+    }
+
     #[test]
     fn test_config_validation() {
         let config = Config::default();
@@ -376,6 +1778,12 @@ mod tests {
         config.api.key = "real-key".to_string();
         config.scout_api.as_mut().unwrap().key = "scout-key".to_string();
         assert!(config.validate().is_ok());
+
+        config.scraping.jitter_min_sec = 0.5;
+        config.scraping.jitter_max_sec = 2.0;
+        assert!(config.validate().is_ok());
+        config.scraping.jitter_max_sec = 0.2;
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -1,25 +1,90 @@
-//! Cookie loading utilities for scrapers.
+//! Cookie loading and management for scrapers.
 //!
-//! Supports Netscape HTTP cookie files, commonly exported by browser extensions.
+//! Supports Netscape HTTP cookie files, commonly exported by browser extensions,
+//! as well as the JSON format produced by extensions such as Cookie-Editor.
+//! Cookie files live anywhere under the config directory and are matched to a
+//! site by filename (e.g. `pixiv-cookies.txt`).
 
 use reqwest::cookie::Jar;
 use reqwest::Url;
+use serde::Deserialize;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
+/// Cookies expiring within this many seconds are reported as expiring soon.
+const EXPIRY_WARNING_SECS: u64 = 7 * 24 * 60 * 60;
+
 /// Cookie entry parsed from a Netscape cookie file.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct NetscapeCookie {
-    domain: String,
-    include_subdomains: bool,
-    path: String,
-    secure: bool,
-    expires_unix: Option<u64>,
-    name: String,
-    value: String,
-    http_only: bool,
+pub struct NetscapeCookie {
+    /// Domain the cookie belongs to (leading dot for domain cookies).
+    pub domain: String,
+    /// Whether subdomains also receive the cookie.
+    pub include_subdomains: bool,
+    /// Path scope of the cookie.
+    pub path: String,
+    /// Whether the cookie is only sent over HTTPS.
+    pub secure: bool,
+    /// Expiry as a Unix timestamp, or `None` for session cookies.
+    pub expires_unix: Option<u64>,
+    /// Cookie name.
+    pub name: String,
+    /// Cookie value.
+    pub value: String,
+    /// Whether the cookie is hidden from scripts.
+    pub http_only: bool,
+}
+
+impl NetscapeCookie {
+    /// Returns true if the cookie has an expiry before `now_unix`.
+    pub fn is_expired(&self, now_unix: u64) -> bool {
+        self.expires_unix.is_some_and(|ts| ts <= now_unix)
+    }
+}
+
+/// Summary of a cookie file found in the config directory.
+#[derive(Debug, Clone)]
+pub struct CookieFileSummary {
+    /// Path to the cookie file.
+    pub path: PathBuf,
+    /// Last modification time of the file.
+    pub modified: SystemTime,
+    /// Number of cookies in the file.
+    pub cookie_count: usize,
+    /// Distinct domains covered by the file.
+    pub domains: Vec<String>,
+}
+
+/// Result of validating the cookies for a site.
+#[derive(Debug, Clone, Default)]
+pub struct CookieCheck {
+    /// Total cookies in the file.
+    pub total: usize,
+    /// Names of cookies that have already expired.
+    pub expired: Vec<String>,
+    /// Names and expiry timestamps of cookies expiring within a week.
+    pub expiring_soon: Vec<(String, u64)>,
+    /// Login cookies the site needs that are missing or expired.
+    pub missing_login: Vec<&'static str>,
+}
+
+impl CookieCheck {
+    /// Returns true if every known login cookie is present and unexpired.
+    pub fn is_ok(&self) -> bool {
+        self.total > 0 && self.missing_login.is_empty()
+    }
+}
+
+/// Returns the cookie names a site uses to keep a login session, if known.
+pub fn login_cookie_names(site: &str) -> &'static [&'static str] {
+    match site.to_ascii_lowercase().as_str() {
+        "pixiv" => &["PHPSESSID"],
+        "syosetu" => &["userl"],
+        _ => &[],
+    }
 }
 
 /// Errors that can occur while loading cookies.
@@ -36,6 +101,14 @@ pub enum CookieError {
     /// Cookie domain could not be converted into a URL.
     #[error("Invalid cookie domain: {0}")]
     InvalidDomain(String),
+
+    /// Browser JSON export could not be parsed.
+    #[error("Invalid cookie JSON export: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// An import did not contain any cookies for the requested site.
+    #[error("No cookies for '{0}' found in import")]
+    NoCookies(String),
 }
 
 /// Loads cookies from a Netscape cookie file into a reqwest cookie jar.
@@ -52,11 +125,15 @@ pub fn load_netscape_cookie_jar(
     Ok((jar, cookie_path))
 }
 
-fn find_cookie_file(
+/// Finds the most recently modified cookie file whose name contains all tokens.
+pub fn find_cookie_file(
     root: &Path,
     name_tokens: &[&str],
 ) -> Result<Option<PathBuf>, std::io::Error> {
     let mut best: Option<(PathBuf, std::time::SystemTime)> = None;
+    if !root.is_dir() {
+        return Ok(None);
+    }
     find_cookie_file_recursive(root, name_tokens, &mut best)?;
     Ok(best.map(|(path, _)| path))
 }
@@ -108,8 +185,14 @@ fn find_cookie_file_recursive(
     Ok(())
 }
 
-fn parse_netscape_cookie_file(path: &Path) -> Result<Vec<NetscapeCookie>, CookieError> {
+/// Parses a Netscape cookie file.
+pub fn parse_netscape_cookie_file(path: &Path) -> Result<Vec<NetscapeCookie>, CookieError> {
     let content = std::fs::read_to_string(path)?;
+    parse_netscape_cookies(&content)
+}
+
+/// Parses Netscape cookie file content.
+fn parse_netscape_cookies(content: &str) -> Result<Vec<NetscapeCookie>, CookieError> {
     let mut cookies = Vec::new();
 
     for raw_line in content.lines() {
@@ -161,6 +244,186 @@ fn parse_netscape_cookie_file(path: &Path) -> Result<Vec<NetscapeCookie>, Cookie
     Ok(cookies)
 }
 
+/// Lists every parseable cookie file under the config directory.
+pub fn list_cookie_files(config_dir: &Path) -> Result<Vec<CookieFileSummary>, CookieError> {
+    let mut summaries = Vec::new();
+    if config_dir.is_dir() {
+        list_cookie_files_recursive(config_dir, &mut summaries)?;
+    }
+    summaries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(summaries)
+}
+
+fn list_cookie_files_recursive(
+    dir: &Path,
+    summaries: &mut Vec<CookieFileSummary>,
+) -> Result<(), CookieError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            list_cookie_files_recursive(&path, summaries)?;
+            continue;
+        }
+
+        let is_txt = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"));
+        if !is_txt {
+            continue;
+        }
+
+        // Files that don't parse aren't cookie files; skip them silently
+        let Ok(cookies) = parse_netscape_cookie_file(&path) else {
+            continue;
+        };
+        if cookies.is_empty() {
+            continue;
+        }
+
+        let mut domains: Vec<String> = cookies
+            .iter()
+            .map(|c| c.domain.trim_start_matches('.').to_string())
+            .collect();
+        domains.sort();
+        domains.dedup();
+
+        let modified = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .unwrap_or(UNIX_EPOCH);
+
+        summaries.push(CookieFileSummary {
+            path,
+            modified,
+            cookie_count: cookies.len(),
+            domains,
+        });
+    }
+
+    Ok(())
+}
+
+/// Validates cookie expiry and the presence of the site's login cookies.
+pub fn check_cookies(cookies: &[NetscapeCookie], site: &str, now_unix: u64) -> CookieCheck {
+    let mut check = CookieCheck {
+        total: cookies.len(),
+        ..Default::default()
+    };
+
+    for cookie in cookies {
+        match cookie.expires_unix {
+            Some(_) if cookie.is_expired(now_unix) => check.expired.push(cookie.name.clone()),
+            Some(ts) if ts <= now_unix + EXPIRY_WARNING_SECS => {
+                check.expiring_soon.push((cookie.name.clone(), ts));
+            }
+            _ => {}
+        }
+    }
+
+    check.missing_login = login_cookie_names(site)
+        .iter()
+        .copied()
+        .filter(|name| {
+            !cookies
+                .iter()
+                .any(|c| c.name == *name && !c.is_expired(now_unix))
+        })
+        .collect();
+
+    check
+}
+
+/// Cookie as exported in JSON by browser extensions (e.g. Cookie-Editor).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrowserCookie {
+    domain: String,
+    #[serde(default)]
+    host_only: bool,
+    #[serde(default = "default_cookie_path")]
+    path: String,
+    #[serde(default)]
+    secure: bool,
+    #[serde(default)]
+    http_only: bool,
+    #[serde(default)]
+    expiration_date: Option<f64>,
+    name: String,
+    value: String,
+}
+
+fn default_cookie_path() -> String {
+    "/".to_string()
+}
+
+/// Imports cookies for a site from a browser export into the config directory.
+///
+/// Accepts either a Netscape `cookies.txt` or a JSON array export. Only cookies
+/// whose domain contains `site` are kept. The result is written to
+/// `<config_dir>/<site>-cookies.txt`, which then takes precedence as the most
+/// recently modified file.
+pub fn import_cookies(
+    source: &Path,
+    config_dir: &Path,
+    site: &str,
+) -> Result<PathBuf, CookieError> {
+    let content = std::fs::read_to_string(source)?;
+    let cookies = if content.trim_start().starts_with('[') {
+        let exported: Vec<BrowserCookie> = serde_json::from_str(&content)?;
+        exported
+            .into_iter()
+            .map(|c| NetscapeCookie {
+                include_subdomains: !c.host_only,
+                domain: c.domain,
+                path: c.path,
+                secure: c.secure,
+                expires_unix: c.expiration_date.map(|ts| ts as u64).filter(|ts| *ts > 0),
+                name: c.name,
+                value: c.value,
+                http_only: c.http_only,
+            })
+            .collect()
+    } else {
+        parse_netscape_cookies(&content)?
+    };
+
+    let site_lower = site.to_ascii_lowercase();
+    let cookies: Vec<NetscapeCookie> = cookies
+        .into_iter()
+        .filter(|c| c.domain.to_ascii_lowercase().contains(&site_lower))
+        .collect();
+    if cookies.is_empty() {
+        return Err(CookieError::NoCookies(site.to_string()));
+    }
+
+    std::fs::create_dir_all(config_dir)?;
+    let target = config_dir.join(format!("{}-cookies.txt", site_lower));
+    std::fs::write(&target, format_netscape_cookies(&cookies))?;
+    Ok(target)
+}
+
+/// Formats cookies as a Netscape cookie file.
+fn format_netscape_cookies(cookies: &[NetscapeCookie]) -> String {
+    let mut out = String::from("# Netscape HTTP Cookie File\n");
+    for cookie in cookies {
+        let bool_str = |b: bool| if b { "TRUE" } else { "FALSE" };
+        out.push_str(&format!(
+            "{}{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            if cookie.http_only { "#HttpOnly_" } else { "" },
+            cookie.domain,
+            bool_str(cookie.include_subdomains),
+            cookie.path,
+            bool_str(cookie.secure),
+            cookie.expires_unix.unwrap_or(0),
+            cookie.name,
+            cookie.value
+        ));
+    }
+    out
+}
+
 fn add_cookies_to_jar(jar: &Jar, cookies: &[NetscapeCookie]) -> Result<(), CookieError> {
     for cookie in cookies {
         let host = cookie.domain.trim_start_matches('.');
@@ -240,6 +503,73 @@ mod tests {
         assert_eq!(found.unwrap(), second);
     }
 
+    #[test]
+    fn test_check_cookies_expiry() {
+        let now = 1_000_000;
+        let cookie = |name: &str, expires: Option<u64>| NetscapeCookie {
+            domain: ".pixiv.net".to_string(),
+            include_subdomains: true,
+            path: "/".to_string(),
+            secure: true,
+            expires_unix: expires,
+            name: name.to_string(),
+            value: "v".to_string(),
+            http_only: false,
+        };
+
+        let cookies = vec![
+            cookie("PHPSESSID", Some(now - 10)),
+            cookie("soon", Some(now + 3600)),
+            cookie("session", None),
+        ];
+        let check = check_cookies(&cookies, "pixiv", now);
+        assert_eq!(check.total, 3);
+        assert_eq!(check.expired, vec!["PHPSESSID".to_string()]);
+        assert_eq!(check.expiring_soon.len(), 1);
+        assert_eq!(check.missing_login, vec!["PHPSESSID"]);
+        assert!(!check.is_ok());
+
+        let fresh = vec![cookie("PHPSESSID", Some(now + EXPIRY_WARNING_SECS * 2))];
+        assert!(check_cookies(&fresh, "pixiv", now).is_ok());
+    }
+
+    #[test]
+    fn test_import_json_export() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("export.json");
+        let json = r#"[
+            {"domain": ".pixiv.net", "hostOnly": false, "path": "/", "secure": true,
+             "httpOnly": true, "expirationDate": 2145916800.5, "name": "PHPSESSID", "value": "abc"},
+            {"domain": "example.com", "name": "other", "value": "x"}
+        ]"#;
+        std::fs::write(&source, json).unwrap();
+
+        let config_dir = dir.path().join("config");
+        let target = import_cookies(&source, &config_dir, "pixiv").unwrap();
+        assert_eq!(target, config_dir.join("pixiv-cookies.txt"));
+
+        let cookies = parse_netscape_cookie_file(&target).unwrap();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].name, "PHPSESSID");
+        assert!(cookies[0].http_only);
+        assert!(cookies[0].include_subdomains);
+        assert_eq!(cookies[0].expires_unix, Some(2145916800));
+
+        let listed = list_cookie_files(&config_dir).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].domains, vec!["pixiv.net".to_string()]);
+    }
+
+    #[test]
+    fn test_import_without_site_cookies_fails() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("export.txt");
+        std::fs::write(&source, ".example.com\tTRUE\t/\tFALSE\t0\ta\tb\n").unwrap();
+
+        let err = import_cookies(&source, dir.path(), "pixiv").unwrap_err();
+        assert!(matches!(err, CookieError::NoCookies(_)));
+    }
+
     #[test]
     fn test_parse_invalid_line() {
         let dir = TempDir::new().unwrap();
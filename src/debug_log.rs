@@ -0,0 +1,209 @@
+//! API request debug log.
+//!
+//! With `translation.debug_log` set, every chat request appends one JSON line
+//! with its model, truncated messages, response status, timing, and token
+//! usage, so provider problems (silently truncated responses, proxies
+//! mangling the stream) can be diagnosed after the fact. The API key never
+//! reaches the file.
+
+use crate::translator::{Message, TokenUsage};
+use crate::utils::{format_unix_timestamp, now_unix};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Characters kept from each message and response; longer text keeps its
+/// start and end around an ellipsis.
+pub const PREVIEW_CHARS: usize = 300;
+
+/// Stands in for the API key wherever it would appear.
+const REDACTED: &str = "[REDACTED]";
+
+/// A message as logged: its role, full length, and a preview.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessagePreview {
+    /// Role: "system", "user", or "assistant".
+    pub role: String,
+    /// Length of the content, in characters.
+    pub chars: usize,
+    /// Content, shortened to [`PREVIEW_CHARS`].
+    pub preview: String,
+}
+
+impl MessagePreview {
+    fn new(message: &Message) -> Self {
+        Self {
+            role: message.role.clone(),
+            chars: message.content.chars().count(),
+            preview: truncate_middle(&message.content, PREVIEW_CHARS),
+        }
+    }
+}
+
+/// One logged request and its outcome.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestEntry {
+    /// When the request was sent (`YYYY-MM-DDTHH:MM:SSZ`).
+    pub started_at: String,
+    /// Endpoint the request went to.
+    pub url: String,
+    /// Model requested.
+    pub model: String,
+    /// Whether a streamed response was requested.
+    pub stream: bool,
+    /// Sampling temperature, if one was sent.
+    pub temperature: Option<f64>,
+    /// Messages sent, oldest first.
+    pub messages: Vec<MessagePreview>,
+    /// HTTP status, if a response arrived.
+    pub status: Option<u16>,
+    /// Time until the response finished or failed, in milliseconds.
+    pub duration_ms: u64,
+    /// `finish_reason` the endpoint reported ("length" means the output
+    /// limit cut the response off).
+    pub finish_reason: Option<String>,
+    /// Usage the endpoint reported for this request.
+    pub usage: Option<TokenUsage>,
+    /// Length of the response text, in characters.
+    pub response_chars: usize,
+    /// Response text, shortened to [`PREVIEW_CHARS`].
+    pub response_preview: String,
+    /// Stream lines that weren't valid response chunks (often a proxy
+    /// rewriting the stream).
+    pub unparsed_lines: usize,
+    /// Error that failed the request, if any.
+    pub error: Option<String>,
+}
+
+impl RequestEntry {
+    /// Starts an entry for a request about to be sent.
+    pub fn new(
+        url: &str,
+        model: &str,
+        stream: bool,
+        temperature: Option<f64>,
+        messages: &[Message],
+    ) -> Self {
+        Self {
+            started_at: format_unix_timestamp(now_unix()),
+            url: url.to_string(),
+            model: model.to_string(),
+            stream,
+            temperature,
+            messages: messages.iter().map(MessagePreview::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Records the response text received for the request.
+    pub fn set_response(&mut self, response: &str) {
+        self.response_chars = response.chars().count();
+        self.response_preview = truncate_middle(response, PREVIEW_CHARS);
+    }
+}
+
+/// Debug log file, shared by every request of a translator.
+#[derive(Debug)]
+pub struct DebugLog {
+    file: Arc<Mutex<std::fs::File>>,
+    api_key: String,
+}
+
+impl DebugLog {
+    /// Opens the log for appending, creating it and its folder if needed.
+    /// `api_key` is scrubbed from everything written.
+    pub fn open(path: &Path, api_key: &str) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+            api_key: api_key.to_string(),
+        })
+    }
+
+    /// Appends an entry, writing on a blocking thread so a slow disk
+    /// doesn't stall other requests.
+    pub async fn record(&self, entry: &RequestEntry) -> std::io::Result<()> {
+        let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        let line = redact(&line, &self.api_key);
+        let file = Arc::clone(&self.file);
+        tokio::task::spawn_blocking(move || {
+            // A writer that panicked mid-line leaves nothing worth guarding
+            let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+            writeln!(file, "{}", line)
+        })
+        .await
+        .map_err(std::io::Error::other)?
+    }
+}
+
+/// Replaces every occurrence of the key (e.g. echoed back in an error body).
+fn redact(text: &str, api_key: &str) -> String {
+    if api_key.trim().is_empty() {
+        text.to_string()
+    } else {
+        text.replace(api_key, REDACTED)
+    }
+}
+
+/// Shortens text to its first and last characters around " … ", keeping at
+/// most `max_chars` of it.
+fn truncate_middle(text: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max_chars {
+        return text.to_string();
+    }
+    let head = max_chars / 2;
+    let tail = max_chars - head;
+    let mut preview: String = chars[..head].iter().collect();
+    preview.push_str(" … ");
+    preview.extend(&chars[chars.len() - tail..]);
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_record_truncates_and_redacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("logs").join("api.jsonl");
+        let log = DebugLog::open(&path, "sk-secret").unwrap();
+
+        let messages = [Message {
+            role: "user".to_string(),
+            content: "あ".repeat(1000),
+        }];
+        let mut entry = RequestEntry::new(
+            "https://api.example.com/v1/chat/completions",
+            "gpt-4o-mini",
+            true,
+            None,
+            &messages,
+        );
+        entry.status = Some(401);
+        entry.error = Some("invalid key sk-secret".to_string());
+        entry.set_response("Short reply");
+        log.record(&entry).await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("sk-secret"));
+        let logged: RequestEntry = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(logged.error.as_deref(), Some("invalid key [REDACTED]"));
+        assert_eq!(logged.messages[0].chars, 1000);
+        assert_eq!(
+            logged.messages[0].preview.chars().count(),
+            PREVIEW_CHARS + " … ".chars().count()
+        );
+        assert_eq!(logged.response_preview, "Short reply");
+    }
+}
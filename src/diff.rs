@@ -0,0 +1,161 @@
+//! Comparison of a live chapter list against a story's manifest.
+//!
+//! Shows what `update` would change (chapters added, removed, retitled, or
+//! moved) without downloading or writing anything. Chapters are matched the
+//! way [`Manifest::sync_chapters`](crate::manifest::Manifest::sync_chapters)
+//! matches them: by episode ID, or by number for records predating IDs.
+
+use crate::manifest::{ChapterRecord, Renumbered};
+use crate::scrapers::ChapterInfo;
+
+/// A chapter whose title changed upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Retitled {
+    /// Chapter number in the live list.
+    pub number: u32,
+    /// Title recorded in the manifest.
+    pub old_title: String,
+    /// Title in the live list.
+    pub new_title: String,
+}
+
+/// Differences between the manifest and the live chapter list.
+#[derive(Debug, Clone, Default)]
+pub struct ChapterDiff {
+    /// Listed chapters the manifest doesn't have (or had flagged as removed).
+    pub added: Vec<ChapterInfo>,
+    /// Recorded chapters no longer listed.
+    pub removed: Vec<ChapterRecord>,
+    /// Chapters whose title changed.
+    pub retitled: Vec<Retitled>,
+    /// Chapters whose number changed, e.g. after an insertion or a move.
+    pub renumbered: Vec<Renumbered>,
+}
+
+impl ChapterDiff {
+    /// Returns true if the manifest matches the live list.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.retitled.is_empty()
+            && self.renumbered.is_empty()
+    }
+}
+
+/// Compares manifest records against a freshly fetched chapter list.
+pub fn diff_chapters(records: &[ChapterRecord], chapters: &[ChapterInfo]) -> ChapterDiff {
+    let mut diff = ChapterDiff::default();
+    let mut matched = vec![false; records.len()];
+
+    for chapter in chapters {
+        let existing = records
+            .iter()
+            .position(|r| r.episode_id == chapter.episode_id)
+            .or_else(|| {
+                records
+                    .iter()
+                    .position(|r| r.episode_id.is_empty() && r.number == chapter.number)
+            });
+        let Some(idx) = existing else {
+            diff.added.push(chapter.clone());
+            continue;
+        };
+        matched[idx] = true;
+        let record = &records[idx];
+        if record.removed {
+            diff.added.push(chapter.clone());
+            continue;
+        }
+
+        if record.number != chapter.number {
+            diff.renumbered.push(Renumbered {
+                episode_id: chapter.episode_id.clone(),
+                title: chapter.title.clone(),
+                old_number: record.number,
+                new_number: chapter.number,
+            });
+        }
+        if record.title != chapter.title {
+            diff.retitled.push(Retitled {
+                number: chapter.number,
+                old_title: record.title.clone(),
+                new_title: chapter.title.clone(),
+            });
+        }
+    }
+
+    diff.removed = records
+        .iter()
+        .zip(&matched)
+        .filter(|(record, matched)| !**matched && !record.removed)
+        .map(|(record, _)| record.clone())
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(episode_id: &str, number: u32, title: &str) -> ChapterRecord {
+        ChapterRecord {
+            episode_id: episode_id.to_string(),
+            number,
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn chapter(episode_id: &str, number: u32, title: &str) -> ChapterInfo {
+        ChapterInfo {
+            title: title.to_string(),
+            url: format!("https://example.com/{}", episode_id),
+            number,
+            episode_id: episode_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_chapters() {
+        let records = vec![
+            record("a", 1, "Prologue"),
+            record("b", 2, "Chapter 1"),
+            record("c", 3, "Chapter 2"),
+            ChapterRecord {
+                removed: true,
+                ..record("d", 4, "Deleted")
+            },
+        ];
+        let chapters = vec![
+            chapter("a", 1, "Prologue"),
+            chapter("x", 2, "Interlude"),
+            chapter("b", 3, "Chapter 1 (revised)"),
+            chapter("d", 4, "Deleted"),
+        ];
+
+        let diff = diff_chapters(&records, &chapters);
+        let added: Vec<&str> = diff.added.iter().map(|c| c.episode_id.as_str()).collect();
+        assert_eq!(added, vec!["x", "d"]);
+        assert_eq!(diff.removed, vec![record("c", 3, "Chapter 2")]);
+        assert_eq!(
+            diff.retitled,
+            vec![Retitled {
+                number: 3,
+                old_title: "Chapter 1".to_string(),
+                new_title: "Chapter 1 (revised)".to_string(),
+            }]
+        );
+        assert_eq!(diff.renumbered.len(), 1);
+        assert_eq!(diff.renumbered[0].old_number, 2);
+        assert_eq!(diff.renumbered[0].new_number, 3);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_unchanged_and_legacy_records() {
+        // Records without episode IDs match by number
+        let records = vec![record("", 1, "One"), record("2", 2, "Two")];
+        let chapters = vec![chapter("1", 1, "One"), chapter("2", 2, "Two")];
+        assert!(diff_chapters(&records, &chapters).is_empty());
+    }
+}
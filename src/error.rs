@@ -7,6 +7,7 @@ use thiserror::Error;
 
 /// Main error type for scraping operations.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ScraperError {
     /// HTTP request failed
     #[error("HTTP request failed: {0}")]
@@ -35,6 +36,61 @@ pub enum ScraperError {
     /// The scraper doesn't support this URL
     #[error("Unsupported URL: {0}")]
     UnsupportedUrl(String),
+
+    /// The page requires a logged-in session (missing or expired cookies)
+    #[error("Login required: {0}")]
+    LoginRequired(String),
+
+    /// The site's robots.txt disallows the page
+    #[error("Disallowed by robots.txt: {0}")]
+    RobotsDisallowed(String),
+
+    /// The downloaded chapter is empty, too short, or a placeholder page
+    #[error("Invalid chapter content: {0}")]
+    InvalidContent(String),
+}
+
+impl ScraperError {
+    /// Returns true if retrying the same request later may succeed.
+    ///
+    /// Timeouts, connection failures, rate limiting, and server-side (5xx)
+    /// errors are transient; parse failures and client errors are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ScraperError::HttpError(e) => is_retryable_reqwest_error(e),
+            ScraperError::RateLimited(_) => true,
+            ScraperError::ParseError(_)
+            | ScraperError::ElementNotFound(_)
+            | ScraperError::InvalidUrl(_)
+            | ScraperError::NotFound(_)
+            | ScraperError::UnsupportedUrl(_)
+            | ScraperError::LoginRequired(_)
+            | ScraperError::RobotsDisallowed(_)
+            | ScraperError::InvalidContent(_) => false,
+        }
+    }
+
+    /// Returns true if the error indicates missing or rejected credentials
+    /// (HTTP 401/403), e.g. expired login cookies.
+    pub fn is_auth(&self) -> bool {
+        match self {
+            ScraperError::HttpError(e) => e.status().is_some_and(is_auth_status),
+            ScraperError::LoginRequired(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the page no longer exists (HTTP 404/410), e.g. a
+    /// deleted or privated chapter.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            ScraperError::HttpError(e) => e.status().is_some_and(|status| {
+                status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE
+            }),
+            ScraperError::NotFound(_) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Error type for configuration operations.
@@ -61,8 +117,31 @@ pub enum ConfigError {
     NoConfigDir,
 }
 
+/// Kind of failure a retry policy budgets separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryKind {
+    /// Network failures, rate limiting, and server errors, retried as-is.
+    Network,
+    /// The model refused or answered with nothing, retried with a nudge.
+    Refusal,
+    /// A response that couldn't be parsed.
+    Parse,
+}
+
+impl std::fmt::Display for RetryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            RetryKind::Network => "network",
+            RetryKind::Refusal => "refusal",
+            RetryKind::Parse => "parse",
+        };
+        f.write_str(label)
+    }
+}
+
 /// Error type for translation operations.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum TranslationError {
     /// HTTP request to API failed
     #[error("API request failed: {0}")]
@@ -72,6 +151,13 @@ pub enum TranslationError {
     #[error("API error: {0}")]
     ApiError(String),
 
+    /// API returned a non-success HTTP status
+    #[error("API error: HTTP {status}: {body}")]
+    HttpStatus {
+        status: reqwest::StatusCode,
+        body: String,
+    },
+
     /// Failed to parse API response
     #[error("Failed to parse API response: {0}")]
     ParseError(String),
@@ -81,12 +167,85 @@ pub enum TranslationError {
     Refused(String),
 
     /// All retry attempts exhausted
-    #[error("All retries exhausted after {attempts} attempts")]
-    RetriesExhausted { attempts: u32 },
+    #[error("All retries exhausted after {attempts} attempt(s): {last_error}")]
+    RetriesExhausted {
+        attempts: u32,
+        #[source]
+        last_error: Box<TranslationError>,
+    },
 
     /// Invalid API configuration
     #[error("Invalid API configuration: {0}")]
     InvalidConfig(String),
+
+    /// The operation was cancelled via a cancellation token
+    #[error("Translation cancelled")]
+    Cancelled,
+}
+
+impl TranslationError {
+    /// Returns true if retrying the same request later may succeed.
+    ///
+    /// Network failures, rate limiting, server errors, malformed responses,
+    /// and refusals are worth retrying; authentication failures, other client
+    /// errors, and configuration problems are not.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TranslationError::HttpError(e) => is_retryable_reqwest_error(e),
+            TranslationError::HttpStatus { status, .. } => is_retryable_status(*status),
+            TranslationError::ApiError(_)
+            | TranslationError::ParseError(_)
+            | TranslationError::Refused(_) => true,
+            TranslationError::RetriesExhausted { .. }
+            | TranslationError::InvalidConfig(_)
+            | TranslationError::Cancelled => false,
+        }
+    }
+
+    /// Which retry budget the error counts against, or None if it isn't
+    /// worth retrying.
+    pub fn retry_kind(&self) -> Option<RetryKind> {
+        if !self.is_retryable() {
+            return None;
+        }
+        Some(match self {
+            TranslationError::Refused(_) => RetryKind::Refusal,
+            TranslationError::ParseError(_) => RetryKind::Parse,
+            _ => RetryKind::Network,
+        })
+    }
+
+    /// Returns true if the API rejected the credentials (HTTP 401/403).
+    ///
+    /// Retrying is pointless and the run should be aborted so the user can
+    /// fix the key.
+    pub fn is_auth(&self) -> bool {
+        match self {
+            TranslationError::HttpError(e) => e.status().is_some_and(is_auth_status),
+            TranslationError::HttpStatus { status, .. } => is_auth_status(*status),
+            _ => false,
+        }
+    }
+}
+
+/// Classifies a reqwest error as transient or permanent.
+fn is_retryable_reqwest_error(e: &reqwest::Error) -> bool {
+    if let Some(status) = e.status() {
+        return is_retryable_status(status);
+    }
+    e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() || e.is_decode()
+}
+
+/// Returns true for statuses that indicate a temporary condition.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Returns true for statuses that indicate rejected credentials.
+fn is_auth_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN
 }
 
 /// Error type for name mapping operations.
@@ -104,10 +263,192 @@ pub enum NameMappingError {
     #[error("Invalid name mapping structure: {0}")]
     InvalidStructure(String),
 
+    /// Mapping file isn't valid JSON (e.g. after a manual edit)
+    #[error("Invalid name mapping JSON on line {line}, column {column}: {explanation}")]
+    InvalidJson {
+        line: usize,
+        column: usize,
+        /// What is wrong, in plain words.
+        explanation: String,
+        /// Text of the offending line.
+        source_line: String,
+    },
+
     /// Failed to write mapping file
     #[error("Failed to save name mapping: {0}")]
     WriteError(String),
 }
 
+/// Error type for library database operations.
+#[derive(Error, Debug)]
+pub enum LibraryError {
+    /// Failed to read or write the library file
+    #[error("Failed to access library: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to parse or serialize the library JSON
+    #[error("Failed to parse library JSON: {0}")]
+    ParseError(#[from] serde_json::Error),
+}
+
+/// Error type for story manifest operations.
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    /// Failed to read or write the manifest file
+    #[error("Failed to access manifest: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to parse or serialize the manifest JSON
+    #[error("Failed to parse manifest JSON: {0}")]
+    ParseError(#[from] serde_json::Error),
+
+    /// Failed to rename entries in the originals archive
+    #[error("Failed to update originals archive: {0}")]
+    Archive(#[from] ArchiveError),
+}
+
+/// Error type for the originals archive.
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    /// Failed to read or write the archive file
+    #[error("Failed to access archive: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The archive is corrupt or an entry couldn't be written
+    #[error("Invalid archive: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// Error type for syncing output to remote storage.
+#[derive(Error, Debug)]
+pub enum SyncError {
+    /// Failed to read an output file or the sync state
+    #[error("Failed to access file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to parse or serialize the sync state
+    #[error("Failed to parse sync state: {0}")]
+    ParseError(#[from] serde_json::Error),
+
+    /// HTTP request failed
+    #[error("Upload request failed: {0}")]
+    HttpError(#[from] reqwest::Error),
+
+    /// The server rejected an upload
+    #[error("Upload of {path} failed: HTTP {status}")]
+    HttpStatus {
+        path: String,
+        status: reqwest::StatusCode,
+    },
+
+    /// No sync target is configured
+    #[error("No sync target configured (add [sync.webdav] or [sync.s3])")]
+    NotConfigured,
+
+    /// `s3.endpoint` isn't a valid URL
+    #[error("Invalid S3 endpoint: {0}")]
+    InvalidEndpoint(String),
+}
+
+/// Error type for packaging a story folder.
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// Failed to read the story folder or write the package
+    #[error("Failed to access file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to write the zip or EPUB container
+    #[error("Failed to write archive: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// The story has no translated chapters
+    #[error("No translated chapters to export")]
+    NoChapters,
+}
+
+/// Error type for batch (URL list) files.
+#[derive(Error, Debug)]
+pub enum BatchError {
+    /// Failed to read the batch file
+    #[error("Failed to read batch file: {0}")]
+    ReadError(#[from] std::io::Error),
+
+    /// A line could not be parsed
+    #[error("Line {line}: {message}")]
+    InvalidLine { line: usize, message: String },
+}
+
 /// Result type alias using anyhow for application-level error handling.
 pub type Result<T> = anyhow::Result<T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    fn status_error(status: StatusCode) -> TranslationError {
+        TranslationError::HttpStatus {
+            status,
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_translation_status_classification() {
+        assert!(status_error(StatusCode::TOO_MANY_REQUESTS).is_retryable());
+        assert!(status_error(StatusCode::BAD_GATEWAY).is_retryable());
+        assert!(!status_error(StatusCode::BAD_REQUEST).is_retryable());
+        assert!(!status_error(StatusCode::NOT_FOUND).is_retryable());
+
+        assert!(status_error(StatusCode::UNAUTHORIZED).is_auth());
+        assert!(status_error(StatusCode::FORBIDDEN).is_auth());
+        assert!(!status_error(StatusCode::UNAUTHORIZED).is_retryable());
+        assert!(!status_error(StatusCode::INTERNAL_SERVER_ERROR).is_auth());
+    }
+
+    #[test]
+    fn test_translation_error_kinds() {
+        assert!(TranslationError::Refused("Empty response".to_string()).is_retryable());
+        assert!(TranslationError::ParseError("bad".to_string()).is_retryable());
+        assert!(!TranslationError::InvalidConfig("bad".to_string()).is_retryable());
+        let exhausted = TranslationError::RetriesExhausted {
+            attempts: 3,
+            last_error: Box::new(TranslationError::Refused("Empty response".to_string())),
+        };
+        assert!(!exhausted.is_retryable());
+        assert_eq!(
+            exhausted.to_string(),
+            "All retries exhausted after 3 attempt(s): Translation refused: Empty response"
+        );
+        assert!(std::error::Error::source(&exhausted).is_some());
+        assert!(!TranslationError::Cancelled.is_retryable());
+
+        assert_eq!(
+            TranslationError::Refused("Empty response".to_string()).retry_kind(),
+            Some(RetryKind::Refusal)
+        );
+        assert_eq!(
+            TranslationError::ParseError("bad".to_string()).retry_kind(),
+            Some(RetryKind::Parse)
+        );
+        assert_eq!(
+            status_error(StatusCode::BAD_GATEWAY).retry_kind(),
+            Some(RetryKind::Network)
+        );
+        assert_eq!(status_error(StatusCode::BAD_REQUEST).retry_kind(), None);
+    }
+
+    #[test]
+    fn test_scraper_error_kinds() {
+        assert!(ScraperError::RateLimited("slow down".to_string()).is_retryable());
+        assert!(!ScraperError::NotFound("gone".to_string()).is_retryable());
+        assert!(!ScraperError::ElementNotFound("title".to_string()).is_retryable());
+        assert!(!ScraperError::NotFound("gone".to_string()).is_auth());
+    }
+
+    #[test]
+    fn test_http_status_message() {
+        let err = status_error(StatusCode::UNAUTHORIZED);
+        assert_eq!(err.to_string(), "API error: HTTP 401 Unauthorized: ");
+    }
+}
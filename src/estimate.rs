@@ -0,0 +1,152 @@
+//! Projections of what translating a whole novel would take.
+//!
+//! `tsundoku info` downloads a few sample chapters; from their text this
+//! works out the chunks, requests, and tokens the full novel needs at the
+//! configured chunk size and history, what that costs at the configured
+//! prices, and how long it takes with the configured request delays.
+
+use crate::config::Config;
+use crate::utils::{Chunker, estimate_tokens};
+
+/// Output tokens per source token when translating Japanese to English
+/// (a thousand characters of Japanese come out as roughly 600 tokens of
+/// English).
+pub const OUTPUT_TOKENS_PER_SOURCE_TOKEN: f64 = 0.6;
+
+/// Output tokens a model generates per second, for the time estimate.
+pub const OUTPUT_TOKENS_PER_SEC: f64 = 50.0;
+
+/// Projected totals for translating a whole novel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunEstimate {
+    /// Japanese characters to translate.
+    pub chars: usize,
+    /// Translation chunks.
+    pub chunks: usize,
+    /// API requests: one per chunk, plus one per chapter title.
+    pub requests: usize,
+    /// Prompt tokens, including the system prompt and history sent with
+    /// each chunk.
+    pub input_tokens: u64,
+    /// Completion tokens.
+    pub output_tokens: u64,
+    /// Cost at `api.input_price_per_mtok` and `api.output_price_per_mtok`
+    /// (none unless both are set).
+    pub cost: Option<f64>,
+    /// Wall-clock time, in seconds: downloads and request delays plus
+    /// generating the output.
+    pub duration_sec: f64,
+}
+
+/// Projects the totals for a novel of `chapter_count` chapters from the text
+/// of some of its chapters. Returns `None` without samples.
+pub fn estimate_run(
+    samples: &[String],
+    chapter_count: usize,
+    config: &Config,
+) -> Option<RunEstimate> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let translation = &config.translation;
+    let chunker = Chunker::new(translation.chunk_size_chars, translation.chunk_size_unit)
+        .with_overlap(translation.chunk_overlap);
+    let system_tokens = estimate_tokens(&config.prompts.content_translation) as f64;
+
+    let (mut chars, mut chunks, mut input, mut output) = (0usize, 0usize, 0.0, 0.0);
+    for sample in samples {
+        chars += sample.chars().count();
+        for chunk in chunker.chunks(sample) {
+            let source = (estimate_tokens(&chunk.overlap) + estimate_tokens(&chunk.text)) as f64;
+            let translated = source * OUTPUT_TOKENS_PER_SOURCE_TOKEN;
+            let mut history = translation.history_length as f64 * (source + translated);
+            if translation.history_max_tokens > 0 {
+                history = history.min(translation.history_max_tokens as f64);
+            }
+            chunks += 1;
+            input += system_tokens + history + source;
+            output += translated;
+        }
+    }
+
+    let scale = chapter_count as f64 / samples.len() as f64;
+    let chunks = (chunks as f64 * scale).round() as usize;
+    let requests = chunks + chapter_count;
+    let input_tokens = (input * scale).round() as u64;
+    let output_tokens = (output * scale).round() as u64;
+    let cost = config
+        .api
+        .input_price_per_mtok
+        .zip(config.api.output_price_per_mtok)
+        .map(|(input_price, output_price)| {
+            (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0
+        });
+    let scraping = &config.scraping;
+    let duration_sec = chapter_count as f64
+        * average_delay(
+            scraping.delay_between_requests_sec,
+            scraping.jitter_min_sec,
+            scraping.jitter_max_sec,
+        )
+        + requests as f64
+            * average_delay(
+                translation.delay_between_requests_sec,
+                translation.jitter_min_sec,
+                translation.jitter_max_sec,
+            )
+        + output_tokens as f64 / OUTPUT_TOKENS_PER_SEC;
+
+    Some(RunEstimate {
+        chars: (chars as f64 * scale).round() as usize,
+        chunks,
+        requests,
+        input_tokens,
+        output_tokens,
+        cost,
+        duration_sec,
+    })
+}
+
+/// Mean of a delay with uniform jitter added.
+fn average_delay(base: f64, jitter_min: f64, jitter_max: f64) -> f64 {
+    base + (jitter_min + jitter_max) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_run() {
+        let mut config = Config::default();
+        config.translation.chunk_size_chars = 101;
+        config.translation.history_length = 0;
+        config.translation.delay_between_requests_sec = 1.0;
+        config.scraping.delay_between_requests_sec = 2.0;
+        config.scraping.jitter_min_sec = 0.0;
+        config.scraping.jitter_max_sec = 0.0;
+        config.prompts.content_translation = String::new();
+
+        // Two samples: 152 characters in two chunks and 50 in one, 201
+        // source tokens (the newline joining two lines counts as one)
+        let line = "あ".repeat(50);
+        let samples = vec![format!("{}\n{}\n{}", line, line, line), line.clone()];
+        let estimate = estimate_run(&samples, 10, &config).unwrap();
+        assert_eq!(estimate.chunks, 15);
+        assert_eq!(estimate.requests, 25);
+        assert_eq!(estimate.chars, 1010);
+        assert_eq!(estimate.output_tokens, 603);
+        assert_eq!(estimate.cost, None);
+        let expected = 10.0 * 2.0 + 25.0 * 1.0 + 603.0 / OUTPUT_TOKENS_PER_SEC;
+        assert!((estimate.duration_sec - expected).abs() < 1e-9);
+
+        config.api.input_price_per_mtok = Some(1.0);
+        config.api.output_price_per_mtok = Some(4.0);
+        let estimate = estimate_run(&samples, 10, &config).unwrap();
+        let expected = (estimate.input_tokens as f64 + 4.0 * 603.0) / 1_000_000.0;
+        assert!((estimate.cost.unwrap() - expected).abs() < 1e-12);
+
+        assert_eq!(estimate_run(&[], 10, &config), None);
+    }
+}
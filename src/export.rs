@@ -0,0 +1,672 @@
+//! Packages a story folder as an EPUB, zip, or single HTML file.
+//!
+//! Works entirely from files already on disk (the manifest and translated
+//! chapters), so exporting never touches the network or the translation
+//! API. EPUB and HTML exports end with a colophon recording
+//! where the text came from and how each run of chapters was translated.
+
+use crate::config::FuriganaMode;
+use crate::error::ExportError;
+use crate::manifest::{Manifest, TranslationProvenance};
+use crate::metadata::split_front_matter;
+use crate::utils::{format_unix_date, format_unix_timestamp, join_relative, now_unix};
+use regex::Regex;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// A kanji run followed by its kana reading in parentheses, as scrapers
+/// write it in `html` furigana mode.
+static RUBY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"([\p{Han}々〆ヶ]+)[(（]([\p{Hiragana}\p{Katakana}ー]+)[)）]").unwrap()
+});
+
+/// Story-folder file with the translated synopsis and tags.
+pub const ABOUT_FILENAME: &str = "about.txt";
+
+/// Package format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// EPUB 3 e-book.
+    Epub,
+    /// Zip of the translated text files and `about.txt`.
+    Zip,
+    /// Single self-contained HTML page.
+    Html,
+}
+
+impl ExportFormat {
+    /// File extension for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Epub => "epub",
+            ExportFormat::Zip => "zip",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "epub" => Ok(ExportFormat::Epub),
+            "zip" => Ok(ExportFormat::Zip),
+            "html" => Ok(ExportFormat::Html),
+            _ => Err(format!(
+                "unknown format '{}' (expected epub, zip, or html)",
+                s
+            )),
+        }
+    }
+}
+
+/// A translated chapter to package.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookChapter {
+    /// Chapter number.
+    pub number: u32,
+    /// Translated title, taken from the file name.
+    pub title: String,
+    /// Translated file, relative to the story folder.
+    pub file: String,
+    /// Translated text.
+    pub text: String,
+    /// When the original was saved (Unix seconds; 0 if unknown).
+    pub downloaded_unix: u64,
+    /// How the translation was made, if recorded.
+    pub provenance: Option<TranslationProvenance>,
+}
+
+/// Everything needed to package a story.
+#[derive(Debug, Clone, Default)]
+pub struct Book {
+    /// Story folder name.
+    pub folder: String,
+    /// English title, taken from the folder name.
+    pub title: String,
+    /// Original title from the manifest.
+    pub original_title: String,
+    /// Author's name as the site gives it, from the manifest.
+    pub author: String,
+    /// Stable identifier ("site:novel_id").
+    pub identifier: String,
+    /// Novel URL, from the manifest.
+    pub source_url: String,
+    /// Translated synopsis, from the manifest.
+    pub synopsis: String,
+    /// Translated tags, from the manifest.
+    pub tags: Vec<String>,
+    /// Raw `about.txt`, if present.
+    pub about: Option<String>,
+    /// Translated chapters, ordered by number.
+    pub chapters: Vec<BookChapter>,
+    /// Furigana mode; `html` renders readings as ruby in HTML and EPUB.
+    pub furigana: FuriganaMode,
+}
+
+/// Collects a story folder's translated chapters and metadata.
+///
+/// Chapters whose translation is missing on disk are skipped.
+pub fn load_book(story_dir: &Path, manifest: &Manifest) -> Result<Book, ExportError> {
+    let data = manifest.data();
+    let folder = story_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // Folders are named "[site: id] Translated Title"
+    let title = match folder.split_once("] ") {
+        Some((_, title)) if !title.is_empty() => title.to_string(),
+        _ => data.title.clone(),
+    };
+
+    let mut chapters: Vec<BookChapter> = data
+        .chapters
+        .iter()
+        .filter(|c| c.translated)
+        .filter_map(|c| {
+            let file = c.translated_file.clone()?;
+            let text = std::fs::read_to_string(join_relative(story_dir, &file)).ok()?;
+            let text = split_front_matter(&text).1.to_string();
+            let stem = Path::new(&file).file_stem()?.to_string_lossy().to_string();
+            let title = match stem.split_once(" - ") {
+                Some((_, title)) => title.to_string(),
+                None => stem,
+            };
+            Some(BookChapter {
+                number: c.number,
+                title,
+                file,
+                text,
+                downloaded_unix: c.downloaded_unix,
+                provenance: c.provenance.clone(),
+            })
+        })
+        .collect();
+    if chapters.is_empty() {
+        return Err(ExportError::NoChapters);
+    }
+    chapters.sort_by_key(|c| c.number);
+
+    let info = data.about.clone().unwrap_or_default();
+    Ok(Book {
+        folder,
+        title,
+        original_title: data.title.clone(),
+        author: info.author,
+        identifier: format!("{}:{}", data.site, data.novel_id),
+        source_url: info.url,
+        synopsis: info.synopsis,
+        tags: info.tags,
+        about: std::fs::read_to_string(story_dir.join(ABOUT_FILENAME)).ok(),
+        chapters,
+        ..Default::default()
+    })
+}
+
+/// Writes the book to `path` in the given format.
+pub fn export_book(book: &Book, format: ExportFormat, path: &Path) -> Result<(), ExportError> {
+    match format {
+        ExportFormat::Epub => write_epub(book, path, now_unix()),
+        ExportFormat::Zip => write_zip(book, path),
+        ExportFormat::Html => Ok(std::fs::write(path, render_html(book, now_unix()))?),
+    }
+}
+
+/// Writes an EPUB 3 container, dated `exported_unix`.
+fn write_epub(book: &Book, path: &Path, exported_unix: u64) -> Result<(), ExportError> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    // The mimetype must come first, uncompressed
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(b"application/epub+zip")?;
+
+    zip.start_file("META-INF/container.xml", deflated)?;
+    zip.write_all(CONTAINER_XML.as_bytes())?;
+    zip.start_file("OEBPS/content.opf", deflated)?;
+    zip.write_all(render_opf(book, &format_unix_timestamp(exported_unix)).as_bytes())?;
+    zip.start_file("OEBPS/nav.xhtml", deflated)?;
+    zip.write_all(render_nav(book).as_bytes())?;
+
+    zip.start_file("OEBPS/title.xhtml", deflated)?;
+    zip.write_all(xhtml_page(&book.title, &title_page(book)).as_bytes())?;
+    for chapter in &book.chapters {
+        let body = format!(
+            "<h1>{}</h1>\n{}",
+            escape_xml(&chapter.title),
+            paragraphs(&chapter.text, book.furigana)
+        );
+        zip.start_file(format!("OEBPS/{}.xhtml", chapter_id(chapter)), deflated)?;
+        zip.write_all(xhtml_page(&chapter.title, &body).as_bytes())?;
+    }
+    let body = format!("<h1>Colophon</h1>\n{}", colophon(book, exported_unix));
+    zip.start_file("OEBPS/colophon.xhtml", deflated)?;
+    zip.write_all(xhtml_page("Colophon", &body).as_bytes())?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+fn render_opf(book: &Book, modified: &str) -> String {
+    let mut metadata = format!(
+        "    <dc:identifier id=\"book-id\">urn:tsundoku:{}</dc:identifier>\n    <dc:title>{}</dc:title>\n    <dc:language>en</dc:language>\n",
+        escape_xml(&book.identifier),
+        escape_xml(&book.title)
+    );
+    if !book.author.is_empty() {
+        metadata.push_str(&format!(
+            "    <dc:creator>{}</dc:creator>\n",
+            escape_xml(&book.author)
+        ));
+    }
+    if !book.synopsis.is_empty() {
+        metadata.push_str(&format!(
+            "    <dc:description>{}</dc:description>\n",
+            escape_xml(&book.synopsis)
+        ));
+    }
+    for tag in &book.tags {
+        metadata.push_str(&format!(
+            "    <dc:subject>{}</dc:subject>\n",
+            escape_xml(tag)
+        ));
+    }
+    if !book.source_url.is_empty() {
+        metadata.push_str(&format!(
+            "    <dc:source>{}</dc:source>\n",
+            escape_xml(&book.source_url)
+        ));
+    }
+    metadata.push_str(&format!(
+        "    <meta property=\"dcterms:modified\">{}</meta>\n",
+        modified
+    ));
+
+    let mut items = String::from(
+        "    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n",
+    );
+    let mut spine = String::new();
+    let pages = std::iter::once("title".to_string())
+        .chain(book.chapters.iter().map(chapter_id))
+        .chain(std::iter::once("colophon".to_string()));
+    for id in pages {
+        items.push_str(&format!(
+            "    <item id=\"{id}\" href=\"{id}.xhtml\" media-type=\"application/xhtml+xml\"/>\n"
+        ));
+        spine.push_str(&format!("    <itemref idref=\"{}\"/>\n", id));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\">\n  <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n{}  </metadata>\n  <manifest>\n{}  </manifest>\n  <spine>\n{}  </spine>\n</package>\n",
+        metadata, items, spine
+    )
+}
+
+fn render_nav(book: &Book) -> String {
+    let mut entries = String::from("<li><a href=\"title.xhtml\">Title Page</a></li>\n");
+    for chapter in &book.chapters {
+        entries.push_str(&format!(
+            "<li><a href=\"{}.xhtml\">{}</a></li>\n",
+            chapter_id(chapter),
+            escape_xml(&chapter.title)
+        ));
+    }
+    entries.push_str("<li><a href=\"colophon.xhtml\">Colophon</a></li>\n");
+    let body = format!(
+        "<nav epub:type=\"toc\" id=\"toc\">\n<h1>{}</h1>\n<ol>\n{}</ol>\n</nav>\n",
+        escape_xml(&book.title),
+        entries
+    );
+    xhtml_page(&book.title, &body)
+}
+
+/// Renders the title page body: the English and original titles, the
+/// author, and the synopsis.
+fn title_page(book: &Book) -> String {
+    let mut body = format!("<h1>{}</h1>\n", escape_xml(&book.title));
+    if book.original_title != book.title {
+        body.push_str(&format!("<p>{}</p>\n", escape_xml(&book.original_title)));
+    }
+    if !book.author.is_empty() {
+        body.push_str(&format!("<p>by {}</p>\n", escape_xml(&book.author)));
+    }
+    body.push_str(&paragraphs(&book.synopsis, book.furigana));
+    body
+}
+
+fn xhtml_page(title: &str, body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE html>\n<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"en\" lang=\"en\">\n<head>\n<meta charset=\"UTF-8\"/>\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(title),
+        body
+    )
+}
+
+/// Writes the translated text files and `about.txt` into a zip, under the
+/// story folder's name.
+fn write_zip(book: &Book, path: &Path) -> Result<(), ExportError> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    if let Some(about) = &book.about {
+        zip.start_file(format!("{}/{}", book.folder, ABOUT_FILENAME), options)?;
+        zip.write_all(about.as_bytes())?;
+    }
+    for chapter in &book.chapters {
+        zip.start_file(format!("{}/{}", book.folder, chapter.file), options)?;
+        zip.write_all(chapter.text.as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Renders the whole book as one HTML page with a table of contents,
+/// dated `exported_unix` in the colophon.
+pub fn render_html(book: &Book, exported_unix: u64) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>body {{ max-width: 40em; margin: 2em auto; padding: 0 1em; line-height: 1.6; }}</style>\n</head>\n<body>\n{title_page}",
+        title = escape_xml(&book.title),
+        title_page = title_page(book)
+    );
+
+    html.push_str("<nav>\n<ol>\n");
+    for chapter in &book.chapters {
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            chapter_id(chapter),
+            escape_xml(&chapter.title)
+        ));
+    }
+    html.push_str("<li><a href=\"#colophon\">Colophon</a></li>\n</ol>\n</nav>\n");
+
+    for chapter in &book.chapters {
+        html.push_str(&format!(
+            "<section id=\"{}\">\n<h2>{}</h2>\n{}</section>\n",
+            chapter_id(chapter),
+            escape_xml(&chapter.title),
+            paragraphs(&chapter.text, book.furigana)
+        ));
+    }
+
+    html.push_str(&format!(
+        "<section id=\"colophon\">\n<h2>Colophon</h2>\n{}</section>\n",
+        colophon(book, exported_unix)
+    ));
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Chapters translated the same way, in reading order.
+struct TranslationRun<'a> {
+    chapters: Vec<&'a BookChapter>,
+}
+
+impl TranslationRun<'_> {
+    fn provenance(&self) -> Option<&TranslationProvenance> {
+        self.chapters[0].provenance.as_ref()
+    }
+
+    /// First and last chapter numbers, e.g. "3-17".
+    fn chapter_range(&self) -> String {
+        let first = self.chapters[0].number;
+        let last = self.chapters[self.chapters.len() - 1].number;
+        if first == last {
+            first.to_string()
+        } else {
+            format!("{}-{}", first, last)
+        }
+    }
+}
+
+/// Groups consecutive chapters with the same model, prompt, and name
+/// mapping.
+fn translation_runs(chapters: &[BookChapter]) -> Vec<TranslationRun<'_>> {
+    let key = |c: &BookChapter| {
+        c.provenance
+            .as_ref()
+            .map(|p| (p.model.clone(), p.prompt_hash.clone(), p.names_hash.clone()))
+    };
+    let mut runs: Vec<TranslationRun> = Vec::new();
+    for chapter in chapters {
+        match runs.last_mut() {
+            Some(run) if key(run.chapters[0]) == key(chapter) => run.chapters.push(chapter),
+            _ => runs.push(TranslationRun {
+                chapters: vec![chapter],
+            }),
+        }
+    }
+    runs
+}
+
+/// Date span of Unix timestamps, ignoring unknown (zero) ones.
+fn date_span(times: impl Iterator<Item = u64>) -> String {
+    let times: Vec<u64> = times.filter(|&t| t > 0).collect();
+    match (times.iter().min(), times.iter().max()) {
+        (Some(&first), Some(&last)) => {
+            let (first, last) = (format_unix_date(first), format_unix_date(last));
+            if first == last {
+                first
+            } else {
+                format!("{} to {}", first, last)
+            }
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Shortens a hash for display.
+fn short_hash(hash: &str) -> &str {
+    hash.get(..12).unwrap_or(hash)
+}
+
+/// Renders the colophon body: the source, the export, and a table of how
+/// each run of chapters was scraped and translated.
+fn colophon(book: &Book, exported_unix: u64) -> String {
+    let source = if book.source_url.is_empty() {
+        escape_xml(&book.original_title)
+    } else {
+        format!(
+            "<a href=\"{}\">{}</a>",
+            escape_xml(&book.source_url),
+            escape_xml(&book.original_title)
+        )
+    };
+    let mut out = format!(
+        "<p>Translated from {} ({}).</p>\n<p>Exported {} with Tsundoku {}.</p>\n",
+        source,
+        escape_xml(&book.identifier),
+        format_unix_date(exported_unix),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    out.push_str("<table>\n<tr><th>Chapters</th><th>Scraped</th><th>Translated</th><th>Model</th><th>Prompt</th><th>Name mapping</th></tr>\n");
+    for run in translation_runs(&book.chapters) {
+        let scraped = date_span(run.chapters.iter().map(|c| c.downloaded_unix));
+        let (translated, model, prompt, names) = match run.provenance() {
+            Some(provenance) => (
+                date_span(
+                    run.chapters
+                        .iter()
+                        .filter_map(|c| c.provenance.as_ref())
+                        .map(|p| p.translated_unix),
+                ),
+                provenance.model.as_str(),
+                short_hash(&provenance.prompt_hash),
+                short_hash(&provenance.names_hash),
+            ),
+            None => ("unknown".to_string(), "unknown", "unknown", "unknown"),
+        };
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            run.chapter_range(),
+            scraped,
+            translated,
+            escape_xml(model),
+            prompt,
+            names
+        ));
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+fn chapter_id(chapter: &BookChapter) -> String {
+    format!("chapter-{:04}", chapter.number)
+}
+
+/// Wraps each non-empty line in a paragraph, turning `漢字(かんじ)`
+/// readings into ruby tags in `html` furigana mode.
+fn paragraphs(text: &str, furigana: FuriganaMode) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let line = escape_xml(l);
+            let line = match furigana {
+                FuriganaMode::Html => RUBY_REGEX
+                    .replace_all(&line, "<ruby>$1<rt>$2</rt></ruby>")
+                    .into_owned(),
+                _ => line,
+            };
+            format!("<p>{}</p>\n", line)
+        })
+        .collect()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::AboutInfo;
+    use crate::scrapers::ChapterInfo;
+    use std::io::Read;
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    fn story() -> (TempDir, Book) {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path().join("[syosetu: n1234ab] The Duel");
+        std::fs::create_dir(&story_dir).unwrap();
+        std::fs::write(
+            story_dir.join("1 - Start.txt"),
+            "Hello & <welcome>.\n\nBye.",
+        )
+        .unwrap();
+        std::fs::write(
+            story_dir.join(ABOUT_FILENAME),
+            "決闘\nhttps://ncode.syosetu.com/n1234ab/\nAuthor: 山田\n\nSynopsis:\nA duel.\n\nTags: none.\n\nTags: Fantasy, Duel\n\n--- Original ---\n\n決闘の話\n",
+        )
+        .unwrap();
+
+        let mut manifest = Manifest::open(&story_dir).unwrap();
+        manifest.set_novel("syosetu", "n1234ab", "決闘");
+        manifest.set_about(AboutInfo {
+            url: "https://ncode.syosetu.com/n1234ab/".to_string(),
+            author: "山田".to_string(),
+            synopsis: "A duel.\n\nTags: none.".to_string(),
+            tags: vec!["Fantasy".to_string(), "Duel".to_string()],
+        });
+        let chapters: Vec<ChapterInfo> = (1..=2)
+            .map(|number| ChapterInfo {
+                title: format!("第{}話", number),
+                url: String::new(),
+                number,
+                episode_id: number.to_string(),
+            })
+            .collect();
+        manifest.sync_chapters(&chapters);
+        manifest.mark_translated("1", "1 - Start.txt", "");
+
+        let book = load_book(&story_dir, &manifest).unwrap();
+        (temp_dir, book)
+    }
+
+    #[test]
+    fn test_load_book() {
+        let (_temp_dir, book) = story();
+        assert_eq!(book.title, "The Duel");
+        assert_eq!(book.original_title, "決闘");
+        assert_eq!(book.identifier, "syosetu:n1234ab");
+        assert_eq!(book.source_url, "https://ncode.syosetu.com/n1234ab/");
+        assert_eq!(book.author, "山田");
+        assert_eq!(book.synopsis, "A duel.\n\nTags: none.");
+        assert_eq!(book.tags, vec!["Fantasy", "Duel"]);
+        assert_eq!(book.chapters.len(), 1);
+        assert_eq!(book.chapters[0].title, "Start");
+
+        let html = render_html(&book, 1_767_323_045);
+        assert!(html.contains("<p>Hello &amp; &lt;welcome&gt;.</p>\n<p>Bye.</p>"));
+        assert!(html.contains("<a href=\"#chapter-0001\">Start</a>"));
+    }
+
+    #[test]
+    fn test_write_epub() {
+        let (temp_dir, book) = story();
+        let path = temp_dir.path().join("book.epub");
+        write_epub(&book, &path, 1_767_323_045).unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let first = archive.by_index(0).unwrap();
+        assert_eq!(first.name(), "mimetype");
+        assert_eq!(first.compression(), CompressionMethod::Stored);
+        drop(first);
+
+        let mut opf = String::new();
+        archive
+            .by_name("OEBPS/content.opf")
+            .unwrap()
+            .read_to_string(&mut opf)
+            .unwrap();
+        assert!(opf.contains("<dc:title>The Duel</dc:title>"));
+        assert!(opf.contains("<dc:subject>Duel</dc:subject>"));
+        assert!(opf.contains("<dc:creator>山田</dc:creator>"));
+        assert!(opf.contains("<itemref idref=\"title\"/>\n    <itemref idref=\"chapter-0001\"/>"));
+        assert!(opf.contains("<meta property=\"dcterms:modified\">2026-01-02T03:04:05Z</meta>"));
+        assert!(
+            opf.contains("<itemref idref=\"chapter-0001\"/>\n    <itemref idref=\"colophon\"/>")
+        );
+        assert!(archive.by_name("OEBPS/chapter-0001.xhtml").is_ok());
+
+        let mut title = String::new();
+        archive
+            .by_name("OEBPS/title.xhtml")
+            .unwrap()
+            .read_to_string(&mut title)
+            .unwrap();
+        assert!(title.contains("<h1>The Duel</h1>\n<p>決闘</p>\n<p>by 山田</p>\n<p>A duel.</p>"));
+
+        let mut colophon = String::new();
+        archive
+            .by_name("OEBPS/colophon.xhtml")
+            .unwrap()
+            .read_to_string(&mut colophon)
+            .unwrap();
+        assert!(colophon.contains("<a href=\"https://ncode.syosetu.com/n1234ab/\">決闘</a>"));
+        assert!(colophon.contains("Exported 2026-01-02"));
+    }
+
+    #[test]
+    fn test_furigana_ruby() {
+        let (_temp_dir, mut book) = story();
+        book.chapters[0].text = "Taro(太郎) read 漢字(かんじ) aloud.".to_string();
+
+        let html = render_html(&book, 1_767_323_045);
+        assert!(html.contains("<p>Taro(太郎) read 漢字(かんじ) aloud.</p>"));
+
+        book.furigana = FuriganaMode::Html;
+        let html = render_html(&book, 1_767_323_045);
+        assert!(html.contains("<p>Taro(太郎) read <ruby>漢字<rt>かんじ</rt></ruby> aloud.</p>"));
+    }
+
+    #[test]
+    fn test_colophon_runs() {
+        let (_temp_dir, mut book) = story();
+        let provenance = |model: &str| TranslationProvenance {
+            model: model.to_string(),
+            prompt_hash: "0123456789abcdef".to_string(),
+            names_hash: "fedcba9876543210".to_string(),
+            translated_unix: 1_767_323_045,
+            profile: None,
+        };
+        let chapter = |number: u32, provenance: Option<TranslationProvenance>| BookChapter {
+            number,
+            provenance,
+            downloaded_unix: 1_767_000_000,
+            ..book.chapters[0].clone()
+        };
+        book.chapters = vec![
+            chapter(1, None),
+            chapter(2, Some(provenance("gemini-2.5-pro"))),
+            chapter(3, Some(provenance("gemini-2.5-pro"))),
+            chapter(4, Some(provenance("gemini-2.5-flash"))),
+        ];
+
+        let html = render_html(&book, 1_767_323_045);
+        assert!(html.contains("<tr><td>1</td><td>2025-12-29</td><td>unknown</td><td>unknown</td><td>unknown</td><td>unknown</td></tr>"));
+        assert!(html.contains("<tr><td>2-3</td><td>2025-12-29</td><td>2026-01-02</td><td>gemini-2.5-pro</td><td>0123456789ab</td><td>fedcba987654</td></tr>"));
+        assert!(html.contains("<tr><td>4</td>"));
+    }
+}
@@ -0,0 +1,196 @@
+//! Per-run failure report written to the story folder.
+//!
+//! Unattended runs (batch, update, cron) leave `errors.json` and a readable
+//! `errors.log` listing every chapter that failed during the run, so failures
+//! can be triaged later without the terminal history. A run without failures
+//! removes the previous report.
+
+use crate::utils::format_unix_timestamp;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Machine-readable report filename inside a story folder.
+pub const ERROR_REPORT_FILENAME: &str = "errors.json";
+
+/// Human-readable report filename inside a story folder.
+pub const ERROR_LOG_FILENAME: &str = "errors.log";
+
+/// Pipeline phase a failure happened in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailurePhase {
+    Download,
+    Translate,
+}
+
+impl fmt::Display for FailurePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FailurePhase::Download => "download",
+            FailurePhase::Translate => "translate",
+        };
+        f.write_str(label)
+    }
+}
+
+/// One chapter's failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterFailure {
+    /// Chapter number.
+    pub chapter: u32,
+    /// Chapter title as published.
+    pub title: String,
+    /// Phase that failed.
+    pub phase: FailurePhase,
+    /// Error messages, outermost first (an error chain, or one entry per
+    /// failed part of the chapter).
+    pub errors: Vec<String>,
+    /// Times the chapter was attempted this run.
+    pub attempts: u32,
+    /// Whether a later attempt in the same run succeeded.
+    pub resolved: bool,
+}
+
+/// Failures collected over one run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailureReport {
+    /// When the report was written (`YYYY-MM-DDTHH:MM:SSZ`).
+    pub written_at: String,
+    /// Failures in the order they happened.
+    pub failures: Vec<ChapterFailure>,
+}
+
+impl FailureReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a failure.
+    pub fn record(&mut self, failure: ChapterFailure) {
+        self.failures.push(failure);
+    }
+
+    /// Updates the failure recorded for a chapter and phase after another
+    /// attempt. Returns false if none was recorded.
+    pub fn reattempted(
+        &mut self,
+        chapter: u32,
+        phase: FailurePhase,
+        errors: Option<Vec<String>>,
+    ) -> bool {
+        let Some(failure) = self
+            .failures
+            .iter_mut()
+            .rev()
+            .find(|f| f.chapter == chapter && f.phase == phase)
+        else {
+            return false;
+        };
+        failure.attempts += 1;
+        match errors {
+            Some(errors) => failure.errors = errors,
+            None => failure.resolved = true,
+        }
+        true
+    }
+
+    /// Failures that no later attempt fixed.
+    pub fn unresolved(&self) -> impl Iterator<Item = &ChapterFailure> {
+        self.failures.iter().filter(|f| !f.resolved)
+    }
+
+    /// Renders the plain-text log.
+    pub fn to_log(&self) -> String {
+        let mut log = format!(
+            "Run finished {} with {} failure(s)\n",
+            self.written_at,
+            self.failures.len()
+        );
+        for failure in &self.failures {
+            let status = if failure.resolved {
+                "resolved"
+            } else {
+                "unresolved"
+            };
+            log.push_str(&format!(
+                "\nChapter {} ({}) - {} - {} attempt(s), {}\n",
+                failure.chapter, failure.title, failure.phase, failure.attempts, status
+            ));
+            for error in &failure.errors {
+                log.push_str(&format!("  {}\n", error));
+            }
+        }
+        log
+    }
+
+    /// Writes `errors.json` and `errors.log` to the story folder, or removes
+    /// them if the run had no failures.
+    pub fn write(&mut self, story_dir: &Path, now_unix: u64) -> std::io::Result<()> {
+        let json_path = story_dir.join(ERROR_REPORT_FILENAME);
+        let log_path = story_dir.join(ERROR_LOG_FILENAME);
+        if self.failures.is_empty() {
+            for path in [json_path, log_path] {
+                if path.exists() {
+                    std::fs::remove_file(path)?;
+                }
+            }
+            return Ok(());
+        }
+
+        self.written_at = format_unix_timestamp(now_unix);
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(json_path, json + "\n")?;
+        std::fs::write(log_path, self.to_log())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn failure(chapter: u32, phase: FailurePhase) -> ChapterFailure {
+        ChapterFailure {
+            chapter,
+            title: format!("第{}話", chapter),
+            phase,
+            errors: vec![
+                "Failed to translate chapter".to_string(),
+                "timeout".to_string(),
+            ],
+            attempts: 1,
+            resolved: false,
+        }
+    }
+
+    #[test]
+    fn test_report_write_and_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+
+        let mut report = FailureReport::new();
+        report.record(failure(2, FailurePhase::Download));
+        report.record(failure(3, FailurePhase::Translate));
+        report.record(failure(5, FailurePhase::Translate));
+        assert!(report.reattempted(3, FailurePhase::Translate, None));
+        assert!(report.reattempted(5, FailurePhase::Translate, Some(vec!["503".to_string()])));
+        assert!(!report.reattempted(4, FailurePhase::Translate, None));
+
+        let unresolved: Vec<u32> = report.unresolved().map(|f| f.chapter).collect();
+        assert_eq!(unresolved, vec![2, 5]);
+
+        report.write(story_dir, 0).unwrap();
+        let json = std::fs::read_to_string(story_dir.join(ERROR_REPORT_FILENAME)).unwrap();
+        let parsed: FailureReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.written_at, "1970-01-01T00:00:00Z");
+        let log = std::fs::read_to_string(story_dir.join(ERROR_LOG_FILENAME)).unwrap();
+        assert!(log.contains("Chapter 5 (第5話) - translate - 2 attempt(s), unresolved\n  503\n"));
+
+        FailureReport::new().write(story_dir, 0).unwrap();
+        assert!(!story_dir.join(ERROR_REPORT_FILENAME).exists());
+        assert!(!story_dir.join(ERROR_LOG_FILENAME).exists());
+    }
+}
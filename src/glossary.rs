@@ -0,0 +1,228 @@
+//! Importing third-party glossaries into a name mapping.
+//!
+//! Community term lists come in a few shapes: tab-separated `JP<TAB>EN`
+//! lists, EPUBReader's `JP=EN` glossaries, and JSON, either a flat
+//! `{"JP": "EN"}` object or a mapping file from the predecessor Python tool
+//! (which uses the same layout as ours).
+
+use crate::error::NameMappingError;
+use crate::name_mapping::{NameEntry, NameMappingData, NamePart};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Glossary file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlossaryFormat {
+    /// `JP<TAB>EN` per line, with an optional third column for the name
+    /// part (family, given, full).
+    Tsv,
+    /// EPUBReader glossary: `JP=EN` per line.
+    EpubReader,
+    /// Flat JSON object, or a name mapping file.
+    Json,
+}
+
+impl GlossaryFormat {
+    /// Guesses the format from the file extension, then the content.
+    pub fn detect(path: &Path, content: &str) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => return GlossaryFormat::Json,
+            Some("tsv") => return GlossaryFormat::Tsv,
+            _ => {}
+        }
+        if content.trim_start().starts_with('{') {
+            GlossaryFormat::Json
+        } else if entry_lines(content).any(|(_, line)| line.contains('\t')) {
+            GlossaryFormat::Tsv
+        } else {
+            GlossaryFormat::EpubReader
+        }
+    }
+}
+
+impl FromStr for GlossaryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "tsv" => Ok(GlossaryFormat::Tsv),
+            "epubreader" => Ok(GlossaryFormat::EpubReader),
+            "json" => Ok(GlossaryFormat::Json),
+            _ => Err(format!(
+                "unknown glossary format '{}' (expected tsv, epubreader, or json)",
+                s
+            )),
+        }
+    }
+}
+
+/// A parsed glossary.
+#[derive(Debug, Clone, Default)]
+pub struct Glossary {
+    /// Name pairs from a list-style glossary.
+    pub entries: Vec<NameEntry>,
+    /// Votes and coverage from a mapping file, kept whole so they can be
+    /// merged rather than flattened.
+    pub mapping: Option<NameMappingData>,
+    /// Line numbers (1-based) that couldn't be read as a pair.
+    pub skipped_lines: Vec<usize>,
+}
+
+/// Parses a glossary file's content.
+pub fn parse(content: &str, format: GlossaryFormat) -> Result<Glossary, NameMappingError> {
+    match format {
+        GlossaryFormat::Tsv => Ok(parse_lines(content, |line| {
+            let mut columns = line.split('\t');
+            let original = columns.next()?;
+            let english = columns.next()?;
+            let part = columns.next().and_then(|p| p.trim().parse().ok());
+            Some((original, english, part))
+        })),
+        GlossaryFormat::EpubReader => Ok(parse_lines(content, |line| {
+            let (original, english) = line.split_once('=')?;
+            Some((original, english, None))
+        })),
+        GlossaryFormat::Json => parse_json(content),
+    }
+}
+
+/// Non-blank, non-comment lines with their 1-based line numbers.
+fn entry_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#') && !trimmed.starts_with("//")
+        })
+}
+
+/// Parses a list-style glossary with `split` pulling the original,
+/// English, and optional part out of each line.
+fn parse_lines(
+    content: &str,
+    split: impl Fn(&str) -> Option<(&str, &str, Option<NamePart>)>,
+) -> Glossary {
+    let mut glossary = Glossary::default();
+    for (number, line) in entry_lines(content) {
+        match split(line) {
+            Some((original, english, part))
+                if !original.trim().is_empty() && !english.trim().is_empty() =>
+            {
+                glossary.entries.push(NameEntry {
+                    original: original.trim().to_string(),
+                    english: english.trim().to_string(),
+                    part: part.unwrap_or_default(),
+                    confidence: None,
+                    reading: None,
+                });
+            }
+            _ => glossary.skipped_lines.push(number),
+        }
+    }
+    glossary
+}
+
+/// Parses a flat JSON glossary or a name mapping file.
+fn parse_json(content: &str) -> Result<Glossary, NameMappingError> {
+    let value: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))?;
+    if value.get("names").is_some_and(|names| names.is_object()) {
+        return Ok(Glossary {
+            mapping: Some(serde_json::from_value(value)?),
+            ..Default::default()
+        });
+    }
+
+    let pairs: HashMap<String, String> = serde_json::from_value(value).map_err(|_| {
+        NameMappingError::InvalidStructure(
+            "expected a mapping file or an object of original to English strings".to_string(),
+        )
+    })?;
+    let mut entries: Vec<NameEntry> = pairs
+        .into_iter()
+        .map(|(original, english)| NameEntry {
+            original: original.trim().to_string(),
+            english: english.trim().to_string(),
+            part: NamePart::Unknown,
+            confidence: None,
+            reading: None,
+        })
+        .collect();
+    entries.sort_by(|a, b| a.original.cmp(&b.original));
+    Ok(Glossary {
+        entries,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(glossary: &Glossary) -> Vec<(&str, &str)> {
+        glossary
+            .entries
+            .iter()
+            .map(|e| (e.original.as_str(), e.english.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_line_formats() {
+        let tsv = "# names\n田中\tTanaka\tfamily\n\n太郎\tTaro\nbroken line\n";
+        let glossary = parse(tsv, GlossaryFormat::Tsv).unwrap();
+        assert_eq!(pairs(&glossary), vec![("田中", "Tanaka"), ("太郎", "Taro")]);
+        assert_eq!(glossary.entries[0].part, NamePart::Family);
+        assert_eq!(glossary.skipped_lines, vec![5]);
+
+        let epub = "\u{feff}魔王 = Demon Lord\r\n// comment\nユキ=Yuki\n=Nobody\n";
+        let glossary = parse(epub, GlossaryFormat::EpubReader).unwrap();
+        assert_eq!(
+            pairs(&glossary),
+            vec![("魔王", "Demon Lord"), ("ユキ", "Yuki")]
+        );
+        assert_eq!(glossary.skipped_lines, vec![4]);
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let glossary = parse(
+            r#"{"太郎": "Taro", "田中": "Tanaka"}"#,
+            GlossaryFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(pairs(&glossary), vec![("太郎", "Taro"), ("田中", "Tanaka")]);
+        assert!(glossary.mapping.is_none());
+
+        let mapping = r#"{"names": {"太郎": {"part": "given", "votes": {"Taro": 2}, "english": "Taro", "count": 2}}, "coverage": [1]}"#;
+        let glossary = parse(mapping, GlossaryFormat::Json).unwrap();
+        assert!(glossary.entries.is_empty());
+        assert_eq!(glossary.mapping.unwrap().coverage, vec![1]);
+
+        assert!(parse(r#"{"太郎": 3}"#, GlossaryFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let path = Path::new("terms.txt");
+        assert_eq!(
+            GlossaryFormat::detect(path, "田中\tTanaka"),
+            GlossaryFormat::Tsv
+        );
+        assert_eq!(
+            GlossaryFormat::detect(path, "田中=Tanaka"),
+            GlossaryFormat::EpubReader
+        );
+        assert_eq!(
+            GlossaryFormat::detect(Path::new("names.json"), ""),
+            GlossaryFormat::Json
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Provenance header prepended to translated chapter files.
+//!
+//! The header is rendered from `output.header_template`, replacing
+//! `{placeholder}` names with details about the chapter and translation.
+
+/// Details available to the header template.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderFields<'a> {
+    /// Novel title as published (`{novel_title}`).
+    pub novel_title: &'a str,
+    /// Chapter number (`{chapter_number}`).
+    pub chapter_number: u32,
+    /// Translated chapter title (`{chapter_title}`).
+    pub chapter_title: &'a str,
+    /// Chapter title as published (`{chapter_title_original}`).
+    pub chapter_title_original: &'a str,
+    /// Source URL (`{url}`).
+    pub url: &'a str,
+    /// Translation date, `YYYY-MM-DD` (`{date}`).
+    pub date: &'a str,
+    /// Model used for translation (`{model}`).
+    pub model: &'a str,
+}
+
+/// Renders the header template. Unknown placeholders are left as written.
+pub fn render_header(template: &str, fields: &HeaderFields<'_>) -> String {
+    let chapter_number = fields.chapter_number.to_string();
+    let replacements = [
+        ("{novel_title}", fields.novel_title),
+        ("{chapter_number}", chapter_number.as_str()),
+        ("{chapter_title}", fields.chapter_title),
+        ("{chapter_title_original}", fields.chapter_title_original),
+        ("{url}", fields.url),
+        ("{date}", fields.date),
+        ("{model}", fields.model),
+    ];
+
+    let mut header = template.to_string();
+    for (placeholder, value) in replacements {
+        header = header.replace(placeholder, value);
+    }
+    header
+}
+
+/// Prepends a rendered header to chapter text, separated by a blank line.
+///
+/// An empty template leaves the text unchanged.
+pub fn with_header(template: &str, fields: &HeaderFields<'_>, body: &str) -> String {
+    if template.trim().is_empty() {
+        return body.to_string();
+    }
+    format!("{}\n\n{}", render_header(template, fields).trim_end(), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> HeaderFields<'static> {
+        HeaderFields {
+            novel_title: "転生したら",
+            chapter_number: 12,
+            chapter_title: "The Duel",
+            chapter_title_original: "決闘",
+            url: "https://ncode.syosetu.com/n1234ab/12/",
+            date: "2026-01-02",
+            model: "gpt-4o",
+        }
+    }
+
+    #[test]
+    fn test_render_header() {
+        let template = "{novel_title} #{chapter_number}: {chapter_title} ({chapter_title_original})\nSource: {url}\n{date} {model} {unknown}";
+        assert_eq!(
+            render_header(template, &fields()),
+            "転生したら #12: The Duel (決闘)\nSource: https://ncode.syosetu.com/n1234ab/12/\n2026-01-02 gpt-4o {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_with_header() {
+        assert_eq!(with_header("", &fields(), "Body"), "Body");
+        assert_eq!(
+            with_header("Chapter {chapter_number}\n\n", &fields(), "Body"),
+            "Chapter 12\n\nBody"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Run history log.
+//!
+//! Every download/translate run (a single novel, `update`, or `batch`)
+//! appends one JSON line to `runs.jsonl` in the config directory, so token
+//! spend and progress can be tracked over months.
+
+use crate::translator::TokenUsage;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// History filename inside the config directory.
+pub const HISTORY_FILENAME: &str = "runs.jsonl";
+
+/// What a run did for one novel.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NovelRun {
+    /// URL or story folder the novel was given as.
+    pub source: String,
+    /// Site ID, once known.
+    pub site: String,
+    /// Novel ID on the site, once known.
+    pub novel_id: String,
+    /// Novel title as published, once known.
+    pub title: String,
+    /// Chapters downloaded this run.
+    pub chapters_downloaded: u32,
+    /// Chapters translated without failures this run.
+    pub chapters_translated: u32,
+    /// Chapters left with unresolved failures.
+    pub chapters_failed: u32,
+    /// Wall-clock time spent on the novel, in seconds.
+    pub duration_sec: f64,
+    /// Tokens used translating the novel's chapters.
+    pub usage: TokenUsage,
+    /// Error that stopped the novel, if any.
+    pub error: Option<String>,
+}
+
+impl NovelRun {
+    /// Starts a record for the novel given as `source`.
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Records how long the novel took and the error that stopped it.
+    pub fn finish(&mut self, elapsed: Duration, error: Option<String>) {
+        self.duration_sec = elapsed.as_secs_f64();
+        self.error = error;
+    }
+}
+
+/// One entry of the history log.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunRecord {
+    /// When the run started (`YYYY-MM-DDTHH:MM:SSZ`).
+    pub started_at: String,
+    /// Command-line arguments, without the program name.
+    pub args: Vec<String>,
+    /// Wall-clock time of the whole run, in seconds.
+    pub duration_sec: f64,
+    /// Per-novel results, in the order the novels were given.
+    pub novels: Vec<NovelRun>,
+    /// Error that failed the run, if any.
+    pub error: Option<String>,
+}
+
+/// Formats a duration in seconds as "45s", "12m03s", or "2h05m".
+pub fn format_duration(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Appends a run to the history file, creating it if needed.
+pub fn append(path: &Path, record: &RunRecord) -> std::io::Result<()> {
+    let line = serde_json::to_string(record).map_err(std::io::Error::other)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Reads every run from the history file, oldest first.
+///
+/// A missing file is an empty history; lines that don't parse (e.g. a run
+/// killed mid-write) are skipped.
+pub fn load(path: &Path) -> std::io::Result<Vec<RunRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn novel(translated: u32, failed: u32, tokens: u64) -> NovelRun {
+        NovelRun {
+            source: "https://ncode.syosetu.com/n1234ab/".to_string(),
+            chapters_translated: translated,
+            chapters_failed: failed,
+            usage: TokenUsage {
+                prompt_tokens: tokens / 2,
+                completion_tokens: tokens / 2,
+                total_tokens: tokens,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config").join(HISTORY_FILENAME);
+        assert!(load(&path).unwrap().is_empty());
+
+        let first = RunRecord {
+            started_at: "2026-01-02T03:04:05Z".to_string(),
+            args: vec!["update".to_string()],
+            novels: vec![novel(3, 1, 100), novel(2, 0, 50)],
+            ..Default::default()
+        };
+        append(&path, &first).unwrap();
+        // A truncated line from an interrupted run is skipped
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        write!(file, "{{\"started_at\":").unwrap();
+        writeln!(file).unwrap();
+        append(&path, &RunRecord::default()).unwrap();
+
+        let runs = load(&path).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0], first);
+        assert_eq!(runs[1], RunRecord::default());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(44.6), "45s");
+        assert_eq!(format_duration(723.0), "12m03s");
+        assert_eq!(format_duration(7500.0), "2h05m");
+    }
+}
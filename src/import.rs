@@ -0,0 +1,111 @@
+//! Reads a folder of raw chapter files written by another tool.
+//!
+//! Chapters are the folder's `.txt` files, ordered by the number their name
+//! starts with (files without one sort after, by name). Titles come from the
+//! file name with that number and any separator stripped.
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Site ID recorded for imported stories.
+pub const IMPORT_SITE: &str = "local";
+
+/// Leading chapter number and separator ("012 - ", "3_", "07. ").
+static LEADING_NUMBER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d+)[\s._\-　]*").unwrap());
+
+/// A chapter file found in an import folder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalChapter {
+    /// Chapter number (1-based position in reading order).
+    pub number: u32,
+    /// Chapter title taken from the file name.
+    pub title: String,
+    /// Path of the source file.
+    pub path: PathBuf,
+}
+
+/// Lists the chapter files in `dir` in reading order.
+pub fn scan_chapter_files(dir: &Path) -> std::io::Result<Vec<LocalChapter>> {
+    let mut files: Vec<(Option<u64>, String, String, PathBuf)> = Vec::new();
+    for entry in std::fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let is_text = path.extension().and_then(|e| e.to_str()) == Some("txt");
+        if !is_text || !path.is_file() {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let (number, title) = split_leading_number(stem);
+        let name = stem.to_string();
+        files.push((number, name, title, path));
+    }
+
+    // Numbered files first, in numeric order; then the rest by name
+    files.sort_by(|a, b| (a.0.is_none(), a.0, &a.1).cmp(&(b.0.is_none(), b.0, &b.1)));
+
+    Ok(files
+        .into_iter()
+        .zip(1..)
+        .map(|((_, _, title, path), number)| LocalChapter {
+            number,
+            title,
+            path,
+        })
+        .collect())
+}
+
+/// Splits "012 - Title" into its number and title.
+///
+/// A name that is only a number keeps it as the title.
+fn split_leading_number(stem: &str) -> (Option<u64>, String) {
+    let Some(caps) = LEADING_NUMBER_REGEX.captures(stem) else {
+        return (None, stem.trim().to_string());
+    };
+    let number = caps[1].parse().ok();
+    let title = stem[caps[0].len()..].trim();
+    if title.is_empty() {
+        (number, stem.trim().to_string())
+    } else {
+        (number, title.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_chapter_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for name in [
+            "10 - 決戦.txt",
+            "2_旅立ち.txt",
+            "001.txt",
+            "あとがき.txt",
+            "notes.md",
+        ] {
+            std::fs::write(dir.join(name), "本文").unwrap();
+        }
+        std::fs::create_dir(dir.join("sub.txt")).unwrap();
+
+        let found: Vec<(u32, String)> = scan_chapter_files(dir)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.number, c.title))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, "001".to_string()),
+                (2, "旅立ち".to_string()),
+                (3, "決戦".to_string()),
+                (4, "あとがき".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! `latest.txt` shortcut to a story's newest translated chapter.
+//!
+//! E-reader sync folders can pick up just the new content by following one
+//! fixed name. It is a relative symlink where the platform allows one
+//! without special privileges, and a copy elsewhere (Windows).
+
+use crate::manifest::Manifest;
+use crate::utils::join_relative;
+use std::path::Path;
+
+/// Shortcut filename inside a story folder.
+pub const LATEST_FILENAME: &str = "latest.txt";
+
+/// Points `latest.txt` at the highest-numbered translated chapter whose
+/// file is present. Returns that file (relative to the story folder), or
+/// None if no chapter is translated yet.
+pub fn refresh(story_dir: &Path, manifest: &Manifest) -> std::io::Result<Option<String>> {
+    let newest = manifest
+        .data()
+        .chapters
+        .iter()
+        .filter(|c| c.translated)
+        .filter_map(|c| Some((c.number, c.translated_file.as_deref()?)))
+        .filter(|(_, file)| join_relative(story_dir, file).is_file())
+        .max_by_key(|(number, _)| *number);
+    let Some((_, file)) = newest else {
+        return Ok(None);
+    };
+
+    let link = story_dir.join(LATEST_FILENAME);
+    if link.symlink_metadata().is_ok() {
+        std::fs::remove_file(&link)?;
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(file, &link)?;
+    #[cfg(not(unix))]
+    std::fs::copy(join_relative(story_dir, file), &link)?;
+    Ok(Some(file.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scrapers::ChapterInfo;
+    use tempfile::TempDir;
+
+    fn chapter(number: u32) -> ChapterInfo {
+        ChapterInfo {
+            title: format!("第{}話", number),
+            url: format!("https://ncode.syosetu.com/n1234ab/{}/", number),
+            number,
+            episode_id: number.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_refresh_follows_newest_chapter() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        manifest.sync_chapters(&[chapter(1), chapter(2), chapter(3)]);
+        assert_eq!(refresh(story_dir, &manifest).unwrap(), None);
+        assert!(!story_dir.join(LATEST_FILENAME).exists());
+
+        for (id, file) in [("1", "1 - One.txt"), ("2", "2 - Two.txt")] {
+            std::fs::write(story_dir.join(file), file).unwrap();
+            manifest.mark_translated(id, file, file);
+        }
+        // Recorded but missing on disk
+        manifest.mark_translated("3", "3 - Three.txt", "");
+
+        assert_eq!(
+            refresh(story_dir, &manifest).unwrap().as_deref(),
+            Some("2 - Two.txt")
+        );
+        let latest = story_dir.join(LATEST_FILENAME);
+        assert_eq!(std::fs::read_to_string(&latest).unwrap(), "2 - Two.txt");
+
+        std::fs::write(story_dir.join("3 - Three.txt"), "3 - Three.txt").unwrap();
+        refresh(story_dir, &manifest).unwrap();
+        assert_eq!(std::fs::read_to_string(&latest).unwrap(), "3 - Three.txt");
+    }
+}
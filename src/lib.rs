@@ -5,15 +5,44 @@
 //! - Extracting and managing character name mappings
 //! - Translating content using OpenAI-compatible APIs
 
+pub mod api_limiter;
+pub mod archive;
+pub mod batch;
+pub mod bilingual;
+pub mod cache;
+pub mod clean;
+pub mod compare;
 pub mod config;
 pub mod console;
-mod cookies;
+pub mod cookies;
+pub mod debug_log;
+pub mod diff;
 pub mod error;
+pub mod estimate;
+pub mod export;
+pub mod failures;
+pub mod glossary;
+pub mod header;
+pub mod history;
+pub mod import;
+pub mod latest;
+pub mod library;
+pub mod lint;
+pub mod manifest;
+pub mod metadata;
 pub mod name_mapping;
 pub mod name_scout;
+pub mod postprocess;
+pub mod romaji;
 pub mod scrapers;
+pub mod search;
+pub mod statblock;
+pub mod stats;
+pub mod sync;
+pub mod terminology;
 pub mod translator;
 pub mod utils;
+pub mod verify;
 
 // Re-export commonly used types
 pub use config::Config;
@@ -22,4 +51,5 @@ pub use error::{ConfigError, NameMappingError, ScraperError, TranslationError};
 pub use name_mapping::{NameEntry, NameMappingStore, NamePart};
 pub use name_scout::NameScout;
 pub use scrapers::{ChapterInfo, ChapterList, NovelInfo, Scraper, ScraperRegistry};
-pub use translator::{ProgressInfo, Translator};
+pub use translator::{ProgressInfo, ProgressPhase, ProgressSink, TranslationEvent, Translator};
+pub use utils::{CancellationToken, Chunk, ChunkStrategy, Chunker};
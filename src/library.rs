@@ -0,0 +1,341 @@
+//! Library database of known novels.
+//!
+//! Records every novel Tsundoku has processed or imported, and which of them
+//! are tracked so `update` can check them for new chapters. Tracked novels
+//! can be given a priority and a check interval, so a daily serial is checked
+//! on every run while a finished work is only looked at occasionally.
+//! Chapters marked as read are recorded per novel, so `library list` can show
+//! how many translated chapters are still unread.
+
+use crate::error::LibraryError;
+use crate::manifest::ChapterRecord;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A novel recorded in the library.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    /// Scraper ID (e.g., "syosetu").
+    pub site: String,
+    /// Novel ID on the site.
+    pub novel_id: String,
+    /// URL used to fetch the novel.
+    pub url: String,
+    /// Original title.
+    pub title: String,
+    /// Whether `update` should check this novel for new chapters.
+    #[serde(default)]
+    pub tracked: bool,
+    /// When the novel was added (Unix seconds).
+    #[serde(default)]
+    pub added_unix: u64,
+    /// Update order; higher priorities are checked first.
+    #[serde(default)]
+    pub priority: i32,
+    /// Minimum hours between update checks (none checks on every run).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_interval_hours: Option<u32>,
+    /// When `update` last checked the novel successfully (Unix seconds).
+    #[serde(default)]
+    pub last_checked_unix: u64,
+    /// Chapters marked as read, by episode ID (`#N`, the chapter number, for
+    /// chapters recorded without one).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub read_chapters: Vec<String>,
+}
+
+impl LibraryEntry {
+    /// Returns true if the novel has never been checked or its check interval
+    /// has elapsed at `now`.
+    pub fn is_due(&self, now: u64) -> bool {
+        match self.check_interval_hours {
+            Some(hours) if self.last_checked_unix > 0 => {
+                now >= self
+                    .last_checked_unix
+                    .saturating_add(u64::from(hours) * 3600)
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns true if the chapter has been marked as read.
+    pub fn is_read(&self, chapter: &ChapterRecord) -> bool {
+        self.read_chapters.contains(&read_key(chapter))
+    }
+
+    /// Marks a chapter as read or unread. Returns false if it already was.
+    pub fn set_read(&mut self, chapter: &ChapterRecord, read: bool) -> bool {
+        if self.is_read(chapter) == read {
+            return false;
+        }
+        let key = read_key(chapter);
+        if read {
+            self.read_chapters.push(key);
+        } else {
+            self.read_chapters.retain(|id| *id != key);
+        }
+        true
+    }
+
+    /// Counts translated chapters in a manifest that haven't been read.
+    /// Chapters removed from the site still count, since the local copy
+    /// remains readable.
+    pub fn unread_count(&self, chapters: &[ChapterRecord]) -> usize {
+        chapters
+            .iter()
+            .filter(|c| c.translated && !self.is_read(c))
+            .count()
+    }
+}
+
+/// Key a chapter's read state is stored under: its episode ID, or its
+/// number for chapters recorded before episode IDs were.
+fn read_key(chapter: &ChapterRecord) -> String {
+    if chapter.episode_id.is_empty() {
+        format!("#{}", chapter.number)
+    } else {
+        chapter.episode_id.clone()
+    }
+}
+
+/// On-disk library format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LibraryData {
+    #[serde(default)]
+    novels: Vec<LibraryEntry>,
+}
+
+/// Persistent library of novels.
+pub struct Library {
+    /// Path to the JSON file.
+    filepath: PathBuf,
+    /// The library data.
+    data: LibraryData,
+}
+
+impl Library {
+    /// Opens the library at the given path, starting empty if it doesn't exist.
+    pub fn open(filepath: &Path) -> Result<Self, LibraryError> {
+        let data = if filepath.exists() {
+            let content = std::fs::read_to_string(filepath)?;
+            serde_json::from_str(&content)?
+        } else {
+            LibraryData::default()
+        };
+
+        Ok(Self {
+            filepath: filepath.to_path_buf(),
+            data,
+        })
+    }
+
+    /// Get the filepath for this library.
+    pub fn filepath(&self) -> &Path {
+        &self.filepath
+    }
+
+    /// Save the library to disk.
+    pub fn save(&self) -> Result<(), LibraryError> {
+        if let Some(parent) = self.filepath.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(&self.data)?;
+        std::fs::write(&self.filepath, content)?;
+
+        Ok(())
+    }
+
+    /// Adds a novel, or updates the existing entry for the same site and ID.
+    ///
+    /// An existing entry keeps its `added_unix` and schedule, and is only ever
+    /// promoted to tracked, never demoted. Returns true if the novel was new.
+    pub fn upsert(&mut self, entry: LibraryEntry) -> bool {
+        match self.find_mut(&entry.site, &entry.novel_id) {
+            Some(existing) => {
+                existing.url = entry.url;
+                existing.title = entry.title;
+                existing.tracked |= entry.tracked;
+                false
+            }
+            None => {
+                self.data.novels.push(entry);
+                true
+            }
+        }
+    }
+
+    /// Finds a novel by site and ID.
+    pub fn find(&self, site: &str, novel_id: &str) -> Option<&LibraryEntry> {
+        self.data
+            .novels
+            .iter()
+            .find(|e| e.site == site && e.novel_id == novel_id)
+    }
+
+    /// Finds a novel by site and ID for modification.
+    pub fn find_mut(&mut self, site: &str, novel_id: &str) -> Option<&mut LibraryEntry> {
+        self.data
+            .novels
+            .iter_mut()
+            .find(|e| e.site == site && e.novel_id == novel_id)
+    }
+
+    /// Iterates over all novels.
+    pub fn entries(&self) -> impl Iterator<Item = &LibraryEntry> {
+        self.data.novels.iter()
+    }
+
+    /// Iterates over novels tracked for updates.
+    pub fn tracked(&self) -> impl Iterator<Item = &LibraryEntry> {
+        self.data.novels.iter().filter(|e| e.tracked)
+    }
+
+    /// Returns the tracked novels due for a check at `now`, highest priority
+    /// first (ties keep library order).
+    pub fn due(&self, now: u64) -> Vec<&LibraryEntry> {
+        let mut due: Vec<&LibraryEntry> = self.tracked().filter(|e| e.is_due(now)).collect();
+        due.sort_by_key(|e| std::cmp::Reverse(e.priority));
+        due
+    }
+
+    /// Records a successful update check. Returns false if the novel is unknown.
+    pub fn mark_checked(&mut self, site: &str, novel_id: &str, now: u64) -> bool {
+        match self.find_mut(site, novel_id) {
+            Some(entry) => {
+                entry.last_checked_unix = now;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the number of novels in the library.
+    pub fn len(&self) -> usize {
+        self.data.novels.len()
+    }
+
+    /// Check if the library is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.novels.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(novel_id: &str, tracked: bool) -> LibraryEntry {
+        LibraryEntry {
+            site: "syosetu".to_string(),
+            novel_id: novel_id.to_string(),
+            url: format!("https://ncode.syosetu.com/{}/", novel_id),
+            title: "タイトル".to_string(),
+            tracked,
+            added_unix: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_persist() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("library.json");
+
+        let mut library = Library::open(&path).unwrap();
+        assert!(library.is_empty());
+        assert!(library.upsert(entry("n1111aa", false)));
+        assert!(library.upsert(entry("n2222bb", true)));
+        library.save().unwrap();
+
+        let mut library = Library::open(&path).unwrap();
+        assert_eq!(library.len(), 2);
+        assert_eq!(library.tracked().count(), 1);
+
+        // Re-adding promotes to tracked but never demotes
+        assert!(!library.upsert(entry("n1111aa", true)));
+        assert!(!library.upsert(entry("n2222bb", false)));
+        assert_eq!(library.tracked().count(), 2);
+    }
+
+    #[test]
+    fn test_due_honours_priority_and_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut library = Library::open(&temp_dir.path().join("library.json")).unwrap();
+
+        library.upsert(entry("n1111aa", true));
+        library.upsert(LibraryEntry {
+            priority: 5,
+            ..entry("n2222bb", true)
+        });
+        library.upsert(LibraryEntry {
+            check_interval_hours: Some(24),
+            ..entry("n3333cc", true)
+        });
+        library.upsert(entry("n4444dd", false));
+
+        let ids = |library: &Library, now| -> Vec<String> {
+            library
+                .due(now)
+                .iter()
+                .map(|e| e.novel_id.clone())
+                .collect()
+        };
+        assert_eq!(ids(&library, 1000), vec!["n2222bb", "n1111aa", "n3333cc"]);
+
+        assert!(library.mark_checked("syosetu", "n3333cc", 1000));
+        assert_eq!(ids(&library, 1000 + 3600), vec!["n2222bb", "n1111aa"]);
+        assert_eq!(ids(&library, 1000 + 24 * 3600).len(), 3);
+
+        // Re-adding keeps the schedule
+        library.upsert(entry("n3333cc", true));
+        assert_eq!(
+            library
+                .find("syosetu", "n3333cc")
+                .unwrap()
+                .last_checked_unix,
+            1000
+        );
+        assert!(!library.mark_checked("syosetu", "missing", 1000));
+    }
+
+    #[test]
+    fn test_read_progress() {
+        let chapter = |episode_id: &str, translated| ChapterRecord {
+            episode_id: episode_id.to_string(),
+            translated,
+            ..Default::default()
+        };
+        let chapters = vec![chapter("1", true), chapter("2", true), chapter("3", false)];
+
+        let mut novel = entry("n1111aa", true);
+        assert_eq!(novel.unread_count(&chapters), 2);
+        assert!(novel.set_read(&chapters[0], true));
+        assert!(!novel.set_read(&chapters[0], true));
+        assert_eq!(novel.unread_count(&chapters), 1);
+
+        // Reading an untranslated chapter doesn't affect the count
+        novel.set_read(&chapters[2], true);
+        assert_eq!(novel.unread_count(&chapters), 1);
+
+        assert!(novel.set_read(&chapters[0], false));
+        assert!(!novel.is_read(&chapters[0]));
+        assert_eq!(novel.unread_count(&chapters), 2);
+    }
+
+    #[test]
+    fn test_read_progress_without_episode_ids() {
+        let chapter = |number| ChapterRecord {
+            number,
+            translated: true,
+            ..Default::default()
+        };
+        let chapters = vec![chapter(1), chapter(2)];
+
+        let mut novel = entry("n1111aa", true);
+        assert!(novel.set_read(&chapters[0], true));
+        assert!(!novel.is_read(&chapters[1]));
+        assert_eq!(novel.unread_count(&chapters), 1);
+    }
+}
@@ -0,0 +1,266 @@
+//! Consistency checks over translated chapters.
+//!
+//! Flags mapped names rendered differently from the chosen English form
+//! (e.g. "Yuuko" where the mapping says "Yuko"), leftover Japanese text, and
+//! chunks whose translation failed.
+
+use crate::name_mapping::NameMappingData;
+use crate::translator::FAILED_CHUNK_MARKER;
+use crate::utils::is_japanese_char;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Shorter names are only checked against their recorded alternatives, since
+/// loose romanization matching would hit ordinary words ("Yo" vs "You").
+const MIN_FUZZY_NAME_LEN: usize = 4;
+
+/// Kind of lint finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LintKind {
+    /// A mapped name rendered differently from its chosen English form.
+    InconsistentName,
+    /// Japanese characters left in the translation.
+    LeftoverJapanese,
+    /// A chunk whose translation failed and was left in Japanese.
+    UntranslatedChunk,
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            LintKind::InconsistentName => "name",
+            LintKind::LeftoverJapanese => "japanese",
+            LintKind::UntranslatedChunk => "untranslated",
+        };
+        f.write_str(label)
+    }
+}
+
+/// One lint finding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintIssue {
+    /// File the issue was found in, as given to [`lint_text`].
+    pub file: String,
+    /// Line number (1-based).
+    pub line: usize,
+    /// What kind of issue this is.
+    pub kind: LintKind,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: [{}] {}",
+            self.file, self.line, self.kind, self.message
+        )
+    }
+}
+
+/// The chosen English form of a mapped name and the other renderings seen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameRendering {
+    /// Original Japanese name.
+    pub original: String,
+    /// Chosen English rendering.
+    pub canonical: String,
+    /// Other renderings the name scout voted for.
+    pub variants: Vec<String>,
+}
+
+impl NameRendering {
+    /// Returns true if `word` looks like this name but isn't the chosen form.
+    fn is_variant(&self, word: &str) -> bool {
+        if word == self.canonical {
+            return false;
+        }
+        if self.variants.iter().any(|v| v == word) {
+            return true;
+        }
+        self.canonical.chars().count() >= MIN_FUZZY_NAME_LEN
+            && word.chars().next().is_some_and(char::is_uppercase)
+            && romaji_key(word) == romaji_key(&self.canonical)
+    }
+}
+
+/// Builds the renderings to check from a novel's name mapping.
+///
+/// Multi-word renderings are skipped, since text is checked word by word.
+pub fn name_renderings(data: &NameMappingData) -> Vec<NameRendering> {
+    let mut renderings: Vec<NameRendering> = data
+        .names
+        .iter()
+        .filter_map(|(original, info)| {
+            let canonical = info.english.clone()?;
+            if canonical.contains(char::is_whitespace) {
+                return None;
+            }
+            let mut variants: Vec<String> = info
+                .votes
+                .keys()
+                .filter(|v| **v != canonical && !v.contains(char::is_whitespace))
+                .cloned()
+                .collect();
+            variants.sort();
+            Some(NameRendering {
+                original: original.clone(),
+                canonical,
+                variants,
+            })
+        })
+        .collect();
+    renderings.sort_by(|a, b| a.original.cmp(&b.original));
+    renderings
+}
+
+/// Checks one translated file's text.
+pub fn lint_text(file: &str, text: &str, names: &[NameRendering]) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    // Lines following a failure marker are the untranslated source
+    let mut in_failed_chunk = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let issue = |kind, message| LintIssue {
+            file: file.to_string(),
+            line: line_number,
+            kind,
+            message,
+        };
+
+        if line.contains(FAILED_CHUNK_MARKER) {
+            in_failed_chunk = true;
+            issues.push(issue(
+                LintKind::UntranslatedChunk,
+                "chunk translation failed; source left untranslated".to_string(),
+            ));
+            continue;
+        }
+
+        let japanese = japanese_runs(line);
+        if in_failed_chunk {
+            if japanese.is_empty() && !line.trim().is_empty() {
+                in_failed_chunk = false;
+            } else {
+                continue;
+            }
+        }
+        if !japanese.is_empty() {
+            issues.push(issue(
+                LintKind::LeftoverJapanese,
+                format!("leftover Japanese: {}", japanese.join(", ")),
+            ));
+        }
+
+        let mut reported = BTreeSet::new();
+        for word in words(line) {
+            for name in names {
+                if name.is_variant(word) && reported.insert((word, &name.canonical)) {
+                    issues.push(issue(
+                        LintKind::InconsistentName,
+                        format!(
+                            "'{}' should be '{}' ({})",
+                            word, name.canonical, name.original
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Splits a line into alphabetic words, keeping macrons and apostrophes.
+fn words(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !(c.is_alphabetic() || c == '\'') || is_japanese_char(c))
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+}
+
+/// Returns the runs of Japanese characters in a line.
+fn japanese_runs(line: &str) -> Vec<&str> {
+    line.split(|c: char| !is_japanese_char(c))
+        .filter(|run| !run.is_empty())
+        .collect()
+}
+
+/// Collapses long-vowel spellings so "Yuuko", "Yūko", and "Yuko" compare equal.
+fn romaji_key(word: &str) -> String {
+    let lower: String = word
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'ā' | 'â' => 'a',
+            'ī' | 'î' => 'i',
+            'ū' | 'û' => 'u',
+            'ē' | 'ê' => 'e',
+            'ō' | 'ô' => 'o',
+            c => c,
+        })
+        .collect();
+    lower
+        .replace("ou", "o")
+        .replace("oo", "o")
+        .replace("uu", "u")
+        .replace("oh", "o")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendering(original: &str, canonical: &str, variants: &[&str]) -> NameRendering {
+        NameRendering {
+            original: original.to_string(),
+            canonical: canonical.to_string(),
+            variants: variants.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_names() {
+        let names = vec![
+            rendering("優子", "Yuuko", &[]),
+            rendering("陽", "Yo", &["Yoh"]),
+        ];
+        let text = "Yuuko smiled.\nYuko and Yūko waved.\nYou saw Yoh there.";
+        let issues = lint_text("001.txt", text, &names);
+
+        let found: Vec<(usize, &str)> = issues
+            .iter()
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, "'Yuko' should be 'Yuuko' (優子)"),
+                (2, "'Yūko' should be 'Yuuko' (優子)"),
+                (3, "'Yoh' should be 'Yo' (陽)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_leftover_japanese_and_failed_chunks() {
+        let text =
+            "He said ありがとう.\n\n[TRANSLATION FAILED]\n本文です。\n\n続き\nBack to English.";
+        let issues = lint_text("002.txt", text, &[]);
+
+        let found: Vec<(usize, LintKind)> = issues.iter().map(|i| (i.line, i.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, LintKind::LeftoverJapanese),
+                (3, LintKind::UntranslatedChunk)
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "002.txt:1: [japanese] leftover Japanese: ありがとう"
+        );
+    }
+}
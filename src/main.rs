@@ -1,23 +1,49 @@
 //! Tsundoku CLI - Japanese web novel downloader and translator.
 
+mod commands;
+
 use anyhow::{Context, Result};
-use clap::Parser;
-use std::io::{self, Write};
-use std::path::{Path, PathBuf};
-use tsundoku::config::Config;
+use clap::{Parser, Subcommand};
+use commands::compare::run_compare;
+use commands::cookies::run_cookies_command;
+use commands::import::run_import;
+use commands::info::{run_diff, run_history, run_info, run_search};
+use commands::library::{run_chapter_profile, run_library_command, run_read};
+use commands::models::run_models;
+use commands::names::{run_names_command, run_terms};
+use commands::process::{JobOptions, Limiters, process_novel, process_offline};
+use commands::story::{
+    run_clean, run_export, run_lint, run_postprocess, run_repair, run_stats, run_verify,
+};
+use commands::sync::{run_sync, sync_after_run};
+use commands::update::{error_message, record_run, run_batch, run_update};
+use commands::{load_run_config, resolve_story_dir, verify_endpoints};
+use std::path::PathBuf;
+use std::time::Instant;
+use tsundoku::config::{Config, PromptPreset};
 use tsundoku::console::Console;
-use tsundoku::name_mapping::NameMappingStore;
-use tsundoku::name_scout::{NameScout, build_chapter_payload};
-use tsundoku::scrapers::{ChapterInfo, ChapterList, ScraperRegistry};
-use tsundoku::translator::{ProgressInfo, Translator};
+use tsundoku::export::ExportFormat;
+use tsundoku::glossary::GlossaryFormat;
+use tsundoku::history::NovelRun;
+use tsundoku::terminology::ReportFormat;
+use tsundoku::utils::now_unix;
 
 /// Japanese web novel downloader and translator.
 #[derive(Parser, Debug)]
 #[command(name = "tsundoku")]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// URL of the novel to download.
-    novel_url: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// URL of the novel to download (with --translate-only, a story folder or novel ID).
+    #[arg(required = true)]
+    novel_url: Option<String>,
+
+    /// Translate an existing story folder from its manifest without downloading anything.
+    #[arg(long)]
+    translate_only: bool,
 
     /// Start downloading from chapter N (1-based).
     #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
@@ -27,6 +53,10 @@ struct Args {
     #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
     end: Option<u32>,
 
+    /// Choose the chapters to process from a searchable list.
+    #[arg(long, conflicts_with_all = ["start", "end"])]
+    pick: bool,
+
     /// Skip manual name mapping review pause.
     #[arg(long)]
     no_name_pause: bool,
@@ -35,691 +65,551 @@ struct Args {
     #[arg(long)]
     no_name_scout: bool,
 
+    /// Apply a named [profiles.NAME] section from the config.
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Genre preset added to the content prompt: fantasy, romance, litrpg, or r18.
+    #[arg(long)]
+    preset: Option<PromptPreset>,
+
+    /// Don't draw the live translation progress line.
+    #[arg(long)]
+    no_progress: bool,
+
     /// Enable debug logging for scrapers.
     #[arg(long)]
     debug: bool,
 }
 
-/// Downloaded chapter data.
-#[allow(dead_code)]
-struct ChapterData {
-    number: u32,
-    title: String,
-    content: String,
-    filename: String,
+/// Auxiliary subcommands.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage login cookies used by the scrapers.
+    #[command(subcommand)]
+    Cookies(CookiesCommand),
+
+    /// Translate one chapter with two models/prompts and write the results side by side.
+    Compare(CompareArgs),
+
+    /// Manage the library of known and tracked novels.
+    #[command(subcommand)]
+    Library(LibraryCommand),
+
+    /// Download and translate new chapters of every tracked novel.
+    Update(UpdateArgs),
+
+    /// Compare a novel's live chapter list with its manifest without downloading anything.
+    Diff {
+        /// URL of the novel.
+        url: String,
+    },
+
+    /// Show a novel's details, chapter count, and estimated length without saving anything.
+    Info {
+        /// URL of the novel.
+        url: String,
+    },
+
+    /// Mark a translated chapter as read (or unread) in the library.
+    Read {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+
+        /// Chapter number.
+        chapter: u32,
+
+        /// Mark the chapter as unread instead.
+        #[arg(long)]
+        unread: bool,
+    },
+
+    /// Translate a range of chapters with a config profile (e.g. a stronger model for a climax).
+    ChapterProfile {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+
+        /// Chapters to mark: N, N-M, N-, or -M.
+        chapters: String,
+
+        /// Name of a [profiles.NAME] section in the config.
+        #[arg(required_unless_present = "clear", conflicts_with = "clear")]
+        profile: Option<String>,
+
+        /// Remove the chapters' profile, translating them with the run's settings again.
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// Process every novel listed in a file (`URL [--chapters 1-50] [--profile NAME]` per line).
+    Batch(BatchArgs),
+
+    /// Show per-chapter sizes, estimated chunks, and translation ratios for a story.
+    Stats {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+    },
+
+    /// Check translated chapters for inconsistent names, leftover Japanese, and failed chunks.
+    Lint {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+    },
+
+    /// Re-translate chunks saved with a [TRANSLATION FAILED] marker and splice them into their chapters.
+    Repair {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+    },
+
+    /// Re-apply post-processing to already translated chapters.
+    Postprocess {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+    },
+
+    /// Upload new and changed output files to the configured WebDAV or S3 target.
+    Sync,
+
+    /// Remove leftover temporary files and chapter files the manifest no longer lists.
+    Clean {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+
+        /// Delete without asking for confirmation.
+        #[arg(long, short)]
+        yes: bool,
+    },
+
+    /// Package a downloaded story as an EPUB, zip, or HTML file (no network access).
+    Export {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+
+        /// Package format: epub, zip, or html.
+        #[arg(long, default_value = "epub")]
+        format: ExportFormat,
+
+        /// Output file (default: next to the story folder).
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Adopt a folder of raw chapter files from another tool as a story folder.
+    Import(ImportArgs),
+
+    /// Check that recorded originals and translations are present and intact.
+    Verify {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+    },
+
+    /// List the models offered by the configured API endpoints.
+    Models(ModelsArgs),
+
+    /// Show past download/translate runs with chapter counts and token usage.
+    History {
+        /// Only show novels whose ID, title, or URL contains this.
+        #[arg(long)]
+        novel: Option<String>,
+
+        /// Number of most recent runs to show (0 shows all).
+        #[arg(long, short = 'n', default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Search library novel titles (original and translated), and optionally translated chapters.
+    Search {
+        /// Text to look for (case-insensitive).
+        query: String,
+
+        /// Also search the text of translated chapters.
+        #[arg(long)]
+        full_text: bool,
+    },
+
+    /// Manage name mapping files.
+    #[command(subcommand)]
+    Names(NamesCommand),
+
+    /// Scout downloaded chapters for names, places, and skills and write a terminology report.
+    Terms {
+        /// Story folder, or novel ID of a folder in the output directory.
+        novel: String,
+
+        /// First chapter to scan (1-based).
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        start: Option<u32>,
+
+        /// Last chapter to scan (1-based, inclusive).
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        end: Option<u32>,
+
+        /// Report format: markdown or csv.
+        #[arg(long, default_value = "markdown")]
+        format: ReportFormat,
+
+        /// Output file (default: Terminology.md or .csv in the story folder).
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
 }
 
-/// Parameters for processing novels.
-struct ProcessParams<'a> {
-    console: &'a Console,
-    scraper: &'a dyn tsundoku::scrapers::Scraper,
-    novel_info: &'a tsundoku::scrapers::NovelInfo,
-    output_dir: &'a Path,
-    translator: &'a Translator,
-    name_scout: &'a NameScout,
-    name_mapping: &'a mut NameMappingStore,
+/// Arguments for `tsundoku batch`.
+#[derive(clap::Args, Debug)]
+struct BatchArgs {
+    /// File with one novel per line.
+    file: PathBuf,
+
+    /// Skip manual name mapping review pause.
+    #[arg(long)]
     no_name_pause: bool,
+
+    /// Disable name scouting entirely.
+    #[arg(long)]
     no_name_scout: bool,
-    config: &'a Config,
-}
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
-    let console = Console::new();
+    /// Don't draw the live translation progress line.
+    #[arg(long)]
+    no_progress: bool,
 
-    console.section("Tsundoku - Web Novel Downloader");
+    /// Enable debug logging for scrapers.
+    #[arg(long)]
+    debug: bool,
 
-    // Load configuration
-    console.step("Loading configuration...");
-    let mut config = Config::load().context("Failed to load configuration")?;
-    config.scraping.debug = args.debug;
+    /// Number of novels to process at once (implies --no-name-pause above 1).
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+}
 
-    // Check if this is first run (API key not configured)
-    if !config.api.is_configured() {
-        let config_path = Config::config_path()?;
-        console.warning(&format!(
-            "API key not configured. Please edit: {}",
-            config_path.display()
-        ));
-        console.info("Set your OpenAI-compatible API key in the config file and run again.");
-        return Ok(());
-    }
+/// Arguments for `tsundoku models`.
+#[derive(clap::Args, Debug)]
+struct ModelsArgs {
+    /// Only list models whose ID contains this text.
+    filter: Option<String>,
 
-    if !args.no_name_scout
-        && config
-            .scout_api
-            .as_ref()
-            .filter(|api| api.is_configured())
-            .is_none()
-    {
-        let config_path = Config::config_path()?;
-        console.warning(&format!(
-            "Name scout API key not configured. Please edit: {}",
-            config_path.display()
-        ));
-        console.info("Set scout_api.key in the config file and run again.");
-        return Ok(());
-    }
+    /// Save MODEL as the main API's model in the config file.
+    #[arg(long, value_name = "MODEL")]
+    set: Option<String>,
 
-    config
-        .validate_with_options(!args.no_name_scout)
-        .context("Invalid configuration")?;
-    console.success("Configuration loaded");
-
-    // Find appropriate scraper
-    console.step("Finding scraper for URL...");
-    let registry = ScraperRegistry::new(&config.scraping);
-    let scraper = registry
-        .find_for_url(&args.novel_url)
-        .ok_or_else(|| anyhow::anyhow!("No scraper found for URL: {}", args.novel_url))?;
-
-    console.success(&format!("Using {} scraper", scraper.name()));
-
-    // Fetch novel info
-    console.step("Fetching novel information...");
-    let novel_info = scraper
-        .get_novel_info(&args.novel_url)
-        .await
-        .context("Failed to fetch novel info")?;
-
-    console.success(&format!("Found: {}", novel_info.title));
-    console.info(&format!("Novel ID: {}", novel_info.novel_id));
-
-    // Fetch chapter list
-    console.step("Fetching chapter list...");
-    let chapter_list = scraper
-        .get_chapter_list(&novel_info.base_url)
-        .await
-        .context("Failed to fetch chapter list")?;
-
-    match &chapter_list {
-        ChapterList::OneShot => {
-            console.success("This is a one-shot story");
-        }
-        ChapterList::Chapters(chapters) => {
-            console.success(&format!("Found {} chapters", chapters.len()));
-        }
-    }
+    /// Save MODEL as the name scout API's model in the config file.
+    #[arg(long, value_name = "MODEL")]
+    set_scout: Option<String>,
+}
 
-    // Validate chapter range
-    let (start_chapter, end_chapter) =
-        validate_chapter_range(args.start, args.end, &chapter_list, &console)?;
-
-    // Initialize name mapping store
-    let names_dir = config.names_dir()?;
-    let mut name_mapping = NameMappingStore::new(&names_dir, scraper.id(), &novel_info.novel_id)
-        .context("Failed to initialize name mapping store")?;
-
-    console.info(&format!(
-        "Name mapping: {} names loaded, {} chapters covered",
-        name_mapping.len(),
-        name_mapping.coverage().len()
-    ));
-
-    // Initialize translator
-    let translator = Translator::new(
-        config.api.clone(),
-        config.translation.clone(),
-        config.prompts.title_translation.clone(),
-        config.prompts.content_translation.clone(),
-    );
+/// Arguments for `tsundoku import`.
+#[derive(clap::Args, Debug)]
+struct ImportArgs {
+    /// Folder of raw chapter `.txt` files, named so they sort in reading order.
+    dir: PathBuf,
 
-    // Initialize name scout
-    let scout_api = if args.no_name_scout {
-        config.api.clone()
-    } else {
-        config
-            .scout_api_config()
-            .context("Name scout API not configured")?
-            .clone()
-    };
-    let name_scout = NameScout::new(
-        scout_api,
-        config.name_scout.clone(),
-        config.prompts.name_scout.clone(),
-    );
-
-    // Get output directory
-    let output_dir = expand_path(&config.paths.output_directory);
-
-    // Create process params
-    let mut params = ProcessParams {
-        console: &console,
-        scraper,
-        novel_info: &novel_info,
-        output_dir: &output_dir,
-        translator: &translator,
-        name_scout: &name_scout,
-        name_mapping: &mut name_mapping,
-        no_name_pause: args.no_name_pause,
-        no_name_scout: args.no_name_scout,
-        config: &config,
-    };
+    /// Original (Japanese) title of the story (defaults to the folder name).
+    #[arg(long)]
+    title: Option<String>,
 
-    // Process based on chapter type
-    if chapter_list.is_oneshot() {
-        process_oneshot(&mut params).await?;
-    } else if let ChapterList::Chapters(chapters) = &chapter_list {
-        process_chapters(&mut params, chapters, start_chapter, end_chapter).await?;
-    }
+    /// Novel ID to file the story under (defaults to a short hash of the title).
+    #[arg(long)]
+    id: Option<String>,
 
-    console.section("Done!");
-    Ok(())
+    /// Run the name scout over the imported chapters.
+    #[arg(long)]
+    scout: bool,
 }
 
-/// Processes a one-shot story.
-async fn process_oneshot(params: &mut ProcessParams<'_>) -> Result<()> {
-    params.console.section("Processing One-Shot Story");
-
-    // Find or create story folder
-    let folder_name = find_or_create_folder(
-        params.console,
-        params.output_dir,
-        params.scraper.id(),
-        &params.novel_info.novel_id,
-        &params.novel_info.title,
-        params.translator,
-    )
-    .await?;
-
-    let story_dir = params.output_dir.join(&folder_name);
-    std::fs::create_dir_all(&story_dir)?;
-
-    // Download original content if not exists
-    let original_path = story_dir.join("original.txt");
-    let content = if original_path.exists() {
-        params
-            .console
-            .info("Original content already exists, loading...");
-        std::fs::read_to_string(&original_path)?
-    } else {
-        params.console.step("Downloading original content...");
-        let content = params
-            .scraper
-            .download_chapter(&params.novel_info.base_url)
-            .await
-            .context("Failed to download content")?;
-        std::fs::write(&original_path, &content)?;
-        params.console.success(&format!(
-            "Saved original ({} chars)",
-            content.chars().count()
-        ));
-        content
-    };
-
-    // Run name scout
-    let scouted = if params.no_name_scout {
-        params.console.info("Name scout disabled; skipping");
-        false
-    } else {
-        run_name_scout(
-            params.console,
-            params.name_scout,
-            params.name_mapping,
-            &[(1, &params.novel_info.title, &content)],
-        )
-        .await?
-    };
-
-    // Manual review (only if scouting was performed)
-    if !params.no_name_pause && scouted {
-        manual_name_review(params.console, params.name_mapping, params.config)?;
-    }
+/// Library subcommands.
+#[derive(Subcommand, Debug)]
+enum LibraryCommand {
+    /// List novels in the library.
+    List,
+
+    /// Add a novel to the library and track it for updates.
+    Add {
+        /// URL of the novel.
+        url: String,
+    },
+
+    /// Stop tracking a novel for updates (it stays in the library).
+    Untrack {
+        /// Site ID (e.g. "syosetu").
+        site: String,
+
+        /// Novel ID on the site.
+        novel_id: String,
+    },
+
+    /// Set a tracked novel's update priority and check interval.
+    Schedule {
+        /// Site ID (e.g. "syosetu").
+        site: String,
+
+        /// Novel ID on the site.
+        novel_id: String,
+
+        /// Update order; higher priorities are checked first.
+        #[arg(long, allow_negative_numbers = true)]
+        priority: Option<i32>,
+
+        /// Minimum hours between update checks (0 checks on every run).
+        #[arg(long)]
+        interval: Option<u32>,
+    },
+
+    /// Track every novel on your Syosetu bookmark list (needs login cookies).
+    ImportSyosetu,
+
+    /// Track every work you follow on Kakuyomu (needs login cookies).
+    ImportKakuyomu,
+}
 
-    // Translate content
-    let translated_path = story_dir.join("oneshot.txt");
-    if translated_path.exists() {
-        params
-            .console
-            .info("Translation already exists, skipping...");
-    } else {
-        params.console.step("Translating content...");
+/// Arguments for `tsundoku update`.
+#[derive(clap::Args, Debug)]
+struct UpdateArgs {
+    /// Check every tracked novel, ignoring check intervals.
+    #[arg(long)]
+    all: bool,
 
-        // Apply name mapping
-        let mapped_content = params.name_mapping.apply_to_text(&content);
+    /// Skip manual name mapping review pause.
+    #[arg(long)]
+    no_name_pause: bool,
 
-        let progress = ProgressInfo {
-            chapter: 1,
-            chunk: 1,
-            total_chunks: 1,
-        };
+    /// Disable name scouting entirely.
+    #[arg(long)]
+    no_name_scout: bool,
 
-        let translated = params
-            .translator
-            .translate(&mapped_content, false, Some(progress))
-            .await
-            .context("Failed to translate content")?;
+    /// Don't draw the live translation progress line.
+    #[arg(long)]
+    no_progress: bool,
 
-        std::fs::write(&translated_path, &translated)?;
-        params.console.success("Translation saved");
-    }
+    /// Enable debug logging for scrapers.
+    #[arg(long)]
+    debug: bool,
 
-    Ok(())
+    /// Number of novels to process at once (implies --no-name-pause above 1).
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
 }
 
-/// Processes multi-chapter stories.
-async fn process_chapters(
-    params: &mut ProcessParams<'_>,
-    chapters: &[ChapterInfo],
-    start_chapter: u32,
-    end_chapter: u32,
-) -> Result<()> {
-    params.console.section("Processing Multi-Chapter Story");
-
-    // Find or create story folder
-    let folder_name = find_or_create_folder(
-        params.console,
-        params.output_dir,
-        params.scraper.id(),
-        &params.novel_info.novel_id,
-        &params.novel_info.title,
-        params.translator,
-    )
-    .await?;
-
-    let story_dir = params.output_dir.join(&folder_name);
-    let original_dir = story_dir.join("Original");
-    std::fs::create_dir_all(&original_dir)?;
-
-    // Calculate padding for chapter numbers
-    let total_chapters = chapters.len();
-    let padding = total_chapters.to_string().len();
-
-    // Download phase
-    params.console.section("Download Phase");
-
-    let mut downloaded_chapters: Vec<ChapterData> = Vec::new();
-
-    for chapter in chapters.iter() {
-        if chapter.number < start_chapter || chapter.number > end_chapter {
-            continue;
-        }
-
-        let chapter_num_str = format!("{:0width$}", chapter.number, width = padding);
-        let filename = format!(
-            "{} - {}.txt",
-            chapter_num_str,
-            sanitize_filename(&chapter.title)
-        );
-        let original_path = original_dir.join(&filename);
-
-        let content = if original_path.exists() {
-            params
-                .console
-                .info(&format!("Chapter {} already downloaded", chapter.number));
-            std::fs::read_to_string(&original_path)?
-        } else {
-            params.console.step(&format!(
-                "Downloading chapter {}: {}",
-                chapter.number, chapter.title
-            ));
-
-            let content = params
-                .scraper
-                .download_chapter(&chapter.url)
-                .await
-                .with_context(|| format!("Failed to download chapter {}", chapter.number))?;
-
-            std::fs::write(&original_path, &content)?;
-            params
-                .console
-                .success(&format!("Saved ({} chars)", content.chars().count()));
-            content
-        };
-
-        downloaded_chapters.push(ChapterData {
-            number: chapter.number,
-            title: chapter.title.clone(),
-            content,
-            filename,
-        });
-    }
-
-    if downloaded_chapters.is_empty() {
-        params.console.warning("No chapters downloaded");
-        return Ok(());
-    }
-
-    // Name scout phase
-    let scout_data: Vec<(u32, &str, &str)> = downloaded_chapters
-        .iter()
-        .map(|c| (c.number, c.title.as_str(), c.content.as_str()))
-        .collect();
-
-    let scouted = if params.no_name_scout {
-        params.console.info("Name scout disabled; skipping");
-        false
-    } else {
-        run_name_scout(
-            params.console,
-            params.name_scout,
-            params.name_mapping,
-            &scout_data,
-        )
-        .await?
-    };
+/// Arguments for `tsundoku compare`.
+#[derive(clap::Args, Debug)]
+struct CompareArgs {
+    /// URL of the novel.
+    novel_url: String,
 
-    // Manual review (only if scouting was performed)
-    if !params.no_name_pause && scouted {
-        manual_name_review(params.console, params.name_mapping, params.config)?;
-    }
+    /// Chapter to translate (ignored for one-shots).
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    chapter: u32,
 
-    // Translation phase
-    params.console.section("Translation Phase");
-
-    for chapter_data in &downloaded_chapters {
-        // Check if translation already exists
-        let chapter_num_str = format!("{:0width$}", chapter_data.number, width = padding);
-        let pattern = format!("{} - ", chapter_num_str);
-
-        let translation_exists = std::fs::read_dir(&story_dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().is_file())
-            .any(|e| e.file_name().to_string_lossy().starts_with(&pattern));
-
-        if translation_exists {
-            params.console.info(&format!(
-                "Chapter {} already translated, skipping",
-                chapter_data.number
-            ));
-            continue;
-        }
+    /// Model for side A (defaults to the content translation model).
+    #[arg(long)]
+    model_a: Option<String>,
 
-        params.console.step(&format!(
-            "Translating chapter {}: {}",
-            chapter_data.number, chapter_data.title
-        ));
+    /// Model for side B (defaults to the content translation model).
+    #[arg(long)]
+    model_b: Option<String>,
 
-        // Translate title
-        let mapped_title = params.name_mapping.apply_to_text(&chapter_data.title);
-        let translated_title = params
-            .translator
-            .translate(&mapped_title, true, None)
-            .await
-            .unwrap_or_else(|_| format!("{} [TRANSLATION_FAILED]", chapter_data.title));
-
-        // Validate translated title for filesystem
-        let safe_title = sanitize_filename(&translated_title);
-
-        // Apply name mapping to content
-        let mapped_content = params.name_mapping.apply_to_text(&chapter_data.content);
-
-        // Translate content
-        let progress = ProgressInfo {
-            chapter: chapter_data.number,
-            chunk: 1,
-            total_chunks: 1, // Will be updated by translator
-        };
+    /// File containing the content prompt for side A (defaults to prompts.content_translation).
+    #[arg(long)]
+    prompt_a: Option<PathBuf>,
 
-        let translated_content = params
-            .translator
-            .translate(&mapped_content, false, Some(progress))
-            .await
-            .context("Failed to translate chapter")?;
+    /// File containing the content prompt for side B (defaults to prompts.content_translation).
+    #[arg(long)]
+    prompt_b: Option<PathBuf>,
 
-        // Save translated chapter
-        let translated_filename = format!("{} - {}.txt", chapter_num_str, safe_title);
-        let translated_path = story_dir.join(&translated_filename);
-        std::fs::write(&translated_path, &translated_content)?;
+    /// Output file (defaults to "compare-<novel id>-<chapter>.md" in the current directory).
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
 
-        params
-            .console
-            .success(&format!("Saved: {}", translated_filename));
-    }
+/// Name mapping subcommands.
+#[derive(Subcommand, Debug)]
+enum NamesCommand {
+    /// Merge votes, coverage, and locked names from a second mapping file into the first.
+    Merge {
+        /// Mapping file to merge into.
+        file1: PathBuf,
+
+        /// Mapping file to merge from (e.g. a copy from another machine).
+        file2: PathBuf,
+
+        /// Write the merged mapping here instead of over the first file.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// Seed a novel's name mapping from a glossary (JP<TAB>EN list, EPUBReader, or JSON).
+    Import {
+        /// Novel as "site:novel_id" (e.g. "syosetu:n1234ab").
+        novel: String,
+
+        /// Glossary file.
+        file: PathBuf,
+
+        /// Glossary format: tsv, epubreader, or json (default: detected).
+        #[arg(long)]
+        format: Option<GlossaryFormat>,
+
+        /// Pin every imported name to the glossary's rendering.
+        #[arg(long)]
+        lock: bool,
+    },
+
+    /// Show which names were substituted into translated chapters: unused entries and chapters the mapping barely covers.
+    Report {
+        /// Story folder path, or novel ID in the output directory.
+        novel: String,
+    },
+
+    /// Clear or scale down a novel's scouted votes (locked names are kept) and scout every chapter again next run.
+    Reset {
+        /// Story folder path, or novel ID in the output directory.
+        novel: String,
+
+        /// Fraction of each vote count to keep, between 0 and 1 (default clears them).
+        #[arg(long, default_value_t = 0.0)]
+        keep: f64,
+    },
+}
 
-    Ok(())
+/// Cookie management subcommands.
+#[derive(Subcommand, Debug)]
+enum CookiesCommand {
+    /// List cookie files found in the config directory.
+    List,
+
+    /// Check that a site's cookies exist and have not expired.
+    Check {
+        /// Site name as used in the cookie filename (e.g. "pixiv").
+        site: String,
+    },
+
+    /// Import cookies for a site from a browser export (Netscape or JSON).
+    Import {
+        /// Exported cookie file.
+        file: PathBuf,
+
+        /// Site to keep cookies for (e.g. "pixiv").
+        #[arg(long)]
+        site: String,
+    },
 }
 
-/// Runs name scout on chapters that haven't been covered.
-/// Returns true if any scouting was performed, false if all chapters were already covered.
-async fn run_name_scout(
-    console: &Console,
-    name_scout: &NameScout,
-    name_mapping: &mut NameMappingStore,
-    chapters: &[(u32, &str, &str)], // (number, title, content)
-) -> Result<bool> {
-    console.section("Name Scout Phase");
-
-    let uncovered: Vec<_> = chapters
-        .iter()
-        .filter(|(num, _, _)| !name_mapping.is_chapter_covered(*num))
-        .collect();
-
-    if uncovered.is_empty() {
-        console.info("All chapters already scouted for names");
-        return Ok(false);
-    }
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let console = Console::new();
 
-    console.info(&format!(
-        "Scouting {} chapters for character names",
-        uncovered.len()
-    ));
+    if let Some(command) = args.command {
+        return match command {
+            Command::Cookies(cmd) => run_cookies_command(&console, cmd),
+            Command::Compare(compare) => run_compare(&console, compare).await,
+            Command::Library(cmd) => run_library_command(&console, cmd).await,
+            Command::Update(update) => run_update(&console, update).await,
+            Command::Diff { url } => run_diff(&console, &url).await,
+            Command::Info { url } => run_info(&console, &url).await,
+            Command::Read {
+                novel,
+                chapter,
+                unread,
+            } => run_read(&console, &novel, chapter, !unread),
+            Command::ChapterProfile {
+                novel,
+                chapters,
+                profile,
+                clear: _,
+            } => run_chapter_profile(&console, &novel, &chapters, profile.as_deref()),
+            Command::Batch(batch) => run_batch(&console, batch).await,
+            Command::Stats { novel } => run_stats(&console, &novel),
+            Command::Lint { novel } => run_lint(&console, &novel),
+            Command::Postprocess { novel } => run_postprocess(&console, &novel),
+            Command::Repair { novel } => run_repair(&console, &novel).await,
+            Command::Verify { novel } => run_verify(&console, &novel),
+            Command::Search { query, full_text } => run_search(&console, &query, full_text),
+            Command::Names(cmd) => run_names_command(&console, cmd),
+            Command::Terms {
+                novel,
+                start,
+                end,
+                format,
+                output,
+            } => run_terms(&console, &novel, (start, end), format, output).await,
+            Command::History { novel, limit } => run_history(&console, novel.as_deref(), limit),
+            Command::Models(models) => run_models(&console, models).await,
+            Command::Export {
+                novel,
+                format,
+                output,
+            } => run_export(&console, &novel, format, output),
+            Command::Clean { novel, yes } => run_clean(&console, &novel, yes),
+            Command::Import(import) => run_import(&console, import).await,
+            Command::Sync => {
+                let config = Config::load().context("Failed to load configuration")?;
+                config.validate_sync()?;
+                run_sync(&console, &config).await
+            }
+        };
+    }
 
-    for (number, title, content) in uncovered {
-        console.step(&format!("Scouting chapter {}: {}", number, title));
+    let novel_url = args
+        .novel_url
+        .context("A novel URL is required when no subcommand is given")?;
 
-        let payload = build_chapter_payload(*number, title, content);
-        let name_chunks = name_scout.collect_names(&payload).await;
+    console.section("Tsundoku - Web Novel Downloader");
 
-        let total_names: usize = name_chunks.iter().map(|c| c.len()).sum();
+    let Some(mut config) = load_run_config(&console, args.debug, args.no_name_scout)? else {
+        return Ok(());
+    };
+    if let Some(profile) = &args.profile {
+        config.apply_profile(profile)?;
         console.info(&format!(
-            "Found {} names in chapter {}",
-            total_names, number
+            "Using profile '{}' ({})",
+            profile, config.api.model
         ));
-
-        // Record votes and save
-        for entries in name_chunks {
-            name_mapping.record_votes(&entries);
-            name_mapping.save()?;
-        }
-
-        // Mark chapter as covered
-        name_mapping.add_coverage(&[*number]);
-        name_mapping.save()?;
     }
+    if let Some(preset) = args.preset {
+        config.force_preset(preset);
+    }
+    verify_endpoints(&console, &config, !args.no_name_scout).await?;
 
-    console.success(&format!(
-        "Name mapping now has {} names",
-        name_mapping.len()
-    ));
-
-    Ok(true)
-}
-
-/// Prompts user to review and edit name mappings.
-fn manual_name_review(
-    console: &Console,
-    name_mapping: &mut NameMappingStore,
-    config: &Config,
-) -> Result<()> {
-    console.section("Name Mapping Review");
-
-    let filepath = name_mapping.filepath();
-    console.info(&format!("Name mapping file: {}", filepath.display()));
-
-    // Try to open in editor
-    let editor_opened = if let Some(ref editor_cmd) = config.paths.editor_command {
-        // Use configured editor
-        match std::process::Command::new(editor_cmd).arg(filepath).spawn() {
-            Ok(_) => {
-                console.info(&format!("Opening in {}...", editor_cmd));
-                true
-            }
-            Err(e) => {
-                console.warning(&format!("Failed to launch {}: {}", editor_cmd, e));
-                false
+    let options = JobOptions {
+        start: args.start,
+        end: args.end,
+        pick: args.pick,
+        no_name_pause: args.no_name_pause,
+        no_name_scout: args.no_name_scout,
+        no_progress: args.no_progress,
+    };
+    let started = Instant::now();
+    let started_unix = now_unix();
+    let mut run = NovelRun::new(&novel_url);
+    let result = if args.translate_only {
+        match resolve_story_dir(&config, &novel_url) {
+            Ok(story_dir) => {
+                let limiters = Limiters::default();
+                process_offline(&console, &config, &story_dir, options, &limiters, &mut run).await
             }
+            Err(e) => Err(e),
         }
     } else {
-        // Auto-detect editor
-        let editors = if cfg!(target_os = "windows") {
-            vec!["notepad", "code", "notepad++"]
-        } else if cfg!(target_os = "macos") {
-            vec!["open", "code", "vim", "nano"]
-        } else {
-            // Linux and other Unix-like systems
-            vec!["kate", "gedit", "code", "vim", "nano", "emacs"]
-        };
-
-        let mut opened = false;
-        for editor in editors {
-            if let Ok(editor_path) = which::which(editor) {
-                match std::process::Command::new(&editor_path)
-                    .arg(filepath)
-                    .spawn()
-                {
-                    Ok(_) => {
-                        console.info(&format!("Opening in {}...", editor));
-                        opened = true;
-                        break;
-                    }
-                    Err(_) => continue,
-                }
-            }
-        }
-        opened
+        let limiters = Limiters::default();
+        process_novel(&console, &config, &novel_url, options, &limiters, &mut run).await
     };
-
-    if !editor_opened {
-        console.info(&format!(
-            "Could not auto-detect editor. Please open the file manually: {}",
-            filepath.display()
+    run.finish(started.elapsed(), error_message(&result));
+    record_run(
+        &console,
+        started,
+        started_unix,
+        vec![run],
+        error_message(&result),
+    );
+    let removed = result?;
+    for record in &removed {
+        console.warning(&format!(
+            "Chapter {} ({}) was removed upstream; your local copy is now the only copy",
+            record.number, record.title
         ));
     }
+    sync_after_run(&console, &config).await;
 
-    // Prompt user
-    loop {
-        console.info("Review the name mappings and press Enter when done.");
-        print!("> ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-
-        // Reload and validate
-        match name_mapping.reload_from_disk() {
-            Ok(_) => {
-                console.success("Name mapping reloaded successfully");
-                break;
-            }
-            Err(e) => {
-                console.error(&format!("Failed to reload name mapping: {}", e));
-                console.info("Please fix the JSON and try again.");
-            }
-        }
-    }
-
+    console.section("Done!");
     Ok(())
 }
-
-/// Finds an existing folder or creates a new one with translated title.
-async fn find_or_create_folder(
-    console: &Console,
-    output_dir: &Path,
-    module_name: &str,
-    novel_id: &str,
-    original_title: &str,
-    translator: &Translator,
-) -> Result<String> {
-    // Check for existing folders
-    let new_format_prefix = format!("[{}: {}]", module_name, novel_id);
-    let old_format_prefix = format!("[{}]", novel_id);
-
-    if let Ok(entries) = std::fs::read_dir(output_dir) {
-        for entry in entries.filter_map(|e| e.ok()) {
-            if entry.path().is_dir() {
-                let name = entry.file_name().to_string_lossy().to_string();
-                if name.starts_with(&new_format_prefix) || name.starts_with(&old_format_prefix) {
-                    console.info(&format!("Using existing folder: {}", name));
-                    return Ok(name);
-                }
-            }
-        }
-    }
-
-    // Create new folder with translated title
-    console.step("Translating title for folder name...");
-    let translated_title = translator
-        .translate(original_title, true, None)
-        .await
-        .unwrap_or_else(|_| original_title.to_string());
-
-    let safe_title = sanitize_filename(&translated_title);
-    let folder_name = format!("[{}: {}] {}", module_name, novel_id, safe_title);
-
-    console.success(&format!("Creating folder: {}", folder_name));
-
-    Ok(folder_name)
-}
-
-/// Validates the chapter range arguments.
-fn validate_chapter_range(
-    start: Option<u32>,
-    end: Option<u32>,
-    chapter_list: &ChapterList,
-    console: &Console,
-) -> Result<(u32, u32)> {
-    let total_chapters = chapter_list.len() as u32;
-
-    // One-shots cannot use range
-    if chapter_list.is_oneshot() {
-        if start.is_some() || end.is_some() {
-            anyhow::bail!("Cannot use --start or --end with one-shot stories");
-        }
-        return Ok((1, 1));
-    }
-
-    let start_chapter = start.unwrap_or(1);
-    let end_chapter = end.unwrap_or(total_chapters);
-
-    // Validate range
-    if start_chapter > end_chapter {
-        anyhow::bail!(
-            "Start chapter ({}) cannot be greater than end chapter ({})",
-            start_chapter,
-            end_chapter
-        );
-    }
-
-    if end_chapter > total_chapters {
-        anyhow::bail!(
-            "End chapter ({}) exceeds total chapters ({})",
-            end_chapter,
-            total_chapters
-        );
-    }
-
-    console.info(&format!(
-        "Processing chapters {} to {} of {}",
-        start_chapter, end_chapter, total_chapters
-    ));
-
-    Ok((start_chapter, end_chapter))
-}
-
-/// Sanitizes a string for use as a filename.
-fn sanitize_filename(name: &str) -> String {
-    // Replace invalid characters with underscore
-    let sanitized: String = name
-        .chars()
-        .map(|c| match c {
-            '\\' | '/' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            _ => c,
-        })
-        .collect();
-
-    // Remove trailing dots and spaces
-    sanitized.trim_end_matches(['.', ' ']).to_string()
-}
-
-/// Expands ~ in paths to the home directory.
-fn expand_path(path: &Path) -> PathBuf {
-    let path_str = path.to_string_lossy();
-    if let Some(stripped) = path_str.strip_prefix("~/")
-        && let Some(home) = dirs::home_dir()
-    {
-        return home.join(stripped);
-    }
-    path.to_path_buf()
-}
@@ -0,0 +1,840 @@
+//! Per-story manifest tracking chapter status.
+//!
+//! Each story folder has a `manifest.json` recording, per chapter, whether it
+//! has been downloaded, scouted, and translated, along with file paths and
+//! content hashes. Skip decisions consult the manifest rather than guessing
+//! from filenames.
+//!
+//! Chapters are keyed by the platform's episode ID, so an author inserting or
+//! deleting chapters renumbers records instead of misaligning them.
+
+use crate::archive::{self, read_story_file};
+use crate::error::ManifestError;
+use crate::name_mapping::ReplacementReport;
+use crate::scrapers::ChapterInfo;
+use crate::utils::{join_relative, now_unix};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Manifest filename inside a story folder.
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// Status and files for one chapter.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChapterRecord {
+    /// Platform episode ID (empty for records written before IDs were tracked).
+    pub episode_id: String,
+    /// Chapter number (1-based) as of the last sync.
+    pub number: u32,
+    /// Original chapter title.
+    pub title: String,
+    /// Source URL.
+    pub url: String,
+    /// Whether the original text has been saved.
+    pub downloaded: bool,
+    /// Whether the name scout has processed the chapter.
+    pub scouted: bool,
+    /// Whether a translation has been saved.
+    pub translated: bool,
+    /// Whether the chapter has disappeared from the site (deleted, privated,
+    /// or returning 404), leaving the local copy as the only one.
+    pub removed: bool,
+    /// Original text file, relative to the story folder.
+    pub original_file: Option<String>,
+    /// SHA-256 of the original text.
+    pub original_hash: Option<String>,
+    /// Translated text file, relative to the story folder.
+    pub translated_file: Option<String>,
+    /// SHA-256 of the translated text.
+    pub translated_hash: Option<String>,
+    /// Names substituted into the text sent for translation, with counts.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub names_replaced: BTreeMap<String, u32>,
+    /// Katakana words the name mapping didn't cover, with counts.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub names_unmapped: BTreeMap<String, u32>,
+    /// Content chunks (0-based) saved with a failure marker instead of a
+    /// translation, for `repair`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed_chunks: Vec<usize>,
+    /// Why the last translation attempt left the chapter unsaved or with
+    /// failed chunks (e.g. a chunk's retries running out), until one
+    /// succeeds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// When the original was saved (Unix seconds; 0 if unknown).
+    pub downloaded_unix: u64,
+    /// Last time this record changed (Unix seconds).
+    pub updated_unix: u64,
+    /// How the saved translation was made (none for translations saved
+    /// before this was recorded, or adopted from elsewhere).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<TranslationProvenance>,
+    /// `[profiles.NAME]` section to translate this chapter with instead of
+    /// the run's settings (set with `tsundoku chapter-profile`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+/// The pipeline that produced a chapter's translation.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslationProvenance {
+    /// Model the content was translated with.
+    pub model: String,
+    /// SHA-256 of the content translation prompt.
+    pub prompt_hash: String,
+    /// Version of the name mapping applied to the source (see
+    /// [`NameMappingStore::version`](crate::name_mapping::NameMappingStore::version)).
+    pub names_hash: String,
+    /// When the translation was saved (Unix seconds).
+    pub translated_unix: u64,
+    /// Profile the chapter was translated with, if it had its own.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+/// On-disk manifest format.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ManifestData {
+    /// Scraper ID (e.g., "syosetu").
+    pub site: String,
+    /// Novel ID on the site.
+    pub novel_id: String,
+    /// Original title.
+    pub title: String,
+    /// Chapters, ordered by number.
+    pub chapters: Vec<ChapterRecord>,
+    /// Translated story details also written to `about.txt` (none for
+    /// folders made before these were recorded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub about: Option<AboutInfo>,
+}
+
+/// Story details shown on the title page of exports.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AboutInfo {
+    /// Story URL on the site.
+    pub url: String,
+    /// Author name, as listed on the site.
+    pub author: String,
+    /// Translated synopsis (the original if translation failed).
+    pub synopsis: String,
+    /// Translated tags (the originals if translation failed).
+    pub tags: Vec<String>,
+}
+
+/// Manifest for one story folder.
+pub struct Manifest {
+    /// Path to the JSON file.
+    filepath: PathBuf,
+    /// The manifest data.
+    data: ManifestData,
+    /// Whether the manifest existed on disk when opened.
+    existed: bool,
+}
+
+impl Manifest {
+    /// Opens the manifest in a story folder, starting empty if it doesn't exist.
+    pub fn open(story_dir: &Path) -> Result<Self, ManifestError> {
+        let filepath = story_dir.join(MANIFEST_FILENAME);
+        let existed = filepath.exists();
+        let data = if existed {
+            let content = std::fs::read_to_string(&filepath)?;
+            serde_json::from_str(&content)?
+        } else {
+            ManifestData::default()
+        };
+
+        Ok(Self {
+            filepath,
+            data,
+            existed,
+        })
+    }
+
+    /// Whether the manifest was loaded from disk (false for a new story
+    /// folder, or one created before manifests existed).
+    pub fn existed(&self) -> bool {
+        self.existed
+    }
+
+    /// Get the filepath for this manifest.
+    pub fn filepath(&self) -> &Path {
+        &self.filepath
+    }
+
+    /// Get the manifest data.
+    pub fn data(&self) -> &ManifestData {
+        &self.data
+    }
+
+    /// Sets the novel identity fields.
+    pub fn set_novel(&mut self, site: &str, novel_id: &str, title: &str) {
+        self.data.site = site.to_string();
+        self.data.novel_id = novel_id.to_string();
+        self.data.title = title.to_string();
+    }
+
+    /// Records the story details written to `about.txt`.
+    pub fn set_about(&mut self, about: AboutInfo) {
+        self.data.about = Some(about);
+    }
+
+    /// Save the manifest, replacing the old file atomically.
+    pub fn save(&self) -> Result<(), ManifestError> {
+        if let Some(parent) = self.filepath.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(&self.data)?;
+        let tmp_path = self.filepath.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, &self.filepath)?;
+
+        Ok(())
+    }
+
+    /// Finds a chapter record by episode ID.
+    pub fn chapter(&self, episode_id: &str) -> Option<&ChapterRecord> {
+        self.data
+            .chapters
+            .iter()
+            .find(|c| c.episode_id == episode_id)
+    }
+
+    /// Returns the record for a chapter, creating it if needed.
+    pub fn chapter_mut(&mut self, episode_id: &str, number: u32) -> &mut ChapterRecord {
+        let idx = match self
+            .data
+            .chapters
+            .iter()
+            .position(|c| c.episode_id == episode_id)
+        {
+            Some(idx) => idx,
+            None => {
+                let idx = self.data.chapters.partition_point(|c| c.number <= number);
+                self.data.chapters.insert(
+                    idx,
+                    ChapterRecord {
+                        episode_id: episode_id.to_string(),
+                        number,
+                        ..Default::default()
+                    },
+                );
+                idx
+            }
+        };
+        &mut self.data.chapters[idx]
+    }
+
+    /// Updates records from a freshly fetched chapter list.
+    ///
+    /// Records are matched by episode ID (or, for records predating episode
+    /// IDs, by number). Returns the chapters whose number changed.
+    pub fn sync_chapters(&mut self, chapters: &[ChapterInfo]) -> Vec<Renumbered> {
+        let mut renumbered = Vec::new();
+
+        for chapter in chapters {
+            let existing = self
+                .data
+                .chapters
+                .iter()
+                .position(|c| c.episode_id == chapter.episode_id)
+                .or_else(|| {
+                    self.data
+                        .chapters
+                        .iter()
+                        .position(|c| c.episode_id.is_empty() && c.number == chapter.number)
+                });
+
+            let record = match existing {
+                Some(idx) => &mut self.data.chapters[idx],
+                None => self.chapter_mut(&chapter.episode_id, chapter.number),
+            };
+
+            if record.number != chapter.number {
+                renumbered.push(Renumbered {
+                    episode_id: chapter.episode_id.clone(),
+                    title: chapter.title.clone(),
+                    old_number: record.number,
+                    new_number: chapter.number,
+                });
+            }
+            record.episode_id = chapter.episode_id.clone();
+            record.removed = false;
+            record.number = chapter.number;
+            record.title = chapter.title.clone();
+            record.url = chapter.url.clone();
+        }
+
+        self.data.chapters.sort_by_key(|c| c.number);
+        renumbered
+    }
+
+    /// Rebuilds the chapter list from the records still listed upstream, for
+    /// runs that work from the manifest alone.
+    pub fn listed_chapters(&self) -> Vec<ChapterInfo> {
+        self.data
+            .chapters
+            .iter()
+            .filter(|c| !c.removed)
+            .map(|c| ChapterInfo {
+                title: c.title.clone(),
+                url: c.url.clone(),
+                number: c.number,
+                episode_id: c.episode_id.clone(),
+            })
+            .collect()
+    }
+
+    /// Flags records missing from a freshly fetched chapter list as removed.
+    ///
+    /// With `positional_ids` (sites whose episode IDs are list positions),
+    /// deleting a chapter shifts every later ID down, so the ID missing from
+    /// the list is the last one rather than the deleted chapter's; nothing is
+    /// flagged then. Returns the records that were newly flagged.
+    pub fn mark_missing(
+        &mut self,
+        chapters: &[ChapterInfo],
+        positional_ids: bool,
+    ) -> Vec<ChapterRecord> {
+        let mut newly_removed = Vec::new();
+        if positional_ids {
+            return newly_removed;
+        }
+
+        for record in &mut self.data.chapters {
+            let listed = chapters.iter().any(|c| c.episode_id == record.episode_id);
+            if !listed && !record.removed {
+                record.removed = true;
+                record.updated_unix = now_unix();
+                newly_removed.push(record.clone());
+            }
+        }
+
+        newly_removed
+    }
+
+    /// Flags a chapter as removed from the site (e.g. its page returned 404).
+    pub fn mark_removed(&mut self, episode_id: &str) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.removed = true;
+            record.updated_unix = now_unix();
+        }
+    }
+
+    /// Renames chapter files whose leading number no longer matches their
+    /// record (after [`Manifest::sync_chapters`] reported renumbering).
+    ///
+    /// File numbers start at `first_number` for chapter 1. Loose files are
+    /// renamed in two passes and originals in `Original.zip` in one rewrite,
+    /// so swaps and shifts don't collide. Returns the (old, new) relative
+    /// paths that were renamed.
+    pub fn remap_files(
+        &mut self,
+        story_dir: &Path,
+        padding: usize,
+        first_number: u32,
+    ) -> Result<Vec<(String, String)>, ManifestError> {
+        let archived: Vec<String> = archive::entry_names(story_dir)?;
+        let mut planned: Vec<PlannedRename> = Vec::new();
+
+        for (idx, record) in self.data.chapters.iter().enumerate() {
+            let files = [
+                (false, record.original_file.as_ref()),
+                (true, record.translated_file.as_ref()),
+            ];
+            for (translated, file) in files {
+                let Some(file) = file else { continue };
+                let number = record.number.saturating_sub(1) + first_number;
+                let Some(new) = renumbered_path(file, number, padding) else {
+                    continue;
+                };
+                let in_archive = if join_relative(story_dir, file).is_file() {
+                    false
+                } else if archived.contains(file) {
+                    true
+                } else {
+                    continue;
+                };
+                planned.push(PlannedRename {
+                    idx,
+                    translated,
+                    in_archive,
+                    old: file.clone(),
+                    new,
+                });
+            }
+        }
+
+        // Never overwrite a file that isn't itself being moved out of the
+        // way. Dropping a rename can leave a file another rename relied on
+        // in place, so repeat until nothing more is dropped.
+        loop {
+            let moving: Vec<&String> = planned.iter().map(|p| &p.old).collect();
+            let kept: Vec<bool> = planned
+                .iter()
+                .map(|p| {
+                    let taken = if p.in_archive {
+                        archived.contains(&p.new)
+                    } else {
+                        join_relative(story_dir, &p.new).exists()
+                    };
+                    !taken || moving.contains(&&p.new)
+                })
+                .collect();
+            if kept.iter().all(|k| *k) {
+                break;
+            }
+            let mut kept = kept.into_iter();
+            planned.retain(|_| kept.next().unwrap_or(true));
+        }
+
+        let (in_archive, loose): (Vec<_>, Vec<_>) = planned.iter().partition(|p| p.in_archive);
+        for rename in &loose {
+            std::fs::rename(
+                join_relative(story_dir, &rename.old),
+                join_relative(story_dir, &format!("{}.remap", rename.old)),
+            )?;
+        }
+        for rename in &loose {
+            std::fs::rename(
+                join_relative(story_dir, &format!("{}.remap", rename.old)),
+                join_relative(story_dir, &rename.new),
+            )?;
+        }
+        let archive_renames: Vec<(String, String)> = in_archive
+            .iter()
+            .map(|r| (r.old.clone(), r.new.clone()))
+            .collect();
+        archive::rename_entries(story_dir, &archive_renames)?;
+
+        for rename in &planned {
+            let record = &mut self.data.chapters[rename.idx];
+            if rename.translated {
+                record.translated_file = Some(rename.new.clone());
+            } else {
+                record.original_file = Some(rename.new.clone());
+            }
+        }
+
+        Ok(planned.into_iter().map(|p| (p.old, p.new)).collect())
+    }
+
+    /// Returns the original text file if the chapter is recorded as
+    /// downloaded and the file is still present.
+    pub fn downloaded_file(&self, story_dir: &Path, episode_id: &str) -> Option<PathBuf> {
+        let record = self.chapter(episode_id).filter(|c| c.downloaded)?;
+        let path = join_relative(story_dir, record.original_file.as_ref()?);
+        path.is_file().then_some(path)
+    }
+
+    /// Reads the original text if the chapter is recorded as downloaded and
+    /// the file is still present, loose or in the originals archive.
+    pub fn downloaded_text(&self, story_dir: &Path, episode_id: &str) -> Option<String> {
+        let record = self.chapter(episode_id).filter(|c| c.downloaded)?;
+        read_story_file(story_dir, record.original_file.as_ref()?)
+            .ok()
+            .flatten()
+    }
+
+    /// Returns the translated file if the chapter is recorded as translated
+    /// and the file is still present.
+    pub fn translated_file(&self, story_dir: &Path, episode_id: &str) -> Option<PathBuf> {
+        let record = self.chapter(episode_id).filter(|c| c.translated)?;
+        let path = join_relative(story_dir, record.translated_file.as_ref()?);
+        path.is_file().then_some(path)
+    }
+
+    /// Records a saved original.
+    ///
+    /// Re-recording identical content at the same path keeps the original
+    /// timestamps, so an unchanged re-download doesn't look like a revision.
+    pub fn mark_downloaded(&mut self, episode_id: &str, relative_path: &str, content: &str) {
+        if let Some(record) = self.find_mut(episode_id) {
+            let hash = content_hash(content);
+            let unchanged = record.downloaded
+                && record.original_file.as_deref() == Some(relative_path)
+                && record.original_hash.as_deref() == Some(hash.as_str());
+            record.downloaded = true;
+            record.original_file = Some(relative_path.to_string());
+            record.original_hash = Some(hash);
+            if !unchanged {
+                record.downloaded_unix = now_unix();
+                record.updated_unix = record.downloaded_unix;
+            }
+        }
+    }
+
+    /// Records that the name scout has processed a chapter.
+    pub fn mark_scouted(&mut self, episode_id: &str) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.scouted = true;
+            record.updated_unix = now_unix();
+        }
+    }
+
+    /// Records a saved translation.
+    pub fn mark_translated(&mut self, episode_id: &str, relative_path: &str, content: &str) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.translated = true;
+            record.translated_file = Some(relative_path.to_string());
+            record.translated_hash = Some(content_hash(content));
+            record.updated_unix = now_unix();
+        }
+    }
+
+    /// Records how a chapter's saved translation was made.
+    pub fn record_provenance(&mut self, episode_id: &str, provenance: TranslationProvenance) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.provenance = Some(provenance);
+        }
+    }
+
+    /// Sets (or with `None`, clears) the profile of every chapter numbered
+    /// within `numbers`. Returns how many chapters changed.
+    pub fn set_profile(
+        &mut self,
+        numbers: std::ops::RangeInclusive<u32>,
+        profile: Option<&str>,
+    ) -> usize {
+        let mut changed = 0;
+        for record in &mut self.data.chapters {
+            if numbers.contains(&record.number) && record.profile.as_deref() != profile {
+                record.profile = profile.map(str::to_string);
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Records which content chunks of a saved translation failed (empty
+    /// once none have).
+    pub fn set_failed_chunks(&mut self, episode_id: &str, chunks: Vec<usize>) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.failed_chunks = chunks;
+        }
+    }
+
+    /// Records why the chapter's translation failed, or clears it with None.
+    pub fn set_last_error(&mut self, episode_id: &str, error: Option<String>) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.last_error = error;
+        }
+    }
+
+    /// Records which names were substituted into a chapter before
+    /// translation.
+    pub fn record_names(&mut self, episode_id: &str, report: &ReplacementReport) {
+        if let Some(record) = self.find_mut(episode_id) {
+            record.names_replaced = report.replaced.clone();
+            record.names_unmapped = report.unmapped.clone();
+        }
+    }
+
+    /// Finds a chapter record by episode ID for modification.
+    fn find_mut(&mut self, episode_id: &str) -> Option<&mut ChapterRecord> {
+        self.data
+            .chapters
+            .iter_mut()
+            .find(|c| c.episode_id == episode_id)
+    }
+}
+
+/// A chapter whose position in the chapter list changed since the last run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Renumbered {
+    /// Platform episode ID.
+    pub episode_id: String,
+    /// Chapter title.
+    pub title: String,
+    /// Number recorded in the manifest.
+    pub old_number: u32,
+    /// Number in the current chapter list.
+    pub new_number: u32,
+}
+
+/// A chapter file [`Manifest::remap_files`] is about to rename.
+struct PlannedRename {
+    /// Index of the chapter record.
+    idx: usize,
+    /// Whether it's the translation (otherwise the original).
+    translated: bool,
+    /// Whether the file is an entry in `Original.zip`.
+    in_archive: bool,
+    old: String,
+    new: String,
+}
+
+/// Returns `file` with its leading "<number> - " prefix replaced by the
+/// padded new number, or `None` if it already matches or has no such prefix.
+fn renumbered_path(file: &str, number: u32, padding: usize) -> Option<String> {
+    let (dir, name) = match file.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, file),
+    };
+    let (prefix, rest) = name.split_once(" - ")?;
+    if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let new_name = format!("{:0width$} - {}", number, rest, width = padding);
+    if new_name == name {
+        return None;
+    }
+    Some(match dir {
+        Some(dir) => format!("{}/{}", dir, new_name),
+        None => new_name,
+    })
+}
+
+/// Hex-encoded SHA-256 of text content.
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn chapter(number: u32, episode_id: &str) -> ChapterInfo {
+        ChapterInfo {
+            title: format!("第{}話", number),
+            url: format!("https://kakuyomu.jp/works/1/episodes/{}", episode_id),
+            number,
+            episode_id: episode_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_manifest_roundtrip_and_skip() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        assert!(!manifest.existed());
+        manifest.set_novel("kakuyomu", "1", "タイトル");
+        manifest.sync_chapters(&[chapter(2, "e2"), chapter(1, "e1")]);
+        manifest.mark_translated("e1", "1 - One.txt", "Hello");
+        manifest.save().unwrap();
+
+        let manifest = Manifest::open(story_dir).unwrap();
+        assert!(manifest.existed());
+        let numbers: Vec<u32> = manifest.data().chapters.iter().map(|c| c.number).collect();
+        assert_eq!(numbers, vec![1, 2]);
+
+        // Recorded but the file is missing: not considered translated
+        assert!(manifest.translated_file(story_dir, "e1").is_none());
+        std::fs::write(story_dir.join("1 - One.txt"), "Hello").unwrap();
+        assert!(manifest.translated_file(story_dir, "e1").is_some());
+        assert!(manifest.translated_file(story_dir, "e2").is_none());
+    }
+
+    #[test]
+    fn test_inserted_chapter_renumbers_and_remaps() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2")]);
+        for (id, file) in [("e1", "1 - A.txt"), ("e2", "2 - B.txt")] {
+            std::fs::write(story_dir.join(file), id).unwrap();
+            manifest.mark_translated(id, file, id);
+        }
+
+        // The author inserts a new chapter between the two
+        let renumbered =
+            manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "new"), chapter(3, "e2")]);
+        assert_eq!(renumbered.len(), 1);
+        assert_eq!(renumbered[0].old_number, 2);
+        assert_eq!(renumbered[0].new_number, 3);
+        assert!(manifest.translated_file(story_dir, "e2").is_some());
+        assert!(manifest.translated_file(story_dir, "new").is_none());
+
+        let renamed = manifest.remap_files(story_dir, 1, 1).unwrap();
+        assert_eq!(
+            renamed,
+            vec![("2 - B.txt".to_string(), "3 - B.txt".to_string())]
+        );
+        assert_eq!(
+            std::fs::read_to_string(story_dir.join("3 - B.txt")).unwrap(),
+            "e2"
+        );
+    }
+
+    #[test]
+    fn test_remap_skips_chained_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2")]);
+        for (id, file) in [("e1", "1 - A.txt"), ("e2", "3 - A.txt")] {
+            std::fs::write(story_dir.join(file), id).unwrap();
+            manifest.mark_translated(id, file, id);
+        }
+        // A file the manifest doesn't know about blocks e1's new name
+        std::fs::write(story_dir.join("2 - A.txt"), "stray").unwrap();
+
+        // e1 can't move to 2, so e2 mustn't move onto e1's file either
+        manifest.sync_chapters(&[chapter(1, "e2"), chapter(2, "e1")]);
+        let renamed = manifest.remap_files(story_dir, 1, 1).unwrap();
+        assert!(renamed.is_empty());
+        for (file, content) in [
+            ("1 - A.txt", "e1"),
+            ("2 - A.txt", "stray"),
+            ("3 - A.txt", "e2"),
+        ] {
+            assert_eq!(
+                std::fs::read_to_string(story_dir.join(file)).unwrap(),
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn test_remap_archived_originals() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2")]);
+        for (id, file) in [("e1", "Original/1 - A.txt"), ("e2", "Original/2 - B.txt")] {
+            archive::append(story_dir, file, id).unwrap();
+            manifest.mark_downloaded(id, file, id);
+        }
+
+        // The two chapters swap places
+        manifest.sync_chapters(&[chapter(1, "e2"), chapter(2, "e1")]);
+        let renamed = manifest.remap_files(story_dir, 1, 1).unwrap();
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(
+            manifest.downloaded_text(story_dir, "e1").as_deref(),
+            Some("e1")
+        );
+        assert_eq!(
+            manifest.chapter("e1").unwrap().original_file.as_deref(),
+            Some("Original/2 - A.txt")
+        );
+        assert_eq!(
+            read_story_file(story_dir, "Original/1 - B.txt")
+                .unwrap()
+                .as_deref(),
+            Some("e2")
+        );
+    }
+
+    #[test]
+    fn test_mark_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::open(temp_dir.path()).unwrap();
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2")]);
+
+        // e1 was deleted upstream
+        let current = [chapter(1, "e2")];
+        manifest.sync_chapters(&current);
+        let removed = manifest.mark_missing(&current, false);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].episode_id, "e1");
+        assert!(manifest.chapter("e1").unwrap().removed);
+
+        // Only reported once
+        assert!(manifest.mark_missing(&current, false).is_empty());
+        let listed: Vec<String> = manifest
+            .listed_chapters()
+            .into_iter()
+            .map(|c| c.episode_id)
+            .collect();
+        assert_eq!(listed, vec!["e2"]);
+
+        // Restored upstream
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2")]);
+        assert!(!manifest.chapter("e1").unwrap().removed);
+    }
+
+    #[test]
+    fn test_set_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::open(temp_dir.path()).unwrap();
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2"), chapter(3, "e3")]);
+
+        assert_eq!(manifest.set_profile(2..=u32::MAX, Some("premium")), 2);
+        assert_eq!(manifest.set_profile(3..=3, Some("premium")), 0);
+        let profiles: Vec<Option<&str>> = manifest
+            .data()
+            .chapters
+            .iter()
+            .map(|c| c.profile.as_deref())
+            .collect();
+        assert_eq!(profiles, vec![None, Some("premium"), Some("premium")]);
+
+        // Kept when the chapter list is synced again
+        manifest.sync_chapters(&[chapter(1, "e1"), chapter(2, "e2"), chapter(3, "e3")]);
+        assert_eq!(
+            manifest.chapter("e2").unwrap().profile.as_deref(),
+            Some("premium")
+        );
+
+        assert_eq!(manifest.set_profile(1..=2, None), 1);
+        assert_eq!(manifest.chapter("e2").unwrap().profile, None);
+    }
+
+    #[test]
+    fn test_mark_missing_positional_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::open(temp_dir.path()).unwrap();
+        manifest.sync_chapters(&[chapter(1, "1"), chapter(2, "2"), chapter(3, "3")]);
+
+        // Chapter 2 was deleted, so the old chapter 3 is now listed as "2"
+        let current = [chapter(1, "1"), chapter(2, "2")];
+        manifest.sync_chapters(&current);
+        assert!(manifest.mark_missing(&current, true).is_empty());
+        assert!(!manifest.chapter("3").unwrap().removed);
+    }
+
+    #[test]
+    fn test_renumbered_path() {
+        assert_eq!(
+            renumbered_path("Original/02 - B.txt", 3, 2).as_deref(),
+            Some("Original/03 - B.txt")
+        );
+        assert_eq!(renumbered_path("03 - B.txt", 3, 2), None);
+        assert_eq!(renumbered_path("oneshot.txt", 1, 1), None);
+    }
+
+    #[test]
+    fn test_unchanged_download_keeps_timestamps() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::open(temp_dir.path()).unwrap();
+        manifest.sync_chapters(&[chapter(1, "e1")]);
+        manifest.mark_downloaded("e1", "Original/1 - A.txt", "本文");
+        let record = manifest.find_mut("e1").unwrap();
+        record.downloaded_unix = 1;
+        record.updated_unix = 1;
+
+        manifest.mark_downloaded("e1", "Original/1 - A.txt", "本文");
+        let record = manifest.chapter("e1").unwrap();
+        assert_eq!((record.downloaded_unix, record.updated_unix), (1, 1));
+
+        manifest.mark_downloaded("e1", "Original/1 - A.txt", "改稿");
+        assert!(manifest.chapter("e1").unwrap().downloaded_unix > 1);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Per-chapter provenance metadata.
+//!
+//! Each translation can carry where it came from and how it was made, either
+//! as a JSON sidecar in `Metadata/` or as YAML front matter at the top of the
+//! translated file, so provenance survives losing the story manifest.
+
+use crate::translator::TokenUsage;
+use serde::{Deserialize, Serialize};
+
+/// Folder of JSON sidecars, relative to the story folder.
+pub const METADATA_DIR: &str = "Metadata";
+
+/// Front matter delimiter line.
+const FRONT_MATTER_FENCE: &str = "---";
+
+/// Keys written by [`ChapterMetadata::to_front_matter`], nested ones included.
+const FRONT_MATTER_KEYS: &[&str] = &[
+    "novel_title",
+    "chapter_number",
+    "original_title",
+    "translated_title",
+    "source_url",
+    "model",
+    "prompt_hash",
+    "downloaded_at",
+    "translated_at",
+    "usage",
+    "prompt_tokens",
+    "completion_tokens",
+    "total_tokens",
+];
+
+/// Provenance of one translated chapter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChapterMetadata {
+    /// Novel title as published.
+    pub novel_title: String,
+    /// Chapter number.
+    pub chapter_number: u32,
+    /// Chapter title as published.
+    pub original_title: String,
+    /// Translated chapter title.
+    pub translated_title: String,
+    /// Source URL.
+    pub source_url: String,
+    /// Model used for translation.
+    pub model: String,
+    /// SHA-256 of the content translation prompt.
+    pub prompt_hash: String,
+    /// When the original was saved (`YYYY-MM-DDTHH:MM:SSZ`), if known.
+    pub downloaded_at: Option<String>,
+    /// When the translation was saved (`YYYY-MM-DDTHH:MM:SSZ`).
+    pub translated_at: String,
+    /// Tokens used translating the title and content.
+    pub usage: TokenUsage,
+}
+
+impl ChapterMetadata {
+    /// Renders the metadata as a pretty-printed JSON sidecar.
+    pub fn to_json(&self) -> String {
+        // Only plain strings and numbers; serializing can't fail
+        serde_json::to_string_pretty(self).unwrap_or_default() + "\n"
+    }
+
+    /// Renders the metadata as a YAML front matter block, fences included.
+    pub fn to_front_matter(&self) -> String {
+        // JSON strings are valid YAML double-quoted scalars
+        let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+        let mut lines = vec![
+            FRONT_MATTER_FENCE.to_string(),
+            format!("novel_title: {}", quote(&self.novel_title)),
+            format!("chapter_number: {}", self.chapter_number),
+            format!("original_title: {}", quote(&self.original_title)),
+            format!("translated_title: {}", quote(&self.translated_title)),
+            format!("source_url: {}", quote(&self.source_url)),
+            format!("model: {}", quote(&self.model)),
+            format!("prompt_hash: {}", quote(&self.prompt_hash)),
+        ];
+        if let Some(downloaded_at) = &self.downloaded_at {
+            lines.push(format!("downloaded_at: {}", quote(downloaded_at)));
+        }
+        lines.extend([
+            format!("translated_at: {}", quote(&self.translated_at)),
+            "usage:".to_string(),
+            format!("  prompt_tokens: {}", self.usage.prompt_tokens),
+            format!("  completion_tokens: {}", self.usage.completion_tokens),
+            format!("  total_tokens: {}", self.usage.total_tokens),
+            FRONT_MATTER_FENCE.to_string(),
+        ]);
+        lines.join("\n") + "\n"
+    }
+}
+
+/// Prepends front matter to chapter text, separated by a blank line.
+pub fn with_front_matter(metadata: &ChapterMetadata, body: &str) -> String {
+    format!("{}\n{}", metadata.to_front_matter(), body)
+}
+
+/// Splits leading YAML front matter from the chapter text.
+///
+/// The front matter part runs through the closing fence and the blank line
+/// after it, so concatenating the two parts gives back the text. It is empty
+/// if the text doesn't start with front matter: a fenced block whose lines
+/// are all metadata keys, so a chapter opening with a `---` scene break
+/// stays whole.
+pub fn split_front_matter(text: &str) -> (&str, &str) {
+    let Some(rest) = text
+        .strip_prefix(FRONT_MATTER_FENCE)
+        .and_then(|r| r.strip_prefix('\n'))
+    else {
+        return ("", text);
+    };
+
+    let mut offset = text.len() - rest.len();
+    for (keys, line) in rest.split_inclusive('\n').enumerate() {
+        offset += line.len();
+        if line.trim_end() == FRONT_MATTER_FENCE {
+            if keys == 0 {
+                return ("", text);
+            }
+            if text[offset..].starts_with('\n') {
+                offset += 1;
+            }
+            return text.split_at(offset);
+        }
+        if !is_front_matter_line(line) {
+            return ("", text);
+        }
+    }
+    ("", text)
+}
+
+/// Returns true if `line` is a `key: value` pair with a metadata key.
+fn is_front_matter_line(line: &str) -> bool {
+    line.trim()
+        .split_once(':')
+        .is_some_and(|(key, _)| FRONT_MATTER_KEYS.contains(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> ChapterMetadata {
+        ChapterMetadata {
+            novel_title: "転生したら".to_string(),
+            chapter_number: 3,
+            original_title: "決闘 \"前編\"".to_string(),
+            translated_title: "The Duel".to_string(),
+            source_url: "https://ncode.syosetu.com/n1234ab/3/".to_string(),
+            model: "gpt-4o".to_string(),
+            prompt_hash: "abc123".to_string(),
+            downloaded_at: None,
+            translated_at: "2026-01-02T03:04:05Z".to_string(),
+            usage: TokenUsage {
+                prompt_tokens: 10,
+                completion_tokens: 20,
+                total_tokens: 30,
+            },
+        }
+    }
+
+    #[test]
+    fn test_front_matter_roundtrip() {
+        let text = with_front_matter(&metadata(), "Body text.\n---\nMore.");
+        assert!(text.starts_with("---\nnovel_title: \"転生したら\"\n"));
+        assert!(text.contains("original_title: \"決闘 \\\"前編\\\"\"\n"));
+        assert!(!text.contains("downloaded_at"));
+        assert!(text.contains("usage:\n  prompt_tokens: 10\n"));
+
+        let (front, body) = split_front_matter(&text);
+        assert_eq!(front, metadata().to_front_matter() + "\n");
+        assert_eq!(body, "Body text.\n---\nMore.");
+    }
+
+    #[test]
+    fn test_split_without_front_matter() {
+        assert_eq!(split_front_matter("Plain text."), ("", "Plain text."));
+        // An unclosed fence isn't front matter
+        assert_eq!(
+            split_front_matter("---\nnot closed"),
+            ("", "---\nnot closed")
+        );
+        // A scene break opening the chapter isn't either
+        let text = "---\nShe turned: nothing there.\n---\n\nMore.";
+        assert_eq!(split_front_matter(text), ("", text));
+        assert_eq!(split_front_matter("---\n---\nBody"), ("", "---\n---\nBody"));
+    }
+
+    #[test]
+    fn test_json_sidecar() {
+        let json = metadata().to_json();
+        let parsed: ChapterMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, metadata());
+    }
+}
@@ -5,8 +5,9 @@
 
 use crate::error::NameMappingError;
 use regex::Regex;
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
@@ -23,6 +24,17 @@ static HONORIFIC_SUFFIX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
         .expect("Invalid HONORIFIC_SUFFIX_REGEX")
 });
 
+/// Regex for katakana words, the usual spelling of foreign names.
+static KATAKANA_WORD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[ァ-ヺ][ァ-ヺー]+").expect("Invalid KATAKANA_WORD_REGEX"));
+
+/// Scout confidence below which a name is worth a second look in review.
+pub const LOW_CONFIDENCE: f64 = 0.5;
+
+/// Unmapped katakana words a chapter needs before it counts as dominated by
+/// them.
+const UNMAPPED_DOMINANCE_MIN: u32 = 5;
+
 /// English honorifics to reject.
 const ENGLISH_HONORIFICS: &[&str] = &[
     "-san", "-chan", "-kun", "-sama", " san", " chan", " kun", " sama",
@@ -67,12 +79,90 @@ const ORIGINAL_NAME_DENYLIST: &[&str] = &[
     "みんな",
 ];
 
-/// Indicates what part of a name this is (family name, given name, or unknown).
+/// Separators between the parts of a full name in Japanese text
+/// (katakana names are usually written "given・family").
+const NAME_SEPARATORS: &[&str] = &["・", "･"];
+
+/// Returns true if the text is written entirely in kana.
+fn is_kana(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| matches!(c, 'ぁ'..='ゖ' | 'ァ'..='ヺ' | 'ー' | '・' | '･'))
+}
+
+/// Spelling-insensitive form of a kana name: katakana, without separators
+/// or long-vowel marks, and with a vowel lengthened by ウ or オ (ショウタ,
+/// コオリ) shortened like one lengthened by ー. ショウタ, ショータ, and
+/// しょうた all give ショタ.
+fn reading_key(kana: &str) -> String {
+    let mut key = String::new();
+    let mut previous = None;
+    for c in kana.chars() {
+        // Hiragana to katakana
+        let c = match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            c => c,
+        };
+        if matches!(c, 'ー' | '・' | '･') {
+            continue;
+        }
+        let lengthens = match c {
+            'ウ' => {
+                previous.is_some_and(|p| vowel_row(p) == Some('u') || vowel_row(p) == Some('o'))
+            }
+            'オ' => previous.is_some_and(|p| vowel_row(p) == Some('o')),
+            _ => false,
+        };
+        if !lengthens {
+            key.push(c);
+        }
+        previous = Some(c);
+    }
+    key
+}
+
+/// Vowel a katakana syllable ends in.
+fn vowel_row(c: char) -> Option<char> {
+    const ROWS: [(char, &str); 5] = [
+        ('a', "アカガサザタダナハバパマヤラワァャ"),
+        ('i', "イキギシジチヂニヒビピミリィ"),
+        ('u', "ウクグスズツヅヌフブプムユルゥュヴ"),
+        ('e', "エケゲセゼテデネヘベペメレェ"),
+        ('o', "オコゴソゾトドノホボポモヨロヲォョ"),
+    ];
+    ROWS.iter()
+        .find(|(_, kana)| kana.contains(c))
+        .map(|(vowel, _)| *vowel)
+}
+
+/// The reading key a name can be matched on: its own spelling if written
+/// in kana, otherwise its reading.
+fn variant_key(original: &str, reading: Option<&str>) -> Option<String> {
+    let kana = if is_kana(original) {
+        original
+    } else {
+        reading.filter(|r| is_kana(r))?
+    };
+    let key = reading_key(kana);
+    // Keys of one or two kana (ユキ for ユウキ) match too many unrelated names
+    (key.chars().count() >= 3).then_some(key)
+}
+
+/// Hash of scout chunks' text, for [`ScoutProgress`].
+fn chunks_hash(chunks: &[String]) -> String {
+    crate::manifest::content_hash(&chunks.concat())
+}
+
+/// Indicates what part of a name this is (family name, given name, full
+/// name, or unknown).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum NamePart {
     Family,
     Given,
+    /// Family and given name together; the English may contain spaces.
+    Full,
     #[default]
     Unknown,
 }
@@ -84,13 +174,14 @@ impl std::str::FromStr for NamePart {
         Ok(match s.to_lowercase().as_str() {
             "family" => Self::Family,
             "given" => Self::Given,
+            "full" => Self::Full,
             _ => Self::Unknown,
         })
     }
 }
 
 /// A name entry for recording votes.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct NameEntry {
     /// Original Japanese name.
     pub original: String,
@@ -98,6 +189,10 @@ pub struct NameEntry {
     pub english: String,
     /// Which part of the name this is.
     pub part: NamePart,
+    /// Scout's confidence in the entry (0 to 1), if it gave one.
+    pub confidence: Option<f64>,
+    /// Kana reading of a name written in kanji, if the scout gave one.
+    pub reading: Option<String>,
 }
 
 /// Information about a single name in the mapping store.
@@ -107,12 +202,44 @@ pub struct NameInfo {
     pub part: NamePart,
     /// Vote counts for each English translation.
     pub votes: HashMap<String, u32>,
+    /// Confidence-weighted votes for each English translation. Renderings
+    /// missing here count one per vote.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub weights: HashMap<String, f64>,
     /// The winning English translation (highest votes).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub english: Option<String>,
     /// The vote count of the winning translation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub count: Option<u32>,
+    /// Average scout confidence in the winning translation, if the scout
+    /// reported any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Hand-written notes passed to the translator with the name (e.g.
+    /// gender).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Character's gender, for pronouns (e.g. "female").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gender: Option<String>,
+    /// Character's role in the story (e.g. "the hero's rival").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Relationships to other characters (e.g. "Hanako's older brother").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relationships: Option<String>,
+    /// Keeps `english` as set by hand, whatever later votes say.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    /// Kana reading of a name written in kanji, used to match its kana
+    /// spellings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reading: Option<String>,
+    /// Other spellings of the name (kana for kanji, long-vowel variants)
+    /// whose votes went to this entry. They're replaced like the name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
 }
 
 impl NameInfo {
@@ -121,61 +248,351 @@ impl NameInfo {
         Self {
             part,
             votes: HashMap::new(),
+            weights: HashMap::new(),
             english: None,
             count: None,
+            confidence: None,
+            notes: None,
+            gender: None,
+            role: None,
+            relationships: None,
+            locked: false,
+            reading: None,
+            variants: Vec::new(),
+        }
+    }
+
+    /// Weighted votes for an English translation.
+    pub fn weight(&self, english: &str) -> f64 {
+        self.weights
+            .get(english)
+            .copied()
+            .unwrap_or_else(|| self.votes.get(english).copied().unwrap_or(0) as f64)
+    }
+
+    /// Record one vote for an English translation, weighted by the scout's
+    /// confidence.
+    pub fn add_vote(&mut self, english: &str, confidence: Option<f64>) {
+        if confidence.is_some() || self.weights.contains_key(english) {
+            let weight = self.weight(english) + confidence.unwrap_or(1.0);
+            self.weights
+                .insert(english.to_string(), (weight * 100.0).round() / 100.0);
         }
+        *self.votes.entry(english.to_string()).or_insert(0) += 1;
     }
 
     /// Recalculate the winning translation from votes.
+    ///
+    /// A locked entry keeps its translation; only its count and confidence
+    /// are refreshed.
     pub fn recalculate_best(&mut self) {
-        if self.votes.is_empty() {
-            self.english = None;
-            self.count = None;
+        if self.locked
+            && let Some(english) = &self.english
+        {
+            self.count = self.votes.get(english).copied();
+            self.confidence = match (self.weights.get(english), self.count) {
+                (Some(weight), Some(count)) if count > 0 => {
+                    Some((weight / count as f64 * 100.0).round() / 100.0)
+                }
+                _ => None,
+            };
             return;
         }
 
-        // Find the translation with the highest vote count.
-        // On tie, prefer the current best for stability.
-        let mut best_english: Option<&String> = None;
-        let mut best_count: u32 = 0;
-
-        for (english, &count) in &self.votes {
-            if count > best_count || (count == best_count && self.english.as_ref() != Some(english))
-            {
-                // Take this one if it has more votes, or same votes but we have no current best
-                if count > best_count {
-                    best_english = Some(english);
-                    best_count = count;
-                }
-            }
-        }
+        // Highest weighted votes wins; on a tie, keep the current best for
+        // stability, then take the alphabetically first
+        let best = self
+            .votes
+            .keys()
+            .max_by(|a, b| {
+                let is_current = |e: &String| self.english.as_ref() == Some(e);
+                self.weight(a)
+                    .total_cmp(&self.weight(b))
+                    .then_with(|| is_current(a).cmp(&is_current(b)))
+                    .then_with(|| b.cmp(a))
+            })
+            .cloned();
 
-        // If we found a best, or the current best is still valid
-        if let Some(english) = best_english {
-            self.english = Some(english.clone());
-            self.count = Some(best_count);
-        } else if let Some(ref current) = self.english {
-            // Keep current if it's still in votes
-            if let Some(&count) = self.votes.get(current) {
-                self.count = Some(count);
-            } else {
-                // Current best no longer exists, pick any
-                if let Some((english, &count)) = self.votes.iter().next() {
-                    self.english = Some(english.clone());
-                    self.count = Some(count);
-                }
-            }
-        }
+        let Some(english) = best else {
+            self.english = None;
+            self.count = None;
+            self.confidence = None;
+            return;
+        };
+        let count = self.votes[&english];
+        self.confidence = self
+            .weights
+            .get(&english)
+            .map(|w| (w / count as f64 * 100.0).round() / 100.0);
+        self.count = Some(count);
+        self.english = Some(english);
     }
 }
 
 /// The full name mapping data structure.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct NameMappingData {
-    /// Map from original Japanese names to their info.
+    /// Map from original Japanese names to their info. Saved least
+    /// confident first, so dubious entries top the file during review.
+    #[serde(serialize_with = "serialize_least_confident_first")]
     pub names: HashMap<String, NameInfo>,
     /// List of chapter numbers that have been scouted.
     pub coverage: Vec<u32>,
+    /// How far scouting of a chapter got before the run was interrupted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scout_progress: Option<ScoutProgress>,
+}
+
+/// Chunks of a chapter already scouted, so an interrupted run can pick up
+/// where it stopped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoutProgress {
+    /// Chapter being scouted.
+    pub chapter: u32,
+    /// Leading chunks whose names have been recorded.
+    pub chunks_done: usize,
+    /// SHA-256 of those chunks' text, to tell whether the chapter or the
+    /// scout's chunk size changed since.
+    pub chunks_hash: String,
+}
+
+/// Serializes names ordered by confidence (unknown counts as certain), then
+/// by original.
+fn serialize_least_confident_first<S: Serializer>(
+    names: &HashMap<String, NameInfo>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut sorted: Vec<(&String, &NameInfo)> = names.iter().collect();
+    sorted.sort_by(|(a_name, a), (b_name, b)| {
+        let a_confidence = a.confidence.unwrap_or(1.0);
+        let b_confidence = b.confidence.unwrap_or(1.0);
+        a_confidence
+            .total_cmp(&b_confidence)
+            .then_with(|| a_name.cmp(b_name))
+    });
+
+    let mut map = serializer.serialize_map(Some(sorted.len()))?;
+    for (original, info) in sorted {
+        map.serialize_entry(original, info)?;
+    }
+    map.end()
+}
+
+/// Which names were substituted into a text, and what was left over.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplacementReport {
+    /// Times each original name was replaced.
+    pub replaced: BTreeMap<String, u32>,
+    /// Katakana words still in the text after substitution, with their
+    /// counts. Mostly names the mapping doesn't cover, plus some loanwords.
+    pub unmapped: BTreeMap<String, u32>,
+}
+
+impl ReplacementReport {
+    /// Total substitutions made.
+    pub fn replaced_total(&self) -> u32 {
+        self.replaced.values().sum()
+    }
+
+    /// Total unmapped katakana words left.
+    pub fn unmapped_total(&self) -> u32 {
+        self.unmapped.values().sum()
+    }
+
+    /// Whether unmapped words outnumber the substituted names, suggesting
+    /// the mapping is missing most of the chapter's cast.
+    pub fn dominated_by_unmapped(&self) -> bool {
+        let unmapped = self.unmapped_total();
+        unmapped >= UNMAPPED_DOMINANCE_MIN && unmapped > self.replaced_total()
+    }
+}
+
+/// A name whose rendering changed between two versions of a mapping.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameChange {
+    /// Original Japanese name.
+    pub original: String,
+    /// Rendering before, if the name had one.
+    pub before: Option<String>,
+    /// Rendering after, if the name has one.
+    pub after: Option<String>,
+}
+
+impl std::fmt::Display for NameChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.before, &self.after) {
+            (None, Some(after)) => write!(f, "+ {} → {}", self.original, after),
+            (Some(before), None) => write!(f, "- {} ({})", self.original, before),
+            (Some(before), Some(after)) => {
+                write!(f, "~ {}: {} → {}", self.original, before, after)
+            }
+            (None, None) => write!(f, "  {}", self.original),
+        }
+    }
+}
+
+/// Lists names added, removed, re-rendered, locked, or unlocked between
+/// two versions of a mapping, in original-name order.
+pub fn diff(before: &NameMappingData, after: &NameMappingData) -> Vec<NameChange> {
+    let describe = |data: &NameMappingData, original: &str| {
+        let info = data.names.get(original)?;
+        let english = info.english.clone().unwrap_or_else(|| "?".to_string());
+        Some(if info.locked {
+            format!("{} (locked)", english)
+        } else {
+            english
+        })
+    };
+
+    let mut originals: Vec<&String> = before.names.keys().chain(after.names.keys()).collect();
+    originals.sort();
+    originals.dedup();
+    originals
+        .into_iter()
+        .filter_map(|original| {
+            let change = NameChange {
+                original: original.clone(),
+                before: describe(before, original),
+                after: describe(after, original),
+            };
+            (change.before != change.after).then_some(change)
+        })
+        .collect()
+}
+
+/// Checks a name entry against the rules every stored name must pass.
+fn is_acceptable(entry: &NameEntry) -> bool {
+    if entry.original.is_empty() || entry.english.is_empty() {
+        return false;
+    }
+
+    // Skip if original contains bad characters
+    if BAD_ORIGINAL_REGEX.is_match(&entry.original) {
+        return false;
+    }
+
+    // Skip if original is in denylist (e.g. pronouns)
+    if ORIGINAL_NAME_DENYLIST.contains(&entry.original.as_str()) {
+        return false;
+    }
+
+    // Skip if original contains honorifics
+    if HONORIFIC_SUFFIX_REGEX.is_match(&entry.original) {
+        return false;
+    }
+
+    is_acceptable_english(&entry.english, &entry.part)
+}
+
+/// Checks an English rendering: a single word, or single-spaced words for a
+/// full name, without honorifics.
+fn is_acceptable_english(english: &str, part: &NamePart) -> bool {
+    let has_bad_whitespace = if *part == NamePart::Full {
+        english
+            .split(' ')
+            .any(|word| word.is_empty() || word.chars().any(char::is_whitespace))
+    } else {
+        english.chars().any(char::is_whitespace)
+    };
+    if has_bad_whitespace {
+        return false;
+    }
+
+    let english_lower = english.to_lowercase();
+    !ENGLISH_HONORIFICS.iter().any(|h| english_lower.contains(h))
+}
+
+/// Turns a JSON error in a hand-edited mapping file into one that says
+/// where the problem is and what it most likely is.
+fn invalid_json(error: &serde_json::Error, content: &str) -> NameMappingError {
+    let message = error.to_string();
+    // serde_json appends " at line N column M"
+    let message = message
+        .rfind(" at line ")
+        .map_or(message.as_str(), |i| &message[..i]);
+
+    let explanation = if message.starts_with("trailing comma") {
+        "trailing comma after the last item; remove it".to_string()
+    } else if message.starts_with("expected `,` or `}`") {
+        "missing comma between entries (or a stray character after a value)".to_string()
+    } else if message.starts_with("expected `,` or `]`") {
+        "missing comma between list items".to_string()
+    } else if message.starts_with("EOF while parsing") {
+        "the file ends too early; a closing `}` or `]` is missing".to_string()
+    } else if message.starts_with("key must be a string") {
+        "keys must be in double quotes".to_string()
+    } else if message.starts_with("expected value") {
+        "expected a value here (text needs double quotes)".to_string()
+    } else if message.starts_with("control character") {
+        "a string contains a line break or tab; keep each string on one line".to_string()
+    } else if message.starts_with("unknown variant") {
+        "unknown name part; use \"family\", \"given\", \"full\", or \"unknown\"".to_string()
+    } else if let Some(field) = message.strip_prefix("missing field ") {
+        format!("an entry is missing its {} field", field)
+    } else if message.starts_with("invalid type") && message.ends_with("expected u32") {
+        "vote counts and chapter numbers must be whole numbers without quotes".to_string()
+    } else {
+        message.to_string()
+    };
+
+    let line = error.line();
+    NameMappingError::InvalidJson {
+        line,
+        column: error.column(),
+        explanation,
+        source_line: content
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// What [`NameMappingStore::merge`] changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeSummary {
+    /// Names only the other mapping had.
+    pub added: usize,
+    /// Names both mappings had, with votes combined.
+    pub merged: usize,
+    /// Names locked to different translations in each mapping; this
+    /// mapping's translation was kept.
+    pub conflicts: Vec<String>,
+}
+
+/// A name [`NameMappingStore::purge_bad_votes`] removed, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurgedName {
+    /// Original Japanese name.
+    pub original: String,
+    /// Rule the entry broke.
+    pub reason: PurgeReason,
+}
+
+/// Why a name was removed from the mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PurgeReason {
+    /// The original has characters no name contains.
+    BadCharacters,
+    /// The original ends in an honorific (さん, 様, ...).
+    Honorific,
+    /// The original is a pronoun or other common word.
+    Denylisted,
+    /// No acceptable English was left and the entry isn't locked.
+    NoVotes,
+}
+
+impl std::fmt::Display for PurgedName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.reason {
+            PurgeReason::BadCharacters => "has characters a name can't contain",
+            PurgeReason::Honorific => "ends in an honorific",
+            PurgeReason::Denylisted => "is a common word, not a name",
+            PurgeReason::NoVotes => "has no acceptable English left",
+        };
+        write!(f, "{}: {}", self.original, reason)
+    }
 }
 
 /// Name mapping store for a specific novel.
@@ -184,6 +601,12 @@ pub struct NameMappingStore {
     filepath: PathBuf,
     /// The mapping data.
     data: NameMappingData,
+    /// Romanize the reading of kanji names without a confident rendering
+    /// (see [`rendering`](Self::rendering)).
+    romanize_fallback: bool,
+    /// Names purged on the last load, if the file had been edited since
+    /// Tsundoku last saved it.
+    external_edit: Option<Vec<PurgedName>>,
 }
 
 impl NameMappingStore {
@@ -211,6 +634,8 @@ impl NameMappingStore {
         let mut store = Self {
             filepath,
             data: NameMappingData::default(),
+            romanize_fallback: false,
+            external_edit: None,
         };
 
         // Load from disk if file exists
@@ -224,105 +649,204 @@ impl NameMappingStore {
         Ok(store)
     }
 
+    /// Open a mapping file by path (e.g. one copied from another machine).
+    pub fn open(filepath: &Path) -> Result<Self, NameMappingError> {
+        let mut store = Self {
+            filepath: filepath.to_path_buf(),
+            data: NameMappingData::default(),
+            romanize_fallback: false,
+            external_edit: None,
+        };
+        store.reload_from_disk()?;
+        Ok(store)
+    }
+
+    /// Use romanized readings for kanji names the scout couldn't settle on
+    /// an English rendering for.
+    pub fn set_romanize_fallback(&mut self, enabled: bool) {
+        self.romanize_fallback = enabled;
+    }
+
+    /// The English a name is replaced with. With the romanization fallback
+    /// on, a name written in kanji whose rendering is missing or below
+    /// [`LOW_CONFIDENCE`] (and not locked) gets its kana reading in romaji
+    /// instead, so every chunk sees the same spelling.
+    pub fn rendering(&self, original: &str, info: &NameInfo) -> Option<String> {
+        let unsettled = !info.locked
+            && (info.english.is_none() || info.confidence.is_some_and(|c| c < LOW_CONFIDENCE));
+        if self.romanize_fallback && unsettled && !is_kana(original) {
+            let romanized = info.reading.as_deref().and_then(crate::romaji::romanize);
+            if romanized.is_some() {
+                return romanized;
+            }
+        }
+        info.english.clone()
+    }
+
     /// Get the filepath for this store.
     pub fn filepath(&self) -> &Path {
         &self.filepath
     }
 
     /// Record votes from a list of name entries.
+    ///
+    /// A spelling of a name already in the mapping (カイト for 海斗 read
+    /// かいと, ショータ for ショウタ) votes for that name and is kept as one of
+    /// its variants, so votes aren't split across spellings.
     pub fn record_votes(&mut self, entries: &[NameEntry]) {
         for entry in entries {
-            // Validate entry
-            if entry.original.is_empty() || entry.english.is_empty() {
-                continue;
-            }
-
-            // Skip if original contains bad characters
-            if BAD_ORIGINAL_REGEX.is_match(&entry.original) {
-                continue;
-            }
-
-            // Skip if original is in denylist (e.g. pronouns)
-            if ORIGINAL_NAME_DENYLIST.contains(&entry.original.as_str()) {
-                continue;
-            }
-
-            // Skip if english contains whitespace
-            if entry.english.chars().any(|c| c.is_whitespace()) {
-                continue;
-            }
-
-            // Skip if original or english contains honorifics
-            if HONORIFIC_SUFFIX_REGEX.is_match(&entry.original) {
+            if !is_acceptable(entry) {
                 continue;
             }
 
-            let english_lower = entry.english.to_lowercase();
-            if ENGLISH_HONORIFICS.iter().any(|h| english_lower.contains(h)) {
-                continue;
+            let reading = entry
+                .reading
+                .as_deref()
+                .map(str::trim)
+                .filter(|r| is_kana(r));
+            let mut original = entry.original.clone();
+            if let Some(name) = self.variant_of(entry, reading)
+                && let Some(name_info) = self.data.names.get_mut(&name)
+            {
+                if !name_info.variants.contains(&entry.original) {
+                    name_info.variants.push(entry.original.clone());
+                    name_info.variants.sort();
+                }
+                original = name;
             }
 
             // Get or create entry
             let name_info = self
                 .data
                 .names
-                .entry(entry.original.clone())
+                .entry(original)
                 .or_insert_with(|| NameInfo::new(entry.part.clone()));
+            if name_info.reading.is_none()
+                && !is_kana(&entry.original)
+                && let Some(reading) = reading
+            {
+                name_info.reading = Some(reading.to_string());
+            }
 
             // Update part if we have a known part and current is unknown
             if name_info.part == NamePart::Unknown && entry.part != NamePart::Unknown {
                 name_info.part = entry.part.clone();
             }
 
-            // Increment vote count
-            *name_info.votes.entry(entry.english.clone()).or_insert(0) += 1;
+            // Add a vote, weighted by confidence
+            name_info.add_vote(&entry.english, entry.confidence);
 
             // Recalculate best
             name_info.recalculate_best();
         }
     }
 
-    /// Purge bad votes from the mapping.
-    pub fn purge_bad_votes(&mut self) {
+    /// The name an entry is another spelling of: a mapped name or variant
+    /// with the same reading and a compatible part. None if the entry's
+    /// spelling has its own entry or matches nothing.
+    fn variant_of(&self, entry: &NameEntry, reading: Option<&str>) -> Option<String> {
+        if self.data.names.contains_key(&entry.original) {
+            return None;
+        }
+        if let Some((name, _)) = self
+            .data
+            .names
+            .iter()
+            .find(|(_, info)| info.variants.contains(&entry.original))
+        {
+            return Some(name.clone());
+        }
+
+        let key = variant_key(&entry.original, reading)?;
+        let compatible = |part: &NamePart| {
+            *part == entry.part || *part == NamePart::Unknown || entry.part == NamePart::Unknown
+        };
+        let mut matches: Vec<&String> = self
+            .data
+            .names
+            .iter()
+            .filter(|(_, info)| compatible(&info.part))
+            .filter(|(original, info)| {
+                variant_key(original, info.reading.as_deref()).as_ref() == Some(&key)
+            })
+            .map(|(original, _)| original)
+            .collect();
+        // Prefer the kanji spelling, then the first alphabetically, so the
+        // choice doesn't depend on map order
+        matches.sort_by_key(|original| (is_kana(original), original.as_str()));
+        matches.first().map(|original| original.to_string())
+    }
+
+    /// Seed the mapping from an imported glossary: one vote per entry, or
+    /// with `lock`, pin each name to the glossary's rendering.
+    ///
+    /// Returns how many entries were taken; the rest are rejected by the
+    /// same checks as scouted names (e.g. multi-word renderings).
+    pub fn import_entries(&mut self, entries: &[NameEntry], lock: bool) -> usize {
+        let accepted: Vec<NameEntry> = entries
+            .iter()
+            .filter(|e| is_acceptable(e))
+            .cloned()
+            .collect();
+        self.record_votes(&accepted);
+        if lock {
+            for entry in &accepted {
+                if let Some(info) = self.data.names.get_mut(&entry.original) {
+                    info.english = Some(entry.english.clone());
+                    info.locked = true;
+                    info.recalculate_best();
+                }
+            }
+        }
+        accepted.len()
+    }
+
+    /// Purge bad votes from the mapping, returning the names removed
+    /// entirely, in original-name order.
+    pub fn purge_bad_votes(&mut self) -> Vec<PurgedName> {
+        let mut purged = Vec::new();
         // Remove entries with bad original names
         self.data.names.retain(|original, info| {
+            let mut purge = |reason| {
+                purged.push(PurgedName {
+                    original: original.clone(),
+                    reason,
+                });
+                false
+            };
+
             // Check original for bad characters
             if BAD_ORIGINAL_REGEX.is_match(original) {
-                return false;
+                return purge(PurgeReason::BadCharacters);
             }
 
             // Check original for honorific suffix
             if HONORIFIC_SUFFIX_REGEX.is_match(original) {
-                return false;
+                return purge(PurgeReason::Honorific);
             }
 
             // Reject if original is in denylist (e.g. pronouns)
             if ORIGINAL_NAME_DENYLIST.contains(&original.as_str()) {
-                return false;
+                return purge(PurgeReason::Denylisted);
             }
 
-            // Filter out bad votes
-            info.votes.retain(|english, _| {
-                // Reject if english contains whitespace
-                if english.chars().any(|c| c.is_whitespace()) {
-                    return false;
-                }
-
-                // Reject if english contains honorifics
-                let english_lower = english.to_lowercase();
-                if ENGLISH_HONORIFICS.iter().any(|h| english_lower.contains(h)) {
-                    return false;
-                }
-
-                true
-            });
+            // Filter out bad votes (stray whitespace, honorifics)
+            let part = info.part.clone();
+            info.votes
+                .retain(|english, _| is_acceptable_english(english, &part));
+            let votes = &info.votes;
+            info.weights
+                .retain(|english, _| votes.contains_key(english));
 
             // Recalculate best after filtering
             info.recalculate_best();
 
-            // Keep entry if it still has votes
-            !info.votes.is_empty()
+            // Keep entry if it still has votes, or a locked translation
+            let keep = !info.votes.is_empty() || (info.locked && info.english.is_some());
+            keep || purge(PurgeReason::NoVotes)
         });
+        purged.sort_by(|a, b| a.original.cmp(&b.original));
+        purged
     }
 
     /// Check if a chapter has been scouted.
@@ -330,8 +854,86 @@ impl NameMappingStore {
         self.data.coverage.contains(&chapter_number)
     }
 
+    /// Scales the votes of every unlocked entry by `keep` (0 clears them),
+    /// so votes from a re-scout with a better model or prompt can outweigh
+    /// the old ones. Counts are rounded, and each rendering keeps its
+    /// average confidence. Entries left with no votes and nothing written
+    /// by hand are dropped.
+    ///
+    /// Returns the number of entries dropped.
+    pub fn decay_votes(&mut self, keep: f64) -> usize {
+        let before = self.data.names.len();
+        self.data.names.retain(|_, info| {
+            if info.locked {
+                return true;
+            }
+            let votes = std::mem::take(&mut info.votes);
+            let mut weights = HashMap::new();
+            for (english, count) in votes {
+                let kept = (count as f64 * keep).round() as u32;
+                if kept == 0 {
+                    continue;
+                }
+                if let Some(weight) = info.weights.get(&english) {
+                    let weight = weight / count as f64 * kept as f64;
+                    weights.insert(english.clone(), (weight * 100.0).round() / 100.0);
+                }
+                info.votes.insert(english, kept);
+            }
+            info.weights = weights;
+            info.recalculate_best();
+
+            !info.votes.is_empty()
+                || info.notes.is_some()
+                || info.gender.is_some()
+                || info.role.is_some()
+                || info.relationships.is_some()
+        });
+        before - self.data.names.len()
+    }
+
+    /// Forgets which chapters have been scouted, so the next run scouts
+    /// them all again.
+    pub fn clear_coverage(&mut self) {
+        self.data.coverage.clear();
+        self.data.scout_progress = None;
+    }
+
+    /// Leading chunks of `chapter` scouted by an interrupted run, if those
+    /// chunks are still the first of `chunks` (0 otherwise).
+    pub fn scouted_chunks(&self, chapter: u32, chunks: &[String]) -> usize {
+        match &self.data.scout_progress {
+            Some(progress)
+                if progress.chapter == chapter
+                    && progress.chunks_done <= chunks.len()
+                    && progress.chunks_hash == chunks_hash(&chunks[..progress.chunks_done]) =>
+            {
+                progress.chunks_done
+            }
+            _ => 0,
+        }
+    }
+
+    /// Records that the first `done` of `chapter`'s `chunks` have been
+    /// scouted.
+    pub fn record_scouted_chunks(&mut self, chapter: u32, chunks: &[String], done: usize) {
+        self.data.scout_progress = Some(ScoutProgress {
+            chapter,
+            chunks_done: done,
+            chunks_hash: chunks_hash(&chunks[..done]),
+        });
+    }
+
     /// Add chapters to the coverage list.
     pub fn add_coverage(&mut self, chapters: &[u32]) {
+        if self
+            .data
+            .scout_progress
+            .as_ref()
+            .is_some_and(|progress| chapters.contains(&progress.chapter))
+        {
+            self.data.scout_progress = None;
+        }
         let coverage_set: HashSet<u32> = self.data.coverage.iter().copied().collect();
         for &chapter in chapters {
             if !coverage_set.contains(&chapter) {
@@ -344,59 +946,347 @@ impl NameMappingStore {
 
     /// Apply name mappings to text, replacing Japanese names with English.
     /// Replaces longest matches first to handle overlapping names.
+    ///
+    /// Full names made of a mapped family and given name (田中太郎,
+    /// アリス・マーガトロイド) are replaced as one unit, in English order.
     pub fn apply_to_text(&self, text: &str) -> String {
+        self.apply_with_report(text).0
+    }
+
+    /// Like [`apply_to_text`](Self::apply_to_text), also reporting which
+    /// names were substituted and which katakana words were left.
+    pub fn apply_with_report(&self, text: &str) -> (String, ReplacementReport) {
         // Build a list of (original, english) pairs, sorted by length descending
-        let mut replacements: Vec<(&str, &str)> = self
+        let composed = self.composed_names(text);
+        let renderings: Vec<(&String, &NameInfo, String)> = self
             .data
             .names
             .iter()
-            .filter_map(|(original, info)| {
-                info.english
-                    .as_ref()
-                    .map(|english| (original.as_str(), english.as_str()))
+            .filter_map(|(original, info)| Some((original, info, self.rendering(original, info)?)))
+            .collect();
+        let mut replacements: Vec<(&str, &str)> = renderings
+            .iter()
+            .flat_map(|(original, info, english)| {
+                std::iter::once(original.as_str())
+                    .chain(info.variants.iter().map(String::as_str))
+                    .map(move |spelling| (spelling, english.as_str()))
             })
+            .chain(composed.iter().map(|(o, e)| (o.as_str(), e.as_str())))
             .collect();
 
         // Sort by length descending (longest first)
-        replacements.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        replacements.sort_by_key(|r| std::cmp::Reverse(r.0.len()));
 
         // Apply replacements
+        let mut report = ReplacementReport::default();
         let mut result = text.to_string();
         for (original, english) in replacements {
-            result = result.replace(original, english);
+            let count = result.matches(original).count() as u32;
+            if count > 0 {
+                result = result.replace(original, english);
+                *report.replaced.entry(original.to_string()).or_default() += count;
+            }
+        }
+
+        for word in KATAKANA_WORD_REGEX.find_iter(&result) {
+            *report
+                .unmapped
+                .entry(word.as_str().to_string())
+                .or_default() += 1;
+        }
+
+        (result, report)
+    }
+
+    /// Full names in `text` built from a mapped family and given name, with
+    /// their English ("Given Family"). Names with their own entry are left
+    /// to it.
+    fn composed_names(&self, text: &str) -> Vec<(String, String)> {
+        let present = |part: NamePart| -> Vec<(&str, String)> {
+            self.data
+                .names
+                .iter()
+                .filter(|(original, info)| info.part == part && text.contains(original.as_str()))
+                .filter_map(|(original, info)| {
+                    Some((original.as_str(), self.rendering(original, info)?))
+                })
+                .collect()
+        };
+        let families = present(NamePart::Family);
+        let givens = present(NamePart::Given);
+
+        let mut composed = Vec::new();
+        for (family, family_english) in &families {
+            for (given, given_english) in &givens {
+                let english = format!("{} {}", given_english, family_english);
+                let mut forms = vec![format!("{}{}", family, given)];
+                for separator in NAME_SEPARATORS {
+                    forms.push(format!("{}{}{}", family, separator, given));
+                    forms.push(format!("{}{}{}", given, separator, family));
+                }
+                for original in forms {
+                    if text.contains(&original) && !self.data.names.contains_key(&original) {
+                        composed.push((original, english.clone()));
+                    }
+                }
+            }
+        }
+        composed
+    }
+
+    /// Renders the mapped names as a list for the translation prompt, or
+    /// None if no name has an English rendering yet.
+    pub fn prompt_name_list(&self) -> Option<String> {
+        let mut names: Vec<(&String, &NameInfo, String)> = self
+            .data
+            .names
+            .iter()
+            .filter_map(|(original, info)| Some((original, info, self.rendering(original, info)?)))
+            .collect();
+        if names.is_empty() {
+            return None;
+        }
+        names.sort_by_key(|(original, ..)| *original);
+
+        let mut list = String::from(
+            "Character names (Japanese → English). They have already been replaced where they appear verbatim; use the same English for any other form of them (inflected, shortened, or written in kana):",
+        );
+        for (original, info, english) in names {
+            let mut details: Vec<&str> = Vec::new();
+            match info.part {
+                NamePart::Family => details.push("family name"),
+                NamePart::Given => details.push("given name"),
+                NamePart::Full => details.push("full name"),
+                NamePart::Unknown => {}
+            }
+            if let Some(notes) = info
+                .notes
+                .as_deref()
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+            {
+                details.push(notes);
+            }
+            list.push_str(&format!("\n- {} → {}", original, english));
+            if !details.is_empty() {
+                list.push_str(&format!(" ({})", details.join("; ")));
+            }
+        }
+        Some(list)
+    }
+
+    /// Renders the gender, role, and relationships noted on mapped names as
+    /// a character sheet for the translation prompt, or None if no name has
+    /// any.
+    pub fn character_sheet(&self) -> Option<String> {
+        let mut characters: Vec<(String, &String, Vec<&str>)> = self
+            .data
+            .names
+            .iter()
+            .filter_map(|(original, info)| {
+                let english = self.rendering(original, info)?;
+                let details: Vec<&str> = [&info.gender, &info.role, &info.relationships]
+                    .into_iter()
+                    .filter_map(|field| field.as_deref().map(str::trim))
+                    .filter(|field| !field.is_empty())
+                    .collect();
+                (!details.is_empty()).then_some((english, original, details))
+            })
+            .collect();
+        if characters.is_empty() {
+            return None;
+        }
+        characters.sort();
+
+        let mut sheet = String::from(
+            "Character sheet. Use it for pronouns, forms of address, and family terms (brother/sister, older/younger) even where the text leaves them implicit:",
+        );
+        for (english, original, details) in characters {
+            sheet.push_str(&format!(
+                "\n- {} ({}): {}",
+                english,
+                original,
+                details.join("; ")
+            ));
+        }
+        Some(sheet)
+    }
+
+    /// Merge another mapping of the same novel into this one.
+    ///
+    /// Votes and weights are summed, coverage is combined, and locked
+    /// translations carry over. When both sides lock a name to different
+    /// translations, this side wins and the name is reported as a conflict.
+    pub fn merge(&mut self, other: &NameMappingData) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+
+        for (original, theirs) in &other.names {
+            let Some(ours) = self.data.names.get_mut(original) else {
+                self.data.names.insert(original.clone(), theirs.clone());
+                summary.added += 1;
+                continue;
+            };
+            summary.merged += 1;
+
+            for (english, &votes) in &theirs.votes {
+                if ours.weights.contains_key(english) || theirs.weights.contains_key(english) {
+                    let weight = ours.weight(english) + theirs.weight(english);
+                    ours.weights.insert(english.clone(), weight);
+                }
+                *ours.votes.entry(english.clone()).or_insert(0) += votes;
+            }
+
+            if ours.part == NamePart::Unknown {
+                ours.part = theirs.part.clone();
+            }
+            if ours.reading.is_none() {
+                ours.reading = theirs.reading.clone();
+            }
+            for variant in &theirs.variants {
+                if !ours.variants.contains(variant) {
+                    ours.variants.push(variant.clone());
+                }
+            }
+            ours.variants.sort();
+            for (field, their_field) in [
+                (&mut ours.notes, &theirs.notes),
+                (&mut ours.gender, &theirs.gender),
+                (&mut ours.role, &theirs.role),
+                (&mut ours.relationships, &theirs.relationships),
+            ] {
+                if field.is_none() {
+                    *field = their_field.clone();
+                }
+            }
+            if theirs.locked {
+                if !ours.locked {
+                    ours.locked = true;
+                    ours.english = theirs.english.clone();
+                } else if ours.english != theirs.english {
+                    summary.conflicts.push(original.clone());
+                }
+            }
+            ours.recalculate_best();
         }
 
-        result
+        self.add_coverage(&other.coverage);
+        self.purge_bad_votes();
+        summary.conflicts.sort();
+        summary
     }
 
-    /// Save the mapping to disk.
+    /// Save the mapping to disk, recording its checksum so later edits
+    /// made outside Tsundoku can be told apart.
     pub fn save(&self) -> Result<(), NameMappingError> {
+        let content = self.write_to(&self.filepath)?;
+        std::fs::write(
+            self.checksum_path(),
+            crate::manifest::content_hash(&content),
+        )
+        .map_err(|e| NameMappingError::WriteError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Save the mapping to another file.
+    pub fn save_to(&self, filepath: &Path) -> Result<(), NameMappingError> {
+        self.write_to(filepath).map(|_| ())
+    }
+
+    /// Writes the mapping to `filepath`, returning what was written.
+    fn write_to(&self, filepath: &Path) -> Result<String, NameMappingError> {
         // Ensure parent directory exists
-        if let Some(parent) = self.filepath.parent() {
+        if let Some(parent) = filepath.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
         let content = serde_json::to_string_pretty(&self.data)?;
-        std::fs::write(&self.filepath, content)
+        std::fs::write(filepath, &content)
             .map_err(|e| NameMappingError::WriteError(e.to_string()))?;
 
-        Ok(())
+        Ok(content)
     }
 
     /// Reload the mapping from disk.
+    ///
+    /// If the file no longer matches the checksum recorded when Tsundoku
+    /// last saved it, the names the purge removes are kept for
+    /// [`external_edit`](Self::external_edit).
     pub fn reload_from_disk(&mut self) -> Result<(), NameMappingError> {
         let content = std::fs::read_to_string(&self.filepath)?;
-        let data: NameMappingData = serde_json::from_str(&content)?;
+        let data: NameMappingData =
+            serde_json::from_str(&content).map_err(|e| invalid_json(&e, &content))?;
 
         // Validate structure (serde already does this, but we ensure required fields)
         self.data = data;
 
         // Purge bad votes after reload
-        self.purge_bad_votes();
+        let purged = self.purge_bad_votes();
+
+        // Files saved before checksums were recorded count as unedited
+        let edited = std::fs::read_to_string(self.checksum_path())
+            .is_ok_and(|saved| saved.trim() != crate::manifest::content_hash(&content));
+        self.external_edit = edited.then_some(purged);
 
         Ok(())
     }
 
+    /// The names purged on load if the file was edited outside Tsundoku
+    /// since it last saved it (empty if the edit broke no rules), or `None`
+    /// if it wasn't.
+    pub fn external_edit(&self) -> Option<&[PurgedName]> {
+        self.external_edit.as_deref()
+    }
+
+    /// Path of the checksum recorded on each save.
+    fn checksum_path(&self) -> PathBuf {
+        let mut path = self.filepath.clone().into_os_string();
+        path.push(".sha256");
+        PathBuf::from(path)
+    }
+
+    /// Path of the backup taken before a manual edit.
+    pub fn backup_path(&self) -> PathBuf {
+        let mut path = self.filepath.clone().into_os_string();
+        path.push(".bak");
+        PathBuf::from(path)
+    }
+
+    /// Save the mapping as it is now to the backup file.
+    pub fn write_backup(&self) -> Result<(), NameMappingError> {
+        self.save_to(&self.backup_path())
+    }
+
+    /// Put the backup back in place of the mapping file and reload it.
+    pub fn restore_backup(&mut self) -> Result<(), NameMappingError> {
+        std::fs::copy(self.backup_path(), &self.filepath)?;
+        self.reload_from_disk()
+    }
+
+    /// Remove the backup file, if any.
+    pub fn discard_backup(&self) -> Result<(), NameMappingError> {
+        let path = self.backup_path();
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// SHA-256 of the current renderings (each original with its chosen
+    /// English), identifying which version of the mapping a translation
+    /// used. Votes that don't change a rendering leave it unchanged.
+    pub fn version(&self) -> String {
+        let mut names: Vec<String> = self
+            .data
+            .names
+            .iter()
+            .filter_map(|(original, info)| {
+                Some(format!("{}\t{}", original, info.english.as_ref()?))
+            })
+            .collect();
+        names.sort();
+        crate::manifest::content_hash(&names.join("\n"))
+    }
+
     /// Get the number of names in the mapping.
     pub fn len(&self) -> usize {
         self.data.names.len()
@@ -417,6 +1307,15 @@ impl NameMappingStore {
         self.data.names.iter().map(|(k, v)| (k.as_str(), v))
     }
 
+    /// Number of names whose confidence is below [`LOW_CONFIDENCE`].
+    pub fn low_confidence_count(&self) -> usize {
+        self.data
+            .names
+            .values()
+            .filter(|info| info.confidence.is_some_and(|c| c < LOW_CONFIDENCE))
+            .count()
+    }
+
     /// Get the raw data (for testing/debugging).
     pub fn data(&self) -> &NameMappingData {
         &self.data
@@ -448,16 +1347,19 @@ mod tests {
                 original: "田中".to_string(),
                 english: "Tanaka".to_string(),
                 part: NamePart::Family,
+                ..Default::default()
             },
             NameEntry {
                 original: "田中".to_string(),
                 english: "Tanaka".to_string(),
                 part: NamePart::Family,
+                ..Default::default()
             },
             NameEntry {
                 original: "太郎".to_string(),
                 english: "Taro".to_string(),
                 part: NamePart::Given,
+                ..Default::default()
             },
         ]);
 
@@ -480,11 +1382,13 @@ mod tests {
                 original: "田中 太郎".to_string(), // Contains space
                 english: "TanakaTaro".to_string(),
                 part: NamePart::Unknown,
+                ..Default::default()
             },
             NameEntry {
                 original: "田中・太郎".to_string(), // Contains ・
                 english: "TanakaTaro".to_string(),
                 part: NamePart::Unknown,
+                ..Default::default()
             },
         ]);
 
@@ -502,11 +1406,13 @@ mod tests {
                 original: "田中さん".to_string(), // Contains -san
                 english: "Tanaka".to_string(),
                 part: NamePart::Family,
+                ..Default::default()
             },
             NameEntry {
                 original: "田中".to_string(),
                 english: "Tanaka-san".to_string(), // English has honorific
                 part: NamePart::Family,
+                ..Default::default()
             },
         ]);
 
@@ -522,6 +1428,7 @@ mod tests {
             original: "田中".to_string(),
             english: "Tanaka San".to_string(), // Contains space
             part: NamePart::Family,
+            ..Default::default()
         }]);
 
         assert!(store.is_empty());
@@ -537,11 +1444,13 @@ mod tests {
                 original: "彼女".to_string(),
                 english: "Kanojo".to_string(),
                 part: NamePart::Unknown,
+                ..Default::default()
             },
             NameEntry {
                 original: "俺".to_string(),
                 english: "Ore".to_string(),
                 part: NamePart::Unknown,
+                ..Default::default()
             },
         ]);
 
@@ -558,17 +1467,146 @@ mod tests {
                 original: "田中".to_string(),
                 english: "Tanaka".to_string(),
                 part: NamePart::Family,
+                ..Default::default()
             },
             NameEntry {
                 original: "太郎".to_string(),
                 english: "Taro".to_string(),
                 part: NamePart::Given,
+                ..Default::default()
             },
         ]);
 
         let text = "田中太郎は学校に行った。";
         let result = store.apply_to_text(text);
-        assert_eq!(result, "TanakaTaroは学校に行った。");
+        assert_eq!(result, "Taro Tanakaは学校に行った。");
+    }
+
+    #[test]
+    fn test_apply_with_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        store.record_votes(&[
+            NameEntry {
+                original: "アリス".to_string(),
+                english: "Alice".to_string(),
+                part: NamePart::Given,
+                ..Default::default()
+            },
+            NameEntry {
+                original: "魔王".to_string(),
+                english: "Demon Lord".to_string(),
+                part: NamePart::Unknown,
+                ..Default::default()
+            },
+        ]);
+
+        let (text, report) = store.apply_with_report("アリスとボブ。アリスはボブとドアを開けた。");
+        assert_eq!(text, "Aliceとボブ。Aliceはボブとドアを開けた。");
+        assert_eq!(report.replaced.get("アリス"), Some(&2));
+        assert!(!report.replaced.contains_key("魔王"));
+        assert_eq!(report.unmapped.get("ボブ"), Some(&2));
+        assert_eq!(report.unmapped.get("ドア"), Some(&1));
+        assert!(!report.dominated_by_unmapped());
+
+        let (_, report) =
+            store.apply_with_report("ボブ、カール、デイヴ、エミル、フランク。アリス。");
+        assert_eq!(report.unmapped_total(), 5);
+        assert!(report.dominated_by_unmapped());
+    }
+
+    #[test]
+    fn test_prompt_name_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        assert_eq!(store.prompt_name_list(), None);
+
+        store.record_votes(&[
+            NameEntry {
+                original: "田中".to_string(),
+                english: "Tanaka".to_string(),
+                part: NamePart::Family,
+                ..Default::default()
+            },
+            NameEntry {
+                original: "ユキ".to_string(),
+                english: "Yuki".to_string(),
+                part: NamePart::Unknown,
+                ..Default::default()
+            },
+        ]);
+        store.data.names.get_mut("ユキ").unwrap().notes = Some("female, a cat".to_string());
+
+        let list = store.prompt_name_list().unwrap();
+        let lines: Vec<&str> = list.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "- ユキ → Yuki (female, a cat)",
+                "- 田中 → Tanaka (family name)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_character_sheet() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        store.record_votes(&[
+            NameEntry {
+                original: "太郎".to_string(),
+                english: "Taro".to_string(),
+                part: NamePart::Given,
+                ..Default::default()
+            },
+            NameEntry {
+                original: "花子".to_string(),
+                english: "Hanako".to_string(),
+                part: NamePart::Given,
+                ..Default::default()
+            },
+        ]);
+        assert_eq!(store.character_sheet(), None);
+
+        let taro = store.data.names.get_mut("太郎").unwrap();
+        taro.gender = Some("male".to_string());
+        taro.relationships = Some(" Hanako's older brother ".to_string());
+        store.data.names.get_mut("花子").unwrap().role = Some(String::new());
+
+        let sheet = store.character_sheet().unwrap();
+        let lines: Vec<&str> = sheet.lines().skip(1).collect();
+        assert_eq!(lines, vec!["- Taro (太郎): male; Hanako's older brother"]);
+    }
+
+    #[test]
+    fn test_full_name_composition() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+
+        let entry = |original: &str, english: &str, part: NamePart| NameEntry {
+            original: original.to_string(),
+            english: english.to_string(),
+            part,
+            ..Default::default()
+        };
+        store.record_votes(&[
+            entry("アリス", "Alice", NamePart::Given),
+            entry("マーガトロイド", "Margatroid", NamePart::Family),
+            entry("佐藤", "Sato", NamePart::Family),
+            entry("花子", "Hanako", NamePart::Given),
+            entry("佐藤花子", "Hana Sato", NamePart::Full),
+            entry("山田", "Yamada Ichiro", NamePart::Family),
+            entry("一郎", "Ichiro  Yamada", NamePart::Full),
+        ]);
+        // Spaces are only allowed, singly, in full names
+        assert!(!store.data.names.contains_key("山田"));
+        assert!(!store.data.names.contains_key("一郎"));
+
+        let text = "アリス・マーガトロイドと佐藤花子。アリスと佐藤";
+        assert_eq!(
+            store.apply_to_text(text),
+            "Alice MargatroidとHana Sato。AliceとSato"
+        );
     }
 
     #[test]
@@ -581,11 +1619,13 @@ mod tests {
                 original: "田".to_string(),
                 english: "Ta".to_string(),
                 part: NamePart::Unknown,
+                ..Default::default()
             },
             NameEntry {
                 original: "田中".to_string(),
                 english: "Tanaka".to_string(),
                 part: NamePart::Family,
+                ..Default::default()
             },
         ]);
 
@@ -615,6 +1655,70 @@ mod tests {
         assert_eq!(store.coverage(), &[1, 2, 3, 5]);
     }
 
+    #[test]
+    fn test_decay_votes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        let vote = |original: &str, english: &str, confidence: Option<f64>| NameEntry {
+            original: original.to_string(),
+            english: english.to_string(),
+            part: NamePart::Given,
+            confidence,
+            ..Default::default()
+        };
+        for _ in 0..4 {
+            store.record_votes(&[vote("翔太", "Shouta", Some(0.8))]);
+        }
+        store.record_votes(&[vote("翔太", "Shota", None), vote("健", "Ken", None)]);
+        store.record_votes(&[vote("花子", "Hanako", None)]);
+        store.data.names.get_mut("花子").unwrap().locked = true;
+        store.add_coverage(&[1, 2]);
+
+        assert_eq!(store.decay_votes(0.5), 0);
+        let shota = &store.data().names["翔太"];
+        assert_eq!(shota.votes["Shouta"], 2);
+        assert_eq!(shota.confidence, Some(0.8));
+        assert_eq!(shota.votes["Shota"], 1);
+
+        // A better model's votes now win
+        for _ in 0..2 {
+            store.record_votes(&[vote("翔太", "Shota", None)]);
+        }
+        assert_eq!(store.data().names["翔太"].english.as_deref(), Some("Shota"));
+
+        store.data.names.get_mut("健").unwrap().gender = Some("male".to_string());
+        assert_eq!(store.decay_votes(0.0), 1);
+        assert!(!store.data().names.contains_key("翔太"));
+        assert!(store.data().names["健"].votes.is_empty());
+        assert_eq!(
+            store.data().names["花子"].english.as_deref(),
+            Some("Hanako")
+        );
+
+        store.clear_coverage();
+        assert!(store.coverage().is_empty());
+    }
+
+    #[test]
+    fn test_scout_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        let chunks: Vec<String> = ["一つ目", "二つ目", "三つ目"].map(String::from).to_vec();
+
+        store.record_scouted_chunks(7, &chunks, 2);
+        store.save().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        assert_eq!(store.scouted_chunks(7, &chunks), 2);
+        assert_eq!(store.scouted_chunks(8, &chunks), 0);
+
+        // The chapter's text changed since
+        let edited: Vec<String> = ["一つ目", "改稿", "三つ目"].map(String::from).to_vec();
+        assert_eq!(store.scouted_chunks(7, &edited), 0);
+
+        store.add_coverage(&[7]);
+        assert_eq!(store.scouted_chunks(7, &chunks), 0);
+    }
+
     #[test]
     fn test_save_and_reload() {
         let temp_dir = TempDir::new().unwrap();
@@ -624,6 +1728,7 @@ mod tests {
             original: "田中".to_string(),
             english: "Tanaka".to_string(),
             part: NamePart::Family,
+            ..Default::default()
         }]);
         store.add_coverage(&[1, 2, 3]);
         store.save().unwrap();
@@ -632,6 +1737,231 @@ mod tests {
         let store2 = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
         assert_eq!(store2.len(), 1);
         assert!(store2.is_chapter_covered(2));
+        assert_eq!(store2.external_edit(), None);
+    }
+
+    #[test]
+    fn test_external_edit() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        store.record_votes(&[NameEntry {
+            original: "田中".to_string(),
+            english: "Tanaka".to_string(),
+            part: NamePart::Family,
+            ..Default::default()
+        }]);
+        store.save().unwrap();
+
+        // Hand-added entries that break the rules are purged and reported
+        let added = concat!(
+            "\"names\": {\n",
+            "    \"佐藤さん\": {\"part\": \"family\", \"votes\": {\"Sato\": 1}},\n",
+            "    \"鈴木\": {\"part\": \"family\", \"votes\": {\"Suzuki-san\": 1}},\n",
+        );
+        let edited =
+            std::fs::read_to_string(store.filepath())
+                .unwrap()
+                .replacen("\"names\": {\n", added, 1);
+        std::fs::write(store.filepath(), edited).unwrap();
+        let store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        assert_eq!(store.len(), 1);
+        let purged = store.external_edit().unwrap();
+        assert_eq!(
+            purged,
+            [
+                PurgedName {
+                    original: "佐藤さん".to_string(),
+                    reason: PurgeReason::Honorific,
+                },
+                PurgedName {
+                    original: "鈴木".to_string(),
+                    reason: PurgeReason::NoVotes,
+                },
+            ]
+        );
+        assert_eq!(purged[0].to_string(), "佐藤さん: ends in an honorific");
+
+        // Saving records the cleaned-up file
+        store.save().unwrap();
+        let store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        assert_eq!(store.external_edit(), None);
+
+        // Mappings saved before checksums were kept aren't flagged
+        std::fs::remove_file(store.checksum_path()).unwrap();
+        std::fs::write(store.filepath(), "{\"names\": {}, \"coverage\": []}").unwrap();
+        let store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        assert_eq!(store.external_edit(), None);
+    }
+
+    #[test]
+    fn test_confidence_weighted_votes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+
+        let entry = |original: &str, english: &str, confidence: Option<f64>| NameEntry {
+            original: original.to_string(),
+            english: english.to_string(),
+            part: NamePart::Given,
+            confidence,
+            ..Default::default()
+        };
+        store.record_votes(&[
+            entry("優子", "Yuuko", Some(0.2)),
+            entry("優子", "Yuuko", Some(0.2)),
+            entry("優子", "Yuko", Some(0.9)),
+            entry("太郎", "Taro", None),
+        ]);
+
+        // One confident vote beats two doubtful ones
+        let yuko = store.data.names.get("優子").unwrap();
+        assert_eq!(yuko.english.as_deref(), Some("Yuko"));
+        assert_eq!(yuko.count, Some(1));
+        assert_eq!(yuko.confidence, Some(0.9));
+        assert_eq!(yuko.votes["Yuuko"], 2);
+        assert_eq!(store.data.names["太郎"].confidence, None);
+
+        store.record_votes(&[entry("花子", "Hanako", Some(0.3))]);
+        assert_eq!(store.low_confidence_count(), 1);
+
+        // Least confident first, unknown confidence last
+        store.save().unwrap();
+        let json = std::fs::read_to_string(store.filepath()).unwrap();
+        let positions: Vec<usize> = ["花子", "優子", "太郎"]
+            .iter()
+            .map(|name| json.find(&format!("\"{}\"", name)).unwrap())
+            .collect();
+        assert!(positions.is_sorted());
+    }
+
+    #[test]
+    fn test_merge() {
+        let temp_dir = TempDir::new().unwrap();
+        let entry = |original: &str, english: &str| NameEntry {
+            original: original.to_string(),
+            english: english.to_string(),
+            part: NamePart::Unknown,
+            ..Default::default()
+        };
+
+        let mut ours = NameMappingStore::new(temp_dir.path(), "syosetu", "a").unwrap();
+        ours.record_votes(&[entry("優子", "Yuuko"), entry("太郎", "Taro")]);
+        ours.add_coverage(&[1, 2]);
+        let taro = ours.data.names.get_mut("太郎").unwrap();
+        taro.english = Some("Tarou".to_string());
+        taro.locked = true;
+
+        let mut theirs = NameMappingStore::new(temp_dir.path(), "syosetu", "b").unwrap();
+        theirs.record_votes(&[
+            entry("優子", "Yuko"),
+            entry("優子", "Yuko"),
+            entry("太郎", "Taro"),
+            entry("花子", "Hanako"),
+        ]);
+        theirs.add_coverage(&[2, 3]);
+        let taro = theirs.data.names.get_mut("太郎").unwrap();
+        taro.locked = true;
+
+        let summary = ours.merge(theirs.data());
+        assert_eq!(summary.added, 1);
+        assert_eq!(summary.merged, 2);
+        assert_eq!(summary.conflicts, vec!["太郎".to_string()]);
+        assert_eq!(ours.coverage(), &[1, 2, 3]);
+
+        let yuko = &ours.data.names["優子"];
+        assert_eq!(yuko.english.as_deref(), Some("Yuko"));
+        assert_eq!(yuko.votes["Yuuko"], 1);
+        // Locked translations survive the combined votes
+        let taro = &ours.data.names["太郎"];
+        assert_eq!(taro.english.as_deref(), Some("Tarou"));
+        assert_eq!(taro.votes["Taro"], 2);
+        assert_eq!(taro.count, None);
+
+        let merged_path = temp_dir.path().join("merged.json");
+        ours.save_to(&merged_path).unwrap();
+        let reopened = NameMappingStore::open(&merged_path).unwrap();
+        assert_eq!(reopened.len(), 3);
+        assert!(reopened.data().names["太郎"].locked);
+    }
+
+    #[test]
+    fn test_invalid_json_explained() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        store.record_votes(&[NameEntry {
+            original: "田中".to_string(),
+            english: "Tanaka".to_string(),
+            part: NamePart::Family,
+            ..Default::default()
+        }]);
+        store.save().unwrap();
+        store.write_backup().unwrap();
+
+        let edited = "{\n  \"names\": {},\n  \"coverage\": [1, 2,]\n}\n";
+        std::fs::write(store.filepath(), edited).unwrap();
+        match store.reload_from_disk() {
+            Err(NameMappingError::InvalidJson {
+                line,
+                explanation,
+                source_line,
+                ..
+            }) => {
+                assert_eq!(line, 3);
+                assert!(explanation.starts_with("trailing comma"));
+                assert_eq!(source_line, "  \"coverage\": [1, 2,]");
+            }
+            other => panic!("expected InvalidJson, got {:?}", other.err()),
+        }
+
+        let edited =
+            r#"{"names": {"田中": {"part": "family", "votes": {"Tanaka": "2"}}}, "coverage": []}"#;
+        std::fs::write(store.filepath(), edited).unwrap();
+        let error = store.reload_from_disk().unwrap_err().to_string();
+        assert!(error.contains("must be whole numbers"), "{}", error);
+
+        // The pre-edit mapping comes back from the backup
+        store.restore_backup().unwrap();
+        assert_eq!(
+            store.data().names["田中"].english.as_deref(),
+            Some("Tanaka")
+        );
+        store.discard_backup().unwrap();
+        assert!(!store.backup_path().exists());
+    }
+
+    #[test]
+    fn test_diff() {
+        let info = |english: &str, locked: bool| NameInfo {
+            english: Some(english.to_string()),
+            locked,
+            ..NameInfo::new(NamePart::Given)
+        };
+        let mut before = NameMappingData::default();
+        before.names.insert("太郎".to_string(), info("Taro", false));
+        before
+            .names
+            .insert("花子".to_string(), info("Hanako", false));
+        before
+            .names
+            .insert("優子".to_string(), info("Yuuko", false));
+
+        let mut after = before.clone();
+        after.names.remove("花子");
+        after.names.insert("優子".to_string(), info("Yuko", true));
+        after.names.insert("ユキ".to_string(), info("Yuki", false));
+
+        let changes: Vec<String> = diff(&before, &after)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "+ ユキ → Yuki",
+                "~ 優子: Yuuko → Yuko (locked)",
+                "- 花子 (Hanako)",
+            ]
+        );
+        assert!(diff(&before, &before).is_empty());
     }
 
     #[test]
@@ -645,16 +1975,19 @@ mod tests {
                 original: "優子".to_string(),
                 english: "Yuko".to_string(),
                 part: NamePart::Given,
+                ..Default::default()
             },
             NameEntry {
                 original: "優子".to_string(),
                 english: "Yuuko".to_string(),
                 part: NamePart::Given,
+                ..Default::default()
             },
             NameEntry {
                 original: "優子".to_string(),
                 english: "Yuko".to_string(),
                 part: NamePart::Given,
+                ..Default::default()
             },
         ]);
 
@@ -662,4 +1995,88 @@ mod tests {
         assert_eq!(info.english, Some("Yuko".to_string())); // Yuko has 2 votes
         assert_eq!(info.count, Some(2));
     }
+
+    #[test]
+    fn test_variants_share_votes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        let entry = |original: &str, english: &str, reading: Option<&str>| NameEntry {
+            original: original.to_string(),
+            english: english.to_string(),
+            part: NamePart::Given,
+            reading: reading.map(str::to_string),
+            ..Default::default()
+        };
+
+        store.record_votes(&[
+            entry("海斗", "Kaito", Some("かいと")),
+            entry("カイト", "Kaito", None),
+            entry("ショウタ", "Shouta", None),
+            entry("ショータ", "Shota", None),
+            entry("しょうた", "Shota", None),
+            // Two-kana keys (ユキ) are too short to match on
+            entry("ユウキ", "Yuuki", None),
+            entry("ユーキ", "Yuki", None),
+            entry("ユキ", "Yuki", None),
+            entry("カイ", "Kai", None),
+        ]);
+
+        assert_eq!(store.len(), 6);
+        let kaito = &store.data.names["海斗"];
+        assert_eq!(kaito.reading.as_deref(), Some("かいと"));
+        assert_eq!(kaito.variants, vec!["カイト"]);
+        assert_eq!(kaito.count, Some(2));
+        let shota = &store.data.names["ショウタ"];
+        assert_eq!(shota.variants, vec!["しょうた", "ショータ"]);
+        assert_eq!(shota.english.as_deref(), Some("Shota"));
+        for name in ["ユウキ", "ユーキ", "ユキ"] {
+            assert!(store.data.names[name].variants.is_empty());
+        }
+
+        // A kanji spelling found later takes in the kana one's votes too
+        store.record_votes(&[entry("カイト", "Kite", None)]);
+        assert_eq!(store.data.names["海斗"].votes["Kite"], 1);
+
+        assert_eq!(
+            store.apply_to_text("海斗とカイトとショータとユウキ"),
+            "KaitoとKaitoとShotaとYuuki"
+        );
+        assert_eq!(reading_key("コオリ"), "コリ");
+        assert_eq!(reading_key("ショウタ"), "ショタ");
+    }
+
+    #[test]
+    fn test_romanize_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        let entry = |original: &str, english: &str, confidence: f64| NameEntry {
+            original: original.to_string(),
+            english: english.to_string(),
+            part: NamePart::Given,
+            confidence: Some(confidence),
+            reading: Some("しょうた".to_string()),
+        };
+        store.record_votes(&[
+            entry("翔太", "Shouta", 0.3),
+            NameEntry {
+                reading: Some("けん".to_string()),
+                ..entry("健", "Ken", 0.9)
+            },
+            // Katakana names are usually foreign; romaji would be wrong
+            NameEntry {
+                reading: None,
+                ..entry("アリス", "Alice", 0.2)
+            },
+        ]);
+
+        let text = "翔太と健とアリス";
+        assert_eq!(store.apply_to_text(text), "ShoutaとKenとAlice");
+        store.set_romanize_fallback(true);
+        assert_eq!(store.apply_to_text(text), "ShotaとKenとAlice");
+
+        // A locked rendering is kept
+        let info = store.data.names.get_mut("翔太").unwrap();
+        info.locked = true;
+        assert_eq!(store.apply_to_text(text), "ShoutaとKenとAlice");
+    }
 }
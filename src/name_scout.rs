@@ -1,16 +1,24 @@
 //! Name Scout - Extract character names from Japanese text using LLM.
 //!
 //! Uses a secondary LLM pass to identify character names in Japanese text
-//! and extract them with English translations.
+//! and extract them with English translations. Built with the terminology
+//! prompt, the same scout lists places, skills, and other terms for
+//! [terminology reports](crate::terminology).
 
-use crate::config::{ApiConfig, NameScoutConfig};
+use crate::api_limiter::ApiLimiter;
+use crate::cache::ResponseCache;
+use crate::config::{ApiConfig, NameScoutConfig, NudgeRole, PromptsConfig};
 use crate::console::Console;
-use crate::error::TranslationError;
+use crate::error::{RetryKind, TranslationError};
 use crate::name_mapping::{NameEntry, NamePart};
+use crate::terminology::{Term, TermCategory};
+use crate::utils::{CancellationToken, ChunkStrategy, Chunker};
+use futures::StreamExt;
 use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::sync::LazyLock;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 
 /// Regex to extract JSON from markdown code fences.
@@ -36,6 +44,8 @@ static REFUSAL_PHRASES: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
 struct ChatRequest {
     model: String,
     messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
 }
 
 /// A message in the conversation.
@@ -69,6 +79,10 @@ struct ParsedNameEntry {
     original: Option<String>,
     english: Option<String>,
     part: Option<String>,
+    /// Usually a number, but some models quote it.
+    confidence: Option<serde_json::Value>,
+    /// Kana reading, for names written in kanji.
+    reading: Option<String>,
 }
 
 /// Parsed names response from LLM.
@@ -77,6 +91,20 @@ struct ParsedNamesResponse {
     names: Vec<ParsedNameEntry>,
 }
 
+/// Parsed term entry from LLM response.
+#[derive(Debug, Deserialize)]
+struct ParsedTermEntry {
+    original: Option<String>,
+    english: Option<String>,
+    category: Option<String>,
+}
+
+/// Parsed terms response from LLM.
+#[derive(Debug, Deserialize)]
+struct ParsedTermsResponse {
+    terms: Vec<ParsedTermEntry>,
+}
+
 /// Name Scout for extracting character names from Japanese text.
 pub struct NameScout {
     /// HTTP client for API requests.
@@ -87,124 +115,393 @@ pub struct NameScout {
     scout_config: NameScoutConfig,
     /// System prompt for name extraction.
     prompt: String,
+    /// Sent along when retrying a refused chunk (empty for none).
+    refusal_nudge: String,
+    /// How the refusal nudge is sent.
+    refusal_nudge_role: NudgeRole,
     /// Console for output.
     console: Console,
+    /// Token checked between chunk requests.
+    cancellation: CancellationToken,
+    /// Optional cache of raw model responses.
+    cache: Option<Arc<dyn ResponseCache>>,
+    /// Spaces request starts with other clients on the same API key.
+    api_limiter: Option<ApiLimiter>,
+}
+
+/// Builder for [`NameScout`] with optional customization.
+pub struct NameScoutBuilder {
+    api_config: ApiConfig,
+    scout_config: NameScoutConfig,
+    prompt: Option<String>,
+    refusal_nudge: Option<(String, NudgeRole)>,
+    client: Option<Client>,
+    console: Option<Console>,
+    cancellation: Option<CancellationToken>,
+    cache: Option<Arc<dyn ResponseCache>>,
+    api_limiter: Option<ApiLimiter>,
+}
+
+impl NameScoutBuilder {
+    /// Sets the system prompt used for name extraction.
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Sets the nudge sent along when retrying a refused chunk (empty to
+    /// retry as-is).
+    pub fn refusal_nudge(mut self, nudge: impl Into<String>, role: NudgeRole) -> Self {
+        self.refusal_nudge = Some((nudge.into(), role));
+        self
+    }
+
+    /// Uses a custom HTTP client.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Uses a custom console for status messages.
+    pub fn console(mut self, console: Console) -> Self {
+        self.console = Some(console);
+        self
+    }
+
+    /// Allows scouting to be cancelled from another task.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Answers repeated chunks from the given cache.
+    pub fn cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Spaces requests together with other clients given the same limiter
+    /// on the same API key (see [`crate::api_limiter`]).
+    pub fn api_limiter(mut self, limiter: ApiLimiter) -> Self {
+        self.api_limiter = Some(limiter);
+        self
+    }
+
+    /// Builds the name scout.
+    pub fn build(self) -> NameScout {
+        let prompts = PromptsConfig::default();
+        let refusal_nudge = self
+            .refusal_nudge
+            .unwrap_or((prompts.scout_refusal_nudge, prompts.refusal_nudge_role));
+        NameScout {
+            client: self.client.unwrap_or_default(),
+            api_config: self.api_config,
+            scout_config: self.scout_config,
+            prompt: self.prompt.unwrap_or(prompts.name_scout),
+            refusal_nudge: refusal_nudge.0,
+            refusal_nudge_role: refusal_nudge.1,
+            console: self.console.unwrap_or_default(),
+            cancellation: self.cancellation.unwrap_or_default(),
+            cache: self.cache,
+            api_limiter: self.api_limiter,
+        }
+    }
 }
 
 impl NameScout {
     /// Create a new NameScout.
     pub fn new(api_config: ApiConfig, scout_config: NameScoutConfig, prompt: String) -> Self {
-        Self {
-            client: Client::new(),
+        Self::builder(api_config, scout_config)
+            .prompt(prompt)
+            .build()
+    }
+
+    /// Start building a NameScout with optional customization.
+    pub fn builder(api_config: ApiConfig, scout_config: NameScoutConfig) -> NameScoutBuilder {
+        NameScoutBuilder {
             api_config,
             scout_config,
-            prompt,
-            console: Console::new(),
+            prompt: None,
+            refusal_nudge: None,
+            client: None,
+            console: None,
+            cancellation: None,
+            cache: None,
+            api_limiter: None,
         }
     }
 
     /// Collect names from text, processing in chunks.
     ///
+    /// Up to `name_scout.concurrency` chunks are scouted at once; results
+    /// keep the chunks' order.
+    ///
     /// Returns a vector of name entry vectors, one per successfully processed chunk.
     pub async fn collect_names(&self, text: &str) -> Vec<Vec<NameEntry>> {
+        self.collect(text, "names", |raw| self.parse_response(raw))
+            .await
+    }
+
+    /// Collect names, places, skills, and other terms from text, processing
+    /// in chunks like [`collect_names`](Self::collect_names).
+    ///
+    /// The scout must be built with a prompt asking for the JSON shape of
+    /// `prompts.terminology`.
+    pub async fn collect_terms(&self, text: &str) -> Vec<Vec<Term>> {
+        self.collect(text, "terms", parse_terms).await
+    }
+
+    /// Scouts the chunks of [`name_chunks`](Self::name_chunks) after the
+    /// first `skip`, handing each chunk's index and names (none if it
+    /// failed every attempt) to `on_chunk` in order, as soon as it and the
+    /// chunks before it are done. A caller can save its progress from there
+    /// and resume an interrupted chapter where it stopped.
+    ///
+    /// Returns whether every chunk was scouted (false if cancelled), or the
+    /// first error `on_chunk` returned.
+    pub async fn scout_names_each<E>(
+        &self,
+        chunks: &[String],
+        skip: usize,
+        on_chunk: impl FnMut(usize, Vec<NameEntry>) -> Result<(), E>,
+    ) -> Result<bool, E> {
+        self.scout_each(
+            chunks,
+            skip,
+            "names",
+            &|raw| self.parse_response(raw),
+            on_chunk,
+        )
+        .await
+    }
+
+    /// The chunks [`collect_names`](Self::collect_names) splits text into.
+    pub fn name_chunks(&self, text: &str) -> Vec<String> {
+        self.split_into_chunks(text)
+    }
+
+    /// Scouts text chunk by chunk, parsing each response with `parse`.
+    async fn collect<T>(
+        &self,
+        text: &str,
+        label: &str,
+        parse: impl Fn(&str) -> Result<Vec<T>, TranslationError>,
+    ) -> Vec<Vec<T>> {
         let chunks = self.split_into_chunks(text);
-        let total_chunks = chunks.len();
         let mut results = Vec::new();
+        let _ = self
+            .scout_each(&chunks, 0, label, &parse, |_, entries| {
+                if !entries.is_empty() {
+                    results.push(entries);
+                }
+                Ok::<_, std::convert::Infallible>(())
+            })
+            .await;
+        results
+    }
 
-        for (i, chunk) in chunks.iter().enumerate() {
-            let chunk_num = i + 1;
-            self.console.info(&format!(
-                "Name scout chunk {}/{} ({} chars)",
-                chunk_num,
-                total_chunks,
-                chunk.len()
-            ));
+    /// Scouts `chunks` after the first `skip`, up to `name_scout.concurrency`
+    /// at once, handing the results to `on_chunk` in the chunks' order.
+    async fn scout_each<T, E>(
+        &self,
+        chunks: &[String],
+        skip: usize,
+        label: &str,
+        parse: &impl Fn(&str) -> Result<Vec<T>, TranslationError>,
+        mut on_chunk: impl FnMut(usize, Vec<T>) -> Result<(), E>,
+    ) -> Result<bool, E> {
+        let total_chunks = chunks.len();
+        let concurrency = self.scout_config.concurrency.max(1);
+
+        let mut results = futures::stream::iter(chunks.iter().enumerate().skip(skip))
+            .map(|(i, chunk)| async move {
+                (
+                    i,
+                    self.scout_chunk(chunk, i + 1, total_chunks, label, parse)
+                        .await,
+                )
+            })
+            .buffered(concurrency);
+        while let Some((i, entries)) = results.next().await {
+            // A chunk cut short by cancellation wasn't scouted
+            if entries.is_none() && self.cancellation.is_cancelled() {
+                self.console.warning("Name scout cancelled");
+                return Ok(false);
+            }
+            on_chunk(i, entries.unwrap_or_default())?;
+        }
+        Ok(true)
+    }
 
-            // Retry loop for JSON parsing
-            let mut attempt = 0;
-            let mut success = false;
-
-            while attempt < self.scout_config.json_retries && !success {
-                // Call the model
-                match self.call_model(chunk).await {
-                    Ok(raw_response) => {
-                        // Check for refusal
-                        let lower = raw_response.to_lowercase();
-                        if REFUSAL_PHRASES.iter().any(|p| lower.starts_with(p)) {
-                            self.console.warning(&format!(
-                                "Model refused to process chunk {}, retrying...",
-                                chunk_num
-                            ));
-                            attempt += 1;
-                            tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
-                            continue;
-                        }
+    /// Scout one chunk, retrying refusals, bad JSON, and retryable API
+    /// errors. Returns None if the chunk failed every attempt or the scout
+    /// was cancelled.
+    async fn scout_chunk<T>(
+        &self,
+        chunk: &str,
+        chunk_num: usize,
+        total_chunks: usize,
+        label: &str,
+        parse: &impl Fn(&str) -> Result<Vec<T>, TranslationError>,
+    ) -> Option<Vec<T>> {
+        if self.cancellation.is_cancelled() {
+            return None;
+        }
 
-                        // Parse the response
-                        match self.parse_response(&raw_response) {
+        self.console.info(&format!(
+            "Name scout chunk {}/{} ({} chars)",
+            chunk_num,
+            total_chunks,
+            chunk.len()
+        ));
+
+        let policy = &self.scout_config.retry;
+        let mut failures: HashMap<RetryKind, u32> = HashMap::new();
+        let mut attempt = 0;
+        let mut nudge = false;
+
+        loop {
+            // Call the model
+            let (kind, problem) = match self.call_model(chunk, nudge).await {
+                Ok(raw_response) => {
+                    // Check for refusal, then parse the response
+                    let lower = raw_response.to_lowercase();
+                    if REFUSAL_PHRASES.iter().any(|p| lower.starts_with(p)) {
+                        (
+                            RetryKind::Refusal,
+                            format!("Model refused to process chunk {}", chunk_num),
+                        )
+                    } else {
+                        match parse(&raw_response) {
                             Ok(entries) => {
+                                if let Some(cache) = &self.cache {
+                                    cache.put(self.model(), &self.prompt, chunk, &raw_response);
+                                }
                                 if !entries.is_empty() {
                                     self.console.success(&format!(
-                                        "Found {} names in chunk {}",
+                                        "Found {} {} in chunk {}",
                                         entries.len(),
+                                        label,
                                         chunk_num
                                     ));
-                                    results.push(entries);
                                 }
-                                success = true;
-                            }
-                            Err(e) => {
-                                self.console.warning(&format!(
-                                    "Failed to parse JSON from chunk {}: {}, retrying...",
-                                    chunk_num, e
-                                ));
-                                attempt += 1;
-                                tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+                                return Some(entries);
                             }
+                            Err(e) => (
+                                RetryKind::Parse,
+                                format!("Failed to parse JSON from chunk {}: {}", chunk_num, e),
+                            ),
                         }
                     }
-                    Err(e) => {
-                        self.console.warning(&format!(
-                            "API error for chunk {}: {}, retrying...",
+                }
+                Err(e) => match e.retry_kind() {
+                    Some(kind) => (kind, format!("API error for chunk {}: {}", chunk_num, e)),
+                    None => {
+                        self.console.error(&format!(
+                            "API error for chunk {}: {}, not retrying",
                             chunk_num, e
                         ));
-                        attempt += 1;
-                        tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+                        return None;
                     }
-                }
+                },
+            };
+
+            attempt += 1;
+            let default_limit = match kind {
+                RetryKind::Network => self.scout_config.retries,
+                RetryKind::Refusal | RetryKind::Parse => self.scout_config.json_retries,
+            };
+            let failed = failures.entry(kind).or_default();
+            *failed += 1;
+            if *failed >= policy.attempts(kind, default_limit) {
+                self.console.warning(&problem);
+                break;
             }
 
-            if !success {
-                self.console.error(&format!(
-                    "Failed to process chunk {} after {} attempts",
-                    chunk_num, self.scout_config.json_retries
-                ));
-            }
+            self.console.warning(&format!("{}, retrying...", problem));
+            nudge = kind == RetryKind::Refusal;
+            tokio::time::sleep(policy.backoff(attempt)).await;
         }
 
-        results
+        self.console.error(&format!(
+            "Failed to process chunk {} after {} attempts",
+            chunk_num, attempt
+        ));
+        None
+    }
+
+    /// Model name scout requests are sent to (`name_scout.model`, falling
+    /// back to the scout endpoint's model).
+    fn model(&self) -> &str {
+        self.scout_config
+            .model
+            .as_deref()
+            .unwrap_or(&self.api_config.model)
     }
 
     /// Split text into chunks for processing.
     fn split_into_chunks(&self, text: &str) -> Vec<String> {
-        let chunk_size = self.scout_config.chunk_size_chars;
-        crate::utils::split_text_into_line_chunks(text, chunk_size)
+        Chunker::new(
+            self.scout_config.chunk_size_chars,
+            self.scout_config.chunk_size_unit,
+        )
+        .with_strategy(ChunkStrategy::Lines)
+        .split(text)
     }
 
-    /// Call the LLM model to extract names.
-    async fn call_model(&self, chunk: &str) -> Result<String, TranslationError> {
-        let request = ChatRequest {
-            model: self.api_config.model.clone(),
-            messages: vec![
-                Message {
+    /// Call the LLM model to extract names, sending the refusal nudge along
+    /// if `nudge` is set.
+    async fn call_model(&self, chunk: &str, nudge: bool) -> Result<String, TranslationError> {
+        self.cancellation.check()?;
+
+        if let Some(cached) = self
+            .cache
+            .as_ref()
+            .and_then(|c| c.get(self.model(), &self.prompt, chunk))
+        {
+            return Ok(cached);
+        }
+
+        let refusal_nudge = self.refusal_nudge.trim();
+        let nudge = nudge && !refusal_nudge.is_empty();
+        let messages = if self.api_config.supports_system_role {
+            let mut messages = vec![Message {
+                role: "system".to_string(),
+                content: self.prompt.clone(),
+            }];
+            let content = if !nudge {
+                chunk.to_string()
+            } else if self.refusal_nudge_role == NudgeRole::System {
+                messages.push(Message {
                     role: "system".to_string(),
-                    content: self.prompt.clone(),
-                },
-                Message {
-                    role: "user".to_string(),
-                    content: chunk.to_string(),
-                },
-            ],
+                    content: refusal_nudge.to_string(),
+                });
+                chunk.to_string()
+            } else {
+                format!("{}\n\n{}", refusal_nudge, chunk)
+            };
+            messages.push(Message {
+                role: "user".to_string(),
+                content,
+            });
+            messages
+        } else {
+            // Endpoint rejects the system role; prepend the prompt instead
+            let content = if nudge {
+                format!("{}\n\n{}\n\n{}", self.prompt, refusal_nudge, chunk)
+            } else {
+                format!("{}\n\n{}", self.prompt, chunk)
+            };
+            vec![Message {
+                role: "user".to_string(),
+                content,
+            }]
+        };
+
+        let request = ChatRequest {
+            model: self.model().to_string(),
+            messages,
+            temperature: self.scout_config.temperature,
         };
 
         // Apply rate limiting delay
@@ -214,15 +511,28 @@ impl NameScout {
             ))
             .await;
         }
+        if let Some(limiter) = &self.api_limiter {
+            limiter
+                .pace(
+                    &self.api_config,
+                    self.scout_config.delay_between_requests_sec,
+                )
+                .await;
+        }
+
+        let body = self
+            .api_config
+            .request_body(&request)
+            .map_err(|e| TranslationError::ParseError(format!("Failed to build request: {}", e)))?;
 
-        let url = format!("{}/chat/completions", self.api_config.base_url);
+        let url = self.api_config.endpoint_url("chat/completions");
         let response = self
             .client
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_config.key))
             .header("Content-Type", "application/json")
             .timeout(Duration::from_secs(60))
-            .json(&request)
+            .json(&body)
             .send()
             .await?;
 
@@ -243,35 +553,7 @@ impl NameScout {
 
     /// Parse the LLM response into name entries.
     fn parse_response(&self, raw: &str) -> Result<Vec<NameEntry>, TranslationError> {
-        let trimmed = raw.trim();
-
-        // Remove markdown code fence if present
-        let json_str = if trimmed.starts_with("```") {
-            if let Some(captures) = CODE_FENCE_REGEX.captures(trimmed) {
-                captures.get(1).map(|m| m.as_str()).unwrap_or(trimmed)
-            } else {
-                // Try to manually strip
-                let without_start = trimmed
-                    .trim_start_matches("```json")
-                    .trim_start_matches("```");
-                without_start.trim_end_matches("```").trim()
-            }
-        } else {
-            trimmed
-        };
-
-        // Find JSON object boundaries
-        let start = json_str.find('{');
-        let end = json_str.rfind('}');
-
-        let json_content = match (start, end) {
-            (Some(s), Some(e)) if s < e => &json_str[s..=e],
-            _ => {
-                return Err(TranslationError::ParseError(
-                    "No valid JSON object found".to_string(),
-                ));
-            }
-        };
+        let json_content = extract_json(raw)?;
 
         // Parse JSON
         let parsed: ParsedNamesResponse = serde_json::from_str(json_content)
@@ -299,6 +581,11 @@ impl NameScout {
                     original,
                     english,
                     part,
+                    confidence: entry.confidence.as_ref().and_then(parse_confidence),
+                    reading: entry
+                        .reading
+                        .map(|r| r.trim().to_string())
+                        .filter(|r| !r.is_empty()),
                 })
             })
             .collect();
@@ -307,6 +594,86 @@ impl NameScout {
     }
 }
 
+/// Parse a terminology response into terms.
+fn parse_terms(raw: &str) -> Result<Vec<Term>, TranslationError> {
+    let parsed: ParsedTermsResponse = serde_json::from_str(extract_json(raw)?)
+        .map_err(|e| TranslationError::ParseError(format!("JSON parse error: {}", e)))?;
+
+    Ok(parsed
+        .terms
+        .into_iter()
+        .filter_map(|entry| {
+            let original = entry.original?.trim().to_string();
+            let english = entry.english?.trim().to_string();
+            if original.is_empty() || english.is_empty() {
+                return None;
+            }
+            let category = entry
+                .category
+                .as_deref()
+                .and_then(|c| c.parse().ok())
+                .unwrap_or(TermCategory::Other);
+            Some(Term {
+                original,
+                english,
+                category,
+            })
+        })
+        .collect())
+}
+
+/// The JSON object in a model response, without any code fence or text
+/// around it.
+fn extract_json(raw: &str) -> Result<&str, TranslationError> {
+    let trimmed = raw.trim();
+
+    // Remove markdown code fence if present
+    let json_str = if trimmed.starts_with("```") {
+        if let Some(captures) = CODE_FENCE_REGEX.captures(trimmed) {
+            captures.get(1).map(|m| m.as_str()).unwrap_or(trimmed)
+        } else {
+            // Try to manually strip
+            let without_start = trimmed
+                .trim_start_matches("```json")
+                .trim_start_matches("```");
+            without_start.trim_end_matches("```").trim()
+        }
+    } else {
+        trimmed
+    };
+
+    // Find JSON object boundaries
+    let start = json_str.find('{');
+    let end = json_str.rfind('}');
+
+    match (start, end) {
+        (Some(s), Some(e)) if s < e => Ok(&json_str[s..=e]),
+        _ => Err(TranslationError::ParseError(
+            "No valid JSON object found".to_string(),
+        )),
+    }
+}
+
+/// Reads a reported confidence as a value from 0 to 1, accepting quoted
+/// numbers and percentages.
+fn parse_confidence(value: &serde_json::Value) -> Option<f64> {
+    let confidence = match value {
+        serde_json::Value::Number(n) => n.as_f64()?,
+        serde_json::Value::String(s) => s.trim().trim_end_matches('%').trim().parse().ok()?,
+        _ => return None,
+    };
+    if !confidence.is_finite() || confidence < 0.0 {
+        return None;
+    }
+    // Some models answer in percent despite the prompt
+    let confidence = if confidence > 1.0 {
+        confidence / 100.0
+    } else {
+        confidence
+    };
+    Some(confidence.min(1.0))
+}
+
 /// Build a chapter payload for name scouting.
 ///
 /// Format:
@@ -376,6 +743,32 @@ I hope this helps!"#;
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_parse_confidence() {
+        let scout = make_scout();
+        let json = r#"{"names":[
+            {"original":"田中","english":"Tanaka","part":"family","confidence":0.9},
+            {"original":"太郎","english":"Taro","part":"given","confidence":"85%"},
+            {"original":"花子","english":"Hanako","part":"given","confidence":"high"},
+            {"original":"ユキ","english":"Yuki","part":"given"}
+        ]}"#;
+        let entries = scout.parse_response(json).unwrap();
+        let confidences: Vec<Option<f64>> = entries.iter().map(|e| e.confidence).collect();
+        assert_eq!(confidences, vec![Some(0.9), Some(0.85), None, None]);
+    }
+
+    #[test]
+    fn test_parse_reading() {
+        let scout = make_scout();
+        let json = r#"{"names":[
+            {"original":"海斗","english":"Kaito","part":"given","reading":"かいと"},
+            {"original":"カイト","english":"Kaito","part":"given","reading":" "}
+        ]}"#;
+        let entries = scout.parse_response(json).unwrap();
+        let readings: Vec<Option<&str>> = entries.iter().map(|e| e.reading.as_deref()).collect();
+        assert_eq!(readings, vec![Some("かいと"), None]);
+    }
+
     #[test]
     fn test_parse_missing_fields() {
         let scout = make_scout();
@@ -395,6 +788,30 @@ I hope this helps!"#;
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_terms() {
+        let json = r#"```json
+{"terms":[
+    {"original":"王都","english":"Royal Capital","category":"place"},
+    {"original":"剣術","english":"Swordsmanship","category":"Skill"},
+    {"original":"魔石","english":"Magic Stone","category":"material"},
+    {"original":"カイト"}
+]}
+```"#;
+        let terms = parse_terms(json).unwrap();
+        let categories: Vec<TermCategory> = terms.iter().map(|t| t.category).collect();
+        assert_eq!(
+            categories,
+            vec![
+                TermCategory::Place,
+                TermCategory::Skill,
+                TermCategory::Other
+            ]
+        );
+        assert_eq!(terms[1].english, "Swordsmanship");
+        assert!(parse_terms(r#"{"names":[]}"#).is_err());
+    }
+
     #[test]
     fn test_split_into_chunks() {
         let config = NameScoutConfig {
@@ -413,6 +830,102 @@ I hope this helps!"#;
         }
     }
 
+    #[tokio::test]
+    async fn test_builder_uses_cache() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put(
+            "gpt-4o-mini",
+            "Extract names",
+            "田中が来た",
+            r#"{"names":[{"original":"田中","english":"Tanaka","part":"family"}]}"#,
+        );
+
+        let scout = NameScout::builder(ApiConfig::default(), NameScoutConfig::default())
+            .prompt("Extract names")
+            .cache(cache)
+            .build();
+
+        let results = scout.collect_names("田中が来た").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0][0].english, "Tanaka");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_chunks_keep_order() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        let names = ["田中", "佐藤", "鈴木", "高橋"];
+        for name in names {
+            cache.put(
+                "gpt-4o-mini",
+                "Extract names",
+                &format!("{}が来た", name),
+                &format!(
+                    r#"{{"names":[{{"original":"{}","english":"{}","part":"family"}}]}}"#,
+                    name, name
+                ),
+            );
+        }
+        cache.put(
+            "gpt-4o-mini",
+            "Extract names",
+            "誰もいない",
+            r#"{"names":[]}"#,
+        );
+
+        let config = NameScoutConfig {
+            chunk_size_chars: 5,
+            concurrency: 3,
+            ..Default::default()
+        };
+        let scout = NameScout::builder(ApiConfig::default(), config)
+            .prompt("Extract names")
+            .cache(cache)
+            .build();
+
+        let text = "田中が来た\n佐藤が来た\n誰もいない\n鈴木が来た\n高橋が来た";
+        let results = scout.collect_names(text).await;
+        let found: Vec<&str> = results.iter().map(|r| r[0].original.as_str()).collect();
+        assert_eq!(found, names);
+    }
+
+    #[tokio::test]
+    async fn test_scout_names_each_resumes() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        for name in ["田中", "佐藤", "鈴木"] {
+            cache.put(
+                "gpt-4o-mini",
+                "Extract names",
+                &format!("{}が来た", name),
+                &format!(
+                    r#"{{"names":[{{"original":"{}","english":"{}","part":"family"}}]}}"#,
+                    name, name
+                ),
+            );
+        }
+
+        let config = NameScoutConfig {
+            chunk_size_chars: 5,
+            concurrency: 2,
+            ..Default::default()
+        };
+        let scout = NameScout::builder(ApiConfig::default(), config)
+            .prompt("Extract names")
+            .cache(cache)
+            .build();
+
+        let chunks = scout.name_chunks("田中が来た\n佐藤が来た\n鈴木が来た");
+        assert_eq!(chunks.len(), 3);
+        let mut seen = Vec::new();
+        let finished = scout
+            .scout_names_each(&chunks, 1, |index, entries| {
+                seen.push((index, entries[0].original.clone()));
+                Ok::<_, ()>(())
+            })
+            .await;
+        assert_eq!(finished, Ok(true));
+        assert_eq!(seen, vec![(1, "佐藤".to_string()), (2, "鈴木".to_string())]);
+    }
+
     #[test]
     fn test_build_chapter_payload() {
         let payload = build_chapter_payload(5, "The Beginning", "Once upon a time...");
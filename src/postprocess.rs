@@ -0,0 +1,389 @@
+//! Post-processing of translated text.
+//!
+//! Normalizes quotes, ellipses, dashes, and spacing, then applies the
+//! configured regex find/replace rules to translations before they are saved,
+//! so recurring model habits can be fixed without re-translating.
+
+use crate::config::{
+    Config, EllipsisStyle, ParagraphSpacing, PostprocessConfig, QuoteStyle, ReplaceRule,
+};
+use crate::translator::{FAILED_CHUNK_MARKER, TranslatedChunk};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Runs of dots, ellipsis characters, or Japanese ellipsis substitutes.
+static ELLIPSIS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.(?: ?\.){2,}|…+|・{3,}|。{3,}").unwrap());
+
+/// Runs of Japanese dash characters, em dashes, or ASCII double hyphens.
+static DASH_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[―─—]+|-{2,}").unwrap());
+
+/// Spaces or tabs before closing punctuation.
+static SPACE_BEFORE_PUNCT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[ \t]+([,.!?;:…)\]”’])").unwrap());
+
+/// Repeated spaces after a non-space character (leading indentation is kept).
+static REPEATED_SPACE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\S)[ \t]{2,}").unwrap());
+
+/// Full-width punctuation and spaces with their ASCII equivalents.
+const FULLWIDTH_PUNCTUATION: &[(char, char)] = &[
+    ('！', '!'),
+    ('？', '?'),
+    ('，', ','),
+    ('：', ':'),
+    ('；', ';'),
+    ('（', '('),
+    ('）', ')'),
+    ('\u{3000}', ' '),
+];
+
+/// Compiled post-processing for one novel.
+#[derive(Debug, Clone, Default)]
+pub struct PostProcessor {
+    /// Quote normalization.
+    quotes: QuoteStyle,
+    /// Ellipsis normalization.
+    ellipsis: EllipsisStyle,
+    /// Whether to normalize dashes.
+    dashes: bool,
+    /// Whether to fix punctuation spacing.
+    spacing: bool,
+    /// Compiled patterns with their replacement text, in order.
+    rules: Vec<(Regex, String)>,
+}
+
+impl PostProcessor {
+    /// Compiles the given rules, with no punctuation normalization.
+    pub fn new(rules: &[ReplaceRule]) -> Result<Self, regex::Error> {
+        let rules = rules
+            .iter()
+            .map(|rule| Ok((Regex::new(&rule.find)?, rule.replace.clone())))
+            .collect::<Result<Vec<_>, regex::Error>>()?;
+        Ok(Self {
+            rules,
+            ..Default::default()
+        })
+    }
+
+    /// Builds the post-processing for a novel from the `[postprocess]`
+    /// settings and the global and per-novel rules.
+    pub fn from_config(config: &Config, site: &str, novel_id: &str) -> Result<Self, regex::Error> {
+        let processor = Self::new(&config.postprocess_rules(site, novel_id))?;
+        Ok(processor.with_style(&config.postprocess))
+    }
+
+    /// Sets the punctuation normalization from the `[postprocess]` settings.
+    pub fn with_style(mut self, style: &PostprocessConfig) -> Self {
+        self.quotes = style.quotes;
+        self.ellipsis = style.ellipsis;
+        self.dashes = style.dashes;
+        self.spacing = style.spacing;
+        self
+    }
+
+    /// Returns true if applying would never change any text.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+            && self.quotes == QuoteStyle::Keep
+            && self.ellipsis == EllipsisStyle::Keep
+            && !self.dashes
+            && !self.spacing
+    }
+
+    /// Applies the normalizations, then every rule in order.
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+
+        if self.spacing {
+            result = fix_spacing(&result);
+        }
+        match self.ellipsis {
+            EllipsisStyle::Keep => {}
+            EllipsisStyle::Unicode => result = ELLIPSIS_REGEX.replace_all(&result, "…").into(),
+            EllipsisStyle::Dots => result = ELLIPSIS_REGEX.replace_all(&result, "...").into(),
+        }
+        if self.dashes {
+            result = normalize_dashes(&result);
+        }
+        match self.quotes {
+            QuoteStyle::Keep => {}
+            QuoteStyle::Curly => result = curly_quotes(&result),
+            QuoteStyle::Straight => result = straight_quotes(&result),
+        }
+
+        for (pattern, replacement) in &self.rules {
+            result = pattern
+                .replace_all(&result, replacement.as_str())
+                .into_owned();
+        }
+        result
+    }
+
+    /// Applies post-processing to each successfully translated chunk.
+    ///
+    /// Failed chunks hold the untranslated source and are left alone.
+    pub fn apply_to_chunks(&self, chunks: &mut [TranslatedChunk]) {
+        if self.is_empty() {
+            return;
+        }
+        for chunk in chunks.iter_mut().filter(|c| !c.failed) {
+            chunk.translated = self.apply(&chunk.translated);
+        }
+    }
+}
+
+/// Lays out a translated chapter for saving: paragraph spacing first, then
+/// wrapping long lines at `wrap_columns` (0 for none).
+///
+/// Unlike the rest of post-processing this isn't idempotent once lines are
+/// wrapped, so it only runs when a translation is saved.
+pub fn apply_layout(text: &str, spacing: ParagraphSpacing, wrap_columns: usize) -> String {
+    let text = match spacing {
+        ParagraphSpacing::Preserve => text.to_string(),
+        ParagraphSpacing::BlankLine => text
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+    if wrap_columns == 0 {
+        return text;
+    }
+
+    let mut wrapped: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        // The failure marker has to stay whole for lint and verify to find
+        if line.contains(FAILED_CHUNK_MARKER) {
+            wrapped.push(line.to_string());
+        } else {
+            wrapped.extend(wrap_line(line, wrap_columns));
+        }
+    }
+    wrapped.join("\n")
+}
+
+/// Breaks a line at spaces into lines of at most `width` characters. Words
+/// longer than `width` get a line to themselves; leading indentation stays
+/// on the first line.
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    if line.chars().count() <= width {
+        return vec![line.to_string()];
+    }
+
+    let indent_len = line.len() - line.trim_start().len();
+    let mut lines = Vec::new();
+    let mut current = line[..indent_len].to_string();
+    let mut current_width = current.chars().count();
+    let mut has_word = false;
+    for word in line[indent_len..].split_whitespace() {
+        let word_width = word.chars().count();
+        if has_word && current_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+            has_word = false;
+        }
+        if has_word {
+            current.push(' ');
+            current_width += 1;
+        }
+        current.push_str(word);
+        current_width += word_width;
+        has_word = true;
+    }
+    lines.push(current);
+    lines
+}
+
+/// Replaces dash runs with em dashes, leaving lines made only of dashes
+/// (scene breaks like `---` or `――――`) as they are.
+fn normalize_dashes(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let trimmed = line.trim();
+            if !trimmed.is_empty() && DASH_REGEX.replace_all(trimmed, "").is_empty() {
+                line.to_string()
+            } else {
+                DASH_REGEX.replace_all(line, "—").into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Converts full-width punctuation, removes spaces before punctuation, and
+/// collapses repeated spaces.
+fn fix_spacing(text: &str) -> String {
+    let converted: String = text
+        .chars()
+        .map(|c| {
+            FULLWIDTH_PUNCTUATION
+                .iter()
+                .find(|(wide, _)| *wide == c)
+                .map_or(c, |(_, ascii)| *ascii)
+        })
+        .collect();
+    let collapsed = REPEATED_SPACE_REGEX.replace_all(&converted, "$1 ");
+    let tightened = SPACE_BEFORE_PUNCT_REGEX.replace_all(&collapsed, "$1");
+
+    // Not `lines()`, which would drop a trailing newline
+    tightened
+        .split('\n')
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Converts Japanese brackets and ASCII quotes to typographic quotes.
+///
+/// A straight quote opens at the start of the text or after whitespace or an
+/// opening bracket, and closes otherwise (which also turns apostrophes into ’).
+fn curly_quotes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut previous: Option<char> = None;
+
+    for c in text.chars() {
+        let opens = previous.is_none_or(|p| p.is_whitespace() || "([{“‘—".contains(p));
+        let converted = match c {
+            '「' => '“',
+            '」' => '”',
+            '『' => '‘',
+            '』' => '’',
+            '"' if opens => '“',
+            '"' => '”',
+            '\'' if opens => '‘',
+            '\'' => '’',
+            c => c,
+        };
+        result.push(converted);
+        previous = Some(converted);
+    }
+
+    result
+}
+
+/// Converts Japanese brackets and typographic quotes to ASCII quotes.
+fn straight_quotes(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '「' | '」' | '“' | '”' => '"',
+            '『' | '』' | '‘' | '’' => '\'',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TranslationError;
+    use std::sync::Arc;
+
+    fn rule(find: &str, replace: &str) -> ReplaceRule {
+        ReplaceRule {
+            find: find.to_string(),
+            replace: replace.to_string(),
+        }
+    }
+
+    fn styled(quotes: QuoteStyle, ellipsis: EllipsisStyle) -> PostProcessor {
+        PostProcessor::default().with_style(&PostprocessConfig {
+            quotes,
+            ellipsis,
+            dashes: true,
+            spacing: true,
+            rules: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_rules_apply_in_order() {
+        let processor = PostProcessor::new(&[
+            rule(r"\.{3,}", "…"),
+            rule(r"\b[Bb]ig brother\b", "Onii-chan"),
+            rule(r"Onii-chan…", "Onii-chan!"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            processor.apply("Big brother... wait...."),
+            "Onii-chan! wait…"
+        );
+    }
+
+    #[test]
+    fn test_capture_groups_and_failed_chunks() {
+        let processor = PostProcessor::new(&[rule(r"(\w+)-sama", "Lord $1")]).unwrap();
+        let mut chunks = vec![
+            TranslatedChunk {
+                source: String::new(),
+                translated: "Aria-sama bowed.".to_string(),
+                failed: false,
+                error: None,
+            },
+            TranslatedChunk {
+                source: String::new(),
+                translated: "[TRANSLATION FAILED]\nAria-sama".to_string(),
+                failed: true,
+                error: Some(Arc::new(TranslationError::ApiError(
+                    "timed out".to_string(),
+                ))),
+            },
+        ];
+        processor.apply_to_chunks(&mut chunks);
+
+        assert_eq!(chunks[0].translated, "Lord Aria bowed.");
+        assert_eq!(chunks[1].translated, "[TRANSLATION FAILED]\nAria-sama");
+        assert!(PostProcessor::new(&[rule("(", "")]).is_err());
+    }
+
+    #[test]
+    fn test_curly_quotes() {
+        let processor = styled(QuoteStyle::Curly, EllipsisStyle::Unicode);
+        assert_eq!(
+            processor.apply("「Wait」 she said. \"It's 『mine』......\""),
+            "“Wait” she said. “It’s ‘mine’…”"
+        );
+    }
+
+    #[test]
+    fn test_straight_quotes_dashes_and_spacing() {
+        let processor = styled(QuoteStyle::Straight, EllipsisStyle::Dots);
+        assert_eq!(
+            processor.apply("「Huh ？」  He froze――then ran . . .  \n"),
+            "\"Huh?\" He froze—then ran...\n"
+        );
+        assert!(PostProcessor::default().is_empty());
+        assert!(!processor.is_empty());
+    }
+
+    #[test]
+    fn test_scene_breaks_and_trailing_newline() {
+        let processor = styled(QuoteStyle::Keep, EllipsisStyle::Keep);
+        assert_eq!(
+            processor.apply("She left--fast.\n\n---\n\n----\n  ――――\nDone. \n"),
+            "She left—fast.\n\n---\n\n----\n  ――――\nDone.\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_layout() {
+        let text = "First line.\nSecond line.\n\n\n「Third」";
+        assert_eq!(apply_layout(text, ParagraphSpacing::Preserve, 0), text);
+        assert_eq!(
+            apply_layout(text, ParagraphSpacing::BlankLine, 0),
+            "First line.\n\nSecond line.\n\n「Third」"
+        );
+
+        let long = "  The demon lord laughed at the hero, supercalifragilistic.\n\nShort.";
+        assert_eq!(
+            apply_layout(long, ParagraphSpacing::Preserve, 20),
+            "  The demon lord\nlaughed at the hero,\nsupercalifragilistic.\n\nShort."
+        );
+        assert_eq!(
+            apply_layout(FAILED_CHUNK_MARKER, ParagraphSpacing::Preserve, 10),
+            FAILED_CHUNK_MARKER
+        );
+    }
+}
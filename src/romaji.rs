@@ -0,0 +1,203 @@
+//! Romanization of kana readings.
+//!
+//! Turns a name's kana reading into Hepburn romaji, so a name the scout
+//! couldn't settle on an English rendering for gets the same spelling in
+//! every chunk instead of whatever the model guesses. Long vowels are
+//! written short, as names usually are in English (ゆうこ → Yuko,
+//! しょうた → Shota).
+
+/// Romaji for single katakana syllables.
+#[rustfmt::skip]
+const SYLLABLES: &[(char, &str)] = &[
+    ('ア', "a"), ('イ', "i"), ('ウ', "u"), ('エ', "e"), ('オ', "o"),
+    ('カ', "ka"), ('キ', "ki"), ('ク', "ku"), ('ケ', "ke"), ('コ', "ko"),
+    ('ガ', "ga"), ('ギ', "gi"), ('グ', "gu"), ('ゲ', "ge"), ('ゴ', "go"),
+    ('サ', "sa"), ('シ', "shi"), ('ス', "su"), ('セ', "se"), ('ソ', "so"),
+    ('ザ', "za"), ('ジ', "ji"), ('ズ', "zu"), ('ゼ', "ze"), ('ゾ', "zo"),
+    ('タ', "ta"), ('チ', "chi"), ('ツ', "tsu"), ('テ', "te"), ('ト', "to"),
+    ('ダ', "da"), ('ヂ', "ji"), ('ヅ', "zu"), ('デ', "de"), ('ド', "do"),
+    ('ナ', "na"), ('ニ', "ni"), ('ヌ', "nu"), ('ネ', "ne"), ('ノ', "no"),
+    ('ハ', "ha"), ('ヒ', "hi"), ('フ', "fu"), ('ヘ', "he"), ('ホ', "ho"),
+    ('バ', "ba"), ('ビ', "bi"), ('ブ', "bu"), ('ベ', "be"), ('ボ', "bo"),
+    ('パ', "pa"), ('ピ', "pi"), ('プ', "pu"), ('ペ', "pe"), ('ポ', "po"),
+    ('マ', "ma"), ('ミ', "mi"), ('ム', "mu"), ('メ', "me"), ('モ', "mo"),
+    ('ヤ', "ya"), ('ユ', "yu"), ('ヨ', "yo"),
+    ('ラ', "ra"), ('リ', "ri"), ('ル', "ru"), ('レ', "re"), ('ロ', "ro"),
+    ('ワ', "wa"), ('ヰ', "i"), ('ヱ', "e"), ('ヲ', "o"), ('ン', "n"),
+    ('ヴ', "vu"),
+    ('ァ', "a"), ('ィ', "i"), ('ゥ', "u"), ('ェ', "e"), ('ォ', "o"),
+];
+
+/// Romaji for a syllable followed by a small ャ, ュ, or ョ, keyed by its
+/// consonant (キャ → "ky" + "a"); シ, チ, and ジ drop the "y".
+#[rustfmt::skip]
+const YOON_CONSONANTS: &[(char, &str)] = &[
+    ('キ', "ky"), ('ギ', "gy"), ('シ', "sh"), ('ジ', "j"), ('チ', "ch"), ('ヂ', "j"),
+    ('ニ', "ny"), ('ヒ', "hy"), ('ビ', "by"), ('ピ', "py"), ('ミ', "my"), ('リ', "ry"),
+];
+
+/// Converts a kana reading to capitalized romaji (`かいと` → "Kaito").
+///
+/// Returns `None` if the reading has anything but kana, or nothing to
+/// romanize.
+pub fn romanize(reading: &str) -> Option<String> {
+    let kana: Vec<char> = reading
+        .trim()
+        .chars()
+        .map(|c| match c {
+            // Hiragana to katakana
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            c => c,
+        })
+        .collect();
+
+    let mut romaji = String::new();
+    let mut double_next = false;
+    let mut i = 0;
+    while i < kana.len() {
+        let c = kana[i];
+        let next = kana.get(i + 1).copied();
+        i += 1;
+
+        let syllable = match c {
+            'ッ' => {
+                double_next = true;
+                continue;
+            }
+            // Long vowels are written short
+            'ー' => continue,
+            'ウ' if ends_in(&romaji, &['o', 'u']) => continue,
+            'オ' if ends_in(&romaji, &['o']) => continue,
+            '・' | '･' => {
+                romaji.push(' ');
+                continue;
+            }
+            _ => {
+                let small_vowel = match next {
+                    Some('ャ') => Some("a"),
+                    Some('ュ') => Some("u"),
+                    Some('ョ') => Some("o"),
+                    _ => None,
+                };
+                match (small_vowel, consonant(c)) {
+                    (Some(vowel), Some(consonant)) => {
+                        i += 1;
+                        format!("{}{}", consonant, vowel)
+                    }
+                    _ => syllable(c)?.to_string(),
+                }
+            }
+        };
+
+        if std::mem::take(&mut double_next) {
+            // っち is "tch" in Hepburn
+            match syllable.strip_prefix("ch") {
+                Some(_) => romaji.push('t'),
+                None => romaji.extend(syllable.chars().next()),
+            }
+        }
+        romaji.push_str(&syllable);
+    }
+
+    let words: Vec<String> = romaji.split_whitespace().map(capitalize).collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+/// Romanizes a title written in kana, keeping Latin letters, digits, and
+/// punctuation (`ぼくの なつやすみ！` → "Bokuno Natsuyasumi!").
+///
+/// Returns `None` if the title has kanji or anything else that can't be
+/// read without a dictionary.
+pub fn romanize_title(title: &str) -> Option<String> {
+    let mut romaji = String::new();
+    let mut kana = String::new();
+    for c in title.trim().chars() {
+        if matches!(c, 'ぁ'..='ゖ' | 'ァ'..='ヺ' | 'ー') {
+            kana.push(c);
+            continue;
+        }
+        if !kana.is_empty() {
+            romaji.push_str(&romanize(&std::mem::take(&mut kana))?);
+        }
+        match c {
+            '　' | '・' => romaji.push(' '),
+            '、' => romaji.push(','),
+            '。' => romaji.push('.'),
+            '「' | '」' | '『' | '』' => romaji.push('"'),
+            '【' => romaji.push('['),
+            '】' => romaji.push(']'),
+            '〜' => romaji.push('~'),
+            // Fullwidth ASCII (！, Ａ, ３)
+            '！'..='～' => romaji.extend(char::from_u32(c as u32 - 0xFEE0)),
+            c if c.is_ascii() => romaji.push(c),
+            _ => return None,
+        }
+    }
+    if !kana.is_empty() {
+        romaji.push_str(&romanize(&kana)?);
+    }
+    let romaji = romaji.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!romaji.is_empty()).then_some(romaji)
+}
+
+fn syllable(c: char) -> Option<&'static str> {
+    SYLLABLES.iter().find(|(k, _)| *k == c).map(|(_, r)| *r)
+}
+
+fn consonant(c: char) -> Option<&'static str> {
+    YOON_CONSONANTS
+        .iter()
+        .find(|(k, _)| *k == c)
+        .map(|(_, r)| *r)
+}
+
+fn ends_in(romaji: &str, vowels: &[char]) -> bool {
+    romaji.chars().last().is_some_and(|c| vowels.contains(&c))
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_romanize() {
+        for (reading, expected) in [
+            ("かいと", "Kaito"),
+            ("ゆうこ", "Yuko"),
+            ("しょうた", "Shota"),
+            ("きょうこ", "Kyoko"),
+            ("いっちゃん", "Itchan"),
+            ("はっとり", "Hattori"),
+            ("じゅん", "Jun"),
+            ("ユーキ", "Yuki"),
+            ("たなか・たろう", "Tanaka Taro"),
+        ] {
+            assert_eq!(romanize(reading).as_deref(), Some(expected), "{}", reading);
+        }
+        assert_eq!(romanize("海斗"), None);
+        assert_eq!(romanize(""), None);
+    }
+
+    #[test]
+    fn test_romanize_title() {
+        assert_eq!(
+            romanize_title("ぼくの なつやすみ！").as_deref(),
+            Some("Bokuno Natsuyasumi!")
+        );
+        assert_eq!(
+            romanize_title("【ＳＳ】スライム・ライフ2").as_deref(),
+            Some("[SS]Suraimu Raifu2")
+        );
+        assert_eq!(romanize_title("転生したらスライムだった件"), None);
+        assert_eq!(romanize_title("「」"), Some("\"\"".to_string()));
+        assert_eq!(romanize_title("  "), None);
+    }
+}
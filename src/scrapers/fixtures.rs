@@ -0,0 +1,41 @@
+//! Stored pages for the scraper conformance tests (`conformance` feature).
+//!
+//! A scraper given a page source with `with_fixtures` reads every page from
+//! it instead of the network: a URL without a fixture fails with
+//! [`ScraperError::NotFound`] rather than reaching the live site.
+
+use crate::error::ScraperError;
+use std::collections::HashMap;
+
+/// Serves page bodies in place of the network.
+pub trait PageSource: Send + Sync {
+    /// The stored body for `url`.
+    fn page(&self, url: &str) -> Result<String, ScraperError>;
+}
+
+/// Page bodies by exact URL.
+#[derive(Debug, Clone, Default)]
+pub struct Fixtures {
+    pages: HashMap<String, String>,
+}
+
+impl Fixtures {
+    /// Creates an empty set of fixtures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves `body` for requests to `url`.
+    pub fn serve(&mut self, url: &str, body: impl Into<String>) {
+        self.pages.insert(url.to_string(), body.into());
+    }
+}
+
+impl PageSource for Fixtures {
+    fn page(&self, url: &str) -> Result<String, ScraperError> {
+        self.pages
+            .get(url)
+            .cloned()
+            .ok_or_else(|| ScraperError::NotFound(format!("No fixture for {}", url)))
+    }
+}
@@ -2,13 +2,28 @@
 //!
 //! Supports downloading novels from Kadokawa's Kakuyomu platform.
 
-use super::{ChapterInfo, ChapterList, NovelInfo, Scraper, create_http_client, rate_limit};
+#[cfg(feature = "conformance")]
+use super::fixtures::PageSource;
+use super::limiter::HostLimiter;
+use super::{
+    BookmarkedNovel, ChapterInfo, ChapterList, NovelInfo, PageCache, Scraper,
+    create_http_client_with_jar, element_text,
+};
 use crate::config::ScrapingConfig;
 use crate::error::ScraperError;
 use async_trait::async_trait;
 use regex::Regex;
+use reqwest::cookie::Jar;
 use scraper::{Html, Selector};
-use std::sync::LazyLock;
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+
+/// The logged-in user's followed works ("フォロー中の作品"), paginated with `?page=N`.
+const FOLLOWED_WORKS_URL: &str = "https://kakuyomu.jp/my/antenna/works";
+
+/// Matches links to a work's main page (not episodes or sub-pages).
+static WORK_LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:https?://kakuyomu\.jp)?/works/(\d+)/?$").unwrap());
 
 /// Compiled regex patterns for Kakuyomu URLs.
 static URL_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
@@ -21,6 +36,10 @@ static URL_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
 /// Regex to extract work ID from URL.
 static WORK_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"/works/(\d+)").unwrap());
 
+/// Regex to extract the episode ID from an episode URL.
+static EPISODE_ID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/episodes/(\d+)").unwrap());
+
 /// Regex to strip episode suffix from URLs.
 static EPISODE_SUFFIX_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"/episodes/\d+/?$").unwrap());
@@ -35,6 +54,10 @@ struct Selectors {
     content: Selector,
     /// Paragraph selector.
     paragraph: Selector,
+    /// Work link selector (followed works list).
+    work_link: Selector,
+    /// Embedded Next.js page data (work metadata).
+    next_data: Selector,
 }
 
 impl Selectors {
@@ -45,6 +68,8 @@ impl Selectors {
             chapter: Selector::parse(r#"a[class^="WorkTocSection_link"]"#).unwrap(),
             content: Selector::parse("div.widget-episodeBody").unwrap(),
             paragraph: Selector::parse("p").unwrap(),
+            work_link: Selector::parse(r#"a[href*="/works/"]"#).unwrap(),
+            next_data: Selector::parse("script#__NEXT_DATA__").unwrap(),
         }
     }
 }
@@ -54,23 +79,73 @@ pub struct KakuyomuScraper {
     client: reqwest::Client,
     config: ScrapingConfig,
     selectors: Selectors,
+    /// Work pages fetched so far.
+    pages: PageCache,
+    limiter: HostLimiter,
+    /// Pages served in place of the network, for conformance tests.
+    #[cfg(feature = "conformance")]
+    fixtures: Option<Arc<dyn PageSource>>,
 }
 
 impl KakuyomuScraper {
     /// Creates a new Kakuyomu scraper with the given configuration.
-    pub fn new(config: ScrapingConfig) -> Self {
-        let client = create_http_client().expect("Failed to create HTTP client");
+    ///
+    /// Login cookies in `jar` (if any) enable importing the follow list.
+    pub fn new(config: ScrapingConfig, jar: Arc<Jar>) -> Self {
+        let client =
+            create_http_client_with_jar(&config, jar).expect("Failed to create HTTP client");
 
         Self {
             client,
             config,
             selectors: Selectors::new(),
+            pages: PageCache::default(),
+            limiter: HostLimiter::new(),
+            #[cfg(feature = "conformance")]
+            fixtures: None,
         }
     }
 
+    /// Counts requests against `limiter`, shared with other scrapers.
+    pub fn with_limiter(mut self, limiter: HostLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Reads every page from `source` instead of the network.
+    #[cfg(feature = "conformance")]
+    pub fn with_fixtures(mut self, source: Arc<dyn PageSource>) -> Self {
+        self.fixtures = Some(source);
+        self
+    }
+
     /// Fetches a page and returns the HTML document.
     async fn fetch_page(&self, url: &str) -> Result<Html, ScraperError> {
-        rate_limit(self.config.delay_between_requests_sec).await;
+        Ok(Html::parse_document(&self.fetch_body(url).await?))
+    }
+
+    /// Fetches a work's main page, reusing it if it was already fetched
+    /// this run.
+    async fn fetch_work_page(&self, url: &str) -> Result<Html, ScraperError> {
+        if let Some(body) = self.pages.get(url) {
+            return Ok(Html::parse_document(&body));
+        }
+        let body = self.fetch_body(url).await?;
+        self.pages.insert(url, &body);
+        Ok(Html::parse_document(&body))
+    }
+
+    /// Fetches a page's HTML.
+    async fn fetch_body(&self, url: &str) -> Result<String, ScraperError> {
+        #[cfg(feature = "conformance")]
+        if let Some(fixtures) = &self.fixtures {
+            return fixtures.page(url);
+        }
+
+        let _permit = self
+            .limiter
+            .acquire(&self.client, url, &self.config)
+            .await?;
 
         let response = self.client.get(url).send().await?;
 
@@ -80,8 +155,7 @@ impl KakuyomuScraper {
             ));
         }
 
-        let text = response.text().await?;
-        Ok(Html::parse_document(&text))
+        Ok(response.text().await?)
     }
 
     /// Extracts the novel title from the page.
@@ -105,6 +179,102 @@ impl KakuyomuScraper {
         Err(ScraperError::ElementNotFound("novel title".to_string()))
     }
 
+    /// Extracts the synopsis and tags from the page's embedded data.
+    ///
+    /// The work page embeds its Apollo cache as JSON, with the work stored
+    /// under a `Work` entry carrying `introduction` and `tagLabels`.
+    fn extract_work_details(&self, doc: &Html, work_id: &str) -> (String, Vec<String>) {
+        let Some(work) = self.extract_work_data(doc, work_id) else {
+            return (String::new(), Vec::new());
+        };
+
+        let synopsis = work["introduction"].as_str().unwrap_or_default().trim();
+        let tags = work["tagLabels"]
+            .as_array()
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        (synopsis.to_string(), tags)
+    }
+
+    /// Finds the work's entry in the page's embedded Apollo cache.
+    fn extract_work_data(&self, doc: &Html, work_id: &str) -> Option<serde_json::Value> {
+        self.apollo_state(doc)?
+            .values()
+            .find(|v| v["__typename"] == "Work" && v["id"] == work_id)
+            .cloned()
+    }
+
+    /// The page's embedded Apollo cache, keyed by `Typename:id`.
+    fn apollo_state(&self, doc: &Html) -> Option<serde_json::Map<String, serde_json::Value>> {
+        let mut data: serde_json::Value = doc
+            .select(&self.selectors.next_data)
+            .next()
+            .and_then(|elem| serde_json::from_str(&elem.text().collect::<String>()).ok())?;
+        match data
+            .pointer_mut("/props/pageProps/__APOLLO_STATE__")?
+            .take()
+        {
+            serde_json::Value::Object(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Extracts the author's name, which the work references as a separate
+    /// `UserAccount` entry.
+    fn extract_author(&self, doc: &Html, work_id: &str) -> String {
+        let Some(state) = self.apollo_state(doc) else {
+            return String::new();
+        };
+        state
+            .values()
+            .find(|v| v["__typename"] == "Work" && v["id"] == work_id)
+            .and_then(|work| work["author"]["__ref"].as_str())
+            .and_then(|key| state.get(key))
+            .and_then(|user| user["activityName"].as_str())
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    }
+
+    /// Whether the work is marked finished (`serialStatus` of `COMPLETED`),
+    /// if the page says.
+    fn completed(&self, doc: &Html, work_id: &str) -> Option<bool> {
+        match self.extract_work_data(doc, work_id)?["serialStatus"].as_str()? {
+            "COMPLETED" => Some(true),
+            "RUNNING" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Extracts the episode list from the work page.
+    fn extract_chapters(&self, doc: &Html) -> Vec<ChapterInfo> {
+        doc.select(&self.selectors.chapter)
+            .enumerate()
+            .filter_map(|(idx, elem)| {
+                let href = elem.value().attr("href")?;
+                let title = elem.text().collect::<String>().trim().to_string();
+                let full_url = Self::resolve_url(href).trim_end_matches('/').to_string();
+                let episode_id = EPISODE_ID_REGEX
+                    .captures(&full_url)
+                    .map(|caps| caps[1].to_string())
+                    .unwrap_or_else(|| full_url.clone());
+
+                Some(ChapterInfo {
+                    title,
+                    url: full_url,
+                    number: (idx + 1) as u32,
+                    episode_id,
+                })
+            })
+            .collect()
+    }
+
     /// Extracts the work ID from a URL.
     fn extract_work_id(url: &str) -> Result<String, ScraperError> {
         WORK_ID_REGEX
@@ -121,6 +291,81 @@ impl KakuyomuScraper {
         base.to_string()
     }
 
+    /// Fetches every work the logged-in user follows.
+    ///
+    /// Requires Kakuyomu login cookies; returns [`ScraperError::LoginRequired`]
+    /// if the site redirects to the login page.
+    pub async fn fetch_followed_works(&self) -> Result<Vec<BookmarkedNovel>, ScraperError> {
+        const MAX_PAGES: u32 = 100; // Safety limit
+
+        let mut seen = HashSet::new();
+        let mut works = Vec::new();
+
+        for page in 1..=MAX_PAGES {
+            let url = format!("{}?page={}", FOLLOWED_WORKS_URL, page);
+            let _permit = self
+                .limiter
+                .acquire(&self.client, &url, &self.config)
+                .await?;
+
+            let response = self.client.get(&url).send().await?;
+            if response.url().path().contains("login") {
+                return Err(ScraperError::LoginRequired(
+                    "Kakuyomu follows need login cookies (see `tsundoku cookies import`)"
+                        .to_string(),
+                ));
+            }
+            if !response.status().is_success() {
+                return Err(ScraperError::HttpError(
+                    response.error_for_status().unwrap_err(),
+                ));
+            }
+
+            let doc = Html::parse_document(&response.text().await?);
+            let before = works.len();
+            for work in self.extract_work_links(&doc) {
+                if seen.insert(work.novel_id.clone()) {
+                    works.push(work);
+                }
+            }
+
+            // An empty page (or a repeat of the last one) means we're past the end
+            if works.len() == before {
+                break;
+            }
+        }
+
+        Ok(works)
+    }
+
+    /// Extracts links to works' main pages from a list page.
+    fn extract_work_links(&self, doc: &Html) -> Vec<BookmarkedNovel> {
+        let mut works: Vec<BookmarkedNovel> = Vec::new();
+
+        for elem in doc.select(&self.selectors.work_link) {
+            let Some(caps) = elem
+                .value()
+                .attr("href")
+                .and_then(|href| WORK_LINK_REGEX.captures(href))
+            else {
+                continue;
+            };
+            let title = elem.text().collect::<String>().trim().to_string();
+            let work_id = caps[1].to_string();
+            if title.is_empty() || works.iter().any(|w| w.novel_id == work_id) {
+                continue;
+            }
+
+            works.push(BookmarkedNovel {
+                url: format!("https://kakuyomu.jp/works/{}", work_id),
+                novel_id: work_id,
+                title,
+            });
+        }
+
+        works
+    }
+
     /// Resolves a relative URL against Kakuyomu's base.
     fn resolve_url(relative: &str) -> String {
         if relative.starts_with("http://") || relative.starts_with("https://") {
@@ -151,47 +396,54 @@ impl Scraper for KakuyomuScraper {
         }
 
         let base_url = Self::get_base_url(url);
-        let doc = self.fetch_page(&base_url).await?;
+        let doc = self.fetch_work_page(&base_url).await?;
         let title = self.extract_title(&doc)?;
         let novel_id = Self::extract_work_id(url)?;
+        let (synopsis, tags) = self.extract_work_details(&doc, &novel_id);
+        let author = self.extract_author(&doc, &novel_id);
+        let completed = self.completed(&doc, &novel_id);
 
         Ok(NovelInfo {
             title,
             base_url,
             novel_id,
+            synopsis,
+            tags,
+            author,
+            completed,
         })
     }
 
     async fn get_chapter_list(&self, base_url: &str) -> Result<ChapterList, ScraperError> {
-        let doc = self.fetch_page(base_url).await?;
-
-        let chapters: Vec<ChapterInfo> = doc
-            .select(&self.selectors.chapter)
-            .enumerate()
-            .filter_map(|(idx, elem)| {
-                let href = elem.value().attr("href")?;
-                let title = elem.text().collect::<String>().trim().to_string();
-                let full_url = Self::resolve_url(href).trim_end_matches('/').to_string();
-
-                Some(ChapterInfo {
-                    title,
-                    url: full_url,
-                    number: (idx + 1) as u32,
-                })
-            })
-            .collect();
-
-        if chapters.is_empty() {
-            // Kakuyomu doesn't really have one-shots in the same way
-            // If no chapters found, return empty list
-            return Ok(ChapterList::Chapters(Vec::new()));
+        let doc = self.fetch_work_page(base_url).await?;
+        let chapters = self.extract_chapters(&doc);
+
+        // A work marked finished with a single episode is a short story;
+        // treat it as a one-shot, downloaded through the work URL (a missing
+        // status counts as still running)
+        let work_id = Self::extract_work_id(base_url)?;
+        if chapters.len() == 1 && self.completed(&doc, &work_id) == Some(true) {
+            return Ok(ChapterList::OneShot);
         }
 
         Ok(ChapterList::Chapters(chapters))
     }
 
     async fn download_chapter(&self, chapter_url: &str) -> Result<String, ScraperError> {
-        let doc = self.fetch_page(chapter_url).await?;
+        // A one-shot is downloaded through its work URL; fetch its only episode
+        let episode_url = if EPISODE_ID_REGEX.is_match(chapter_url) {
+            chapter_url.to_string()
+        } else {
+            let doc = self
+                .fetch_work_page(&Self::get_base_url(chapter_url))
+                .await?;
+            self.extract_chapters(&doc)
+                .into_iter()
+                .next()
+                .map(|c| c.url)
+                .ok_or_else(|| ScraperError::ElementNotFound("episode link".to_string()))?
+        };
+        let doc = self.fetch_page(&episode_url).await?;
 
         // Find content div
         let content_elem = doc
@@ -202,13 +454,15 @@ impl Scraper for KakuyomuScraper {
         // Extract text from paragraphs
         let paragraphs: Vec<String> = content_elem
             .select(&self.selectors.paragraph)
-            .map(|p| p.text().collect::<String>().trim().to_string())
+            .map(|p| element_text(p, self.config.furigana).trim().to_string())
             .filter(|s| !s.is_empty())
             .collect();
 
         if paragraphs.is_empty() {
             // Fall back to all text
-            let text = content_elem.text().collect::<String>().trim().to_string();
+            let text = element_text(content_elem, self.config.furigana)
+                .trim()
+                .to_string();
             return Ok(text);
         }
 
@@ -222,7 +476,7 @@ mod tests {
 
     #[test]
     fn test_url_patterns() {
-        let scraper = KakuyomuScraper::new(ScrapingConfig::default());
+        let scraper = KakuyomuScraper::new(ScrapingConfig::default(), Arc::default());
 
         // Valid URLs
         assert!(scraper.can_handle("https://kakuyomu.jp/works/1234567890"));
@@ -235,6 +489,79 @@ mod tests {
         assert!(!scraper.can_handle("https://kakuyomu.jp/users/123"));
     }
 
+    #[test]
+    fn test_extract_work_details() {
+        let scraper = KakuyomuScraper::new(ScrapingConfig::default(), Arc::default());
+        let html = r#"<script id="__NEXT_DATA__" type="application/json">
+            {"props":{"pageProps":{"__APOLLO_STATE__":{
+              "Work:999":{"__typename":"Work","id":"999","introduction":"別の作品"},
+              "Work:123":{"__typename":"Work","id":"123","introduction":" あらすじ\n二行目 ","tagLabels":["異世界","ファンタジー"],"author":{"__ref":"UserAccount:7"}},
+              "UserAccount:7":{"__typename":"UserAccount","activityName":"作者名"}
+            }}}}
+            </script>"#;
+        let doc = Html::parse_document(html);
+
+        let (synopsis, tags) = scraper.extract_work_details(&doc, "123");
+        assert_eq!(synopsis, "あらすじ\n二行目");
+        assert_eq!(tags, vec!["異世界", "ファンタジー"]);
+        assert_eq!(scraper.extract_author(&doc, "123"), "作者名");
+        assert_eq!(scraper.extract_author(&doc, "999"), "");
+
+        let (synopsis, tags) =
+            scraper.extract_work_details(&Html::parse_document("<p></p>"), "123");
+        assert!(synopsis.is_empty() && tags.is_empty());
+    }
+
+    #[test]
+    fn test_single_episode_status() {
+        let scraper = KakuyomuScraper::new(ScrapingConfig::default(), Arc::default());
+        let page = |status: &str| {
+            Html::parse_document(&format!(
+                r#"<a class="WorkTocSection_link__x" href="/works/123/episodes/456/">
+                  <div>第1話</div></a>
+                <script id="__NEXT_DATA__" type="application/json">
+                {{"props":{{"pageProps":{{"__APOLLO_STATE__":{{
+                  "Work:123":{{"__typename":"Work","id":"123","serialStatus":"{}"}}
+                }}}}}}}}
+                </script>"#,
+                status
+            ))
+        };
+
+        let doc = page("COMPLETED");
+        let chapters = scraper.extract_chapters(&doc);
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(
+            chapters[0].url,
+            "https://kakuyomu.jp/works/123/episodes/456"
+        );
+        assert_eq!(chapters[0].episode_id, "456");
+        assert_eq!(scraper.completed(&doc, "123"), Some(true));
+        assert_eq!(scraper.completed(&page("RUNNING"), "123"), Some(false));
+        assert_eq!(scraper.completed(&doc, "999"), None);
+    }
+
+    #[test]
+    fn test_extract_work_links() {
+        let scraper = KakuyomuScraper::new(ScrapingConfig::default(), Arc::default());
+        let html = r#"
+            <div>
+              <a href="/works/1111111111">作品A</a>
+              <a href="/works/1111111111/episodes/222">第1話</a>
+              <a href="https://kakuyomu.jp/works/3333333333">作品B</a>
+              <a href="/works/1111111111"><img alt=""></a>
+            </div>
+        "#;
+        let doc = Html::parse_document(html);
+
+        let works = scraper.extract_work_links(&doc);
+        assert_eq!(works.len(), 2);
+        assert_eq!(works[0].novel_id, "1111111111");
+        assert_eq!(works[0].url, "https://kakuyomu.jp/works/1111111111");
+        assert_eq!(works[0].title, "作品A");
+        assert_eq!(works[1].title, "作品B");
+    }
+
     #[test]
     fn test_extract_work_id() {
         assert_eq!(
@@ -0,0 +1,224 @@
+//! Per-host request budget shared by scrapers.
+//!
+//! Novels downloaded in parallel (`--jobs`, batch files) often live on the
+//! same site. Each host gets a minimum spacing between request starts and a
+//! cap on requests in flight at once, both counted across every scraper
+//! built with the same [`HostLimiter`], so parallelism never multiplies the
+//! load on one site.
+
+use super::robots::RobotsCache;
+use crate::config::ScrapingConfig;
+use crate::error::ScraperError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// Request state of every host contacted so far.
+///
+/// Clones share the same state; give every scraper of a run a clone of one
+/// limiter so they count against the same budget.
+#[derive(Clone, Default)]
+pub struct HostLimiter {
+    hosts: Arc<Mutex<HashMap<String, HostState>>>,
+    /// robots.txt rules, with `respect_robots_txt`.
+    robots: RobotsCache,
+}
+
+/// Budget bookkeeping for one host.
+struct HostState {
+    /// When the next request may start (None before the first).
+    next_at: Option<Instant>,
+    /// Requests that may be in flight at once.
+    in_flight: Arc<Semaphore>,
+    /// Capacity `in_flight` was created with.
+    max_in_flight: usize,
+}
+
+/// Held for the duration of a request; dropping it lets the next request
+/// to the same host proceed.
+pub struct HostPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl HostLimiter {
+    /// Creates a limiter with no hosts contacted yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits until `url`'s host may be requested again and returns a permit
+    /// to hold until the response has been read.
+    ///
+    /// Requests to a host start at least the configured delay apart (its
+    /// `host_delays` entry, or `delay_between_requests_sec` with jitter), and
+    /// at most `max_requests_per_host` run at once. With `respect_robots_txt`,
+    /// the host's `Crawl-delay` raises the delay, and a disallowed page is
+    /// refused with [`ScraperError::RobotsDisallowed`].
+    pub async fn acquire(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        config: &ScrapingConfig,
+    ) -> Result<HostPermit, ScraperError> {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let mut delay_sec = config.host_delay_sec(&host);
+        if config.respect_robots_txt {
+            let rules = self.robots.rules_for(client, url).await;
+            if !rules.allows(&robots_path(url)) {
+                return Err(ScraperError::RobotsDisallowed(url.to_string()));
+            }
+            delay_sec = delay_sec.max(rules.crawl_delay_sec.unwrap_or(0.0));
+        }
+
+        let in_flight = {
+            let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+            host_state(&mut hosts, &host, config.max_requests_per_host)
+                .in_flight
+                .clone()
+        };
+        let permit = in_flight
+            .acquire_owned()
+            .await
+            .expect("host semaphore is never closed");
+
+        if delay_sec > 0.0 {
+            let slot = {
+                let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+                reserve_slot(
+                    host_state(&mut hosts, &host, config.max_requests_per_host),
+                    Instant::now(),
+                    Duration::from_secs_f64(delay_sec),
+                )
+            };
+            tokio::time::sleep_until(slot).await;
+        }
+        Ok(HostPermit { _permit: permit })
+    }
+}
+
+/// Path and query of a URL, as robots.txt rules match them.
+fn robots_path(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(parsed) => match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        },
+        Err(_) => "/".to_string(),
+    }
+}
+
+/// The host's state, created on first contact.
+///
+/// Jobs with different `max_requests_per_host` (e.g. batch profiles) may share
+/// a host; a caller asking for a different cap gets a new semaphore with it.
+/// Requests already holding a permit from the old one finish undisturbed.
+fn host_state<'a>(
+    hosts: &'a mut HashMap<String, HostState>,
+    host: &str,
+    max_in_flight: usize,
+) -> &'a mut HostState {
+    let max_in_flight = max_in_flight.max(1);
+    let state = hosts.entry(host.to_string()).or_insert_with(|| HostState {
+        next_at: None,
+        in_flight: Arc::new(Semaphore::new(max_in_flight)),
+        max_in_flight,
+    });
+    if state.max_in_flight != max_in_flight {
+        state.in_flight = Arc::new(Semaphore::new(max_in_flight));
+        state.max_in_flight = max_in_flight;
+    }
+    state
+}
+
+/// Reserves the host's next request slot and returns when it starts.
+fn reserve_slot(state: &mut HostState, now: Instant, delay: Duration) -> Instant {
+    let slot = state.next_at.filter(|t| *t > now).unwrap_or(now);
+    state.next_at = Some(slot + delay);
+    slot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_slot_per_host() {
+        let mut hosts = HashMap::new();
+        let now = Instant::now();
+        let delay = Duration::from_secs(1);
+        let mut reserve =
+            |host: &str, at: Instant| reserve_slot(host_state(&mut hosts, host, 1), at, delay);
+
+        assert_eq!(reserve("a", now), now);
+        assert_eq!(reserve("a", now), now + delay);
+        assert_eq!(reserve("a", now), now + delay * 2);
+        // Other hosts aren't held up
+        assert_eq!(reserve("b", now), now);
+        // Idle hosts start again immediately
+        let later = now + delay * 10;
+        assert_eq!(reserve("a", later), later);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_cap() {
+        let config = ScrapingConfig {
+            delay_between_requests_sec: 0.0,
+            max_requests_per_host: 2,
+            ..Default::default()
+        };
+        let client = reqwest::Client::new();
+        let limiter = HostLimiter::new();
+        let url = "https://limiter-test.example/page";
+        let first = limiter.acquire(&client, url, &config).await.unwrap();
+        let _second = limiter
+            .clone()
+            .acquire(&client, url, &config)
+            .await
+            .unwrap();
+
+        // A third request waits until one of the others finishes
+        let third = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire(&client, url, &config),
+        );
+        assert!(third.await.is_err());
+        drop(first);
+        let third = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire(&client, url, &config),
+        );
+        assert!(third.await.is_ok());
+
+        // Separate limiters don't share a budget
+        let separate = HostLimiter::new();
+        let other = tokio::time::timeout(
+            Duration::from_millis(50),
+            separate.acquire(&client, url, &config),
+        );
+        assert!(other.await.is_ok());
+    }
+
+    #[test]
+    fn test_host_state_follows_limit() {
+        let mut hosts = HashMap::new();
+        let first = host_state(&mut hosts, "a", 2).in_flight.clone();
+        assert_eq!(first.available_permits(), 2);
+        // The same limit keeps the semaphore (and its outstanding permits)
+        assert!(Arc::ptr_eq(
+            &first,
+            &host_state(&mut hosts, "a", 2).in_flight
+        ));
+
+        let resized = host_state(&mut hosts, "a", 4).in_flight.clone();
+        assert_eq!(resized.available_permits(), 4);
+        assert_eq!(
+            host_state(&mut hosts, "a", 0).in_flight.available_permits(),
+            1
+        );
+    }
+}
@@ -3,17 +3,24 @@
 //! This module defines the interface that all scrapers must implement,
 //! along with common data types for novels and chapters.
 
+#[cfg(feature = "conformance")]
+pub mod fixtures;
 mod kakuyomu;
+pub mod limiter;
 mod pixiv;
+pub mod robots;
 mod syosetu;
 
 pub use kakuyomu::KakuyomuScraper;
+pub use limiter::HostLimiter;
 pub use pixiv::PixivScraper;
 pub use syosetu::SyosetuScraper;
 
-use crate::config::ScrapingConfig;
+use crate::config::{FuriganaMode, HttpVersion, ScrapingConfig};
 use crate::error::ScraperError;
 use async_trait::async_trait;
+use std::collections::HashSet;
+use std::path::Path;
 use std::time::Duration;
 
 /// Information about a novel.
@@ -27,6 +34,31 @@ pub struct NovelInfo {
 
     /// Unique identifier for the novel on the platform.
     pub novel_id: String,
+
+    /// The author's synopsis (empty if the site doesn't provide one).
+    pub synopsis: String,
+
+    /// Tags/keywords as listed on the site.
+    pub tags: Vec<String>,
+
+    /// The author's pen name (empty if the site doesn't show one).
+    pub author: String,
+
+    /// Whether the site marks the novel as finished, if it says.
+    pub completed: Option<bool>,
+}
+
+/// A novel found on the user's bookmark/follow list on a site.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookmarkedNovel {
+    /// URL of the novel's main page.
+    pub url: String,
+
+    /// Unique identifier for the novel on the platform.
+    pub novel_id: String,
+
+    /// Title as shown in the list.
+    pub title: String,
 }
 
 /// Information about a single chapter.
@@ -40,6 +72,10 @@ pub struct ChapterInfo {
 
     /// Chapter number (1-based).
     pub number: u32,
+
+    /// The platform's own ID for the chapter, taken from its URL. Unlike
+    /// `number`, it doesn't shift when the author inserts or deletes chapters.
+    pub episode_id: String,
 }
 
 /// Represents the chapter list for a novel.
@@ -66,6 +102,28 @@ impl ChapterList {
         }
     }
 
+    /// Returns the highest chapter number, or 1 for one-shots.
+    ///
+    /// Equals [`len`](Self::len) unless the list has gaps.
+    pub fn last_number(&self) -> u32 {
+        match self {
+            ChapterList::Chapters(chapters) => chapters.iter().map(|c| c.number).max().unwrap_or(0),
+            ChapterList::OneShot => 1,
+        }
+    }
+
+    /// Returns chapter numbers below the last one that aren't listed, e.g.
+    /// entries deleted from a series that keeps its numbering.
+    pub fn missing_numbers(&self) -> Vec<u32> {
+        let ChapterList::Chapters(chapters) = self else {
+            return Vec::new();
+        };
+        let listed: HashSet<u32> = chapters.iter().map(|c| c.number).collect();
+        (1..self.last_number())
+            .filter(|n| !listed.contains(n))
+            .collect()
+    }
+
     /// Returns true if there are no chapters.
     pub fn is_empty(&self) -> bool {
         match self {
@@ -98,6 +156,12 @@ pub trait Scraper: Send + Sync {
 
     /// Downloads the content of a single chapter.
     async fn download_chapter(&self, chapter_url: &str) -> Result<String, ScraperError>;
+
+    /// Returns true if episode IDs are positions in the chapter list, so
+    /// deleting a chapter shifts the IDs of every chapter after it.
+    fn positional_episode_ids(&self) -> bool {
+        false
+    }
 }
 
 /// Registry of available scrapers.
@@ -106,12 +170,35 @@ pub struct ScraperRegistry {
 }
 
 impl ScraperRegistry {
-    /// Creates a new registry with all available scrapers.
-    pub fn new(config: &ScrapingConfig) -> Self {
+    /// Creates a new registry with all available scrapers, loading login
+    /// cookies from `cookie_dir` (none if `None`).
+    pub fn new(config: &ScrapingConfig, cookie_dir: Option<&Path>) -> Self {
+        Self::with_limiter(config, cookie_dir, HostLimiter::new())
+    }
+
+    /// Creates a registry whose scrapers count requests against `limiter`,
+    /// so registries built for parallel jobs share one per-host budget.
+    pub fn with_limiter(
+        config: &ScrapingConfig,
+        cookie_dir: Option<&Path>,
+        limiter: HostLimiter,
+    ) -> Self {
         let scrapers: Vec<Box<dyn Scraper>> = vec![
-            Box::new(SyosetuScraper::new(config.clone())),
-            Box::new(KakuyomuScraper::new(config.clone())),
-            Box::new(PixivScraper::new(config.clone())),
+            Box::new(
+                SyosetuScraper::new(
+                    config.clone(),
+                    site_cookie_jar(cookie_dir, "syosetu", config.debug),
+                )
+                .with_limiter(limiter.clone()),
+            ),
+            Box::new(
+                KakuyomuScraper::new(
+                    config.clone(),
+                    site_cookie_jar(cookie_dir, "kakuyomu", config.debug),
+                )
+                .with_limiter(limiter.clone()),
+            ),
+            Box::new(PixivScraper::new(config.clone()).with_limiter(limiter)),
         ];
 
         Self { scrapers }
@@ -131,19 +218,191 @@ impl ScraperRegistry {
     }
 }
 
-/// Common HTTP client configuration for scrapers.
-pub fn create_http_client() -> Result<reqwest::Client, reqwest::Error> {
-    reqwest::Client::builder()
+/// Client builder with the browser user agent and the connection settings
+/// from `[scraping]`.
+pub fn http_client_builder(config: &ScrapingConfig) -> reqwest::ClientBuilder {
+    let keepalive =
+        (config.tcp_keepalive_sec > 0).then(|| Duration::from_secs(config.tcp_keepalive_sec));
+    let mut builder = reqwest::Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .cookie_store(true)
-        .timeout(Duration::from_secs(30))
-        .build()
+        .timeout(Duration::from_secs(config.timeout_sec))
+        .tcp_keepalive(keepalive);
+    if let Some(max) = config.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max);
+    }
+    match config.http_version {
+        HttpVersion::Auto => builder,
+        HttpVersion::Http1 => builder.http1_only(),
+        HttpVersion::Http2 => builder.http2_prior_knowledge(),
+    }
+}
+
+/// Common HTTP client configuration for scrapers.
+pub fn create_http_client(config: &ScrapingConfig) -> Result<reqwest::Client, reqwest::Error> {
+    http_client_builder(config).cookie_store(true).build()
+}
+
+/// Loads the most recent cookie file for a site from `cookie_dir` into a jar.
+///
+/// No directory, or a missing or unreadable cookie file, yields an empty
+/// jar, since login is optional for most pages.
+pub fn site_cookie_jar(
+    cookie_dir: Option<&Path>,
+    site: &str,
+    debug: bool,
+) -> std::sync::Arc<reqwest::cookie::Jar> {
+    let loaded =
+        cookie_dir.and_then(|dir| crate::cookies::load_netscape_cookie_jar(dir, &[site]).ok());
+
+    match loaded {
+        Some((jar, source)) => {
+            if debug {
+                match source {
+                    Some(path) => {
+                        eprintln!("[{} Debug] Loaded cookie file: {}", site, path.display())
+                    }
+                    None => eprintln!("[{} Debug] No cookie file found for {}", site, site),
+                }
+            }
+            jar
+        }
+        None => std::sync::Arc::new(reqwest::cookie::Jar::default()),
+    }
+}
+
+/// HTTP client for scrapers that share a preloaded cookie jar.
+pub fn create_http_client_with_jar(
+    config: &ScrapingConfig,
+    jar: std::sync::Arc<reqwest::cookie::Jar>,
+) -> Result<reqwest::Client, reqwest::Error> {
+    http_client_builder(config).cookie_provider(jar).build()
+}
+
+/// Most pages a [`PageCache`] holds before starting over.
+const MAX_CACHED_PAGES: usize = 200;
+
+/// Table-of-contents pages fetched during a run, keyed by URL.
+///
+/// Novel info, the chapter list, and one-shot detection all read a novel's
+/// main page; caching it (and the list's other pages) for the life of the
+/// scraper means each is fetched once. Chapter pages aren't cached.
+#[derive(Debug, Default)]
+pub(crate) struct PageCache {
+    pages: std::sync::Mutex<std::collections::HashMap<String, String>>,
 }
 
-/// Applies rate limiting delay.
-pub async fn rate_limit(delay_sec: f64) {
-    if delay_sec > 0.0 {
-        tokio::time::sleep(Duration::from_secs_f64(delay_sec)).await;
+impl PageCache {
+    /// A cached page's body.
+    pub(crate) fn get(&self, url: &str) -> Option<String> {
+        let pages = self.pages.lock().unwrap_or_else(|e| e.into_inner());
+        pages.get(Self::key(url)).cloned()
+    }
+
+    /// Caches a page's body.
+    pub(crate) fn insert(&self, url: &str, body: &str) {
+        let mut pages = self.pages.lock().unwrap_or_else(|e| e.into_inner());
+        if pages.len() >= MAX_CACHED_PAGES {
+            pages.clear();
+        }
+        pages.insert(Self::key(url).to_string(), body.to_string());
+    }
+
+    /// URLs with and without a trailing slash are the same page.
+    fn key(url: &str) -> &str {
+        url.trim_end_matches('/')
+    }
+}
+
+/// Notices sites show in place of a chapter: deletions, private works, age
+/// gates, and error pages.
+const PLACEHOLDER_MARKERS: &[&str] = &[
+    "この作品は削除されました",
+    "このエピソードは削除されました",
+    "このエピソードは非公開",
+    "この作品は非公開",
+    "該当作品は削除されたか",
+    "作品は存在しません",
+    "年齢確認",
+    "年齢認証",
+    "18歳以上ですか",
+    "18歳未満の方",
+    "エラーが発生しました",
+];
+
+/// Chapters at least this long are taken as real text even if they mention
+/// a placeholder phrase; notice pages are short.
+const PLACEHOLDER_MAX_CHARS: usize = 500;
+
+/// Checks downloaded chapter text before it's saved, so an empty page or a
+/// deletion notice or age gate never gets cached and translated.
+pub fn validate_chapter(content: &str, min_chars: usize) -> Result<(), ScraperError> {
+    let chars = content.chars().filter(|c| !c.is_whitespace()).count();
+    if chars == 0 {
+        return Err(ScraperError::InvalidContent("chapter is empty".to_string()));
+    }
+    if chars < min_chars {
+        return Err(ScraperError::InvalidContent(format!(
+            "chapter has only {} characters (minimum {})",
+            chars, min_chars
+        )));
+    }
+    if chars < PLACEHOLDER_MAX_CHARS
+        && let Some(marker) = PLACEHOLDER_MARKERS.iter().find(|m| content.contains(*m))
+    {
+        return Err(ScraperError::InvalidContent(format!(
+            "looks like a placeholder page (\"{}\")",
+            marker
+        )));
+    }
+    Ok(())
+}
+
+/// Formats ruby base text with its reading according to the furigana mode.
+pub fn render_ruby(base: &str, reading: &str, mode: FuriganaMode) -> String {
+    if reading.is_empty() {
+        return base.to_string();
+    }
+    match mode {
+        FuriganaMode::Drop => base.to_string(),
+        // Originals stay plain text; exports turn these into ruby tags
+        FuriganaMode::Parens | FuriganaMode::Html => format!("{}({})", base, reading),
+    }
+}
+
+/// Extracts the text of an HTML element, rendering `<ruby>` annotations
+/// according to the furigana mode.
+///
+/// `<rp>` fallback parentheses are always dropped.
+pub(crate) fn element_text(elem: scraper::ElementRef, mode: FuriganaMode) -> String {
+    let mut text = String::new();
+    push_element_text(elem, mode, &mut text);
+    text
+}
+
+fn push_element_text(elem: scraper::ElementRef, mode: FuriganaMode, out: &mut String) {
+    for child in elem.children() {
+        if let scraper::node::Node::Text(t) = child.value() {
+            out.push_str(t);
+            continue;
+        }
+        let Some(child_elem) = scraper::ElementRef::wrap(child) else {
+            continue;
+        };
+        match child_elem.value().name() {
+            "rt" | "rp" => {}
+            "ruby" => {
+                let mut base = String::new();
+                push_element_text(child_elem, FuriganaMode::Drop, &mut base);
+                let reading: String = child_elem
+                    .children()
+                    .filter_map(scraper::ElementRef::wrap)
+                    .filter(|e| e.value().name() == "rt")
+                    .flat_map(|e| e.text())
+                    .collect();
+                out.push_str(&render_ruby(&base, reading.trim(), mode));
+            }
+            _ => push_element_text(child_elem, mode, out),
+        }
     }
 }
 
@@ -162,14 +421,90 @@ mod tests {
                 title: "Ch 1".to_string(),
                 url: "http://example.com/1".to_string(),
                 number: 1,
+                episode_id: "1".to_string(),
             },
             ChapterInfo {
                 title: "Ch 2".to_string(),
                 url: "http://example.com/2".to_string(),
                 number: 2,
+                episode_id: "2".to_string(),
             },
         ]);
         assert_eq!(chapters.len(), 2);
         assert!(!chapters.is_oneshot());
+        assert!(chapters.missing_numbers().is_empty());
+    }
+
+    #[test]
+    fn test_chapter_list_gaps() {
+        let chapter = |number: u32| ChapterInfo {
+            title: format!("Ch {}", number),
+            url: format!("http://example.com/{}", number),
+            number,
+            episode_id: number.to_string(),
+        };
+        let chapters = ChapterList::Chapters(vec![chapter(1), chapter(3), chapter(6)]);
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters.last_number(), 6);
+        assert_eq!(chapters.missing_numbers(), vec![2, 4, 5]);
+        assert_eq!(ChapterList::OneShot.last_number(), 1);
+    }
+
+    #[test]
+    fn test_element_text_furigana() {
+        let html = scraper::Html::parse_fragment(
+            "<p>彼は<ruby><rb>漢字</rb><rp>(</rp><rt>かんじ</rt><rp>)</rp></ruby>を読む</p>",
+        );
+        let selector = scraper::Selector::parse("p").unwrap();
+        let p = html.select(&selector).next().unwrap();
+
+        assert_eq!(element_text(p, FuriganaMode::Drop), "彼は漢字を読む");
+        assert_eq!(
+            element_text(p, FuriganaMode::Parens),
+            "彼は漢字(かんじ)を読む"
+        );
+        assert_eq!(
+            element_text(p, FuriganaMode::Html),
+            "彼は漢字(かんじ)を読む"
+        );
+    }
+
+    #[test]
+    fn test_validate_chapter() {
+        assert!(validate_chapter("彼は剣を抜いた。そして走り出した。", 10).is_ok());
+        assert!(validate_chapter("短い。", 0).is_ok());
+
+        for content in [
+            "",
+            " \n\u{3000}",
+            "短い。",
+            "この作品は削除されました。",
+            "あなたは18歳以上ですか？\nはい　いいえ",
+        ] {
+            let err = validate_chapter(content, 10).unwrap_err();
+            assert!(
+                matches!(err, ScraperError::InvalidContent(_)),
+                "{}",
+                content
+            );
+        }
+
+        // A long chapter may mention the phrase in passing
+        let chapter = format!(
+            "「年齢確認をお願いします」と受付嬢は言った。{}",
+            "続く。".repeat(200)
+        );
+        assert!(validate_chapter(&chapter, 10).is_ok());
+    }
+
+    #[test]
+    fn test_page_cache() {
+        let cache = PageCache::default();
+        assert_eq!(cache.get("https://kakuyomu.jp/works/1"), None);
+        cache.insert("https://kakuyomu.jp/works/1/", "<html>目次</html>");
+        assert_eq!(
+            cache.get("https://kakuyomu.jp/works/1").as_deref(),
+            Some("<html>目次</html>")
+        );
     }
 }
@@ -3,9 +3,12 @@
 //! Supports downloading novels from Pixiv's novel section, including
 //! both individual novels and series.
 
-use super::{ChapterInfo, ChapterList, NovelInfo, Scraper, rate_limit};
+#[cfg(feature = "conformance")]
+use super::fixtures::PageSource;
+use super::limiter::HostLimiter;
+use super::{ChapterInfo, ChapterList, NovelInfo, Scraper, http_client_builder, render_ruby};
 use crate::config::Config;
-use crate::config::ScrapingConfig;
+use crate::config::{FuriganaMode, ScrapingConfig};
 use crate::cookies::load_netscape_cookie_jar;
 use crate::error::ScraperError;
 use async_trait::async_trait;
@@ -29,6 +32,13 @@ static SERIES_PATTERN: LazyLock<Regex> =
 static UNICODE_ESCAPE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\\u([0-9a-fA-F]{4})").unwrap());
 
+/// Regex for HTML tags in descriptions.
+static HTML_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+/// Regex for Pixiv's `[[rb:漢字 > かんじ]]` ruby markup.
+static RUBY_MARKUP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\[rb:\s*(.+?)\s*>\s*(.+?)\s*\]\]").unwrap());
+
 /// URL type for Pixiv.
 #[derive(Debug, Clone, PartialEq)]
 enum PixivUrlType {
@@ -45,6 +55,42 @@ struct ApiResponse<T> {
     body: Option<T>,
 }
 
+impl<T> ApiResponse<T> {
+    /// Returns the body, or the API's error.
+    fn into_body(self) -> Result<T, ScraperError> {
+        if self.error {
+            if is_age_restricted_message(&self.message) {
+                return Err(age_restricted_error());
+            }
+            return Err(ScraperError::NotFound(format!(
+                "API error: {}",
+                self.message
+            )));
+        }
+
+        self.body
+            .ok_or_else(|| ScraperError::ParseError("API response missing body".to_string()))
+    }
+}
+
+/// Returns true if an API error message says the work is hidden behind
+/// pixiv's age setting rather than gone.
+fn is_age_restricted_message(message: &str) -> bool {
+    ["R-18", "R18", "年齢", "閲覧制限"]
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+/// Error for an R-18 work the session can't see. pixiv has no
+/// confirmation page to click through: the account must be logged in and
+/// have R-18 works turned on in its viewing settings.
+fn age_restricted_error() -> ScraperError {
+    ScraperError::LoginRequired(
+        "R-18 work: needs login cookies from an account with R-18 works shown in its viewing settings (see `tsundoku cookies import`)"
+            .to_string(),
+    )
+}
+
 /// Novel info from API.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -55,6 +101,15 @@ struct NovelBody {
     content: Option<String>,
     #[serde(default)]
     series_id: Option<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: JsonValue,
+    #[serde(default)]
+    user_name: String,
+    /// 0 for all ages, 1 for R-18, 2 for R-18G.
+    #[serde(default)]
+    x_restrict: u8,
 }
 
 /// Series info from API.
@@ -64,6 +119,14 @@ struct NovelBody {
 struct SeriesBody {
     id: String,
     title: String,
+    #[serde(default)]
+    caption: String,
+    #[serde(default)]
+    tags: JsonValue,
+    #[serde(default)]
+    user_name: String,
+    #[serde(default)]
+    is_concluded: bool,
 }
 
 /// Series content page from API.
@@ -99,6 +162,10 @@ struct SeriesContent {
 pub struct PixivScraper {
     client: reqwest::Client,
     config: ScrapingConfig,
+    limiter: HostLimiter,
+    /// Pages served in place of the network, for conformance tests.
+    #[cfg(feature = "conformance")]
+    fixtures: Option<Arc<dyn PageSource>>,
 }
 
 impl PixivScraper {
@@ -156,15 +223,32 @@ impl PixivScraper {
             }
         };
 
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        let client = http_client_builder(&config)
             .default_headers(headers)
             .cookie_provider(cookie_jar)
-            .timeout(std::time::Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client, config }
+        Self {
+            client,
+            config,
+            limiter: HostLimiter::new(),
+            #[cfg(feature = "conformance")]
+            fixtures: None,
+        }
+    }
+
+    /// Counts requests against `limiter`, shared with other scrapers.
+    pub fn with_limiter(mut self, limiter: HostLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Reads every page from `source` instead of the network.
+    #[cfg(feature = "conformance")]
+    pub fn with_fixtures(mut self, source: Arc<dyn PageSource>) -> Self {
+        self.fixtures = Some(source);
+        self
     }
 
     /// Parses a Pixiv URL to determine its type.
@@ -183,7 +267,19 @@ impl PixivScraper {
         &self,
         url: &str,
     ) -> Result<T, ScraperError> {
-        rate_limit(self.config.delay_between_requests_sec).await;
+        #[cfg(feature = "conformance")]
+        if let Some(fixtures) = &self.fixtures {
+            let body = fixtures.page(url)?;
+            let api_response: ApiResponse<T> = serde_json::from_str(&body).map_err(|e| {
+                ScraperError::ParseError(format!("Failed to parse API response: {}", e))
+            })?;
+            return api_response.into_body();
+        }
+
+        let _permit = self
+            .limiter
+            .acquire(&self.client, url, &self.config)
+            .await?;
 
         let response = self.client.get(url).send().await?;
         let status = response.status();
@@ -219,11 +315,11 @@ impl PixivScraper {
             ScraperError::ParseError(format!("Failed to read response body: {}", e))
         })?;
 
-        if self.config.debug {
-            if let Ok(json_value) = serde_json::from_slice::<JsonValue>(&body_bytes) {
-                eprintln!("[Pixiv Debug] JSON key dump for {}", url);
-                dump_json_keys(&json_value, "root");
-            }
+        if self.config.debug
+            && let Ok(json_value) = serde_json::from_slice::<JsonValue>(&body_bytes)
+        {
+            eprintln!("[Pixiv Debug] JSON key dump for {}", url);
+            dump_json_keys(&json_value, "root");
         }
 
         if !content_type.contains("application/json") {
@@ -255,16 +351,7 @@ impl PixivScraper {
             ScraperError::ParseError(format!("Failed to parse API response: {}", e))
         })?;
 
-        if api_response.error {
-            return Err(ScraperError::NotFound(format!(
-                "API error: {}",
-                api_response.message
-            )));
-        }
-
-        api_response
-            .body
-            .ok_or_else(|| ScraperError::ParseError("API response missing body".to_string()))
+        api_response.into_body()
     }
 
     /// Gets all chapters in a series with pagination.
@@ -315,6 +402,7 @@ impl PixivScraper {
                     title,
                     url: content.id.clone(), // Store ID as URL for later retrieval
                     number: content.series.content_order,
+                    episode_id: content.id.clone(),
                 });
             }
 
@@ -330,15 +418,26 @@ impl PixivScraper {
                 .unwrap_or(last_order);
         }
 
-        // Sort by order to ensure correct sequence
-        all_chapters.sort_by_key(|c| c.number);
+        number_series_chapters(&mut all_chapters);
+        Ok(all_chapters)
+    }
+}
 
-        // Renumber chapters sequentially (1-based)
-        for (idx, chapter) in all_chapters.iter_mut().enumerate() {
+/// Sorts series chapters and settles their numbers.
+///
+/// Chapters keep their `content_order` as the number, so an entry deleted or
+/// hidden from the series leaves a gap instead of shifting every later
+/// chapter down. Orders that can't serve as numbers (zero or duplicated) fall
+/// back to sequential numbering.
+fn number_series_chapters(chapters: &mut [ChapterInfo]) {
+    chapters.sort_by_key(|c| c.number);
+
+    let usable = chapters.first().is_some_and(|c| c.number >= 1)
+        && chapters.windows(2).all(|w| w[0].number < w[1].number);
+    if !usable {
+        for (idx, chapter) in chapters.iter_mut().enumerate() {
             chapter.number = (idx + 1) as u32;
         }
-
-        Ok(all_chapters)
     }
 }
 
@@ -361,10 +460,10 @@ fn log_decode_failure(
     eprintln!("[Pixiv Debug] Body length: {} bytes", body.len());
     eprintln!("[Pixiv Debug] Body preview: {}", preview);
 
-    if let Some(value) = json_hint {
-        if let Ok(pretty) = serde_json::to_string_pretty(&value) {
-            eprintln!("[Pixiv Debug] Parsed JSON preview: {}", pretty);
-        }
+    if let Some(value) = json_hint
+        && let Ok(pretty) = serde_json::to_string_pretty(&value)
+    {
+        eprintln!("[Pixiv Debug] Parsed JSON preview: {}", pretty);
     }
 }
 
@@ -403,6 +502,44 @@ fn dump_json_keys(value: &JsonValue, path: &str) {
     }
 }
 
+/// Converts an HTML description (with `<br />` line breaks) to plain text.
+fn description_text(html: &str) -> String {
+    let with_breaks = html.replace("<br />", "\n").replace("<br>", "\n");
+    HTML_TAG_REGEX
+        .replace_all(&with_breaks, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Collects tag names from either `{"tags": [{"tag": "..."}]}` (novels) or
+/// `["..."]` (series).
+fn tag_names(tags: &JsonValue) -> Vec<String> {
+    let list = tags.get("tags").unwrap_or(tags);
+    list.as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().or_else(|| item.get("tag")?.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Converts `[[rb:base > reading]]` markup according to the furigana mode.
+fn convert_ruby_markup(text: &str, mode: FuriganaMode) -> String {
+    RUBY_MARKUP_REGEX
+        .replace_all(text, |caps: &regex::Captures| {
+            render_ruby(&caps[1], &caps[2], mode)
+        })
+        .to_string()
+}
+
 /// Unescapes Unicode escape sequences like \u3042 to actual characters.
 fn unescape_unicode(text: &str) -> String {
     if text.is_empty() || !text.contains("\\u") {
@@ -448,6 +585,10 @@ impl Scraper for PixivScraper {
                     title: unescape_unicode(&body.title),
                     base_url: url.to_string(),
                     novel_id,
+                    synopsis: description_text(&unescape_unicode(&body.description)),
+                    tags: tag_names(&body.tags),
+                    author: unescape_unicode(&body.user_name),
+                    completed: Some(true),
                 })
             }
             PixivUrlType::Series(series_id) => {
@@ -458,6 +599,10 @@ impl Scraper for PixivScraper {
                     title: unescape_unicode(&body.title),
                     base_url: url.to_string(),
                     novel_id: series_id,
+                    synopsis: description_text(&unescape_unicode(&body.caption)),
+                    tags: tag_names(&body.tags),
+                    author: unescape_unicode(&body.user_name),
+                    completed: Some(body.is_concluded),
                 })
             }
         }
@@ -512,11 +657,21 @@ impl Scraper for PixivScraper {
             );
         }
 
-        let content = body
-            .content
-            .ok_or_else(|| ScraperError::NotFound("Novel content not found".to_string()))?;
+        let content = match body.content {
+            Some(content) if !content.trim().is_empty() => content,
+            // The API leaves out the text of R-18 works the session may not see
+            _ if body.x_restrict > 0 => return Err(age_restricted_error()),
+            _ => {
+                return Err(ScraperError::NotFound(
+                    "Novel content not found".to_string(),
+                ));
+            }
+        };
 
-        Ok(unescape_unicode(&content))
+        Ok(convert_ruby_markup(
+            &unescape_unicode(&content),
+            self.config.furigana,
+        ))
     }
 }
 
@@ -568,9 +723,78 @@ mod tests {
         assert_eq!(unescape_unicode(input), expected);
     }
 
+    #[test]
+    fn test_convert_ruby_markup() {
+        let input = "彼は[[rb:漢字 > かんじ]]を読む";
+        assert_eq!(
+            convert_ruby_markup(input, FuriganaMode::Drop),
+            "彼は漢字を読む"
+        );
+        assert_eq!(
+            convert_ruby_markup(input, FuriganaMode::Parens),
+            "彼は漢字(かんじ)を読む"
+        );
+    }
+
+    #[test]
+    fn test_description_and_tags() {
+        assert_eq!(
+            description_text("一行目<br />二行目 &amp; <a href=\"x\">リンク</a>"),
+            "一行目\n二行目 & リンク"
+        );
+
+        let novel_tags = serde_json::json!({"tags": [{"tag": "オリジナル"}, {"tag": "恋愛"}]});
+        assert_eq!(tag_names(&novel_tags), vec!["オリジナル", "恋愛"]);
+        let series_tags = serde_json::json!(["ファンタジー"]);
+        assert_eq!(tag_names(&series_tags), vec!["ファンタジー"]);
+        assert!(tag_names(&JsonValue::Null).is_empty());
+    }
+
+    #[test]
+    fn test_number_series_chapters() {
+        let chapter = |number: u32, id: &str| ChapterInfo {
+            title: String::new(),
+            url: id.to_string(),
+            number,
+            episode_id: id.to_string(),
+        };
+
+        // Entry 2 was deleted; later chapters keep their numbers
+        let mut chapters = vec![chapter(3, "c"), chapter(1, "a"), chapter(4, "d")];
+        number_series_chapters(&mut chapters);
+        let numbers: Vec<(u32, &str)> = chapters
+            .iter()
+            .map(|c| (c.number, c.episode_id.as_str()))
+            .collect();
+        assert_eq!(numbers, vec![(1, "a"), (3, "c"), (4, "d")]);
+
+        let mut chapters = vec![chapter(0, "a"), chapter(0, "b")];
+        number_series_chapters(&mut chapters);
+        assert_eq!(chapters[1].number, 2);
+    }
+
     #[test]
     fn test_unescape_unicode_invalid() {
         // Invalid sequences should be preserved
         assert_eq!(unescape_unicode("\\uZZZZ"), "\\uZZZZ");
     }
+
+    #[test]
+    fn test_age_restricted_responses() {
+        let response: ApiResponse<NovelBody> = serde_json::from_str(
+            r#"{"error":true,"message":"R-18作品の閲覧にはログインが必要です","body":null}"#,
+        )
+        .unwrap();
+        assert!(response.into_body().unwrap_err().is_auth());
+
+        let response: ApiResponse<NovelBody> = serde_json::from_str(
+            r#"{"error":true,"message":"該当作品は削除されたか、存在しない作品IDです。","body":null}"#,
+        )
+        .unwrap();
+        assert!(response.into_body().unwrap_err().is_not_found());
+
+        let body: NovelBody =
+            serde_json::from_str(r#"{"id":"1","title":"t","content":"","xRestrict":1}"#).unwrap();
+        assert_eq!(body.x_restrict, 1);
+    }
 }
@@ -0,0 +1,216 @@
+//! Opt-in robots.txt support (`scraping.respect_robots_txt`).
+//!
+//! Each host's robots.txt is fetched once per [`RobotsCache`], which the
+//! scrapers of a run share through their
+//! [`HostLimiter`](super::limiter::HostLimiter). Pages it disallows are
+//! refused before any request is made, and its `Crawl-delay` raises the
+//! spacing between requests to that host.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Product token matched against `User-agent` lines; groups naming it win
+/// over the `*` group.
+const ROBOTS_AGENT: &str = "tsundoku";
+
+/// The robots.txt rules that apply to us on one host.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    /// Path patterns, each allowed (true) or disallowed (false).
+    rules: Vec<(String, bool)>,
+    /// Seconds to wait between requests, if the site asks for it.
+    pub crawl_delay_sec: Option<f64>,
+}
+
+impl RobotsRules {
+    /// Parses robots.txt content, keeping the group for [`ROBOTS_AGENT`] if
+    /// there is one, or the `*` group otherwise.
+    pub fn parse(content: &str) -> Self {
+        let mut ours = None;
+        let mut wildcard = None;
+        // Agents of the group being read, and whether its rules started
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        let mut group = RobotsRules::default();
+
+        let mut finish = |agents: &[String], group: RobotsRules| {
+            if agents.iter().any(|a| a.contains(ROBOTS_AGENT)) {
+                ours.get_or_insert(group);
+            } else if agents.iter().any(|a| a == "*") {
+                wildcard.get_or_insert(group);
+            }
+        };
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        finish(&agents, std::mem::take(&mut group));
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_ascii_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow allows everything
+                    if !value.is_empty() {
+                        group
+                            .rules
+                            .push((value.to_string(), key.trim().eq_ignore_ascii_case("allow")));
+                    }
+                }
+                "crawl-delay" => {
+                    in_rules = true;
+                    group.crawl_delay_sec = value.parse().ok().filter(|d: &f64| *d >= 0.0);
+                }
+                _ => {}
+            }
+        }
+        finish(&agents, group);
+
+        ours.or(wildcard).unwrap_or_default()
+    }
+
+    /// Returns true if `path` (with its query) may be fetched: the longest
+    /// matching pattern decides, with Allow winning ties.
+    pub fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(pattern, _)| pattern_matches(pattern, path))
+            .max_by_key(|(pattern, allow)| (pattern.len(), *allow))
+            .is_none_or(|(_, allow)| *allow)
+    }
+}
+
+/// Matches a robots.txt path pattern, where `*` matches any run of
+/// characters and a trailing `$` anchors the end.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if anchored && i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// Rules fetched so far, by scheme and host.
+///
+/// Clones share the same rules, so each robots.txt is fetched once however
+/// many scrapers hold the cache.
+#[derive(Debug, Clone, Default)]
+pub struct RobotsCache {
+    rules: Arc<Mutex<HashMap<String, Arc<RobotsRules>>>>,
+}
+
+impl RobotsCache {
+    /// The rules for `url`'s host, fetched on first use.
+    ///
+    /// A robots.txt that is missing or can't be fetched allows everything.
+    pub async fn rules_for(&self, client: &reqwest::Client, url: &str) -> Arc<RobotsRules> {
+        let Ok(parsed) = url::Url::parse(url) else {
+            return Arc::default();
+        };
+        let origin = parsed.origin().ascii_serialization();
+        if let Some(rules) = self.lock().get(&origin) {
+            return rules.clone();
+        }
+
+        let rules = Arc::new(fetch(client, &format!("{}/robots.txt", origin)).await);
+        self.lock().insert(origin, rules.clone());
+        rules
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<RobotsRules>>> {
+        self.rules.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Downloads and parses a robots.txt.
+async fn fetch(client: &reqwest::Client, robots_url: &str) -> RobotsRules {
+    let response = match client.get(robots_url).send().await {
+        Ok(response) if response.status().is_success() => response,
+        _ => return RobotsRules::default(),
+    };
+    match response.text().await {
+        Ok(content) => RobotsRules::parse(&content),
+        Err(_) => RobotsRules::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cache_is_per_instance() {
+        let cache = RobotsCache::default();
+        let rules = Arc::new(RobotsRules::parse("User-agent: *\nDisallow: /private\n"));
+        cache
+            .lock()
+            .insert("https://robots-test.example".to_string(), rules.clone());
+
+        // Cached rules are served without a request, to clones as well
+        let client = reqwest::Client::new();
+        let url = "https://robots-test.example/private/page";
+        let shared = cache.clone();
+        assert!(Arc::ptr_eq(&shared.rules_for(&client, url).await, &rules));
+        assert!(RobotsCache::default().lock().is_empty());
+    }
+
+    #[test]
+    fn test_parse_picks_group() {
+        let content = "\
+User-agent: Googlebot
+Disallow: /
+
+User-agent: *
+Disallow: /search
+Allow: /search/help
+Crawl-delay: 5 # be gentle
+
+User-agent: Tsundoku
+User-agent: OtherBot
+Disallow: /private
+";
+        let rules = RobotsRules::parse(content);
+        assert_eq!(rules.crawl_delay_sec, None);
+        assert!(!rules.allows("/private/page"));
+        assert!(rules.allows("/search"));
+
+        let rules = RobotsRules::parse(&content.replace("Tsundoku", "Unrelated"));
+        assert_eq!(rules.crawl_delay_sec, Some(5.0));
+        assert!(!rules.allows("/search?q=1"));
+        assert!(rules.allows("/search/help"));
+        assert!(rules.allows("/n1234ab/1/"));
+        assert_eq!(RobotsRules::parse(""), RobotsRules::default());
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        assert!(pattern_matches("/novel/", "/novel/123"));
+        assert!(pattern_matches("/*.json$", "/api/list.json"));
+        assert!(!pattern_matches("/*.json$", "/api/list.json?page=2"));
+        assert!(pattern_matches("/*/print", "/n1234ab/print/1"));
+        assert!(!pattern_matches("/novel/", "/novels"));
+    }
+}
@@ -1,14 +1,35 @@
 //! Syosetu (ncode.syosetu.com / novel18.syosetu.com) scraper implementation.
 //!
-//! Supports both general audience and 18+ content from the Syosetu platform.
-
-use super::{ChapterInfo, ChapterList, NovelInfo, Scraper, create_http_client, rate_limit};
+//! Supports both general audience and 18+ content from the Syosetu platform,
+//! including links from the Nocturne, Moonlight and Midnight portals
+//! (noc/mnlt/mid.syosetu.com), which are served from novel18.
+
+#[cfg(feature = "conformance")]
+use super::fixtures::PageSource;
+use super::limiter::HostLimiter;
+use super::{
+    BookmarkedNovel, ChapterInfo, ChapterList, NovelInfo, PageCache, Scraper,
+    create_http_client_with_jar, element_text,
+};
 use crate::config::ScrapingConfig;
 use crate::error::ScraperError;
 use async_trait::async_trait;
 use regex::Regex;
+use reqwest::cookie::Jar;
 use scraper::{Html, Selector};
-use std::sync::LazyLock;
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+
+/// Bookmark list pages for general and 18+ novels (login required).
+const BOOKMARK_LIST_URLS: &[&str] = &[
+    "https://syosetu.com/favnovelmain/list/",
+    "https://syosetu.com/favnovelmain18/list/",
+];
+
+/// Matches links to a novel's main page in the bookmark list.
+static BOOKMARK_NOVEL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^https?://((?:ncode|novel18)\.syosetu\.com)/(n[a-z0-9]+)/?$").unwrap()
+});
 
 /// Compiled regex patterns for Syosetu URLs.
 static URL_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
@@ -17,14 +38,34 @@ static URL_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
         Regex::new(r"https?://ncode\.syosetu\.com/n\w+/?(\d+/?)?").unwrap(),
         // novel18.syosetu.com URLs (18+ content)
         Regex::new(r"https?://novel18\.syosetu\.com/n\w+/?(\d+/?)?").unwrap(),
+        // Nocturne/Moonlight/Midnight portal URLs
+        ADULT_PORTAL_REGEX.clone(),
     ]
 });
 
+/// Matches novel links on the 18+ portal hosts, which use their own info-page
+/// paths (`/novelview/infotop/ncode/n1234ab/`) as well as bare ncodes.
+static ADULT_PORTAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"https?://(?:noc|mnlt|mid)\.syosetu\.com/(?:novelview/infotop/ncode/)?(n[a-z0-9]+)/?(?:(\d+)/?)?",
+    )
+    .unwrap()
+});
+
 /// Regex to extract novel ID from URL (n followed by alphanumerics in the path).
 /// Matches patterns like /n1234ab/ but not the domain ncode.syosetu.com
 static NOVEL_ID_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\.com/(n[a-z0-9]+)").unwrap());
 
+/// Regex to extract the episode number from a chapter URL (`/n1234ab/15/`).
+///
+/// Syosetu has no separate episode ID, so this is the best stable key available.
+static EPISODE_ID_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/n[a-z0-9]+/(\d+)/?$").unwrap());
+
+/// Cookie that tells novel18 the reader confirmed being 18 or older.
+const OVER18_COOKIE: &str = "over18=yes; Domain=.syosetu.com; Path=/";
+
 /// Regex to extract base URL from full URL.
 static BASE_URL_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(https://[\w.]+/n\w+)/?").unwrap());
@@ -47,6 +88,16 @@ struct Selectors {
     content_fallback: Selector,
     /// Paragraph selector.
     paragraph: Selector,
+    /// Any link (bookmark list parsing).
+    link: Selector,
+    /// Synopsis on the novel's main page (new and old layouts).
+    synopsis: Selector,
+    /// Labels in the novel info page's data table (new and old layouts).
+    info_label: Selector,
+    /// Serialization status on the novel info page (new and old layouts).
+    info_status: Selector,
+    /// "Yes, I'm 18 or older" link on the age confirmation page.
+    age_confirm: Selector,
 }
 
 impl Selectors {
@@ -55,7 +106,10 @@ impl Selectors {
             title_primary: Selector::parse(".p-novel__title").unwrap(),
             title_fallback: Selector::parse("p.novel_title").unwrap(),
             chapter_primary: Selector::parse(".p-eplist__sublist > a").unwrap(),
-            chapter_fallback: Selector::parse(".novel_sublist2 > dd > a").unwrap(),
+            chapter_fallback: Selector::parse(
+                ".novel_sublist2 > dd > a, .novel_sublist2 .subtitle > a",
+            )
+            .unwrap(),
             next_page_primary: Selector::parse(".c-pager__item--next").unwrap(),
             content_primary: Selector::parse(
                 ".p-novel__text.js-novel-text:not(.p-novel__text--preface):not(.p-novel__text--afterword)",
@@ -63,49 +117,181 @@ impl Selectors {
             .unwrap(),
             content_fallback: Selector::parse("#novel_honbun").unwrap(),
             paragraph: Selector::parse("p").unwrap(),
+            link: Selector::parse("a[href]").unwrap(),
+            synopsis: Selector::parse(".p-novel__summary, #novel_ex").unwrap(),
+            info_label: Selector::parse("dt, th").unwrap(),
+            info_status: Selector::parse(".p-infotop-type, #noveltype, #noveltype_notend")
+                .unwrap(),
+            age_confirm: Selector::parse("#yes18, a.yes18").unwrap(),
         }
     }
 }
 
+/// Details read from a novel's info page.
+#[derive(Debug, Default)]
+struct InfoDetails {
+    tags: Vec<String>,
+    author: String,
+    completed: Option<bool>,
+}
+
 /// Syosetu scraper for ncode.syosetu.com and novel18.syosetu.com.
 pub struct SyosetuScraper {
     client: reqwest::Client,
+    /// The client's cookies, for renewing the over18 cookie.
+    jar: Arc<Jar>,
     config: ScrapingConfig,
     selectors: Selectors,
+    /// Main and chapter list pages fetched so far.
+    pages: PageCache,
+    limiter: HostLimiter,
+    /// Pages served in place of the network, for conformance tests.
+    #[cfg(feature = "conformance")]
+    fixtures: Option<Arc<dyn PageSource>>,
 }
 
 impl SyosetuScraper {
     /// Creates a new Syosetu scraper with the given configuration.
-    pub fn new(config: ScrapingConfig) -> Self {
-        let client = create_http_client().expect("Failed to create HTTP client");
+    ///
+    /// Login cookies in `jar` (if any) enable bookmark import; the over18
+    /// cookie that skips the age check on novel18 is added to it.
+    pub fn new(config: ScrapingConfig, jar: Arc<Jar>) -> Self {
+        let over18_url = "https://syosetu.com/".parse().expect("valid URL");
+        jar.add_cookie_str(OVER18_COOKIE, &over18_url);
+
+        let client = create_http_client_with_jar(&config, jar.clone())
+            .expect("Failed to create HTTP client");
 
         Self {
             client,
+            jar,
             config,
             selectors: Selectors::new(),
+            pages: PageCache::default(),
+            limiter: HostLimiter::new(),
+            #[cfg(feature = "conformance")]
+            fixtures: None,
         }
     }
 
+    /// Counts requests against `limiter`, shared with other scrapers.
+    pub fn with_limiter(mut self, limiter: HostLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Reads every page from `source` instead of the network.
+    #[cfg(feature = "conformance")]
+    pub fn with_fixtures(mut self, source: Arc<dyn PageSource>) -> Self {
+        self.fixtures = Some(source);
+        self
+    }
+
     /// Fetches a page and returns the HTML document.
     async fn fetch_page(&self, url: &str) -> Result<Html, ScraperError> {
-        rate_limit(self.config.delay_between_requests_sec).await;
-
-        // Build request with over18 cookie for adult content
-        let response = self
-            .client
-            .get(url)
-            .header("Cookie", "over18=yes")
-            .send()
+        Ok(Html::parse_document(&self.fetch_body(url).await?))
+    }
+
+    /// Fetches a novel's main or chapter list page, reusing it if it was
+    /// already fetched this run.
+    async fn fetch_index_page(&self, url: &str) -> Result<Html, ScraperError> {
+        if let Some(body) = self.pages.get(url) {
+            return Ok(Html::parse_document(&body));
+        }
+        let body = self.fetch_body(url).await?;
+        self.pages.insert(url, &body);
+        Ok(Html::parse_document(&body))
+    }
+
+    /// Fetches a page's HTML.
+    ///
+    /// If novel18 answers with its age confirmation page anyway (the
+    /// over18 cookie was dropped or overridden by an imported cookie file),
+    /// the cookie is set again and the page fetched once more.
+    async fn fetch_body(&self, url: &str) -> Result<String, ScraperError> {
+        #[cfg(feature = "conformance")]
+        if let Some(fixtures) = &self.fixtures {
+            return fixtures.page(url);
+        }
+
+        let (final_url, body) = self.fetch_document(url).await?;
+        // Html isn't Send, so it can't be held across the retry
+        let target = {
+            let doc = Html::parse_document(&body);
+            match self.age_gate_target(&final_url, &doc, url) {
+                Some(target) => target,
+                None => return Ok(body),
+            }
+        };
+        if self.config.debug {
+            eprintln!(
+                "[Syosetu Debug] Age confirmation page for {}; confirming and retrying {}",
+                url, target
+            );
+        }
+
+        // The cookie for the target's own host too, in case a host-only
+        // over18 cookie from the cookie file shadows the domain one
+        for cookie_url in ["https://syosetu.com/", target.as_str()] {
+            if let Ok(cookie_url) = cookie_url.parse() {
+                self.jar.add_cookie_str(OVER18_COOKIE, &cookie_url);
+            }
+        }
+        let (final_url, body) = self.fetch_document(&target).await?;
+        let doc = Html::parse_document(&body);
+        if self.age_gate_target(&final_url, &doc, &target).is_some() {
+            return Err(ScraperError::InvalidContent(format!(
+                "{} keeps returning the age confirmation page",
+                url
+            )));
+        }
+        Ok(body)
+    }
+
+    /// Fetches a page, returning the URL it ended up at and the body.
+    async fn fetch_document(&self, url: &str) -> Result<(String, String), ScraperError> {
+        let _permit = self
+            .limiter
+            .acquire(&self.client, url, &self.config)
             .await?;
 
+        // The over18 cookie for adult content comes from the client's jar
+        let response = self.client.get(url).send().await?;
+
         if !response.status().is_success() {
             return Err(ScraperError::HttpError(
                 response.error_for_status().unwrap_err(),
             ));
         }
 
-        let text = response.text().await?;
-        Ok(Html::parse_document(&text))
+        let final_url = response.url().to_string();
+        Ok((final_url, response.text().await?))
+    }
+
+    /// If the page is novel18's age confirmation page, returns the URL to
+    /// fetch once confirmed: the "yes" link's target, the redirect's `url`
+    /// parameter, or else the requested URL.
+    fn age_gate_target(&self, final_url: &str, doc: &Html, requested: &str) -> Option<String> {
+        let confirm = doc.select(&self.selectors.age_confirm).next();
+        if confirm.is_none() && !final_url.contains("/ageauth") {
+            return None;
+        }
+
+        let confirm_href = confirm
+            .and_then(|a| a.value().attr("href"))
+            .filter(|href| href.starts_with("http") || href.starts_with('/'))
+            .map(|href| resolve_url(final_url, href))
+            .filter(|href| !href.contains("/ageauth"));
+        let redirect_param = url::Url::parse(final_url).ok().and_then(|u| {
+            u.query_pairs()
+                .find(|(key, _)| key == "url")
+                .map(|(_, value)| value.into_owned())
+        });
+        Some(
+            confirm_href
+                .or(redirect_param)
+                .unwrap_or_else(|| requested.to_string()),
+        )
     }
 
     /// Extracts the novel title from the page.
@@ -129,6 +315,102 @@ impl SyosetuScraper {
         Err(ScraperError::ElementNotFound("novel title".to_string()))
     }
 
+    /// Fetches every novel on the logged-in user's bookmark list.
+    ///
+    /// Requires Syosetu login cookies; returns [`ScraperError::LoginRequired`]
+    /// if the site redirects to the login page.
+    pub async fn fetch_bookmarks(&self) -> Result<Vec<BookmarkedNovel>, ScraperError> {
+        const MAX_PAGES: u32 = 100; // Safety limit per list
+
+        let mut seen = HashSet::new();
+        let mut novels = Vec::new();
+
+        for list_url in BOOKMARK_LIST_URLS {
+            for page in 1..=MAX_PAGES {
+                let url = format!("{}?p={}", list_url, page);
+                let _permit = self
+                    .limiter
+                    .acquire(&self.client, &url, &self.config)
+                    .await?;
+
+                let response = self.client.get(&url).send().await?;
+                if response.url().path().contains("login") {
+                    return Err(ScraperError::LoginRequired(
+                        "Syosetu bookmarks need login cookies (see `tsundoku cookies import`)"
+                            .to_string(),
+                    ));
+                }
+                if !response.status().is_success() {
+                    return Err(ScraperError::HttpError(
+                        response.error_for_status().unwrap_err(),
+                    ));
+                }
+
+                let doc = Html::parse_document(&response.text().await?);
+                let before = novels.len();
+                for novel in self.extract_bookmark_links(&doc) {
+                    if seen.insert(novel.url.clone()) {
+                        novels.push(novel);
+                    }
+                }
+
+                // An empty page (or a repeat of the last one) means we're past the end
+                if novels.len() == before {
+                    break;
+                }
+            }
+        }
+
+        Ok(novels)
+    }
+
+    /// Extracts links to novel main pages from a bookmark list page.
+    fn extract_bookmark_links(&self, doc: &Html) -> Vec<BookmarkedNovel> {
+        let mut novels: Vec<BookmarkedNovel> = Vec::new();
+
+        for elem in doc.select(&self.selectors.link) {
+            let Some(href) = elem.value().attr("href") else {
+                continue;
+            };
+            let Some(caps) = BOOKMARK_NOVEL_REGEX.captures(href) else {
+                continue;
+            };
+            let title = elem.text().collect::<String>().trim().to_string();
+            if title.is_empty() {
+                continue;
+            }
+
+            let url = format!("https://{}/{}/", &caps[1], &caps[2]);
+            if !novels.iter().any(|n| n.url == url) {
+                novels.push(BookmarkedNovel {
+                    url,
+                    novel_id: caps[2].to_string(),
+                    title,
+                });
+            }
+        }
+
+        novels
+    }
+
+    /// Rewrites 18+ portal links to their novel18.syosetu.com equivalent.
+    ///
+    /// Other URLs are returned unchanged.
+    fn canonical_url(url: &str) -> String {
+        let Some(caps) = ADULT_PORTAL_REGEX.captures(url) else {
+            return url.to_string();
+        };
+        let ncode = &caps[1];
+        match caps.get(2) {
+            Some(chapter) => format!(
+                "https://novel18.syosetu.com/{}/{}/",
+                ncode,
+                chapter.as_str()
+            ),
+            None => format!("https://novel18.syosetu.com/{}/", ncode),
+        }
+    }
+
     /// Extracts the novel ID from a URL.
     fn extract_novel_id(url: &str) -> Result<String, ScraperError> {
         NOVEL_ID_REGEX
@@ -138,6 +420,90 @@ impl SyosetuScraper {
             .ok_or_else(|| ScraperError::InvalidUrl("Could not extract novel ID".to_string()))
     }
 
+    /// Extracts the synopsis from the novel's main page.
+    fn extract_synopsis(&self, doc: &Html) -> String {
+        doc.select(&self.selectors.synopsis)
+            .next()
+            .map(|elem| {
+                let text = elem.text().collect::<String>();
+                text.lines()
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Extracts the text of a labelled field in the novel info page's data
+    /// table.
+    fn extract_info_field(&self, doc: &Html, label: &str) -> Option<String> {
+        doc.select(&self.selectors.info_label)
+            .find(|e| e.text().collect::<String>().trim() == label)?
+            .next_siblings()
+            .find_map(scraper::ElementRef::wrap)
+            .map(|value| value.text().collect())
+    }
+
+    /// Extracts the keywords from the novel info page.
+    fn extract_keywords(&self, doc: &Html) -> Vec<String> {
+        self.extract_info_field(doc, "キーワード")
+            .unwrap_or_default()
+            .split(|c: char| c.is_whitespace())
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Extracts the author's name from the novel info page.
+    fn extract_author(&self, doc: &Html) -> String {
+        self.extract_info_field(doc, "作者名")
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    }
+
+    /// Whether the novel info page marks the novel as finished. Short
+    /// stories count as finished.
+    fn extract_completed(&self, doc: &Html) -> Option<bool> {
+        let status = doc
+            .select(&self.selectors.info_status)
+            .next()?
+            .text()
+            .collect::<String>();
+        if status.contains("完結") || status.contains("短編") {
+            Some(true)
+        } else if status.contains("連載中") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Fetches the keywords, author, and status from the novel info page,
+    /// which the main page doesn't show. Failures just leave them empty.
+    async fn fetch_info_details(&self, base_url: &str, novel_id: &str) -> InfoDetails {
+        let Ok(mut info_url) = url::Url::parse(base_url) else {
+            return InfoDetails::default();
+        };
+        info_url.set_path(&format!("/novelview/infotop/ncode/{}/", novel_id));
+
+        match self.fetch_page(info_url.as_str()).await {
+            Ok(doc) => InfoDetails {
+                tags: self.extract_keywords(&doc),
+                author: self.extract_author(&doc),
+                completed: self.extract_completed(&doc),
+            },
+            Err(e) => {
+                if self.config.debug {
+                    eprintln!("[Syosetu Debug] Could not fetch novel info page: {}", e);
+                }
+                InfoDetails::default()
+            }
+        }
+    }
+
     /// Extracts the base URL from a full URL.
     fn extract_base_url(url: &str) -> Result<String, ScraperError> {
         BASE_URL_REGEX
@@ -227,21 +593,18 @@ impl SyosetuScraper {
         // Get the inner HTML so we can manipulate it
         let inner_html = content_elem.inner_html();
 
-        // Parse the content to remove ruby annotations
+        // Re-parse the content so ruby annotations can be rendered
         let content_doc = Html::parse_fragment(&inner_html);
 
         // Extract text from paragraphs, or all text if no paragraphs
         let paragraphs: Vec<String> = content_doc
             .select(&self.selectors.paragraph)
-            .map(|p| {
-                // Get text, excluding <rt> elements (ruby text)
-                extract_text_without_ruby(p)
-            })
+            .map(|p| element_text(p, self.config.furigana))
             .collect();
 
         let text = if paragraphs.is_empty() {
             // No paragraphs, get all text
-            extract_text_without_ruby(content_elem)
+            element_text(content_elem, self.config.furigana)
         } else {
             paragraphs.join("\n")
         };
@@ -250,32 +613,6 @@ impl SyosetuScraper {
     }
 }
 
-/// Extracts text from an element, excluding ruby annotation (<rt>) content.
-fn extract_text_without_ruby(elem: scraper::ElementRef) -> String {
-    let mut text = String::new();
-
-    for node in elem.descendants() {
-        if let scraper::node::Node::Text(t) = node.value() {
-            // Check if this text is inside an <rt> element
-            let mut is_in_rt = false;
-            for ancestor in node.ancestors() {
-                if let Some(elem) = ancestor.value().as_element()
-                    && elem.name() == "rt"
-                {
-                    is_in_rt = true;
-                    break;
-                }
-            }
-
-            if !is_in_rt {
-                text.push_str(t);
-            }
-        }
-    }
-
-    text
-}
-
 /// Resolves a relative URL against a base URL.
 fn resolve_url(base: &str, relative: &str) -> String {
     if relative.starts_with("http://") || relative.starts_with("https://") {
@@ -308,24 +645,39 @@ impl Scraper for SyosetuScraper {
         URL_PATTERNS.iter().any(|pattern| pattern.is_match(url))
     }
 
+    fn positional_episode_ids(&self) -> bool {
+        // Episode URLs are /n1234ab/1/, /n1234ab/2/, ...
+        true
+    }
+
     async fn get_novel_info(&self, url: &str) -> Result<NovelInfo, ScraperError> {
         if !self.can_handle(url) {
             return Err(ScraperError::UnsupportedUrl(url.to_string()));
         }
 
-        let doc = self.fetch_page(url).await?;
-        let title = self.extract_title(&doc)?;
-        let novel_id = Self::extract_novel_id(url)?;
-        let base_url = Self::extract_base_url(url)?;
+        let url = Self::canonical_url(url);
+        let (title, synopsis) = {
+            // Html isn't Send, so it can't be held across the keyword fetch
+            let doc = self.fetch_index_page(&url).await?;
+            (self.extract_title(&doc)?, self.extract_synopsis(&doc))
+        };
+        let novel_id = Self::extract_novel_id(&url)?;
+        let base_url = Self::extract_base_url(&url)?;
+        let details = self.fetch_info_details(&base_url, &novel_id).await;
 
         Ok(NovelInfo {
             title,
             base_url,
             novel_id,
+            synopsis,
+            tags: details.tags,
+            author: details.author,
+            completed: details.completed,
         })
     }
 
     async fn get_chapter_list(&self, base_url: &str) -> Result<ChapterList, ScraperError> {
+        let base_url = &Self::canonical_url(base_url);
         let mut all_chapters = Vec::new();
         let mut current_url = base_url.to_string();
         let mut page_count = 0;
@@ -337,7 +689,7 @@ impl Scraper for SyosetuScraper {
                 break;
             }
 
-            let doc = self.fetch_page(&current_url).await?;
+            let doc = self.fetch_index_page(&current_url).await?;
 
             // Extract chapters from this page
             let chapters = self.extract_chapter_links(&doc, base_url);
@@ -365,10 +717,18 @@ impl Scraper for SyosetuScraper {
         let chapter_infos: Vec<ChapterInfo> = all_chapters
             .into_iter()
             .enumerate()
-            .map(|(idx, (title, url))| ChapterInfo {
-                title,
-                url,
-                number: (idx + 1) as u32,
+            .map(|(idx, (title, url))| {
+                let number = (idx + 1) as u32;
+                let episode_id = EPISODE_ID_REGEX
+                    .captures(&url)
+                    .map(|caps| caps[1].to_string())
+                    .unwrap_or_else(|| number.to_string());
+                ChapterInfo {
+                    title,
+                    url,
+                    number,
+                    episode_id,
+                }
             })
             .collect();
 
@@ -376,8 +736,13 @@ impl Scraper for SyosetuScraper {
     }
 
     async fn download_chapter(&self, chapter_url: &str) -> Result<String, ScraperError> {
-        let doc = self.fetch_page(chapter_url).await?;
-        self.extract_content(&doc)
+        // A one-shot's text is on its main page, usually fetched already
+        let url = Self::canonical_url(chapter_url);
+        let body = match self.pages.get(&url) {
+            Some(body) => body,
+            None => self.fetch_body(&url).await?,
+        };
+        self.extract_content(&Html::parse_document(&body))
     }
 }
 
@@ -387,13 +752,16 @@ mod tests {
 
     #[test]
     fn test_url_patterns() {
-        let scraper = SyosetuScraper::new(ScrapingConfig::default());
+        let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default());
 
         // Valid URLs
         assert!(scraper.can_handle("https://ncode.syosetu.com/n1234ab/"));
         assert!(scraper.can_handle("https://ncode.syosetu.com/n1234ab/1/"));
         assert!(scraper.can_handle("https://novel18.syosetu.com/n5678cd/"));
         assert!(scraper.can_handle("http://ncode.syosetu.com/n1234ab"));
+        assert!(scraper.can_handle("https://noc.syosetu.com/n5678cd/"));
+        assert!(scraper.can_handle("https://mnlt.syosetu.com/novelview/infotop/ncode/n5678cd/"));
+        assert!(scraper.can_handle("https://mid.syosetu.com/n5678cd/3/"));
 
         // Invalid URLs
         assert!(!scraper.can_handle("https://example.com/"));
@@ -424,6 +792,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_bookmark_links() {
+        let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default());
+        let html = r#"
+            <ul>
+              <li class="p-up-bookmark-item">
+                <a class="p-up-bookmark-item__title" href="https://ncode.syosetu.com/n1234ab/">作品A</a>
+                <a href="https://ncode.syosetu.com/n1234ab/15/">15部分</a>
+              </li>
+              <li class="p-up-bookmark-item">
+                <a href="http://novel18.syosetu.com/n5678cd">作品B</a>
+              </li>
+              <li><a href="https://syosetu.com/favnovelmain/list/?p=2">次へ</a></li>
+            </ul>
+        "#;
+        let doc = Html::parse_document(html);
+
+        let novels = scraper.extract_bookmark_links(&doc);
+        assert_eq!(
+            novels,
+            vec![
+                BookmarkedNovel {
+                    url: "https://ncode.syosetu.com/n1234ab/".to_string(),
+                    novel_id: "n1234ab".to_string(),
+                    title: "作品A".to_string(),
+                },
+                BookmarkedNovel {
+                    url: "https://novel18.syosetu.com/n5678cd/".to_string(),
+                    novel_id: "n5678cd".to_string(),
+                    title: "作品B".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_canonical_url() {
+        assert_eq!(
+            SyosetuScraper::canonical_url(
+                "https://mnlt.syosetu.com/novelview/infotop/ncode/n5678cd/"
+            ),
+            "https://novel18.syosetu.com/n5678cd/"
+        );
+        assert_eq!(
+            SyosetuScraper::canonical_url("https://noc.syosetu.com/n5678cd/12/"),
+            "https://novel18.syosetu.com/n5678cd/12/"
+        );
+        assert_eq!(
+            SyosetuScraper::canonical_url("https://ncode.syosetu.com/n1234ab/"),
+            "https://ncode.syosetu.com/n1234ab/"
+        );
+    }
+
+    #[test]
+    fn test_extract_synopsis_and_keywords() {
+        let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default());
+        let top = Html::parse_document("<div id=\"novel_ex\">  一行目<br />\n  二行目\n</div>");
+        assert_eq!(scraper.extract_synopsis(&top), "一行目\n二行目");
+
+        let info = Html::parse_document(
+            "<dl><dt>ジャンル</dt><dd>ハイファンタジー</dd>\
+             <dt>キーワード</dt>\n<dd>異世界転生　チート 主人公最強</dd></dl>",
+        );
+        assert_eq!(
+            scraper.extract_keywords(&info),
+            vec!["異世界転生", "チート", "主人公最強"]
+        );
+        assert!(scraper.extract_keywords(&top).is_empty());
+    }
+
+    #[test]
+    fn test_extract_author_and_status() {
+        let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default());
+        let info = Html::parse_document(
+            "<span class=\"p-infotop-type\">完結済 全42エピソード</span>\
+             <dl><dt>作者名</dt><dd>\n  <a href=\"/user/1\">山田太郎</a>\n</dd></dl>",
+        );
+        assert_eq!(scraper.extract_author(&info), "山田太郎");
+        assert_eq!(scraper.extract_completed(&info), Some(true));
+
+        let old = Html::parse_document("<div id=\"noveltype_notend\">連載中</div>");
+        assert_eq!(scraper.extract_completed(&old), Some(false));
+        assert_eq!(scraper.extract_author(&old), "");
+
+        let short = Html::parse_document("<div id=\"noveltype\">短編</div>");
+        assert_eq!(scraper.extract_completed(&short), Some(true));
+        assert_eq!(
+            scraper.extract_completed(&Html::parse_document("<p></p>")),
+            None
+        );
+    }
+
     #[test]
     fn test_resolve_url() {
         assert_eq!(
@@ -442,4 +902,32 @@ mod tests {
             "https://other.com/page"
         );
     }
+
+    #[test]
+    fn test_age_gate_target() {
+        let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default());
+        let chapter = "https://novel18.syosetu.com/n5678cd/3/";
+
+        let gate = Html::parse_document(
+            r#"<html><body><p>年齢確認</p><a id="yes18" href="https://novel18.syosetu.com/n5678cd/3/">Enter</a><a id="no18" href="https://syosetu.com/">Exit</a></body></html>"#,
+        );
+        assert_eq!(
+            scraper.age_gate_target("https://nl.syosetu.com/redirect/ageauth/", &gate, chapter),
+            Some(chapter.to_string())
+        );
+
+        // A confirm button without a usable link falls back to the redirect's target
+        let gate = Html::parse_document(r#"<a id="yes18" href="javascript:void(0)">Enter</a>"#);
+        assert_eq!(
+            scraper.age_gate_target(
+                "https://nl.syosetu.com/redirect/ageauth/?url=https%3A%2F%2Fnovel18.syosetu.com%2Fn5678cd%2F",
+                &gate,
+                chapter
+            ),
+            Some("https://novel18.syosetu.com/n5678cd/".to_string())
+        );
+
+        let page = Html::parse_document(r#"<div id="novel_honbun"><p>本文</p></div>"#);
+        assert_eq!(scraper.age_gate_target(chapter, &page, chapter), None);
+    }
 }
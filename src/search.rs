@@ -0,0 +1,130 @@
+//! Searching novel titles and translated chapter text.
+//!
+//! Matching is a case-insensitive substring test, so English and Japanese
+//! queries both work without any index.
+
+/// Characters of context kept before a full-text match in its snippet.
+const SNIPPET_LEAD_CHARS: usize = 30;
+
+/// Longest snippet shown for a full-text match, in characters.
+const SNIPPET_MAX_CHARS: usize = 100;
+
+/// A line of chapter text containing the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    /// Line number (1-based).
+    pub line: usize,
+    /// The line, shortened around the match.
+    pub snippet: String,
+}
+
+/// A search query.
+#[derive(Debug, Clone)]
+pub struct Query {
+    lowered: String,
+}
+
+impl Query {
+    /// Creates a query from the user's search text.
+    pub fn new(text: &str) -> Self {
+        Self {
+            lowered: text.trim().to_lowercase(),
+        }
+    }
+
+    /// Returns true if `text` contains the query.
+    pub fn matches(&self, text: &str) -> bool {
+        text.to_lowercase().contains(&self.lowered)
+    }
+
+    /// Finds every line of `text` containing the query.
+    pub fn find_in_text(&self, text: &str) -> Vec<TextMatch> {
+        text.lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let lowered = line.to_lowercase();
+                let byte_index = lowered.find(&self.lowered)?;
+                // Lower-casing rarely changes character counts; close enough
+                // for placing a snippet
+                let char_index = lowered[..byte_index].chars().count();
+                Some(TextMatch {
+                    line: i + 1,
+                    snippet: snippet(line.trim_end(), char_index),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Translated title from a story folder name ("[site: id] Title").
+pub fn folder_title(folder_name: &str) -> Option<&str> {
+    folder_name
+        .split_once("] ")
+        .map(|(_, title)| title.trim())
+        .filter(|title| !title.is_empty())
+}
+
+/// Cuts a line down to a window around the character at `match_index`.
+fn snippet(line: &str, match_index: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= SNIPPET_MAX_CHARS {
+        return line.trim_start().to_string();
+    }
+
+    let start = match_index
+        .saturating_sub(SNIPPET_LEAD_CHARS)
+        .min(chars.len() - SNIPPET_MAX_CHARS);
+    let end = start + SNIPPET_MAX_CHARS;
+    let mut snippet: String = chars[start..end].iter().collect();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_matching() {
+        let query = Query::new(" Demon Lord ");
+        assert!(query.matches("Reborn as the demon lord's cat"));
+        assert!(!query.matches("Reborn as a cat"));
+        assert!(Query::new("魔王").matches("魔王の猫に転生した"));
+
+        let long_line = format!(
+            "{}The DEMON LORD laughed.{}",
+            "a".repeat(60),
+            "b".repeat(60)
+        );
+        let text = format!("First line.\n\n{}\nThe demon lord again.", long_line);
+        let found = query.find_in_text(&text);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].line, 3);
+        assert!(found[0].snippet.starts_with('…'));
+        assert!(found[0].snippet.ends_with('…'));
+        assert!(found[0].snippet.contains("The DEMON LORD laughed."));
+        assert_eq!(found[0].snippet.chars().count(), SNIPPET_MAX_CHARS + 2);
+        assert_eq!(
+            found[1],
+            TextMatch {
+                line: 4,
+                snippet: "The demon lord again.".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_folder_title() {
+        assert_eq!(
+            folder_title("[syosetu: n1234ab] The Demon Lord's Cat"),
+            Some("The Demon Lord's Cat")
+        );
+        assert_eq!(folder_title("[n1234ab] Old Folder"), Some("Old Folder"));
+        assert_eq!(folder_title("no brackets"), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Detection of stat blocks in chapter text.
+//!
+//! LitRPG novels show status screens, skill lists, and tables as runs of
+//! bracketed, boxed, or `label: value` lines. Chunked with the prose around
+//! them, the model tends to reflow or merge those lines, so a block is found
+//! here and translated on its own (or left as is) per
+//! `translation.stat_blocks`.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Box-drawing and ruling characters a separator line is made of.
+const LINE_ART: &[char] = &[
+    '─', '━', '│', '┃', '┌', '┐', '└', '┘', '├', '┤', '┬', '┴', '┼', '┏', '┓', '┗', '┛', '┣', '┫',
+    '┳', '┻', '╋', '═', '║', '╔', '╗', '╚', '╝', '╠', '╣', '╦', '╩', '╬', '＝', '=', '-', '―',
+    '＊', '*', '■', '□', '◆', '◇', '★', '☆', '+', '＋', '|', '｜', '_', '＿', '~', '～',
+];
+
+/// Characters a line inside a box starts with.
+const BOX_EDGES: &[char] = &['│', '┃', '║', '|', '｜', '├', '┣', '╠'];
+
+/// Brackets status headings and entries open with (`【ステータス】`,
+/// `［スキル］`). Dialogue brackets (`「」『』`) are left out.
+const OPENING_BRACKETS: &[char] = &['【', '［', '[', '《', '〈', '＜', '<', '｛', '{'];
+
+/// Short labels followed by a colon and a value (`HP：120/120`, `名前：カイト`).
+static LABEL_VALUE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[^\s「『（(。、：:]{1,12}[ \t　]*[：:][ \t　]*\S").unwrap());
+
+/// Short labels followed by a number or a fraction (`ＨＰ　120／120`, `Lv 3`).
+static LABEL_NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[^\s「『（(。、]{1,8}[ \t　]+[0-9０-９]+(?:[ \t　]*[/／][ \t　]*[0-9０-９]+)?$")
+        .unwrap()
+});
+
+/// Consecutive stat lines needed for a block, so a lone system message or
+/// scene break stays with the prose.
+const MIN_BLOCK_LINES: usize = 3;
+
+/// A run of chapter text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Ordinary prose.
+    Prose(String),
+    /// A stat block, with its lines as in the source.
+    StatBlock(String),
+}
+
+/// Returns true if a line looks like part of a stat block: a ruled
+/// separator, a boxed or bracketed entry, or a `label: value` pair.
+pub fn is_stat_line(line: &str) -> bool {
+    let line = line.trim();
+    let Some(first) = line.chars().next() else {
+        return false;
+    };
+    if line.chars().count() >= 3
+        && line
+            .chars()
+            .all(|c| LINE_ART.contains(&c) || c.is_whitespace())
+    {
+        return true;
+    }
+    BOX_EDGES.contains(&first)
+        || OPENING_BRACKETS.contains(&first)
+        || LABEL_VALUE_REGEX.is_match(line)
+        || LABEL_NUMBER_REGEX.is_match(line)
+}
+
+/// Splits text into prose and stat blocks.
+///
+/// A block is at least three stat lines, allowing single blank lines
+/// between them. Joining the segments with newlines gives back the text.
+pub fn split_stat_blocks(text: &str) -> Vec<Segment> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut segments = Vec::new();
+    let mut prose_start = 0;
+    let mut i = 0;
+
+    while i < lines.len() {
+        if !is_stat_line(lines[i]) {
+            i += 1;
+            continue;
+        }
+
+        // Extend the run over stat lines and single blank lines between them
+        let mut end = i + 1;
+        let mut stat_lines = 1;
+        while end < lines.len() {
+            if is_stat_line(lines[end]) {
+                stat_lines += 1;
+                end += 1;
+            } else if lines[end].trim().is_empty()
+                && lines.get(end + 1).is_some_and(|l| is_stat_line(l))
+            {
+                end += 1;
+            } else {
+                break;
+            }
+        }
+
+        if stat_lines >= MIN_BLOCK_LINES {
+            if prose_start < i {
+                segments.push(Segment::Prose(lines[prose_start..i].join("\n")));
+            }
+            segments.push(Segment::StatBlock(lines[i..end].join("\n")));
+            prose_start = end;
+        }
+        i = end;
+    }
+
+    if prose_start < lines.len() {
+        segments.push(Segment::Prose(lines[prose_start..].join("\n")));
+    }
+    segments
+}
+
+/// Lays a block's translation over the source's lines: blank lines stay
+/// where the source has them and each other line gets the next translated
+/// line. Returns `None` if the translation has a different number of
+/// non-empty lines, i.e. the model merged or split some.
+pub fn restore_lines(source: &str, translated: &str) -> Option<String> {
+    let mut translated_lines = translated.lines().filter(|l| !l.trim().is_empty());
+    let expected = source.lines().filter(|l| !l.trim().is_empty()).count();
+    if translated.lines().filter(|l| !l.trim().is_empty()).count() != expected {
+        return None;
+    }
+    let lines: Vec<&str> = source
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                translated_lines.next().unwrap_or_default()
+            }
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stat_line() {
+        for line in [
+            "【ステータス】",
+            "━━━━━━━━━━",
+            "│ 名前：カイト　│",
+            "HP：120/120",
+            "ＭＰ　45／50",
+            "スキル：【剣術Lv3】【鑑定】",
+            "Lv 12",
+        ] {
+            assert!(is_stat_line(line), "{}", line);
+        }
+        for line in [
+            "「おはようございます」",
+            "彼は剣を抜いた。",
+            "",
+            "――",
+            "『どうして？』と彼女は言った。",
+        ] {
+            assert!(!is_stat_line(line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_split_stat_blocks() {
+        let text = "カイトは自分のステータスを確認した。\n━━━━━━━━\n名前：カイト\n\nLv：3\nHP：120/120\n━━━━━━━━\n「よし」\n【スキル《剣術》を獲得しました】\n彼は笑った。";
+        let segments = split_stat_blocks(text);
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Prose("カイトは自分のステータスを確認した。".to_string()),
+                Segment::StatBlock(
+                    "━━━━━━━━\n名前：カイト\n\nLv：3\nHP：120/120\n━━━━━━━━".to_string()
+                ),
+                // A lone system message stays with the prose
+                Segment::Prose(
+                    "「よし」\n【スキル《剣術》を獲得しました】\n彼は笑った。".to_string()
+                ),
+            ]
+        );
+
+        assert_eq!(
+            split_stat_blocks("ただの文章。\n続き。"),
+            vec![Segment::Prose("ただの文章。\n続き。".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_restore_lines() {
+        let source = "━━━━\n名前：カイト\n\nHP：120/120";
+        assert_eq!(
+            restore_lines(source, "━━━━\nName: Kaito\nHP: 120/120").as_deref(),
+            Some("━━━━\nName: Kaito\n\nHP: 120/120")
+        );
+        assert_eq!(
+            restore_lines(source, "━━━━\nName: Kaito, HP: 120/120"),
+            None
+        );
+    }
+}
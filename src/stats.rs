@@ -0,0 +1,241 @@
+//! Per-chapter statistics for a downloaded story.
+//!
+//! Reports text sizes, estimated translation chunks, and the length ratio of
+//! translations to originals, and flags originals that are suspiciously short
+//! compared to the rest of the story (often a truncated download).
+
+use crate::archive::read_story_file;
+use crate::config::ChunkSizeUnit;
+use crate::manifest::{ChapterRecord, Manifest};
+use crate::utils::Chunker;
+use std::path::Path;
+
+/// Originals shorter than this fraction of the story's median length are flagged.
+pub const SHORT_CHAPTER_FRACTION: f64 = 0.25;
+
+/// Statistics for one chapter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChapterStats {
+    /// Chapter number (1-based).
+    pub number: u32,
+    /// Original chapter title.
+    pub title: String,
+    /// Characters in the original text, if downloaded.
+    pub original_chars: Option<usize>,
+    /// Chunks the original would be split into for translation.
+    pub estimated_chunks: usize,
+    /// Characters in the translation, if translated.
+    pub translated_chars: Option<usize>,
+    /// Whether the original is suspiciously short for this story.
+    pub suspicious: bool,
+}
+
+impl ChapterStats {
+    /// Translated length divided by original length.
+    pub fn ratio(&self) -> Option<f64> {
+        length_ratio(self.original_chars?, self.translated_chars?)
+    }
+}
+
+/// Statistics for a whole story.
+#[derive(Debug, Clone, Default)]
+pub struct StoryStats {
+    /// Per-chapter statistics, ordered by number.
+    pub chapters: Vec<ChapterStats>,
+}
+
+impl StoryStats {
+    /// Total characters across downloaded originals.
+    pub fn original_chars(&self) -> usize {
+        self.chapters.iter().filter_map(|c| c.original_chars).sum()
+    }
+
+    /// Total characters across translations.
+    pub fn translated_chars(&self) -> usize {
+        self.chapters
+            .iter()
+            .filter_map(|c| c.translated_chars)
+            .sum()
+    }
+
+    /// Total estimated translation chunks.
+    pub fn estimated_chunks(&self) -> usize {
+        self.chapters.iter().map(|c| c.estimated_chunks).sum()
+    }
+
+    /// Number of chapters with a downloaded original.
+    pub fn downloaded(&self) -> usize {
+        self.chapters
+            .iter()
+            .filter(|c| c.original_chars.is_some())
+            .count()
+    }
+
+    /// Number of chapters with a translation.
+    pub fn translated(&self) -> usize {
+        self.chapters
+            .iter()
+            .filter(|c| c.translated_chars.is_some())
+            .count()
+    }
+
+    /// Translated length divided by original length, over translated chapters.
+    pub fn ratio(&self) -> Option<f64> {
+        let (original, translated) = self
+            .chapters
+            .iter()
+            .filter_map(|c| Some((c.original_chars?, c.translated_chars?)))
+            .fold((0, 0), |(o, t), (co, ct)| (o + co, t + ct));
+        length_ratio(original, translated)
+    }
+
+    /// Chapters flagged as suspiciously short.
+    pub fn suspicious(&self) -> impl Iterator<Item = &ChapterStats> {
+        self.chapters.iter().filter(|c| c.suspicious)
+    }
+}
+
+/// Collects statistics for every chapter recorded in a story's manifest.
+///
+/// Chapters whose files are missing are reported without sizes.
+pub fn collect_stats(
+    story_dir: &Path,
+    manifest: &Manifest,
+    chunk_size: usize,
+    unit: ChunkSizeUnit,
+) -> StoryStats {
+    let mut chapters: Vec<ChapterStats> = manifest
+        .data()
+        .chapters
+        .iter()
+        .map(|record| chapter_stats(story_dir, record, chunk_size, unit))
+        .collect();
+    flag_short_chapters(&mut chapters);
+    StoryStats { chapters }
+}
+
+/// Computes statistics for one manifest record.
+fn chapter_stats(
+    story_dir: &Path,
+    record: &ChapterRecord,
+    chunk_size: usize,
+    unit: ChunkSizeUnit,
+) -> ChapterStats {
+    let original = read_record_file(story_dir, record.original_file.as_deref());
+    let translated = read_record_file(story_dir, record.translated_file.as_deref());
+
+    ChapterStats {
+        number: record.number,
+        title: record.title.clone(),
+        original_chars: original.as_ref().map(|t| t.chars().count()),
+        estimated_chunks: original
+            .as_ref()
+            .map(|t| Chunker::new(chunk_size, unit).split(t).len())
+            .unwrap_or(0),
+        translated_chars: translated.as_ref().map(|t| t.chars().count()),
+        suspicious: false,
+    }
+}
+
+/// Reads a file recorded relative to the story folder, if present (originals
+/// may be in the archive).
+fn read_record_file(story_dir: &Path, relative_path: Option<&str>) -> Option<String> {
+    read_story_file(story_dir, relative_path?).ok().flatten()
+}
+
+/// Flags originals shorter than [`SHORT_CHAPTER_FRACTION`] of the median.
+pub fn flag_short_chapters(chapters: &mut [ChapterStats]) {
+    let mut lengths: Vec<usize> = chapters.iter().filter_map(|c| c.original_chars).collect();
+    if lengths.len() < 3 {
+        // Too few chapters to say what "normal" looks like
+        return;
+    }
+    lengths.sort_unstable();
+    let median = lengths[lengths.len() / 2] as f64;
+    let threshold = median * SHORT_CHAPTER_FRACTION;
+
+    for chapter in chapters.iter_mut() {
+        chapter.suspicious = chapter
+            .original_chars
+            .is_some_and(|chars| (chars as f64) < threshold);
+    }
+}
+
+/// Indices of up to `samples` chapters spread evenly over a list of
+/// `count`, always including the first and last.
+pub fn sample_indices(count: usize, samples: usize) -> Vec<usize> {
+    if count <= samples {
+        return (0..count).collect();
+    }
+    match samples {
+        0 => Vec::new(),
+        1 => vec![0],
+        _ => {
+            let mut indices: Vec<usize> = (0..samples)
+                .map(|i| i * (count - 1) / (samples - 1))
+                .collect();
+            indices.dedup();
+            indices
+        }
+    }
+}
+
+fn length_ratio(original: usize, translated: usize) -> Option<f64> {
+    (original > 0).then(|| translated as f64 / original as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(number: u32, original: Option<usize>, translated: Option<usize>) -> ChapterStats {
+        ChapterStats {
+            number,
+            title: format!("Chapter {}", number),
+            original_chars: original,
+            estimated_chunks: 1,
+            translated_chars: translated,
+            suspicious: false,
+        }
+    }
+
+    #[test]
+    fn test_flag_short_chapters() {
+        let mut chapters = vec![
+            stats(1, Some(5000), None),
+            stats(2, Some(4800), None),
+            stats(3, Some(300), None),
+            stats(4, Some(5200), None),
+            stats(5, None, None),
+        ];
+        flag_short_chapters(&mut chapters);
+
+        let flagged: Vec<u32> = chapters
+            .iter()
+            .filter(|c| c.suspicious)
+            .map(|c| c.number)
+            .collect();
+        assert_eq!(flagged, vec![3]);
+    }
+
+    #[test]
+    fn test_totals_and_ratio() {
+        let story = StoryStats {
+            chapters: vec![stats(1, Some(1000), Some(2500)), stats(2, Some(1000), None)],
+        };
+        assert_eq!(story.original_chars(), 2000);
+        assert_eq!(story.translated_chars(), 2500);
+        assert_eq!(story.downloaded(), 2);
+        assert_eq!(story.translated(), 1);
+        assert_eq!(story.ratio(), Some(2.5));
+        assert_eq!(story.chapters[1].ratio(), None);
+    }
+
+    #[test]
+    fn test_sample_indices() {
+        assert_eq!(sample_indices(100, 3), vec![0, 49, 99]);
+        assert_eq!(sample_indices(2, 3), vec![0, 1]);
+        assert_eq!(sample_indices(5, 1), vec![0]);
+        assert!(sample_indices(0, 3).is_empty());
+    }
+}
@@ -0,0 +1,384 @@
+//! Terminology reports for human translators.
+//!
+//! The name scout, run with the terminology prompt, lists the character
+//! names, places, skills, and other recurring terms of each chapter. A
+//! [`TermReport`] tallies them across chapters with how often each appears
+//! and the rendering most often suggested (or the one in the novel's name
+//! mapping), written out as Markdown or CSV.
+
+use crate::name_mapping::NameMappingStore;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// Kind of term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TermCategory {
+    /// A person's name or name part.
+    Character,
+    /// A place: country, city, dungeon, building.
+    Place,
+    /// A guild, order, family, company, or other group.
+    Organization,
+    /// A skill, spell, title, or game-system term.
+    Skill,
+    /// An item, weapon, or material.
+    Item,
+    /// Anything else worth keeping consistent.
+    Other,
+}
+
+impl TermCategory {
+    /// All categories, in report order.
+    pub const ALL: [TermCategory; 6] = [
+        TermCategory::Character,
+        TermCategory::Place,
+        TermCategory::Organization,
+        TermCategory::Skill,
+        TermCategory::Item,
+        TermCategory::Other,
+    ];
+
+    /// Heading used for the category in Markdown reports.
+    fn heading(self) -> &'static str {
+        match self {
+            TermCategory::Character => "Characters",
+            TermCategory::Place => "Places",
+            TermCategory::Organization => "Organizations",
+            TermCategory::Skill => "Skills and Abilities",
+            TermCategory::Item => "Items",
+            TermCategory::Other => "Other Terms",
+        }
+    }
+}
+
+impl fmt::Display for TermCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TermCategory::Character => "character",
+            TermCategory::Place => "place",
+            TermCategory::Organization => "organization",
+            TermCategory::Skill => "skill",
+            TermCategory::Item => "item",
+            TermCategory::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for TermCategory {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "character" | "name" | "person" => Self::Character,
+            "place" | "location" => Self::Place,
+            "organization" | "organisation" | "group" => Self::Organization,
+            "skill" | "ability" | "spell" | "title" => Self::Skill,
+            "item" | "weapon" => Self::Item,
+            _ => Self::Other,
+        })
+    }
+}
+
+/// A term found by the scout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    /// Term as written in the source.
+    pub original: String,
+    /// Suggested English rendering.
+    pub english: String,
+    /// What kind of term it is.
+    pub category: TermCategory,
+}
+
+/// Report file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Tables grouped by category.
+    Markdown,
+    /// One row per term.
+    Csv,
+}
+
+impl ReportFormat {
+    /// File extension for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("unknown format '{}' (expected markdown or csv)", s)),
+        }
+    }
+}
+
+/// One line of a terminology report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermRow {
+    /// Term as written in the source.
+    pub original: String,
+    /// Category the scout gave most often.
+    pub category: TermCategory,
+    /// The name mapping's rendering, or else the most suggested one.
+    pub suggested: String,
+    /// Other renderings the scout suggested, most frequent first.
+    pub alternatives: Vec<String>,
+    /// Times the term appears in the scanned chapters.
+    pub occurrences: usize,
+    /// Scanned chapters the term appears in.
+    pub chapters: usize,
+    /// First chapter the scout found it in.
+    pub first_chapter: u32,
+}
+
+/// Votes for a term across chapters.
+#[derive(Debug, Default)]
+struct Tally {
+    categories: HashMap<TermCategory, u32>,
+    renderings: HashMap<String, u32>,
+    first_chapter: u32,
+}
+
+/// Terms collected across chapters.
+#[derive(Debug, Default)]
+pub struct TermReport {
+    terms: BTreeMap<String, Tally>,
+}
+
+impl TermReport {
+    /// Records the terms the scout found in a chapter.
+    pub fn add(&mut self, chapter: u32, terms: &[Term]) {
+        for term in terms {
+            let tally = self
+                .terms
+                .entry(term.original.clone())
+                .or_insert_with(|| Tally {
+                    first_chapter: chapter,
+                    ..Default::default()
+                });
+            tally.first_chapter = tally.first_chapter.min(chapter);
+            *tally.categories.entry(term.category).or_default() += 1;
+            *tally.renderings.entry(term.english.clone()).or_default() += 1;
+        }
+    }
+
+    /// Number of distinct terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns true if no terms were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// The report's rows, most frequent first, counting occurrences in the
+    /// scanned chapter texts. Terms in the name mapping are suggested as
+    /// mapped.
+    pub fn rows(&self, texts: &[&str], mapping: &NameMappingStore) -> Vec<TermRow> {
+        let mut rows: Vec<TermRow> = self
+            .terms
+            .iter()
+            .map(|(original, tally)| {
+                let mut renderings: Vec<(&String, &u32)> = tally.renderings.iter().collect();
+                renderings.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+                let mut renderings: Vec<String> =
+                    renderings.into_iter().map(|(r, _)| r.clone()).collect();
+
+                let mapped = mapping
+                    .data()
+                    .names
+                    .get(original)
+                    .and_then(|info| info.english.clone());
+                let suggested = match mapped {
+                    Some(mapped) => {
+                        renderings.retain(|r| *r != mapped);
+                        mapped
+                    }
+                    None => renderings.remove(0),
+                };
+
+                let category = tally
+                    .categories
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+                    .map_or(TermCategory::Other, |(c, _)| *c);
+
+                TermRow {
+                    original: original.clone(),
+                    category,
+                    suggested,
+                    alternatives: renderings,
+                    occurrences: texts
+                        .iter()
+                        .map(|t| t.matches(original.as_str()).count())
+                        .sum(),
+                    chapters: texts
+                        .iter()
+                        .filter(|t| t.contains(original.as_str()))
+                        .count(),
+                    first_chapter: tally.first_chapter,
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            b.occurrences
+                .cmp(&a.occurrences)
+                .then_with(|| a.original.cmp(&b.original))
+        });
+        rows
+    }
+}
+
+/// Renders rows as Markdown tables, one per category.
+pub fn to_markdown(title: &str, rows: &[TermRow]) -> String {
+    let mut out = format!("# Terminology: {}\n", title);
+    for category in TermCategory::ALL {
+        let rows: Vec<&TermRow> = rows.iter().filter(|r| r.category == category).collect();
+        if rows.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n## {}\n\n", category.heading()));
+        out.push_str(
+            "| Term | Suggested | Other renderings | Occurrences | Chapters | First seen |\n",
+        );
+        out.push_str("|---|---|---|---:|---:|---:|\n");
+        for row in rows {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                escape_markdown(&row.original),
+                escape_markdown(&row.suggested),
+                escape_markdown(&row.alternatives.join(", ")),
+                row.occurrences,
+                row.chapters,
+                row.first_chapter
+            ));
+        }
+    }
+    out
+}
+
+/// Renders rows as CSV with a header line.
+pub fn to_csv(rows: &[TermRow]) -> String {
+    let mut out =
+        String::from("term,category,suggested,alternatives,occurrences,chapters,first_chapter\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            escape_csv(&row.original),
+            row.category,
+            escape_csv(&row.suggested),
+            escape_csv(&row.alternatives.join("; ")),
+            row.occurrences,
+            row.chapters,
+            row.first_chapter
+        ));
+    }
+    out
+}
+
+/// Escapes pipes so a value stays in its table cell.
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Quotes a CSV field if it contains a separator, quote, or line break.
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn term(original: &str, english: &str, category: TermCategory) -> Term {
+        Term {
+            original: original.to_string(),
+            english: english.to_string(),
+            category,
+        }
+    }
+
+    #[test]
+    fn test_term_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mapping = NameMappingStore::new(temp_dir.path(), "syosetu", "n1234ab").unwrap();
+        mapping.record_votes(&[crate::name_mapping::NameEntry {
+            original: "カイト".to_string(),
+            english: "Kaito".to_string(),
+            part: crate::name_mapping::NamePart::Given,
+            ..Default::default()
+        }]);
+
+        let mut report = TermReport::default();
+        report.add(
+            2,
+            &[
+                term("カイト", "Kite", TermCategory::Character),
+                term("剣術", "Swordsmanship", TermCategory::Skill),
+            ],
+        );
+        report.add(
+            1,
+            &[
+                term("剣術", "Sword Arts", TermCategory::Skill),
+                term("剣術", "Swordsmanship", TermCategory::Skill),
+                term("王都", "Royal Capital", TermCategory::Place),
+            ],
+        );
+        assert_eq!(report.len(), 3);
+
+        let texts = ["カイトは剣術を学んだ。剣術は難しい。", "王都へ向かう。"];
+        let rows = report.rows(&texts, &mapping);
+        assert_eq!(
+            rows[0],
+            TermRow {
+                original: "剣術".to_string(),
+                category: TermCategory::Skill,
+                suggested: "Swordsmanship".to_string(),
+                alternatives: vec!["Sword Arts".to_string()],
+                occurrences: 2,
+                chapters: 1,
+                first_chapter: 1,
+            }
+        );
+        // The name mapping's rendering wins over the scout's
+        assert_eq!(rows[1].original, "カイト");
+        assert_eq!(rows[1].suggested, "Kaito");
+        assert_eq!(rows[1].alternatives, vec!["Kite"]);
+
+        let markdown = to_markdown("テスト", &rows);
+        assert!(markdown.contains("## Skills and Abilities"));
+        assert!(markdown.contains("| 剣術 | Swordsmanship | Sword Arts | 2 | 1 | 1 |"));
+        assert!(!markdown.contains("## Items"));
+
+        let csv = to_csv(&rows);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("剣術,skill,Swordsmanship,Sword Arts,2,1,1")
+        );
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_csv("a, b"), "\"a, b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
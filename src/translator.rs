@@ -3,14 +3,20 @@
 //! Provides text translation with streaming progress display,
 //! message history management, and retry logic.
 
-use crate::config::{ApiConfig, TranslationConfig};
-use crate::console::Console;
-use crate::error::TranslationError;
-use futures::StreamExt;
+use crate::api_limiter::ApiLimiter;
+use crate::cache::ResponseCache;
+use crate::config::{ApiConfig, NudgeRole, PromptsConfig, StatBlockMode, TranslationConfig};
+use crate::console::{Console, Style};
+use crate::debug_log::{DebugLog, RequestEntry};
+use crate::error::{RetryKind, TranslationError};
+use crate::statblock::{Segment, restore_lines, split_stat_blocks};
+use crate::utils::{CancellationToken, Chunk, Chunker, estimate_tokens};
+use futures::{Stream, StreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::io::{self, Write};
-use std::sync::LazyLock;
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::{Duration, Instant};
 
 /// Refusal phrases that indicate the model declined to translate.
@@ -26,15 +32,344 @@ static REFUSAL_PHRASES: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
     ]
 });
 
+/// Returns the refusal phrase the response starts with, if any.
+fn detect_refusal(response: &str) -> Option<&'static str> {
+    let lower = response.trim_start().to_lowercase();
+    REFUSAL_PHRASES
+        .iter()
+        .copied()
+        .find(|phrase| lower.starts_with(phrase))
+}
+
+/// Whether enough of a streamed response has arrived to rule a refusal in or out.
+fn refusal_decidable(response: &str) -> bool {
+    let longest = REFUSAL_PHRASES.iter().map(|p| p.len()).max().unwrap_or(0);
+    response.trim_start().len() >= longest
+}
+
+/// Follow-up sent when a response is cut off by the output token limit.
+const CONTINUE_PROMPT: &str = "Continue the translation exactly where you stopped. Do not repeat any text that was already translated.";
+
+/// What the translator is doing with a text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProgressPhase {
+    /// Translating a chapter's content, chunk by chunk.
+    #[default]
+    Translate,
+    /// Re-translating chunks that failed earlier.
+    Repair,
+}
+
 /// Progress information for display during translation.
-#[derive(Debug, Clone)]
+///
+/// Callers fill in the chapter (and phase); the translator fills in the
+/// chunk, source character counts, and timing before handing it to the
+/// [`ProgressSink`]. Serializes to JSON with durations in seconds.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ProgressInfo {
+    /// What is being done.
+    pub phase: ProgressPhase,
     /// Current chapter number (1-based).
     pub chapter: u32,
     /// Current chunk number (1-based).
     pub chunk: u32,
     /// Total number of chunks.
     pub total_chunks: u32,
+    /// Source characters in the chunks finished so far.
+    pub chars_done: usize,
+    /// Source characters in all chunks.
+    pub total_chars: usize,
+    /// Time since the text started translating.
+    #[serde(serialize_with = "serialize_secs")]
+    pub elapsed: Duration,
+    /// Estimated time left for the text, from the pace so far (none before
+    /// the first chunk finishes).
+    #[serde(serialize_with = "serialize_optional_secs")]
+    pub eta: Option<Duration>,
+}
+
+impl ProgressInfo {
+    /// This progress at a chunk, with `chars_done` of `total_chars` source
+    /// characters finished since `started`.
+    fn at_chunk(
+        &self,
+        chunk: usize,
+        total_chunks: usize,
+        chars_done: usize,
+        total_chars: usize,
+        started: Instant,
+    ) -> Self {
+        let elapsed = started.elapsed();
+        let eta = (chars_done > 0).then(|| {
+            elapsed.mul_f64(total_chars.saturating_sub(chars_done) as f64 / chars_done as f64)
+        });
+        Self {
+            phase: self.phase,
+            chapter: self.chapter,
+            chunk: chunk as u32,
+            total_chunks: total_chunks as u32,
+            chars_done,
+            total_chars,
+            elapsed,
+            eta,
+        }
+    }
+}
+
+fn serialize_secs<S: serde::Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64((duration.as_secs_f64() * 10.0).round() / 10.0)
+}
+
+fn serialize_optional_secs<S: serde::Serializer>(
+    duration: &Option<Duration>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serialize_secs(duration, s),
+        None => s.serialize_none(),
+    }
+}
+
+/// Formats a duration for the progress line ("45s", "3m05s", "1h02m").
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// An incremental update from [`Translator::translate_stream`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TranslationEvent {
+    /// A piece of streamed text for the chunk currently being translated.
+    ///
+    /// With `chunk_overlap`, the deltas include the translation of the
+    /// overlap, which `ChunkDone` leaves out.
+    Delta {
+        /// Index of the chunk (0-based).
+        chunk_index: usize,
+        /// Newly received text.
+        text: String,
+    },
+    /// The current chunk failed and will be retried; discard its deltas.
+    Retrying {
+        /// Index of the chunk (0-based).
+        chunk_index: usize,
+        /// Attempt number about to start (1-based).
+        attempt: u32,
+    },
+    /// A chunk finished translating.
+    ChunkDone {
+        /// Index of the chunk (0-based).
+        chunk_index: usize,
+        /// Total number of chunks in the text.
+        total_chunks: usize,
+        /// Final translation of the chunk (or a failure marker with the source).
+        text: String,
+    },
+}
+
+/// Marker line written in place of a chunk whose translation failed; the
+/// untranslated source follows it.
+pub const FAILED_CHUNK_MARKER: &str = "[TRANSLATION FAILED]";
+
+/// A translated chunk together with the source text it came from.
+#[derive(Debug, Clone)]
+pub struct TranslatedChunk {
+    /// The source chunk sent to the model (after name substitution).
+    pub source: String,
+    /// The translation, or a failure marker followed by the source.
+    pub translated: String,
+    /// True if every retry failed and `translated` holds the failure marker.
+    pub failed: bool,
+    /// Why the chunk failed: [`TranslationError::RetriesExhausted`] with the
+    /// attempts made and the last error, or an error not worth retrying.
+    pub error: Option<Arc<TranslationError>>,
+}
+
+/// Tokens billed for API requests, as reported by the API.
+///
+/// Endpoints that don't report usage (or streams without
+/// `stream_options.include_usage`) leave this at zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Adds another request's usage to this total.
+    pub fn add(&mut self, other: TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+}
+
+/// Response body of the `/models` endpoint.
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    #[serde(default)]
+    data: Vec<ModelEntry>,
+}
+
+/// One model in a `/models` response.
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+/// Lists the model IDs offered by an OpenAI-compatible endpoint, sorted.
+pub async fn list_models(
+    client: &Client,
+    api_config: &ApiConfig,
+) -> Result<Vec<String>, TranslationError> {
+    let response = client
+        .get(api_config.endpoint_url("models"))
+        .header("Authorization", format!("Bearer {}", api_config.key))
+        .send()
+        .await?;
+    let response = crate::utils::check_response_status(response).await?;
+    let body = response.text().await?;
+    parse_model_ids(&body)
+}
+
+/// Prompt of the pre-flight request, kept tiny so it costs next to nothing.
+const PREFLIGHT_PROMPT: &str = "Translate into English: 猫";
+
+/// Sends one tiny chat request to `model`, confirming the key and model
+/// work before a long run.
+pub async fn preflight(
+    client: &Client,
+    api_config: &ApiConfig,
+    model: &str,
+) -> Result<(), TranslationError> {
+    let request = ChatRequest {
+        model: model.to_string(),
+        messages: vec![Message {
+            role: "user".to_string(),
+            content: PREFLIGHT_PROMPT.to_string(),
+        }],
+        stream: false,
+        temperature: None,
+    };
+    let body = api_config
+        .request_body(&request)
+        .map_err(|e| TranslationError::ParseError(format!("Failed to build request: {}", e)))?;
+    let response = client
+        .post(api_config.endpoint_url("chat/completions"))
+        .header("Authorization", format!("Bearer {}", api_config.key))
+        .json(&body)
+        .send()
+        .await?;
+    let response = crate::utils::check_response_status(response).await?;
+    let body: ChatResponse = response.json().await.map_err(|e| {
+        TranslationError::ParseError(format!("Failed to parse API response: {}", e))
+    })?;
+    if body.choices.is_empty() {
+        return Err(TranslationError::ParseError(
+            "No choices in API response".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that an OpenAI-compatible API answers at the configured base URL,
+/// with a cheap `GET /models`.
+///
+/// Only rejected credentials fail. Returns false on a 404, which usually
+/// means a wrong path but also comes from providers without `/models`; any
+/// other answer means an API is listening, even if it doesn't list models.
+pub async fn verify_endpoint(
+    client: &Client,
+    api_config: &ApiConfig,
+) -> Result<bool, TranslationError> {
+    let response = client
+        .get(api_config.endpoint_url("models"))
+        .header("Authorization", format!("Bearer {}", api_config.key))
+        .send()
+        .await?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        crate::utils::check_response_status(response).await?;
+    }
+    Ok(true)
+}
+
+/// Extracts sorted, deduplicated model IDs from a `/models` response body.
+fn parse_model_ids(body: &str) -> Result<Vec<String>, TranslationError> {
+    let response: ModelsResponse = serde_json::from_str(body)
+        .map_err(|e| TranslationError::ParseError(format!("Invalid model list: {}", e)))?;
+    let mut ids: Vec<String> = response.data.into_iter().map(|m| m.id).collect();
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Join translated chunks into the final chapter text.
+pub fn join_chunks(chunks: &[TranslatedChunk]) -> String {
+    chunks
+        .iter()
+        .map(|c| c.translated.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Drops the translation of a chunk's overlap: as many leading non-empty
+/// lines as the overlap has.
+///
+/// If the translation has no more lines than that, the model must have
+/// merged lines, so it is kept whole rather than losing the chunk's own text.
+fn strip_overlap(translated: &str, overlap: &str) -> String {
+    let overlap_lines = overlap.lines().filter(|l| !l.trim().is_empty()).count();
+    if overlap_lines == 0 {
+        return translated.to_string();
+    }
+    let mut lines = translated.lines();
+    let mut dropped = 0;
+    for line in lines.by_ref() {
+        if !line.trim().is_empty() {
+            dropped += 1;
+            if dropped == overlap_lines {
+                break;
+            }
+        }
+    }
+    let rest = lines.collect::<Vec<_>>().join("\n");
+    if rest.trim().is_empty() {
+        return translated.to_string();
+    }
+    rest.trim_start_matches('\n').to_string()
+}
+
+/// The last `count` non-empty lines of `text`, joined by newlines.
+///
+/// Web novels put each paragraph on its own line, so these are the
+/// closing paragraphs.
+pub fn last_paragraphs(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+/// A chunk of content to translate.
+struct ContentChunk {
+    chunk: Chunk,
+    /// Sent on its own per `translation.stat_blocks` rather than with the
+    /// prose history.
+    stat_block: bool,
 }
 
 /// A message in the conversation history.
@@ -46,38 +381,83 @@ pub struct Message {
     pub content: String,
 }
 
+/// Merges a leading system message into the first user message, for
+/// endpoints that reject the `system` role.
+fn fold_system_prompt(mut messages: Vec<Message>) -> Vec<Message> {
+    if messages.first().is_none_or(|m| m.role != "system") {
+        return messages;
+    }
+    let system = messages.remove(0);
+    match messages.iter_mut().find(|m| m.role == "user") {
+        Some(user) => user.content = format!("{}\n\n{}", system.content, user.content),
+        None => messages.insert(
+            0,
+            Message {
+                role: "user".to_string(),
+                content: system.content,
+            },
+        ),
+    }
+    messages
+}
+
 /// Request body for the chat completions API.
 #[derive(Debug, Serialize)]
 struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+}
+
+/// What [`Translator::send_completion`] learned about a response, for the
+/// debug log.
+#[derive(Debug, Default)]
+struct ResponseInfo {
+    /// HTTP status, once a response arrived.
+    status: Option<u16>,
+    /// Usage the endpoint reported.
+    usage: Option<TokenUsage>,
+    /// Stream data lines that didn't parse.
+    unparsed_lines: usize,
+}
+
+/// Which kind of text a request translates; picks its model and temperature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestKind {
+    Title,
+    Content,
 }
 
 /// Response from the chat completions API (non-streaming).
-/// Used for non-streaming API calls.
+/// Used for endpoints that don't support streaming.
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct ChatResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<TokenUsage>,
 }
 
 /// A single choice in the response.
 #[derive(Debug, Deserialize)]
 struct Choice {
-    #[allow(dead_code)]
     message: Option<ResponseMessage>,
     delta: Option<Delta>,
+    /// Why generation stopped ("stop", "length", ...); only set on the final chunk.
+    #[serde(default)]
+    finish_reason: Option<String>,
     #[allow(dead_code)]
     index: u32,
 }
 
 /// Message content in a non-streaming response.
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct ResponseMessage {
+    #[allow(dead_code)]
     role: String,
-    content: String,
+    #[serde(default)]
+    content: Option<String>,
 }
 
 /// Delta content in a streaming response.
@@ -89,7 +469,152 @@ struct Delta {
 /// Streaming chunk from the API.
 #[derive(Debug, Deserialize)]
 struct StreamChunk {
+    #[serde(default)]
     choices: Vec<Choice>,
+    /// Only on the final chunk, when the endpoint reports usage.
+    #[serde(default)]
+    usage: Option<TokenUsage>,
+}
+
+/// Receives progress updates while the translator streams responses.
+///
+/// The default [`TerminalProgress`] draws a single updating status line;
+/// front-ends can supply their own sink through [`TranslatorBuilder::progress_sink`].
+pub trait ProgressSink: Send + Sync {
+    /// Called periodically while a chunk's response is streaming in.
+    fn on_progress(&self, progress: Option<&ProgressInfo>, response: &str, elapsed: Duration);
+
+    /// Called before each chunk after the first starts translating.
+    fn on_preparing(&self, progress: Option<&ProgressInfo>);
+
+    /// Called once all chunks of a text have been translated.
+    fn on_finish(&self);
+}
+
+/// Progress sink that renders a single updating line on stdout.
+///
+/// Colors follow the console's `NO_COLOR` and TTY detection, and nothing is
+/// drawn when stdout isn't a terminal, so logs captured from cron don't fill
+/// up with status line redraws.
+#[derive(Debug)]
+pub struct TerminalProgress {
+    console: Console,
+    interactive: bool,
+}
+
+impl TerminalProgress {
+    /// Creates a sink that draws only when stdout is a terminal.
+    pub fn new() -> Self {
+        Self {
+            console: Console::new(),
+            interactive: io::stdout().is_terminal(),
+        }
+    }
+
+    /// Replaces the status line with `line`.
+    fn draw(&self, line: &str) {
+        if self.interactive {
+            print!("\r\x1b[2K{}", line);
+            let _ = io::stdout().flush();
+        }
+    }
+
+    /// The styled "[Chapter 3, Chunk 2/5, ETA 1m20s] " prefix, if any.
+    fn prefix(&self, progress: Option<&ProgressInfo>) -> String {
+        progress
+            .map(|info| {
+                let prefix = self
+                    .console
+                    .style(&progress_prefix(info), &[Style::Cyan, Style::Bold]);
+                format!("{} ", prefix)
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Default for TerminalProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressSink for TerminalProgress {
+    fn on_progress(&self, progress: Option<&ProgressInfo>, response: &str, elapsed: Duration) {
+        if !self.interactive {
+            return;
+        }
+        let char_count = response.len();
+        let speed = if elapsed.as_secs_f64() > 0.0 {
+            (char_count as f64 / elapsed.as_secs_f64()) as u32
+        } else {
+            0
+        };
+
+        // Get preview (last 50 chars, newlines replaced with spaces)
+        let preview: String = response
+            .chars()
+            .rev()
+            .take(50)
+            .collect::<String>()
+            .chars()
+            .rev()
+            .map(|c| if c == '\n' { ' ' } else { c })
+            .collect();
+
+        self.draw(&format!(
+            "{}Progress: {} chars at {}. {}",
+            self.prefix(progress),
+            self.console.count(char_count),
+            self.console.speed(speed as f64),
+            self.console
+                .style(&format!("{}...", preview), &[Style::Gray])
+        ));
+    }
+
+    fn on_preparing(&self, progress: Option<&ProgressInfo>) {
+        let message = format!(
+            "Preparing to translate chunk {}/{}",
+            progress.map(|p| p.chunk).unwrap_or(1),
+            progress.map(|p| p.total_chunks).unwrap_or(1)
+        );
+        self.draw(&format!(
+            "{}Progress: {}",
+            self.prefix(progress),
+            self.console.style(&message, &[Style::Yellow, Style::Bold])
+        ));
+    }
+
+    fn on_finish(&self) {
+        self.draw("");
+    }
+}
+
+/// The "[Chapter 3, Chunk 2/5, ETA 1m20s]" prefix of the progress line.
+fn progress_prefix(info: &ProgressInfo) -> String {
+    let label = match info.phase {
+        ProgressPhase::Translate => "Chunk",
+        ProgressPhase::Repair => "Repairing chunk",
+    };
+    let eta = info
+        .eta
+        .map(|eta| format!(", ETA {}", format_duration(eta)))
+        .unwrap_or_default();
+    format!(
+        "[Chapter {}, {} {}/{}{}]",
+        info.chapter, label, info.chunk, info.total_chunks, eta
+    )
+}
+
+/// Progress sink that discards all updates.
+#[derive(Debug, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn on_progress(&self, _progress: Option<&ProgressInfo>, _response: &str, _elapsed: Duration) {}
+
+    fn on_preparing(&self, _progress: Option<&ProgressInfo>) {}
+
+    fn on_finish(&self) {}
 }
 
 /// Translator for converting Japanese text to English.
@@ -104,8 +629,160 @@ pub struct Translator {
     title_prompt: String,
     /// System prompt for content translation.
     content_prompt: String,
+    /// System prompt for stat blocks sent on their own.
+    stat_block_prompt: String,
+    /// Sent along when retrying a refused chunk (empty for none).
+    refusal_nudge: String,
+    /// How the refusal nudge is sent.
+    refusal_nudge_role: NudgeRole,
     /// Console for output.
     console: Console,
+    /// Destination for streaming progress updates.
+    progress: Arc<dyn ProgressSink>,
+    /// Token checked between and during requests.
+    cancellation: CancellationToken,
+    /// Optional cache of previous chunk translations.
+    cache: Option<Arc<dyn ResponseCache>>,
+    /// Tokens used since the last [`Translator::take_usage`].
+    usage: Mutex<TokenUsage>,
+    /// Tokens used over the translator's lifetime.
+    total_usage: Mutex<TokenUsage>,
+    /// Request log, if `translation.debug_log` is set.
+    debug_log: Option<DebugLog>,
+    /// Spaces request starts with other clients on the same API key.
+    api_limiter: Option<ApiLimiter>,
+}
+
+/// Builder for [`Translator`] with optional customization.
+///
+/// ```no_run
+/// use tsundoku::config::{ApiConfig, TranslationConfig};
+/// use tsundoku::translator::{NoProgress, Translator};
+/// use std::sync::Arc;
+///
+/// let translator = Translator::builder(ApiConfig::default(), TranslationConfig::default())
+///     .content_prompt("Translate into English.")
+///     .progress_sink(Arc::new(NoProgress))
+///     .build();
+/// ```
+pub struct TranslatorBuilder {
+    api_config: ApiConfig,
+    translation_config: TranslationConfig,
+    title_prompt: Option<String>,
+    content_prompt: Option<String>,
+    stat_block_prompt: Option<String>,
+    refusal_nudge: Option<(String, NudgeRole)>,
+    client: Option<Client>,
+    console: Option<Console>,
+    progress: Option<Arc<dyn ProgressSink>>,
+    cancellation: Option<CancellationToken>,
+    cache: Option<Arc<dyn ResponseCache>>,
+    api_limiter: Option<ApiLimiter>,
+}
+
+impl TranslatorBuilder {
+    /// Sets the system prompt used for title translation.
+    pub fn title_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.title_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Sets the system prompt used for content translation.
+    pub fn content_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.content_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Sets the system prompt used for stat blocks when
+    /// `translation.stat_blocks` is "translate".
+    pub fn stat_block_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.stat_block_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Sets the nudge sent along when retrying a refused chunk (empty to
+    /// retry as-is).
+    pub fn refusal_nudge(mut self, nudge: impl Into<String>, role: NudgeRole) -> Self {
+        self.refusal_nudge = Some((nudge.into(), role));
+        self
+    }
+
+    /// Uses a custom HTTP client (e.g. with a proxy or custom timeouts).
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Uses a custom console for status messages.
+    pub fn console(mut self, console: Console) -> Self {
+        self.console = Some(console);
+        self
+    }
+
+    /// Sends streaming progress to the given sink instead of the terminal.
+    pub fn progress_sink(mut self, sink: Arc<dyn ProgressSink>) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    /// Allows the translation to be cancelled from another task.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Answers repeated chunks from the given cache.
+    pub fn cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Spaces requests together with other clients given the same limiter
+    /// on the same API key (see [`crate::api_limiter`]).
+    pub fn api_limiter(mut self, limiter: ApiLimiter) -> Self {
+        self.api_limiter = Some(limiter);
+        self
+    }
+
+    /// Builds the translator.
+    ///
+    /// A debug log that can't be opened is reported and left out.
+    pub fn build(self) -> Translator {
+        let prompts = PromptsConfig::default();
+        let refusal_nudge = self
+            .refusal_nudge
+            .unwrap_or((prompts.refusal_nudge, prompts.refusal_nudge_role));
+        let console = self.console.unwrap_or_default();
+        let debug_log = self.translation_config.debug_log.as_ref().and_then(|path| {
+            DebugLog::open(path, &self.api_config.key)
+                .inspect_err(|e| {
+                    console.warning(&format!("Can't open debug log {}: {}", path.display(), e))
+                })
+                .ok()
+        });
+        Translator {
+            client: self.client.unwrap_or_default(),
+            api_config: self.api_config,
+            translation_config: self.translation_config,
+            title_prompt: self.title_prompt.unwrap_or(prompts.title_translation),
+            content_prompt: self.content_prompt.unwrap_or(prompts.content_translation),
+            stat_block_prompt: self
+                .stat_block_prompt
+                .unwrap_or(prompts.stat_block_translation),
+            refusal_nudge: refusal_nudge.0,
+            refusal_nudge_role: refusal_nudge.1,
+            console,
+            progress: self
+                .progress
+                .unwrap_or_else(|| Arc::new(TerminalProgress::new())),
+            cancellation: self.cancellation.unwrap_or_default(),
+            cache: self.cache,
+            usage: Mutex::new(TokenUsage::default()),
+            total_usage: Mutex::new(TokenUsage::default()),
+            debug_log,
+            api_limiter: self.api_limiter,
+        }
+    }
 }
 
 impl Translator {
@@ -116,13 +793,89 @@ impl Translator {
         title_prompt: String,
         content_prompt: String,
     ) -> Self {
-        Self {
-            client: Client::new(),
+        Self::builder(api_config, translation_config)
+            .title_prompt(title_prompt)
+            .content_prompt(content_prompt)
+            .build()
+    }
+
+    /// Model content requests are sent to.
+    pub fn model(&self) -> &str {
+        self.request_model(RequestKind::Content)
+    }
+
+    /// Model for a kind of request (`translation.title_model` or
+    /// `translation.content_model`, falling back to `api.model`).
+    fn request_model(&self, kind: RequestKind) -> &str {
+        let model = match kind {
+            RequestKind::Title => &self.translation_config.title_model,
+            RequestKind::Content => &self.translation_config.content_model,
+        };
+        model.as_deref().unwrap_or(&self.api_config.model)
+    }
+
+    /// Sampling temperature for a kind of request, if configured.
+    fn request_temperature(&self, kind: RequestKind) -> Option<f64> {
+        match kind {
+            RequestKind::Title => self.translation_config.title_temperature,
+            RequestKind::Content => self.translation_config.content_temperature,
+        }
+    }
+
+    /// System prompt used for content translation.
+    pub fn content_prompt(&self) -> &str {
+        &self.content_prompt
+    }
+
+    /// Content prompt with the name list, if any, appended.
+    fn content_system_prompt(&self, name_list: Option<&str>) -> String {
+        match name_list {
+            Some(names) => format!("{}\n\n{}", self.content_prompt.trim_end(), names),
+            None => self.content_prompt.clone(),
+        }
+    }
+
+    /// Returns the tokens used since the last call and resets the count.
+    pub fn take_usage(&self) -> TokenUsage {
+        std::mem::take(&mut *self.usage.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Returns the tokens used since the translator was created.
+    pub fn total_usage(&self) -> TokenUsage {
+        *self.total_usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record_usage(&self, usage: Option<TokenUsage>) {
+        if let Some(usage) = usage {
+            self.usage
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .add(usage);
+            self.total_usage
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .add(usage);
+        }
+    }
+
+    /// Start building a Translator with optional customization.
+    pub fn builder(
+        api_config: ApiConfig,
+        translation_config: TranslationConfig,
+    ) -> TranslatorBuilder {
+        TranslatorBuilder {
             api_config,
             translation_config,
-            title_prompt,
-            content_prompt,
-            console: Console::new(),
+            title_prompt: None,
+            content_prompt: None,
+            stat_block_prompt: None,
+            refusal_nudge: None,
+            client: None,
+            console: None,
+            progress: None,
+            cancellation: None,
+            cache: None,
+            api_limiter: None,
         }
     }
 
@@ -162,162 +915,690 @@ impl Translator {
                 content: self.title_prompt.clone(),
             }];
 
-            self.translate_single_chunk(text, &mut history, None).await
+            self.translate_single_chunk(
+                text,
+                &mut history,
+                RequestKind::Title,
+                None,
+                false,
+                &mut |_| {},
+            )
+            .await
         } else {
-            // Content translation: chunk and translate with history
-            let chunks = self.split_text_into_chunks(text);
-            let total_chunks = chunks.len() as u32;
-            let mut results = Vec::new();
+            let chunks = self
+                .translate_content(text, None, None, progress_info, &mut |_| {})
+                .await?;
+            Ok(join_chunks(&chunks))
+        }
+    }
+
+    /// Translate content and return each chunk's source alongside its translation.
+    ///
+    /// `name_list`, if given, is appended to the content prompt, and
+    /// `lead_in`, a source/translation pair such as the end of the previous
+    /// chapter, opens the history. Joining the translations with
+    /// [`join_chunks`] gives the same text as [`translate`](Self::translate)
+    /// without either.
+    pub async fn translate_chunked(
+        &self,
+        text: &str,
+        name_list: Option<&str>,
+        lead_in: Option<(&str, &str)>,
+        progress_info: Option<ProgressInfo>,
+    ) -> Result<Vec<TranslatedChunk>, TranslationError> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        self.translate_content(text, name_list, lead_in, progress_info, &mut |_| {})
+            .await
+    }
+
+    /// Translate only the chunks of `text` at `indices`, numbered as in
+    /// [`split_text_into_chunks`](Self::split_text_into_chunks), for
+    /// repairing chunks that failed earlier.
+    ///
+    /// Each chunk starts from a fresh history, since the translations
+    /// around it aren't re-sent. Indices past the last chunk are skipped.
+    pub async fn translate_chunks_at(
+        &self,
+        text: &str,
+        indices: &[usize],
+        name_list: Option<&str>,
+        progress_info: Option<ProgressInfo>,
+    ) -> Result<Vec<(usize, TranslatedChunk)>, TranslationError> {
+        let chunks = self.content_chunks(text);
+        let started = Instant::now();
+        let total_chars: usize = indices
+            .iter()
+            .filter_map(|&i| chunks.get(i))
+            .map(|c| c.chunk.text.chars().count())
+            .sum();
+        let mut chars_done = 0;
+        let mut results = Vec::new();
+        for &index in indices {
+            let Some(chunk) = chunks.get(index) else {
+                continue;
+            };
+            self.cancellation.check()?;
+            let progress = progress_info
+                .as_ref()
+                .map(|p| p.at_chunk(index + 1, chunks.len(), chars_done, total_chars, started));
+            chars_done += chunk.chunk.text.chars().count();
             let mut history = vec![Message {
                 role: "system".to_string(),
-                content: self.content_prompt.clone(),
+                content: self.content_system_prompt(name_list),
             }];
+            let result = self
+                .translate_with_retries(
+                    chunk,
+                    &mut history,
+                    index,
+                    chunks.len(),
+                    progress,
+                    &mut |_| {},
+                )
+                .await?;
+            results.push((index, result));
+        }
+        self.progress.on_finish();
+        Ok(results)
+    }
+
+    /// Translate content, yielding streamed text as it arrives.
+    ///
+    /// The returned stream emits [`TranslationEvent::Delta`] pieces while each
+    /// chunk streams in, [`TranslationEvent::Retrying`] if a chunk must be
+    /// redone (discard its deltas), and [`TranslationEvent::ChunkDone`] with the
+    /// final text of each chunk. A fatal error ends the stream with `Err`.
+    ///
+    /// Concatenating the `ChunkDone` texts with blank lines yields the same
+    /// result as [`translate`](Self::translate).
+    pub fn translate_stream<'a>(
+        &'a self,
+        text: &'a str,
+        progress_info: Option<ProgressInfo>,
+    ) -> impl Stream<Item = Result<TranslationEvent, TranslationError>> + Send + 'a {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+
+        let work = async move {
+            if text.trim().is_empty() {
+                return;
+            }
+            let events = tx.clone();
+            let result = self
+                .translate_content(text, None, None, progress_info, &mut |event| {
+                    let _ = events.unbounded_send(Ok(event));
+                })
+                .await;
+            if let Err(e) = result {
+                let _ = tx.unbounded_send(Err(e));
+            }
+        };
+
+        // Drive the translation alongside the receiver; the channel closes
+        // once the work future finishes and drops its senders.
+        let driver = futures::stream::once(work).filter_map(|()| async { None });
+        futures::stream::select(rx, driver)
+    }
+
+    /// Chunk and translate content with history, reporting events as it goes.
+    async fn translate_content(
+        &self,
+        text: &str,
+        name_list: Option<&str>,
+        lead_in: Option<(&str, &str)>,
+        progress_info: Option<ProgressInfo>,
+        on_event: &mut (dyn FnMut(TranslationEvent) + Send),
+    ) -> Result<Vec<TranslatedChunk>, TranslationError> {
+        let chunks = self.content_chunks(text);
+        let started = Instant::now();
+        let total_chars: usize = chunks.iter().map(|c| c.chunk.text.chars().count()).sum();
+        let mut chars_done = 0;
+        let mut results = Vec::new();
+        let mut history = vec![Message {
+            role: "system".to_string(),
+            content: self.content_system_prompt(name_list),
+        }];
+        if let Some((source, translated)) = lead_in {
+            self.push_history(&mut history, source, translated);
+        }
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            self.cancellation.check()?;
+            let progress = progress_info
+                .as_ref()
+                .map(|p| p.at_chunk(i + 1, chunks.len(), chars_done, total_chars, started));
+            chars_done += chunk.chunk.text.chars().count();
+
+            // Show "Preparing..." status before starting chunk (except first)
+            if i > 0 {
+                self.progress.on_preparing(progress.as_ref());
+            }
+
+            let result = self
+                .translate_with_retries(chunk, &mut history, i, chunks.len(), progress, on_event)
+                .await?;
+            results.push(result);
+        }
+
+        // Clear progress line after all chunks complete
+        self.progress.on_finish();
+
+        Ok(results)
+    }
 
-            for (i, chunk) in chunks.iter().enumerate() {
-                let chunk_num = (i + 1) as u32;
-                let progress = progress_info.as_ref().map(|p| ProgressInfo {
-                    chapter: p.chapter,
-                    chunk: chunk_num,
+    /// Translates one chunk of content, retrying failures with exponential
+    /// backoff. A chunk that fails every retry comes back marked as failed,
+    /// with the failure marker and its source as the translation and the
+    /// error (usually [`TranslationError::RetriesExhausted`]) alongside;
+    /// only errors that should end the run are returned as `Err`.
+    async fn translate_with_retries(
+        &self,
+        chunk: &ContentChunk,
+        history: &mut Vec<Message>,
+        chunk_index: usize,
+        total_chunks: usize,
+        progress: Option<ProgressInfo>,
+        on_event: &mut (dyn FnMut(TranslationEvent) + Send),
+    ) -> Result<TranslatedChunk, TranslationError> {
+        let (chunk, stat_block) = (&chunk.chunk, chunk.stat_block);
+        if stat_block {
+            return self
+                .translate_stat_block(&chunk.text, chunk_index, total_chunks, progress, on_event)
+                .await;
+        }
+
+        // The overlap goes ahead of the chunk as lines of its own, so its
+        // translation can be dropped by line count
+        let request = if chunk.overlap.is_empty() {
+            chunk.text.clone()
+        } else {
+            format!("{}\n{}", chunk.overlap, chunk.text)
+        };
+        let error = match self
+            .retry_chunk(&request, history, chunk_index, progress, on_event)
+            .await
+        {
+            Ok(translated) => {
+                let translated = strip_overlap(&translated, &chunk.overlap);
+                on_event(TranslationEvent::ChunkDone {
+                    chunk_index,
                     total_chunks,
+                    text: translated.clone(),
+                });
+                return Ok(TranslatedChunk {
+                    source: chunk.text.clone(),
+                    translated,
+                    failed: false,
+                    error: None,
                 });
+            }
+            Err(e) if e.is_auth() || matches!(e, TranslationError::Cancelled) => {
+                // Bad credentials won't fix themselves; abort the run
+                return Err(e);
+            }
+            Err(e) => e,
+        };
 
-                // Show "Preparing..." status before starting chunk (except first)
-                if i > 0 {
-                    self.display_preparing(progress.as_ref());
-                }
+        self.console
+            .error(&format!("Translation failed: {}", error));
+        let failed = format!("{}\n{}", FAILED_CHUNK_MARKER, chunk.text);
+        on_event(TranslationEvent::ChunkDone {
+            chunk_index,
+            total_chunks,
+            text: failed.clone(),
+        });
+        Ok(TranslatedChunk {
+            source: chunk.text.clone(),
+            translated: failed,
+            failed: true,
+            error: Some(Arc::new(error)),
+        })
+    }
 
-                // Retry loop for this chunk
-                let mut attempt = 0;
-                let mut last_error: Option<TranslationError> = None;
+    /// Translates a stat block apart from the prose history, or passes it
+    /// through with `stat_blocks = "keep"`. A translation that doesn't keep
+    /// the block's lines, or that fails every retry, leaves the block as is.
+    async fn translate_stat_block(
+        &self,
+        block: &str,
+        chunk_index: usize,
+        total_chunks: usize,
+        progress: Option<ProgressInfo>,
+        on_event: &mut (dyn FnMut(TranslationEvent) + Send),
+    ) -> Result<TranslatedChunk, TranslationError> {
+        let translated = if self.translation_config.stat_blocks == StatBlockMode::Keep {
+            block.to_string()
+        } else {
+            let mut history = vec![Message {
+                role: "system".to_string(),
+                content: self.stat_block_prompt.clone(),
+            }];
+            match self
+                .retry_chunk(block, &mut history, chunk_index, progress, on_event)
+                .await
+            {
+                Ok(translated) => restore_lines(block, &translated).unwrap_or_else(|| {
+                    self.console
+                        .warning("Stat block translation changed its lines; keeping the original");
+                    block.to_string()
+                }),
+                Err(e) if e.is_auth() || matches!(e, TranslationError::Cancelled) => {
+                    return Err(e);
+                }
+                Err(e) => {
+                    self.console.warning(&format!(
+                        "Stat block translation failed ({}); keeping the original",
+                        e
+                    ));
+                    block.to_string()
+                }
+            }
+        };
 
-                while attempt < self.translation_config.retries {
-                    let translation_result = self
-                        .translate_single_chunk(chunk, &mut history, progress.clone())
-                        .await;
+        on_event(TranslationEvent::ChunkDone {
+            chunk_index,
+            total_chunks,
+            text: translated.clone(),
+        });
+        Ok(TranslatedChunk {
+            source: block.to_string(),
+            translated,
+            failed: false,
+            error: None,
+        })
+    }
 
-                    match translation_result {
-                        Ok(translated) => {
-                            results.push(translated);
-                            last_error = None;
-                            break;
-                        }
-                        Err(e) => {
-                            last_error = Some(e);
-                            attempt += 1;
-                            if attempt < self.translation_config.retries {
-                                // Exponential backoff
-                                let delay = Duration::from_secs(2u64.pow(attempt));
-                                self.console.warning(&format!(
-                                    "Translation failed, retrying in {:?} (attempt {}/{})",
-                                    delay,
-                                    attempt + 1,
-                                    self.translation_config.retries
-                                ));
-                                tokio::time::sleep(delay).await;
-                            }
-                        }
-                    }
-                }
+    /// Requests a chunk's translation until it succeeds or its retries for
+    /// the kind of error run out, which returns
+    /// [`TranslationError::RetriesExhausted`] wrapping the last error. Errors
+    /// not worth retrying are returned as they are.
+    async fn retry_chunk(
+        &self,
+        chunk: &str,
+        history: &mut Vec<Message>,
+        chunk_index: usize,
+        progress: Option<ProgressInfo>,
+        on_event: &mut (dyn FnMut(TranslationEvent) + Send),
+    ) -> Result<String, TranslationError> {
+        let policy = &self.translation_config.retry;
+        let mut failures: HashMap<RetryKind, u32> = HashMap::new();
+        let mut attempt = 0;
+        let mut nudge = false;
 
-                if let Some(e) = last_error {
-                    // All retries exhausted, include failure marker
-                    self.console
-                        .error(&format!("Translation failed after all retries: {}", e));
-                    results.push(format!("[TRANSLATION FAILED]\n{}", chunk));
-                }
-            }
+        loop {
+            let e = match self
+                .translate_single_chunk(
+                    chunk,
+                    history,
+                    RequestKind::Content,
+                    progress.clone(),
+                    nudge,
+                    &mut |delta| {
+                        on_event(TranslationEvent::Delta {
+                            chunk_index,
+                            text: delta.to_string(),
+                        })
+                    },
+                )
+                .await
+            {
+                Ok(translated) => return Ok(translated),
+                Err(e) => e,
+            };
 
-            // Clear progress line after all chunks complete
-            print!("\r\x1b[2K");
-            let _ = io::stdout().flush();
+            attempt += 1;
+            let Some(kind) = e.retry_kind() else {
+                return Err(e);
+            };
+            let failed = failures.entry(kind).or_default();
+            *failed += 1;
+            if *failed >= policy.attempts(kind, self.translation_config.retries) {
+                return Err(TranslationError::RetriesExhausted {
+                    attempts: attempt,
+                    last_error: Box::new(e),
+                });
+            }
 
-            Ok(results.join("\n\n"))
+            // Refusals get a nudge; everything else is retried as-is
+            nudge = kind == RetryKind::Refusal;
+            let delay = policy.backoff(attempt);
+            self.console.warning(&format!(
+                "Translation failed ({} error), retrying in {:.1}s (attempt {})",
+                kind,
+                delay.as_secs_f64(),
+                attempt + 1
+            ));
+            on_event(TranslationEvent::Retrying {
+                chunk_index,
+                attempt: attempt + 1,
+            });
+            tokio::time::sleep(delay).await;
         }
     }
 
     /// Split text into chunks that fit within the configured size limit.
-    fn split_text_into_chunks(&self, text: &str) -> Vec<String> {
-        let chunk_size = self.translation_config.chunk_size_chars;
-
-        // Phase 1: Line-based chunking
-        let chunks = crate::utils::split_text_into_line_chunks(text, chunk_size);
-
-        // Phase 2: Word-based splitting for oversized chunks
-        let mut final_chunks: Vec<String> = Vec::new();
+    ///
+    /// These are the same chunks [`translate`](Self::translate) sends to the API.
+    pub fn split_text_into_chunks(&self, text: &str) -> Vec<String> {
+        self.content_chunks(text)
+            .into_iter()
+            .map(|c| c.chunk.text)
+            .collect()
+    }
 
-        for chunk in chunks {
-            if chunk.len() <= chunk_size {
-                final_chunks.push(chunk);
-            } else {
-                // Split by whitespace (for Japanese, this mainly handles mixed content)
-                let words: Vec<&str> = chunk.split_whitespace().collect();
-                let mut current_chunk: Vec<&str> = Vec::new();
-                let mut current_size: usize = 0;
-
-                for word in words {
-                    let word_size = word.len() + if current_chunk.is_empty() { 0 } else { 1 };
-
-                    if current_size + word_size > chunk_size && !current_chunk.is_empty() {
-                        final_chunks.push(current_chunk.join(" "));
-                        current_chunk = vec![word];
-                        current_size = word.len();
-                    } else {
-                        current_chunk.push(word);
-                        current_size += word_size;
-                    }
-                }
+    /// The chunks of [`split_text_into_chunks`](Self::split_text_into_chunks),
+    /// each with the end of the one before it if `chunk_overlap` is set.
+    ///
+    /// Unless `stat_blocks` is off, each stat block is a chunk of its own
+    /// (however long) and the prose between blocks is chunked separately.
+    fn content_chunks(&self, text: &str) -> Vec<ContentChunk> {
+        let chunker = Chunker::new(
+            self.translation_config.chunk_size_chars,
+            self.translation_config.chunk_size_unit,
+        )
+        .with_overlap(self.translation_config.chunk_overlap);
+        let prose = |text: &str| {
+            chunker.chunks(text).into_iter().map(|chunk| ContentChunk {
+                chunk,
+                stat_block: false,
+            })
+        };
 
-                if !current_chunk.is_empty() {
-                    final_chunks.push(current_chunk.join(" "));
-                }
+        if self.translation_config.stat_blocks == StatBlockMode::Off {
+            return prose(text).collect();
+        }
+        let mut chunks = Vec::new();
+        for segment in split_stat_blocks(text) {
+            match segment {
+                Segment::Prose(text) => chunks.extend(prose(&text)),
+                Segment::StatBlock(text) => chunks.push(ContentChunk {
+                    chunk: Chunk {
+                        overlap: String::new(),
+                        text,
+                    },
+                    stat_block: true,
+                }),
             }
         }
-
-        final_chunks
+        chunks
     }
 
-    /// Translate a single chunk of text.
+    /// Translate a single chunk of text, with the refusal nudge if `nudge`
+    /// is set.
     async fn translate_single_chunk(
         &self,
         chunk: &str,
         history: &mut Vec<Message>,
+        kind: RequestKind,
         progress_info: Option<ProgressInfo>,
+        nudge: bool,
+        on_delta: &mut (dyn FnMut(&str) + Send),
     ) -> Result<String, TranslationError> {
+        self.cancellation.check()?;
+        let model = self.request_model(kind);
+
+        let system_prompt = history
+            .first()
+            .map(|m| m.content.clone())
+            .unwrap_or_default();
+        if let Some(cached) = self
+            .cache
+            .as_ref()
+            .and_then(|c| c.get(model, &system_prompt, chunk))
+        {
+            self.push_history(history, chunk, &cached);
+            return Ok(cached);
+        }
+
         // Add user message to history for this request
         let mut messages = history.clone();
+        let refusal_nudge = self.refusal_nudge.trim();
+        let system_nudge =
+            self.refusal_nudge_role == NudgeRole::System && self.api_config.supports_system_role;
+        let content = if !nudge || refusal_nudge.is_empty() {
+            chunk.to_string()
+        } else if system_nudge {
+            messages.push(Message {
+                role: "system".to_string(),
+                content: refusal_nudge.to_string(),
+            });
+            chunk.to_string()
+        } else {
+            format!("{}\n\n{}", refusal_nudge, chunk)
+        };
         messages.push(Message {
             role: "user".to_string(),
-            content: chunk.to_string(),
+            content,
         });
 
+        let mut full_response = String::new();
+        let start_time = Instant::now();
+        let mut finish_reason = self
+            .stream_completion(
+                messages.clone(),
+                kind,
+                progress_info.as_ref(),
+                start_time,
+                &mut full_response,
+                on_delta,
+            )
+            .await?;
+
+        // The model hit its output limit mid-chunk; ask it to pick up where it stopped
+        let mut continuations = 0;
+        while finish_reason.as_deref() == Some("length") {
+            if continuations >= self.translation_config.max_continuations {
+                self.console.warning(&format!(
+                    "Translation still truncated after {} continuation(s); output may be incomplete",
+                    continuations
+                ));
+                break;
+            }
+            continuations += 1;
+
+            let mut continuation = messages.clone();
+            continuation.push(Message {
+                role: "assistant".to_string(),
+                content: full_response.clone(),
+            });
+            continuation.push(Message {
+                role: "user".to_string(),
+                content: CONTINUE_PROMPT.to_string(),
+            });
+
+            finish_reason = self
+                .stream_completion(
+                    continuation,
+                    kind,
+                    progress_info.as_ref(),
+                    start_time,
+                    &mut full_response,
+                    on_delta,
+                )
+                .await?;
+        }
+
+        // Note: Progress line is NOT cleared here to maintain continuity.
+        // The next chunk's "Preparing..." message will replace it, or
+        // the caller will clear it when all chunks are done.
+
+        // Validate response
+        let trimmed = full_response.trim().to_string();
+
+        if trimmed.is_empty() {
+            return Err(TranslationError::Refused("Empty response".to_string()));
+        }
+
+        // Check for refusal phrases (short responses never reach the streaming check)
+        if let Some(phrase) = detect_refusal(&trimmed) {
+            return Err(TranslationError::Refused(format!(
+                "Response starts with refusal phrase: {}",
+                phrase
+            )));
+        }
+
+        if let Some(cache) = &self.cache {
+            cache.put(model, &system_prompt, chunk, &trimmed);
+        }
+
+        self.push_history(history, chunk, &trimmed);
+
+        // Delay before next request
+        let delay = self.translation_config.request_delay_sec();
+        if delay > 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(delay)).await;
+        }
+
+        Ok(trimmed)
+    }
+
+    /// Send a chat request, appending the streamed text to `full_response`.
+    ///
+    /// Returns the `finish_reason` reported by the API, if any.
+    async fn stream_completion(
+        &self,
+        messages: Vec<Message>,
+        kind: RequestKind,
+        progress_info: Option<&ProgressInfo>,
+        start_time: Instant,
+        full_response: &mut String,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<Option<String>, TranslationError> {
+        let messages = if self.api_config.supports_system_role {
+            messages
+        } else {
+            fold_system_prompt(messages)
+        };
+
         // Build request
         let request = ChatRequest {
-            model: self.api_config.model.clone(),
+            model: self.request_model(kind).to_string(),
             messages,
-            stream: true,
+            stream: self.api_config.supports_streaming,
+            temperature: self.request_temperature(kind),
         };
+        let url = self.api_config.endpoint_url("chat/completions");
+
+        let mut info = ResponseInfo::default();
+        let Some(debug_log) = &self.debug_log else {
+            return self
+                .send_completion(
+                    &url,
+                    &request,
+                    progress_info,
+                    start_time,
+                    full_response,
+                    on_delta,
+                    &mut info,
+                )
+                .await;
+        };
+
+        let mut entry = RequestEntry::new(
+            &url,
+            &request.model,
+            request.stream,
+            request.temperature,
+            &request.messages,
+        );
+        let sent = Instant::now();
+        let response_start = full_response.len();
+        let result = self
+            .send_completion(
+                &url,
+                &request,
+                progress_info,
+                start_time,
+                full_response,
+                on_delta,
+                &mut info,
+            )
+            .await;
+
+        entry.status = info.status;
+        entry.duration_ms = sent.elapsed().as_millis() as u64;
+        entry.usage = info.usage;
+        entry.unparsed_lines = info.unparsed_lines;
+        entry.set_response(&full_response[response_start..]);
+        match &result {
+            Ok(finish_reason) => entry.finish_reason = finish_reason.clone(),
+            Err(e) => entry.error = Some(e.to_string()),
+        }
+        if let Err(e) = debug_log.record(&entry).await {
+            self.console
+                .warning(&format!("Failed to write debug log: {}", e));
+        }
+        result
+    }
+
+    /// Sends a built chat request and reads its response, noting what the
+    /// debug log needs in `info`.
+    #[allow(clippy::too_many_arguments)]
+    async fn send_completion(
+        &self,
+        url: &str,
+        request: &ChatRequest,
+        progress_info: Option<&ProgressInfo>,
+        start_time: Instant,
+        full_response: &mut String,
+        on_delta: &mut (dyn FnMut(&str) + Send),
+        info: &mut ResponseInfo,
+    ) -> Result<Option<String>, TranslationError> {
+        if let Some(limiter) = &self.api_limiter {
+            limiter
+                .pace(
+                    &self.api_config,
+                    self.translation_config.delay_between_requests_sec,
+                )
+                .await;
+        }
 
         // Make streaming request
-        let url = format!("{}/chat/completions", self.api_config.base_url);
+        let body = self
+            .api_config
+            .request_body(request)
+            .map_err(|e| TranslationError::ParseError(format!("Failed to build request: {}", e)))?;
+
         let response = self
             .client
-            .post(&url)
+            .post(url)
             .header("Authorization", format!("Bearer {}", self.api_config.key))
             .header("Content-Type", "application/json")
-            .json(&request)
+            .json(&body)
             .send()
             .await?;
+        info.status = Some(response.status().as_u16());
 
         let response = crate::utils::check_response_status(response).await?;
 
+        if !self.api_config.supports_streaming {
+            let body: ChatResponse = response.json().await.map_err(|e| {
+                TranslationError::ParseError(format!("Failed to parse API response: {}", e))
+            })?;
+            info.usage = body.usage;
+            self.record_usage(body.usage);
+            let choice = body.choices.into_iter().next().ok_or_else(|| {
+                TranslationError::ParseError("No choices in API response".to_string())
+            })?;
+            let content = choice.message.and_then(|m| m.content).unwrap_or_default();
+            full_response.push_str(&content);
+            on_delta(&content);
+            self.progress
+                .on_progress(progress_info, full_response, start_time.elapsed());
+            return Ok(choice.finish_reason);
+        }
+
         // Stream and accumulate response
-        let mut full_response = String::new();
-        let start_time = Instant::now();
         let mut last_update = Instant::now();
+        let mut finish_reason = None;
 
         let mut stream = response.bytes_stream();
+        // Continuations already passed the opening of the response
+        let mut refusal_checked = refusal_decidable(full_response);
 
         loop {
+            self.cancellation.check()?;
             let chunk_result = stream.next().await;
             let Some(chunk_result) = chunk_result else {
                 break;
@@ -333,141 +1614,94 @@ impl Translator {
                     }
 
                     // Try to parse as JSON
-                    if let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) {
-                        for choice in chunk.choices {
-                            if let Some(delta) = choice.delta
-                                && let Some(content) = delta.content
-                            {
-                                full_response.push_str(&content);
-
-                                // Update progress display every second
-                                if last_update.elapsed() >= Duration::from_secs(1) {
-                                    self.display_progress(
-                                        &full_response,
-                                        start_time.elapsed(),
-                                        progress_info.as_ref(),
-                                    );
-                                    last_update = Instant::now();
+                    let Ok(chunk) = serde_json::from_str::<StreamChunk>(data) else {
+                        info.unparsed_lines += 1;
+                        continue;
+                    };
+                    if chunk.usage.is_some() {
+                        info.usage = chunk.usage;
+                    }
+                    self.record_usage(chunk.usage);
+                    for choice in chunk.choices {
+                        if choice.finish_reason.is_some() {
+                            finish_reason = choice.finish_reason;
+                        }
+                        if let Some(delta) = choice.delta
+                            && let Some(content) = delta.content
+                        {
+                            full_response.push_str(&content);
+                            on_delta(&content);
+
+                            // Abort as soon as the opening reveals a refusal;
+                            // dropping the stream closes the connection.
+                            if !refusal_checked && refusal_decidable(full_response) {
+                                refusal_checked = true;
+                                if let Some(phrase) = detect_refusal(full_response) {
+                                    return Err(TranslationError::Refused(format!(
+                                        "Response starts with refusal phrase: {}",
+                                        phrase
+                                    )));
                                 }
                             }
+
+                            // Update progress display every second
+                            if last_update.elapsed() >= Duration::from_secs(1) {
+                                self.progress.on_progress(
+                                    progress_info,
+                                    full_response,
+                                    start_time.elapsed(),
+                                );
+                                last_update = Instant::now();
+                            }
                         }
                     }
                 }
             }
         }
 
-        // Note: Progress line is NOT cleared here to maintain continuity.
-        // The next chunk's "Preparing..." message will replace it, or
-        // the caller will clear it when all chunks are done.
-
-        // Validate response
-        let trimmed = full_response.trim().to_string();
-
-        if trimmed.is_empty() {
-            return Err(TranslationError::Refused("Empty response".to_string()));
-        }
-
-        // Check for refusal phrases
-        let lower = trimmed.to_lowercase();
-        for phrase in REFUSAL_PHRASES.iter() {
-            if lower.starts_with(phrase) {
-                return Err(TranslationError::Refused(format!(
-                    "Response starts with refusal phrase: {}",
-                    phrase
-                )));
-            }
-        }
+        Ok(finish_reason)
+    }
 
-        // Update history
+    /// Record a completed exchange and trim history to the configured length.
+    fn push_history(&self, history: &mut Vec<Message>, chunk: &str, translated: &str) {
         history.push(Message {
             role: "user".to_string(),
             content: chunk.to_string(),
         });
         history.push(Message {
             role: "assistant".to_string(),
-            content: trimmed.clone(),
+            content: translated.to_string(),
         });
 
-        // Trim history to configured length (keep system message + last N pairs)
-        let max_messages = 1 + (self.translation_config.history_length * 2);
-        if history.len() > max_messages {
-            // Keep system message (index 0) and last N pairs
-            let remove_count = history.len() - max_messages;
-            history.drain(1..1 + remove_count);
-        }
-
-        // Delay before next request
-        if self.translation_config.delay_between_requests_sec > 0.0 {
-            tokio::time::sleep(Duration::from_secs_f64(
-                self.translation_config.delay_between_requests_sec,
-            ))
-            .await;
-        }
-
-        Ok(trimmed)
-    }
-
-    /// Display progress during streaming.
-    fn display_progress(
-        &self,
-        response: &str,
-        elapsed: Duration,
-        progress_info: Option<&ProgressInfo>,
-    ) {
-        let char_count = response.len();
-        let speed = if elapsed.as_secs_f64() > 0.0 {
-            (char_count as f64 / elapsed.as_secs_f64()) as u32
-        } else {
-            0
-        };
-
-        // Get preview (last 50 chars, newlines replaced with spaces)
-        let preview: String = response
-            .chars()
-            .rev()
-            .take(50)
-            .collect::<String>()
-            .chars()
-            .rev()
-            .map(|c| if c == '\n' { ' ' } else { c })
-            .collect();
-
-        // Build progress line
-        let progress_prefix = if let Some(info) = progress_info {
-            format!(
-                "\x1b[1;36m[Chapter {}, Chunk {}/{}]\x1b[0m ",
-                info.chapter, info.chunk, info.total_chunks
-            )
-        } else {
-            String::new()
-        };
-
-        print!(
-            "\r\x1b[2K{}Progress: \x1b[1;32m{}\x1b[0m chars at \x1b[1;33m{}/sec\x1b[0m. \x1b[90m{}...\x1b[0m",
-            progress_prefix, char_count, speed, preview
+        trim_history(
+            history,
+            self.translation_config.history_length,
+            self.translation_config.history_max_tokens,
         );
-        let _ = io::stdout().flush();
     }
+}
 
-    /// Display "Preparing..." status between chunks.
-    fn display_preparing(&self, progress_info: Option<&ProgressInfo>) {
-        let progress_prefix = if let Some(info) = progress_info {
-            format!(
-                "\x1b[1;36m[Chapter {}, Chunk {}/{}]\x1b[0m ",
-                info.chapter, info.chunk, info.total_chunks
-            )
-        } else {
-            String::new()
-        };
-
-        print!(
-            "\r\x1b[2K{}Progress: \x1b[1;33mPreparing to translate chunk {}/{}\x1b[0m",
-            progress_prefix,
-            progress_info.map(|p| p.chunk).unwrap_or(1),
-            progress_info.map(|p| p.total_chunks).unwrap_or(1)
-        );
-        let _ = io::stdout().flush();
+/// Trims history to the system message plus the most recent pairs, at most
+/// `max_pairs` of them and only as many as fit in `max_tokens` (estimated;
+/// 0 for no limit).
+fn trim_history(history: &mut Vec<Message>, max_pairs: usize, max_tokens: usize) {
+    let budget = if max_tokens == 0 {
+        usize::MAX
+    } else {
+        max_tokens.saturating_sub(history.first().map_or(0, |m| estimate_tokens(&m.content)))
+    };
+    let mut used = 0;
+    let mut kept_pairs = 0;
+    for pair in history[1..].rchunks(2).take(max_pairs) {
+        let tokens: usize = pair.iter().map(|m| estimate_tokens(&m.content)).sum();
+        if used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        kept_pairs += 1;
     }
+    let remove_count = history.len() - 1 - kept_pairs * 2;
+    history.drain(1..1 + remove_count);
 }
 
 /// Translate text without a persistent Translator instance (convenience function).
@@ -502,6 +1736,278 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn test_builder_uses_cache() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put("gpt-4o-mini", "Translate this content", "テスト", "Test");
+
+        let translator = Translator::builder(ApiConfig::default(), TranslationConfig::default())
+            .content_prompt("Translate this content")
+            .progress_sink(Arc::new(NoProgress))
+            .cache(cache)
+            .build();
+
+        // A cache hit must not touch the network
+        let result = translator.translate("テスト", false, None).await.unwrap();
+        assert_eq!(result, "Test");
+    }
+
+    #[tokio::test]
+    async fn test_name_list_in_content_prompt() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content\n\n- 田中 → Tanaka",
+            "テスト",
+            "Test",
+        );
+
+        let translator = Translator::builder(ApiConfig::default(), TranslationConfig::default())
+            .content_prompt("Translate this content\n")
+            .progress_sink(Arc::new(NoProgress))
+            .cache(cache)
+            .build();
+
+        // Only a cache hit under the extended prompt avoids the network
+        let chunks = translator
+            .translate_chunked("テスト", Some("- 田中 → Tanaka"), None, None)
+            .await
+            .unwrap();
+        assert_eq!(join_chunks(&chunks), "Test");
+        assert_eq!(translator.content_prompt(), "Translate this content\n");
+    }
+
+    #[tokio::test]
+    async fn test_translate_stream_emits_chunks() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content",
+            "一行目",
+            "Line one",
+        );
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content",
+            "二行目",
+            "Line two",
+        );
+
+        let config = TranslationConfig {
+            chunk_size_chars: 4,
+            ..Default::default()
+        };
+        let translator = Translator::builder(ApiConfig::default(), config)
+            .content_prompt("Translate this content")
+            .progress_sink(Arc::new(NoProgress))
+            .cache(cache)
+            .build();
+
+        let events: Vec<_> = translator
+            .translate_stream("一行目\n二行目", None)
+            .collect()
+            .await;
+
+        let done: Vec<(usize, usize, String)> = events
+            .into_iter()
+            .filter_map(|e| match e.unwrap() {
+                TranslationEvent::ChunkDone {
+                    chunk_index,
+                    total_chunks,
+                    text,
+                } => Some((chunk_index, total_chunks, text)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            done,
+            vec![
+                (0, 2, "Line one".to_string()),
+                (1, 2, "Line two".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stat_blocks_sent_on_their_own() {
+        let block = "━━━━\n名前：カイト\nHP：120/120";
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content",
+            "確認した。",
+            "He checked.",
+        );
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this block",
+            block,
+            "━━━━\nName: Kaito\nHP: 120/120",
+        );
+        let text = format!("確認した。\n{}", block);
+
+        let translate = |stat_blocks| {
+            let config = TranslationConfig {
+                stat_blocks,
+                ..Default::default()
+            };
+            Translator::builder(ApiConfig::default(), config)
+                .content_prompt("Translate this content")
+                .stat_block_prompt("Translate this block")
+                .progress_sink(Arc::new(NoProgress))
+                .cache(cache.clone())
+                .build()
+        };
+
+        let translator = translate(StatBlockMode::Translate);
+        assert_eq!(translator.split_text_into_chunks(&text).len(), 2);
+        let result = translator.translate(&text, false, None).await.unwrap();
+        assert_eq!(result, "He checked.\n\n━━━━\nName: Kaito\nHP: 120/120");
+
+        let translator = translate(StatBlockMode::Keep);
+        let result = translator.translate(&text, false, None).await.unwrap();
+        assert_eq!(result, format!("He checked.\n\n{}", block));
+    }
+
+    #[tokio::test]
+    async fn test_chunk_overlap_translation_dropped() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content",
+            "一行目",
+            "Line one",
+        );
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content",
+            "一行目\n二行目",
+            "Line one\n\nLine two",
+        );
+
+        let config = TranslationConfig {
+            chunk_size_chars: 4,
+            chunk_overlap: 4,
+            ..Default::default()
+        };
+        let translator = Translator::builder(ApiConfig::default(), config)
+            .content_prompt("Translate this content")
+            .progress_sink(Arc::new(NoProgress))
+            .cache(cache)
+            .build();
+
+        let chunks = translator
+            .translate_chunked("一行目\n二行目", None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(chunks[1].source, "二行目");
+        assert_eq!(join_chunks(&chunks), "Line one\n\nLine two");
+    }
+
+    #[test]
+    fn test_strip_overlap() {
+        assert_eq!(strip_overlap("A\nB\nC", ""), "A\nB\nC");
+        assert_eq!(strip_overlap("A\n\nB\nC", "一\n二"), "C");
+        // Merged lines leave nothing to drop safely
+        assert_eq!(strip_overlap("A B", "一"), "A B");
+    }
+
+    #[tokio::test]
+    async fn test_translate_chunks_at() {
+        let cache = Arc::new(crate::cache::MemoryCache::new());
+        cache.put(
+            "gpt-4o-mini",
+            "Translate this content",
+            "二行目",
+            "Line two",
+        );
+
+        let config = TranslationConfig {
+            chunk_size_chars: 4,
+            ..Default::default()
+        };
+        let translator = Translator::builder(ApiConfig::default(), config)
+            .content_prompt("Translate this content")
+            .progress_sink(Arc::new(NoProgress))
+            .cache(cache)
+            .build();
+
+        // Only the second chunk is sent; an index past the end is skipped
+        let results = translator
+            .translate_chunks_at("一行目\n二行目", &[1, 5], None, None)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 1);
+        assert_eq!(results[0].1.source, "二行目");
+        assert_eq!(results[0].1.translated, "Line two");
+        assert!(!results[0].1.failed);
+    }
+
+    #[tokio::test]
+    async fn test_failed_chunk_reports_retries_exhausted() {
+        // Nothing listens on port 1, so every attempt fails to connect
+        let api = ApiConfig {
+            base_url: "http://127.0.0.1:1/v1".to_string(),
+            ..Default::default()
+        };
+        let config = TranslationConfig {
+            retries: 1,
+            ..Default::default()
+        };
+        let translator = Translator::builder(api, config)
+            .progress_sink(Arc::new(NoProgress))
+            .build();
+
+        let chunks = translator
+            .translate_chunked("テスト", None, None, None)
+            .await
+            .unwrap();
+        assert!(chunks[0].failed);
+        assert_eq!(chunks[0].translated, "[TRANSLATION FAILED]\nテスト");
+        let error = chunks[0].error.as_deref().unwrap();
+        assert!(
+            matches!(
+                error,
+                TranslationError::RetriesExhausted { attempts: 1, last_error }
+                    if last_error.retry_kind() == Some(RetryKind::Network)
+            ),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_last_paragraphs() {
+        let text = "One.\n\nTwo.  \n\n\nThree.\n";
+        assert_eq!(last_paragraphs(text, 2), "Two.\nThree.");
+        assert_eq!(last_paragraphs(text, 10), "One.\nTwo.\nThree.");
+        assert_eq!(last_paragraphs(text, 0), "");
+    }
+
+    #[tokio::test]
+    async fn test_builder_cancellation() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let translator = Translator::builder(ApiConfig::default(), TranslationConfig::default())
+            .progress_sink(Arc::new(NoProgress))
+            .cancellation(token)
+            .build();
+
+        let result = translator.translate("テスト", false, None).await;
+        assert!(matches!(result, Err(TranslationError::Cancelled)));
+    }
+
+    #[test]
+    fn test_builder_default_prompts() {
+        let translator =
+            Translator::builder(ApiConfig::default(), TranslationConfig::default()).build();
+        let prompts = PromptsConfig::default();
+        assert_eq!(translator.title_prompt, prompts.title_translation);
+        assert_eq!(translator.content_prompt, prompts.content_translation);
+    }
+
     #[test]
     fn test_split_text_simple() {
         let translator = make_translator();
@@ -530,6 +2036,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_long_line_at_sentences() {
+        let config = TranslationConfig {
+            chunk_size_chars: 15,
+            ..Default::default()
+        };
+        let translator =
+            Translator::new(ApiConfig::default(), config, String::new(), String::new());
+
+        // No two of these sentences fit together
+        let text = "彼は静かに扉を開けた。「誰かいるの？」返事はなかった。";
+        let chunks = translator.split_text_into_chunks(text);
+        assert_eq!(
+            chunks,
+            vec![
+                "彼は静かに扉を開けた。",
+                "「誰かいるの？」",
+                "返事はなかった。"
+            ]
+        );
+
+        // Without terminators, whitespace still splits the line
+        let text = "word ".repeat(20);
+        let chunks = translator.split_text_into_chunks(&text);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.len() <= 15));
+    }
+
     #[test]
     fn test_refusal_detection() {
         let phrases = vec![
@@ -539,9 +2073,11 @@ mod tests {
         ];
 
         for phrase in phrases {
-            let lower = phrase.to_lowercase();
-            let is_refusal = REFUSAL_PHRASES.iter().any(|p| lower.starts_with(p));
-            assert!(is_refusal, "Should detect refusal: {}", phrase);
+            assert!(
+                detect_refusal(phrase).is_some(),
+                "Should detect refusal: {}",
+                phrase
+            );
         }
     }
 
@@ -554,10 +2090,107 @@ mod tests {
         ];
 
         for phrase in phrases {
-            let lower = phrase.to_lowercase();
-            let is_refusal = REFUSAL_PHRASES.iter().any(|p| lower.starts_with(p));
-            assert!(!is_refusal, "Should not detect refusal: {}", phrase);
+            assert!(
+                detect_refusal(phrase).is_none(),
+                "Should not detect refusal: {}",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_chunk_finish_reason() {
+        let data = r#"{"choices":[{"index":0,"delta":{},"finish_reason":"length"}]}"#;
+        let chunk: StreamChunk = serde_json::from_str(data).unwrap();
+        assert_eq!(chunk.choices[0].finish_reason.as_deref(), Some("length"));
+
+        let data = r#"{"choices":[{"index":0,"delta":{"content":"Hi"}}]}"#;
+        let chunk: StreamChunk = serde_json::from_str(data).unwrap();
+        assert!(chunk.choices[0].finish_reason.is_none());
+        assert!(chunk.usage.is_none());
+    }
+
+    #[test]
+    fn test_parse_model_ids() {
+        let body = r#"{"object":"list","data":[{"id":"gpt-4o","object":"model"},{"id":"gpt-4o-mini"},{"id":"gpt-4o"}]}"#;
+        assert_eq!(
+            parse_model_ids(body).unwrap(),
+            vec!["gpt-4o", "gpt-4o-mini"]
+        );
+        assert!(parse_model_ids(r#"{"object":"list"}"#).unwrap().is_empty());
+        assert!(parse_model_ids("<html>").is_err());
+    }
+
+    #[test]
+    fn test_stream_chunk_usage() {
+        let data = r#"{"choices":[],"usage":{"prompt_tokens":120,"completion_tokens":80,"total_tokens":200}}"#;
+        let chunk: StreamChunk = serde_json::from_str(data).unwrap();
+
+        let translator = make_translator();
+        translator.record_usage(chunk.usage);
+        translator.record_usage(chunk.usage);
+        let usage = translator.take_usage();
+        assert_eq!(usage.prompt_tokens, 240);
+        assert_eq!(usage.total_tokens, 400);
+        assert_eq!(translator.take_usage(), TokenUsage::default());
+        assert_eq!(translator.total_usage().total_tokens, 400);
+    }
+
+    #[test]
+    fn test_fold_system_prompt() {
+        let messages = vec![
+            Message {
+                role: "system".to_string(),
+                content: "Translate".to_string(),
+            },
+            Message {
+                role: "user".to_string(),
+                content: "テスト".to_string(),
+            },
+        ];
+        let folded = fold_system_prompt(messages);
+        assert_eq!(folded.len(), 1);
+        assert_eq!(folded[0].role, "user");
+        assert_eq!(folded[0].content, "Translate\n\nテスト");
+    }
+
+    #[test]
+    fn test_refusal_decidable() {
+        assert!(!refusal_decidable("  I'm"));
+        assert!(refusal_decidable("I'm sorry, but I can't translate this."));
+        assert!(refusal_decidable(&"あ".repeat(10)));
+    }
+
+    #[test]
+    fn test_trim_history() {
+        let message = |role: &str, content: &str| Message {
+            role: role.to_string(),
+            content: content.to_string(),
+        };
+        let mut history = vec![message("system", "Translate")];
+        for source in ["あ", "いいいい", "うう"] {
+            history.push(message("user", source));
+            history.push(message("assistant", "abcd"));
         }
+        let contents = |history: &[Message]| -> Vec<String> {
+            history.iter().map(|m| m.content.clone()).collect()
+        };
+
+        let mut by_count = history.clone();
+        trim_history(&mut by_count, 2, 0);
+        assert_eq!(
+            contents(&by_count),
+            vec!["Translate", "いいいい", "abcd", "うう", "abcd"]
+        );
+
+        // System prompt is 3 tokens; the newest pair is 3 and the next 5
+        let mut by_tokens = history.clone();
+        trim_history(&mut by_tokens, 5, 9);
+        assert_eq!(contents(&by_tokens), vec!["Translate", "うう", "abcd"]);
+
+        let mut none_fit = history;
+        trim_history(&mut none_fit, 5, 3);
+        assert_eq!(contents(&none_fit), vec!["Translate"]);
     }
 
     #[test]
@@ -574,15 +2207,33 @@ mod tests {
 
     #[test]
     fn test_progress_info() {
-        let info = ProgressInfo {
-            chapter: 1,
-            chunk: 2,
-            total_chunks: 5,
+        let base = ProgressInfo {
+            chapter: 3,
+            ..Default::default()
         };
+        let started = Instant::now() - Duration::from_secs(10);
+
+        let first = base.at_chunk(1, 4, 0, 1000, started);
+        assert_eq!((first.chapter, first.chunk, first.total_chunks), (3, 1, 4));
+        assert_eq!(first.eta, None);
+
+        // A quarter done in 10 seconds leaves about 30
+        let second = base.at_chunk(2, 4, 250, 1000, started);
+        let eta = second.eta.unwrap().as_secs_f64();
+        assert!((29.0..32.0).contains(&eta), "{}", eta);
+
+        let json = serde_json::to_value(&second).unwrap();
+        assert_eq!(json["phase"], "translate");
+        assert_eq!(json["chars_done"], 250);
+        assert!(json["elapsed"].as_f64().unwrap() >= 10.0);
+        assert!(progress_prefix(&second).contains("[Chapter 3, Chunk 2/4, ETA 3"));
+    }
 
-        assert_eq!(info.chapter, 1);
-        assert_eq!(info.chunk, 2);
-        assert_eq!(info.total_chunks, 5);
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3m05s");
+        assert_eq!(format_duration(Duration::from_secs(3720)), "1h02m");
     }
 
     #[test]
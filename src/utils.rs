@@ -1,33 +1,244 @@
 //! Utility functions for common operations.
 
+use crate::config::ChunkSizeUnit;
 use crate::error::TranslationError;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use unicode_segmentation::UnicodeSegmentation;
 
-/// Splits text into chunks by lines, respecting a maximum chunk size.
+/// Longest file or folder name most filesystems accept, in bytes.
+pub const MAX_FILENAME_BYTES: usize = 255;
+
+/// Characters of non-Japanese text per estimated token.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Characters that can't appear in filenames on some platform.
+const INVALID_FILENAME_CHARS: [char; 8] = ['\\', '/', '*', '?', '"', '<', '>', '|'];
+
+/// Names Windows reserves for devices, with or without an extension.
+const RESERVED_FILENAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// A cheap, cloneable flag used to cancel long-running translation or scout work.
 ///
-/// This function splits text into chunks where each chunk is at most `chunk_size`
-/// characters. It prefers to split on line boundaries to maintain context.
+/// Cancelling is cooperative: work checks the token between requests and while
+/// streaming, and stops with [`TranslationError::Cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new, uncancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signals cancellation to every clone of this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true once [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns `Err(TranslationError::Cancelled)` if cancelled.
+    pub fn check(&self) -> Result<(), TranslationError> {
+        if self.is_cancelled() {
+            Err(TranslationError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// How a [`Chunker`] handles a line too long for one chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkStrategy {
+    /// Keep it whole, as a chunk over the limit.
+    Lines,
+    /// Split it at sentence ends, or at whitespace for a sentence that is
+    /// still too long, so no chunk ends mid-sentence.
+    #[default]
+    Sentences,
+}
+
+/// A chunk of text, with the end of the chunk before it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Chunk {
+    /// Closing lines of the previous chunk, repeated for context (empty for
+    /// the first chunk or without overlap).
+    pub overlap: String,
+    /// The chunk's own text.
+    pub text: String,
+}
+
+/// Splits text into chunks of at most a maximum size, breaking between lines
+/// where it can.
 ///
-/// # Arguments
-/// * `text` - The text to split
-/// * `chunk_size` - Maximum size of each chunk in characters
+/// Sizes count [`ChunkSizeUnit`]s (characters, bytes or estimated tokens).
+/// Translation, name scouting and the stats estimate all chunk through this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chunker {
+    max_size: usize,
+    unit: ChunkSizeUnit,
+    strategy: ChunkStrategy,
+    overlap: usize,
+}
+
+impl Chunker {
+    /// Creates a chunker for chunks of at most `max_size` `unit`s, splitting
+    /// long lines at sentences and without overlap.
+    pub fn new(max_size: usize, unit: ChunkSizeUnit) -> Self {
+        Self {
+            max_size,
+            unit,
+            strategy: ChunkStrategy::default(),
+            overlap: 0,
+        }
+    }
+
+    /// Sets how lines too long for one chunk are handled.
+    pub fn with_strategy(mut self, strategy: ChunkStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets how much of each chunk's end (in the chunker's unit) is repeated
+    /// as the next chunk's [`overlap`](Chunk::overlap).
+    pub fn with_overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Splits text into chunk texts.
+    pub fn split(&self, text: &str) -> Vec<String> {
+        let chunks = split_lines(text, self.max_size, self.unit);
+        if self.strategy == ChunkStrategy::Lines {
+            return chunks;
+        }
+
+        let mut final_chunks: Vec<String> = Vec::new();
+        for chunk in chunks {
+            if text_size(&chunk, self.unit) <= self.max_size {
+                final_chunks.push(chunk);
+                continue;
+            }
+
+            // A single line over the limit
+            let mut current_chunk = String::new();
+            let mut current_size = 0;
+            for sentence in split_sentences(&chunk) {
+                let sentence_size = text_size(sentence, self.unit);
+                if current_size + sentence_size > self.max_size && !current_chunk.is_empty() {
+                    final_chunks.push(std::mem::take(&mut current_chunk).trim_end().to_string());
+                    current_size = 0;
+                }
+                if sentence_size > self.max_size {
+                    // A sentence too long on its own (or text without
+                    // terminators) falls back to splitting on whitespace
+                    final_chunks.extend(split_words(sentence, self.max_size, self.unit));
+                } else {
+                    current_chunk.push_str(sentence);
+                    current_size += sentence_size;
+                }
+            }
+            if !current_chunk.trim().is_empty() {
+                final_chunks.push(current_chunk.trim_end().to_string());
+            }
+        }
+
+        final_chunks
+    }
+
+    /// Splits text into chunks, each with the end of the one before it.
+    pub fn chunks(&self, text: &str) -> Vec<Chunk> {
+        let texts = self.split(text);
+        let mut chunks: Vec<Chunk> = Vec::with_capacity(texts.len());
+        for (i, text) in texts.iter().enumerate() {
+            let overlap = match i.checked_sub(1) {
+                Some(prev) if self.overlap > 0 => self.tail(&texts[prev]),
+                _ => String::new(),
+            };
+            chunks.push(Chunk {
+                overlap,
+                text: text.clone(),
+            });
+        }
+        chunks
+    }
+
+    /// The closing lines of `text` that fit in the overlap, or the closing
+    /// sentences of its last line if that line alone is too long.
+    fn tail(&self, text: &str) -> String {
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        let mut size = 0;
+        let mut start = lines.len();
+        while start > 0 {
+            let line_size = text_size(lines[start - 1], self.unit);
+            if size + line_size > self.overlap {
+                break;
+            }
+            size += line_size;
+            start -= 1;
+        }
+        if start < lines.len() {
+            return lines[start..].join("\n");
+        }
+
+        let Some(last) = lines.last() else {
+            return String::new();
+        };
+        let sentences = split_sentences(last);
+        let mut size = 0;
+        let mut start = sentences.len();
+        while start > 0 {
+            let sentence_size = text_size(sentences[start - 1], self.unit);
+            if size + sentence_size > self.overlap {
+                break;
+            }
+            size += sentence_size;
+            start -= 1;
+        }
+        sentences[start..].concat().trim().to_string()
+    }
+}
+
+/// Splits text into chunks by lines, respecting a maximum chunk size.
 ///
-/// # Returns
-/// A vector of text chunks, each no larger than `chunk_size` (unless a single line
-/// exceeds the limit, in which case that line becomes its own chunk).
-pub fn split_text_into_line_chunks(text: &str, chunk_size: usize) -> Vec<String> {
-    let lines: Vec<&str> = text.lines().collect();
+/// Same as a [`Chunker`] with [`ChunkStrategy::Lines`]: each chunk is at most
+/// `chunk_size` `unit`s, unless a single line exceeds the limit, in which
+/// case that line becomes its own chunk.
+pub fn split_text_into_line_chunks(
+    text: &str,
+    chunk_size: usize,
+    unit: ChunkSizeUnit,
+) -> Vec<String> {
+    Chunker::new(chunk_size, unit)
+        .with_strategy(ChunkStrategy::Lines)
+        .split(text)
+}
+
+/// Groups whole lines into chunks of at most `chunk_size` `unit`s.
+fn split_lines(text: &str, chunk_size: usize, unit: ChunkSizeUnit) -> Vec<String> {
     let mut chunks: Vec<String> = Vec::new();
     let mut current_chunk: Vec<&str> = Vec::new();
     let mut current_size: usize = 0;
 
-    for line in lines {
-        let line_size = line.len() + if current_chunk.is_empty() { 0 } else { 1 };
+    for line in text.lines() {
+        let line_size = text_size(line, unit) + if current_chunk.is_empty() { 0 } else { 1 };
 
         if current_size + line_size > chunk_size && !current_chunk.is_empty() {
             // Push current chunk and start new one
             chunks.push(current_chunk.join("\n"));
             current_chunk = vec![line];
-            current_size = line.len();
+            current_size = text_size(line, unit);
         } else {
             current_chunk.push(line);
             current_size += line_size;
@@ -42,6 +253,103 @@ pub fn split_text_into_line_chunks(text: &str, chunk_size: usize) -> Vec<String>
     chunks
 }
 
+/// Splits text into pieces of at most `chunk_size` `unit`s at whitespace. A
+/// word longer than the limit becomes its own piece.
+fn split_words(text: &str, chunk_size: usize, unit: ChunkSizeUnit) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current_chunk: Vec<&str> = Vec::new();
+    let mut current_size: usize = 0;
+
+    for word in text.split_whitespace() {
+        let word_size = text_size(word, unit) + if current_chunk.is_empty() { 0 } else { 1 };
+
+        if current_size + word_size > chunk_size && !current_chunk.is_empty() {
+            pieces.push(current_chunk.join(" "));
+            current_chunk = vec![word];
+            current_size = text_size(word, unit);
+        } else {
+            current_chunk.push(word);
+            current_size += word_size;
+        }
+    }
+
+    if !current_chunk.is_empty() {
+        pieces.push(current_chunk.join(" "));
+    }
+    pieces
+}
+
+/// Size of `text` in the given unit.
+pub fn text_size(text: &str, unit: ChunkSizeUnit) -> usize {
+    match unit {
+        ChunkSizeUnit::Chars => text.chars().count(),
+        ChunkSizeUnit::Bytes => text.len(),
+        ChunkSizeUnit::Tokens => estimate_tokens(text),
+    }
+}
+
+/// Rough token count without a tokenizer: one per Japanese character, and
+/// one per [`CHARS_PER_TOKEN`] characters of anything else.
+pub fn estimate_tokens(text: &str) -> usize {
+    let (japanese, other) = text.chars().fold((0usize, 0usize), |(japanese, other), c| {
+        if is_japanese_char(c) {
+            (japanese + 1, other)
+        } else {
+            (japanese, other + 1)
+        }
+    });
+    japanese + other.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Characters that end a sentence.
+const SENTENCE_TERMINATORS: [char; 5] = ['。', '！', '？', '!', '?'];
+
+/// Closing brackets and quotes that can follow a sentence's terminator.
+const CLOSING_BRACKETS: [char; 10] = ['」', '』', '）', ')', '】', '〉', '》', '〕', '”', '’'];
+
+/// Opening brackets of quoted speech, which start a new sentence when they
+/// directly follow a closing one (`「…」「…」`).
+const OPENING_QUOTES: [char; 2] = ['「', '『'];
+
+/// Splits text into sentences, so that concatenating the pieces gives back
+/// the text.
+///
+/// A sentence ends after a run of terminators (`。！？`) and any closing
+/// brackets and whitespace after them, or after a closing quote that is
+/// directly followed by another quote or whitespace.
+pub fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        let ends_quote = matches!(c, '」' | '』')
+            && chars
+                .peek()
+                .is_some_and(|&(_, next)| OPENING_QUOTES.contains(&next) || next.is_whitespace());
+        if !SENTENCE_TERMINATORS.contains(&c) && !ends_quote {
+            continue;
+        }
+        while let Some(&(_, next)) = chars.peek() {
+            if SENTENCE_TERMINATORS.contains(&next)
+                || CLOSING_BRACKETS.contains(&next)
+                || next.is_whitespace()
+            {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let end = chars.peek().map_or(text.len(), |&(i, _)| i);
+        sentences.push(&text[start..end]);
+        start = end;
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
 /// Checks if an HTTP response is successful, and if not, returns a detailed error.
 ///
 /// This helper extracts both the status code and response body for better error messages.
@@ -56,29 +364,266 @@ pub async fn check_response_status(
 ) -> Result<reqwest::Response, TranslationError> {
     if !response.status().is_success() {
         let status = response.status();
-        let text = response.text().await.unwrap_or_default();
-        return Err(TranslationError::ApiError(format!(
-            "HTTP {}: {}",
-            status, text
-        )));
+        let body = response.text().await.unwrap_or_default();
+        return Err(TranslationError::HttpStatus { status, body });
     }
     Ok(response)
 }
 
+/// Returns the current time as a Unix timestamp.
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats Unix seconds as a UTC calendar date (`YYYY-MM-DD`).
+pub fn format_unix_date(unix_secs: u64) -> String {
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let days = (unix_secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Formats Unix seconds as a UTC ISO 8601 timestamp (`YYYY-MM-DDTHH:MM:SSZ`).
+pub fn format_unix_timestamp(unix_secs: u64) -> String {
+    let seconds = unix_secs % 86_400;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_unix_date(unix_secs),
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Adds a random jitter between `min_sec` and `max_sec` to a base delay.
+///
+/// A zero or invalid range adds nothing.
+pub fn jittered_delay(base_sec: f64, min_sec: f64, max_sec: f64) -> f64 {
+    if max_sec <= 0.0 || max_sec < min_sec {
+        return base_sec;
+    }
+    let min_sec = min_sec.max(0.0);
+    base_sec + min_sec + fastrand::f64() * (max_sec - min_sec)
+}
+
+/// Makes `name` safe to use as (part of) a file or folder name.
+///
+/// Characters invalid on some platform and control characters become
+/// `replacement` (line breaks and tabs become spaces), trailing dots and
+/// spaces are dropped, Windows device names (CON, NUL, ...) get
+/// `replacement` appended, and the result is cut to at most `max_bytes`
+/// without splitting a character cluster.
+pub fn sanitize_filename(name: &str, replacement: char, max_bytes: usize) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            '\t' | '\n' | '\r' => ' ',
+            c if is_filename_safe(c) => c,
+            _ => replacement,
+        })
+        .collect();
+    let mut sanitized = sanitized.trim_end_matches(['.', ' ']).to_string();
+
+    let stem = sanitized.split('.').next().unwrap_or_default();
+    if RESERVED_FILENAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        sanitized.insert(stem.len(), replacement);
+    }
+
+    if sanitized.len() > max_bytes {
+        let mut end = 0;
+        for (index, grapheme) in sanitized.grapheme_indices(true) {
+            if index + grapheme.len() > max_bytes {
+                break;
+            }
+            end = index + grapheme.len();
+        }
+        sanitized.truncate(end);
+        sanitized.truncate(sanitized.trim_end_matches(['.', ' ']).len());
+    }
+    sanitized
+}
+
+/// Returns `path` in a form Windows doesn't limit to MAX_PATH (260
+/// characters): absolute, with the `\\?\` extended-length prefix.
+/// Elsewhere the path is returned unchanged.
+///
+/// Story folders named after long translated titles, with chapter files
+/// named the same way inside, easily pass 260 characters.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        if let Some(extended) = absolute.to_str().and_then(extended_length_path) {
+            return PathBuf::from(extended);
+        }
+        absolute
+    }
+    #[cfg(not(windows))]
+    {
+        path.to_path_buf()
+    }
+}
+
+/// Joins a `/`-separated path relative to a story folder, as the manifest
+/// records them, onto `base` one component at a time.
+///
+/// A plain `join` would leave the `/` in place, which an extended-length
+/// base from [`long_path`] doesn't treat as a separator.
+pub fn join_relative(base: &Path, relative: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for part in relative.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                path.pop();
+            }
+            part => path.push(part),
+        }
+    }
+    path
+}
+
+/// Adds the extended-length prefix to an absolute Windows path, or None if
+/// it has one already or isn't a drive or UNC path.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn extended_length_path(absolute: &str) -> Option<String> {
+    if absolute.starts_with(r"\\?\") || absolute.starts_with(r"\\.\") {
+        return None;
+    }
+    // Extended-length paths aren't normalized, so only backslashes work
+    let absolute = absolute.replace('/', r"\");
+    if let Some(unc) = absolute.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", unc));
+    }
+    let mut chars = absolute.chars();
+    let is_drive = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.next() == Some(':')
+        && chars.next() == Some('\\');
+    is_drive.then(|| format!(r"\\?\{}", absolute))
+}
+
+/// Returns true if `c` can appear in a filename on every platform.
+pub fn is_filename_safe(c: char) -> bool {
+    !INVALID_FILENAME_CHARS.contains(&c) && !c.is_control()
+}
+
+/// Returns true for hiragana, katakana, and kanji characters.
+pub fn is_japanese_char(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}'   // Hiragana
+        | '\u{30A0}'..='\u{30FF}' // Katakana
+        | '\u{31F0}'..='\u{31FF}' // Katakana phonetic extensions
+        | '\u{3400}'..='\u{4DBF}' // CJK extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+        | '\u{FF66}'..='\u{FF9F}' // Halfwidth katakana
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_jittered_delay() {
+        assert_eq!(jittered_delay(1.0, 0.0, 0.0), 1.0);
+        assert_eq!(jittered_delay(1.0, 2.0, 1.0), 1.0);
+        for _ in 0..100 {
+            let delay = jittered_delay(1.0, 0.5, 2.0);
+            assert!((1.5..=3.0).contains(&delay), "{}", delay);
+        }
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(
+            sanitize_filename("What? A \"cat\"/dog...", '_', 255),
+            "What_ A _cat__dog"
+        );
+        assert_eq!(
+            sanitize_filename("Line one\nLine\u{7}two", '-', 255),
+            "Line one Line-two"
+        );
+        assert_eq!(sanitize_filename("nul", '_', 255), "nul_");
+        assert_eq!(sanitize_filename("Con.txt", '_', 255), "Con_.txt");
+        assert_eq!(sanitize_filename("Console", '_', 255), "Console");
+
+        // Truncation keeps whole characters and clusters
+        let title = "魔王".repeat(100);
+        let cut = sanitize_filename(&title, '_', 255);
+        assert_eq!(cut, "魔王".repeat(42) + "魔");
+        let flags = "🇯🇵".repeat(3);
+        assert_eq!(sanitize_filename(&flags, '_', 10), "🇯🇵");
+        assert_eq!(
+            sanitize_filename("Chapter 1. The End", '_', 11),
+            "Chapter 1"
+        );
+    }
+
+    #[test]
+    fn test_extended_length_path() {
+        assert_eq!(
+            extended_length_path(r"C:\Users\me\Novels").as_deref(),
+            Some(r"\\?\C:\Users\me\Novels")
+        );
+        assert_eq!(
+            extended_length_path("D:/Novels/[syosetu: n1234ab] Title").as_deref(),
+            Some(r"\\?\D:\Novels\[syosetu: n1234ab] Title")
+        );
+        assert_eq!(
+            extended_length_path(r"\\nas\books\Novels").as_deref(),
+            Some(r"\\?\UNC\nas\books\Novels")
+        );
+        assert_eq!(extended_length_path(r"\\?\C:\Novels"), None);
+        assert_eq!(extended_length_path("/home/me/Novels"), None);
+    }
+
+    #[test]
+    fn test_join_relative_nested() {
+        let base = Path::new("novels").join("[syosetu: n1234ab] Title");
+        let joined = join_relative(&base, "Original/001 - Start.txt");
+        // Pushed as separate components, not one name containing `/`
+        let expected = base.join("Original").join("001 - Start.txt");
+        assert_eq!(joined, expected);
+        assert_eq!(joined.components().count(), base.components().count() + 2);
+        assert_eq!(
+            join_relative(&base, "./Annotated//001 - Start.txt"),
+            base.join("Annotated").join("001 - Start.txt")
+        );
+        assert_eq!(
+            join_relative(&base, "../other.txt"),
+            Path::new("novels/other.txt")
+        );
+    }
+
     #[test]
     fn test_split_empty_text() {
-        let chunks = split_text_into_line_chunks("", 100);
+        let chunks = split_text_into_line_chunks("", 100, ChunkSizeUnit::Chars);
         assert_eq!(chunks.len(), 0);
     }
 
     #[test]
     fn test_split_single_line() {
         let text = "Hello world";
-        let chunks = split_text_into_line_chunks(text, 100);
+        let chunks = split_text_into_line_chunks(text, 100, ChunkSizeUnit::Chars);
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0], text);
     }
@@ -86,7 +631,7 @@ mod tests {
     #[test]
     fn test_split_multiple_lines_fits() {
         let text = "Line 1\nLine 2\nLine 3";
-        let chunks = split_text_into_line_chunks(text, 100);
+        let chunks = split_text_into_line_chunks(text, 100, ChunkSizeUnit::Chars);
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0], text);
     }
@@ -94,7 +639,7 @@ mod tests {
     #[test]
     fn test_split_multiple_chunks_needed() {
         let text = "Line 1\nLine 2\nLine 3\nLine 4";
-        let chunks = split_text_into_line_chunks(text, 15);
+        let chunks = split_text_into_line_chunks(text, 15, ChunkSizeUnit::Chars);
         // "Line 1\nLine 2" = 13 chars
         // "Line 3\nLine 4" = 13 chars
         assert_eq!(chunks.len(), 2);
@@ -105,16 +650,151 @@ mod tests {
     #[test]
     fn test_split_single_long_line() {
         let text = "This is a very long line that exceeds the chunk size limit";
-        let chunks = split_text_into_line_chunks(text, 20);
+        let chunks = split_text_into_line_chunks(text, 20, ChunkSizeUnit::Chars);
         // Should keep the whole line as one chunk even though it exceeds limit
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0], text);
     }
 
+    #[test]
+    fn test_split_by_unit() {
+        let text = "吾輩は猫である\n名前はまだ無い";
+        assert_eq!(text_size("吾輩は猫である", ChunkSizeUnit::Chars), 7);
+        assert_eq!(text_size("吾輩は猫である", ChunkSizeUnit::Bytes), 21);
+        assert_eq!(text_size("吾輩は猫 cat", ChunkSizeUnit::Tokens), 5);
+
+        assert_eq!(
+            split_text_into_line_chunks(text, 15, ChunkSizeUnit::Chars),
+            vec![text]
+        );
+        assert_eq!(
+            split_text_into_line_chunks(text, 15, ChunkSizeUnit::Bytes).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_chunker_strategies() {
+        let text = "短い行。\n彼は静かに扉を開けた。「誰かいるの？」返事はなかった。";
+        let chunker = Chunker::new(15, ChunkSizeUnit::Chars);
+        assert_eq!(
+            chunker.split(text),
+            vec![
+                "短い行。",
+                "彼は静かに扉を開けた。",
+                "「誰かいるの？」",
+                "返事はなかった。"
+            ]
+        );
+        assert_eq!(
+            chunker.with_strategy(ChunkStrategy::Lines).split(text),
+            vec![
+                "短い行。",
+                "彼は静かに扉を開けた。「誰かいるの？」返事はなかった。"
+            ]
+        );
+
+        // Without terminators, whitespace still splits the line
+        let text = "word ".repeat(20);
+        let chunks = chunker.split(&text);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.len() <= 15));
+    }
+
+    #[test]
+    fn test_chunker_overlap() {
+        let text = "一行目。\n二行目。\n三行目。\n四行目です。五行目です。";
+        let chunker = Chunker::new(10, ChunkSizeUnit::Chars).with_overlap(5);
+        let chunks = chunker.chunks(text);
+        assert_eq!(
+            chunks,
+            vec![
+                Chunk {
+                    overlap: String::new(),
+                    text: "一行目。\n二行目。".to_string(),
+                },
+                Chunk {
+                    overlap: "二行目。".to_string(),
+                    text: "三行目。".to_string(),
+                },
+                Chunk {
+                    overlap: "三行目。".to_string(),
+                    text: "四行目です。".to_string(),
+                },
+                // Nothing of the previous chunk fits in the overlap
+                Chunk {
+                    overlap: String::new(),
+                    text: "五行目です。".to_string(),
+                },
+            ]
+        );
+
+        // A last line too long for the overlap gives its closing sentences
+        let chunker = Chunker::new(20, ChunkSizeUnit::Chars).with_overlap(6);
+        assert_eq!(
+            chunker.tail("前。\n四行目です。五行目です。"),
+            "五行目です。"
+        );
+
+        let chunks = Chunker::new(10, ChunkSizeUnit::Chars).chunks(text);
+        assert!(chunks.iter().all(|c| c.overlap.is_empty()));
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let text = "「行くぞ！」「待って……」彼は走った。本当に？！　そうだ";
+        assert_eq!(
+            split_sentences(text),
+            vec![
+                "「行くぞ！」",
+                "「待って……」彼は走った。",
+                "本当に？！　",
+                "そうだ"
+            ]
+        );
+        assert_eq!(
+            split_sentences("「そうか」と彼は言った。"),
+            vec!["「そうか」と彼は言った。"]
+        );
+        assert_eq!(
+            split_sentences("He left. She stayed!"),
+            vec!["He left. She stayed!"]
+        );
+        assert!(split_sentences("").is_empty());
+    }
+
+    #[test]
+    fn test_cancellation_token_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(token.check().is_ok());
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert!(matches!(token.check(), Err(TranslationError::Cancelled)));
+    }
+
+    #[test]
+    fn test_format_unix_date() {
+        assert_eq!(format_unix_date(0), "1970-01-01");
+        assert_eq!(format_unix_date(951_782_400), "2000-02-29");
+        assert_eq!(format_unix_date(1_767_225_599), "2025-12-31");
+        assert_eq!(format_unix_timestamp(1_767_225_599), "2025-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_is_japanese_char() {
+        assert!(is_japanese_char('あ'));
+        assert!(is_japanese_char('カ'));
+        assert!(is_japanese_char('漢'));
+        assert!(!is_japanese_char('a'));
+        assert!(!is_japanese_char('「'));
+    }
+
     #[test]
     fn test_split_with_empty_lines() {
         let text = "Line 1\n\nLine 3";
-        let chunks = split_text_into_line_chunks(text, 100);
+        let chunks = split_text_into_line_chunks(text, 100, ChunkSizeUnit::Chars);
         assert_eq!(chunks.len(), 1);
         assert_eq!(chunks[0], text);
     }
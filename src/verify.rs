@@ -0,0 +1,202 @@
+//! Integrity checks for a story folder against its manifest.
+//!
+//! Every original and translation recorded in the manifest must still exist,
+//! be non-empty, and match its recorded hash; translations must also be free
+//! of failed-chunk markers.
+
+use crate::archive::read_story_file;
+use crate::manifest::{ChapterRecord, Manifest, content_hash};
+use crate::translator::FAILED_CHUNK_MARKER;
+use std::fmt;
+use std::path::Path;
+
+/// Kind of integrity problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyKind {
+    /// The recorded file no longer exists.
+    Missing,
+    /// The file exists but couldn't be read as text.
+    Unreadable,
+    /// The file is zero bytes.
+    Empty,
+    /// The content no longer matches the recorded hash.
+    HashMismatch,
+    /// The translation contains a failed-chunk marker.
+    FailureMarker,
+}
+
+impl fmt::Display for VerifyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            VerifyKind::Missing => "missing",
+            VerifyKind::Unreadable => "unreadable",
+            VerifyKind::Empty => "empty",
+            VerifyKind::HashMismatch => "modified or corrupt",
+            VerifyKind::FailureMarker => "contains failed chunks",
+        };
+        f.write_str(label)
+    }
+}
+
+/// One integrity problem.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyIssue {
+    /// Chapter number.
+    pub number: u32,
+    /// File the problem was found in, relative to the story folder.
+    pub file: String,
+    /// What is wrong.
+    pub kind: VerifyKind,
+}
+
+impl fmt::Display for VerifyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "chapter {}: {}: {}", self.number, self.file, self.kind)
+    }
+}
+
+/// Result of verifying a story folder.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    /// Files checked.
+    pub checked: usize,
+    /// Problems found, in chapter order.
+    pub issues: Vec<VerifyIssue>,
+    /// Listed chapters with no downloaded original (excluding removed ones).
+    pub not_downloaded: Vec<u32>,
+}
+
+/// Checks every original and translation recorded in the manifest.
+///
+/// Originals kept in the originals archive are checked there.
+pub fn verify_story(story_dir: &Path, manifest: &Manifest) -> VerifyReport {
+    let mut report = VerifyReport::default();
+
+    for record in &manifest.data().chapters {
+        if !record.downloaded {
+            if !record.removed {
+                report.not_downloaded.push(record.number);
+            }
+            continue;
+        }
+
+        let files = [
+            (
+                false,
+                record.original_file.as_ref(),
+                record.original_hash.as_ref(),
+            ),
+            (
+                true,
+                record
+                    .translated_file
+                    .as_ref()
+                    .filter(|_| record.translated),
+                record.translated_hash.as_ref(),
+            ),
+        ];
+        for (translated, file, hash) in files {
+            let Some(file) = file else { continue };
+            report.checked += 1;
+            if let Some(kind) = check_file(story_dir, file, hash, translated) {
+                report.issues.push(issue(record, file, kind));
+            }
+        }
+    }
+
+    report
+}
+
+/// Checks one recorded file, returning the first problem found.
+fn check_file(
+    story_dir: &Path,
+    relative_path: &str,
+    hash: Option<&String>,
+    translated: bool,
+) -> Option<VerifyKind> {
+    let text = match read_story_file(story_dir, relative_path) {
+        Ok(Some(text)) => text,
+        Ok(None) => return Some(VerifyKind::Missing),
+        Err(_) => return Some(VerifyKind::Unreadable),
+    };
+
+    if text.is_empty() {
+        Some(VerifyKind::Empty)
+    } else if hash.is_some_and(|h| *h != content_hash(&text)) {
+        Some(VerifyKind::HashMismatch)
+    } else if translated && text.contains(FAILED_CHUNK_MARKER) {
+        Some(VerifyKind::FailureMarker)
+    } else {
+        None
+    }
+}
+
+fn issue(record: &ChapterRecord, file: &str, kind: VerifyKind) -> VerifyIssue {
+    VerifyIssue {
+        number: record.number,
+        file: file.to_string(),
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scrapers::ChapterInfo;
+    use tempfile::TempDir;
+
+    fn chapter(number: u32) -> ChapterInfo {
+        ChapterInfo {
+            number,
+            title: format!("Chapter {}", number),
+            url: format!("https://example.com/{}", number),
+            episode_id: number.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_story() {
+        let temp_dir = TempDir::new().unwrap();
+        let story_dir = temp_dir.path();
+        let mut manifest = Manifest::open(story_dir).unwrap();
+        manifest.sync_chapters(&(1..=5).map(chapter).collect::<Vec<_>>());
+
+        let write =
+            |name: &str, content: &str| std::fs::write(story_dir.join(name), content).unwrap();
+        write("1.txt", "本文");
+        write("1-en.txt", "Text");
+        manifest.mark_downloaded("1", "1.txt", "本文");
+        manifest.mark_translated("1", "1-en.txt", "Text");
+
+        write("2.txt", "本文");
+        manifest.mark_downloaded("2", "2.txt", "別の本文");
+
+        write("3.txt", "");
+        manifest.mark_downloaded("3", "3.txt", "");
+        manifest.mark_downloaded("4", "4.txt", "本文");
+
+        write("5.txt", "本文");
+        write("5-en.txt", "[TRANSLATION FAILED]\n本文");
+        manifest.mark_downloaded("5", "5.txt", "本文");
+        manifest.mark_translated("5", "5-en.txt", "[TRANSLATION FAILED]\n本文");
+
+        let report = verify_story(story_dir, &manifest);
+        let found: Vec<(u32, VerifyKind)> =
+            report.issues.iter().map(|i| (i.number, i.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, VerifyKind::HashMismatch),
+                (3, VerifyKind::Empty),
+                (4, VerifyKind::Missing),
+                (5, VerifyKind::FailureMarker),
+            ]
+        );
+        assert_eq!(report.checked, 7);
+        assert!(report.not_downloaded.is_empty());
+        assert_eq!(
+            report.issues[0].to_string(),
+            "chapter 2: 2.txt: modified or corrupt"
+        );
+    }
+}
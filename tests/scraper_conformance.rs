@@ -0,0 +1,305 @@
+//! Scraper conformance tests against stored pages in `tests/fixtures`.
+//!
+//! Run with `cargo test --features conformance`. Each test serves the
+//! site's pages from fixtures and checks what the scraper extracts, so a
+//! layout change shows up here once the fixtures are refreshed from the
+//! live site.
+
+#![cfg(feature = "conformance")]
+
+use std::sync::Arc;
+use tsundoku::config::ScrapingConfig;
+use tsundoku::scrapers::fixtures::Fixtures;
+use tsundoku::scrapers::{ChapterList, KakuyomuScraper, PixivScraper, Scraper, SyosetuScraper};
+
+/// Registers a fixture file under `tests/fixtures` for `url`.
+fn serve(pages: &mut Fixtures, url: &str, fixture: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture);
+    let body = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", path.display(), e));
+    pages.serve(url, body);
+}
+
+/// Titles, URLs and episode IDs of a chapter list.
+fn chapter_summary(list: &ChapterList) -> Vec<(u32, String, String, String)> {
+    let ChapterList::Chapters(chapters) = list else {
+        panic!("Expected a chapter list, got {:?}", list);
+    };
+    chapters
+        .iter()
+        .map(|c| {
+            (
+                c.number,
+                c.title.clone(),
+                c.url.clone(),
+                c.episode_id.clone(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_syosetu_serial() {
+    let mut pages = Fixtures::new();
+    serve(
+        &mut pages,
+        "https://ncode.syosetu.com/n1234ab/",
+        "syosetu/toc_page1.html",
+    );
+    serve(
+        &mut pages,
+        "https://ncode.syosetu.com/n1234ab/?p=2",
+        "syosetu/toc_page2.html",
+    );
+    serve(
+        &mut pages,
+        "https://ncode.syosetu.com/novelview/infotop/ncode/n1234ab/",
+        "syosetu/info.html",
+    );
+    serve(
+        &mut pages,
+        "https://ncode.syosetu.com/n1234ab/1/",
+        "syosetu/episode.html",
+    );
+    let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default())
+        .with_fixtures(Arc::new(pages));
+
+    let info = scraper
+        .get_novel_info("https://ncode.syosetu.com/n1234ab/")
+        .await
+        .unwrap();
+    assert_eq!(info.title, "転生したら図書館の司書だった件");
+    assert_eq!(info.base_url, "https://ncode.syosetu.com/n1234ab/");
+    assert_eq!(info.novel_id, "n1234ab");
+    assert_eq!(
+        info.synopsis,
+        "目が覚めると、そこは本の海だった。\n元書店員の主人公が異世界の図書館で働く物語。"
+    );
+    assert_eq!(info.tags, vec!["異世界転生", "図書館", "スローライフ"]);
+
+    let list = scraper.get_chapter_list(&info.base_url).await.unwrap();
+    let base = "https://ncode.syosetu.com/n1234ab";
+    assert_eq!(
+        chapter_summary(&list),
+        vec![
+            (1, "プロローグ".into(), format!("{}/1/", base), "1".into()),
+            (
+                2,
+                "第一話　本の海".into(),
+                format!("{}/2/", base),
+                "2".into()
+            ),
+            (
+                3,
+                "第二話　司書長".into(),
+                format!("{}/3/", base),
+                "3".into()
+            ),
+        ]
+    );
+
+    // The preface and afterword aren't part of the chapter text
+    let content = scraper
+        .download_chapter("https://ncode.syosetu.com/n1234ab/1/")
+        .await
+        .unwrap();
+    assert_eq!(
+        content,
+        "目が覚めると、そこは本の海だった。\n司書の朝は早い。\n\n「おはようございます」"
+    );
+}
+
+#[tokio::test]
+async fn test_syosetu_oneshot() {
+    let mut pages = Fixtures::new();
+    serve(
+        &mut pages,
+        "https://ncode.syosetu.com/n9999zz/",
+        "syosetu/oneshot.html",
+    );
+    let scraper = SyosetuScraper::new(ScrapingConfig::default(), Arc::default())
+        .with_fixtures(Arc::new(pages));
+
+    let info = scraper
+        .get_novel_info("https://ncode.syosetu.com/n9999zz/")
+        .await
+        .unwrap();
+    assert_eq!(info.title, "雨の日の短編");
+    assert_eq!(info.synopsis, "雨の日の出会いを描いた短編です。");
+    // No info page fixture, so no keywords
+    assert!(info.tags.is_empty());
+
+    let list = scraper.get_chapter_list(&info.base_url).await.unwrap();
+    assert!(list.is_oneshot());
+
+    let content = scraper.download_chapter(&info.base_url).await.unwrap();
+    assert_eq!(
+        content,
+        "雨が降っていた。\n傘を忘れた彼女は、軒下で空を見上げていた。"
+    );
+}
+
+#[tokio::test]
+async fn test_kakuyomu() {
+    let mut pages = Fixtures::new();
+    let work_url = "https://kakuyomu.jp/works/1177354054880000001";
+    serve(&mut pages, work_url, "kakuyomu/work.html");
+    serve(
+        &mut pages,
+        &format!("{}/episodes/1177354054880000101", work_url),
+        "kakuyomu/episode.html",
+    );
+    let scraper = KakuyomuScraper::new(ScrapingConfig::default(), Arc::default())
+        .with_fixtures(Arc::new(pages));
+
+    let info = scraper
+        .get_novel_info(&format!("{}/episodes/1177354054880000102", work_url))
+        .await
+        .unwrap();
+    assert_eq!(info.title, "星降る街の魔導具師");
+    assert_eq!(info.base_url, work_url);
+    assert_eq!(info.novel_id, "1177354054880000001");
+    assert_eq!(
+        info.synopsis,
+        "流れ星を拾った少女が、魔導具師を目指す。\n街の人々との出会いの物語。"
+    );
+    assert_eq!(info.tags, vec!["ファンタジー", "職人", "ほのぼの"]);
+
+    let list = scraper.get_chapter_list(&info.base_url).await.unwrap();
+    assert_eq!(
+        chapter_summary(&list),
+        vec![
+            (
+                1,
+                "第1話 流れ星の夜".into(),
+                format!("{}/episodes/1177354054880000101", work_url),
+                "1177354054880000101".into()
+            ),
+            (
+                2,
+                "第2話 工房の朝".into(),
+                format!("{}/episodes/1177354054880000102", work_url),
+                "1177354054880000102".into()
+            ),
+        ]
+    );
+
+    let content = scraper
+        .download_chapter(&format!("{}/episodes/1177354054880000101", work_url))
+        .await
+        .unwrap();
+    assert_eq!(
+        content,
+        "その夜、空から星が降ってきた。\n少女は魔導具を握りしめた。"
+    );
+}
+
+#[tokio::test]
+async fn test_kakuyomu_oneshot() {
+    let mut pages = Fixtures::new();
+    let work_url = "https://kakuyomu.jp/works/1177354054880000002";
+    serve(&mut pages, work_url, "kakuyomu/oneshot_work.html");
+    serve(
+        &mut pages,
+        &format!("{}/episodes/1177354054880000201", work_url),
+        "kakuyomu/oneshot_episode.html",
+    );
+    let scraper = KakuyomuScraper::new(ScrapingConfig::default(), Arc::default())
+        .with_fixtures(Arc::new(pages));
+
+    let info = scraper.get_novel_info(work_url).await.unwrap();
+    assert_eq!(info.title, "夏の終わりの手紙");
+
+    // A finished single-episode work is downloaded through the work URL
+    let list = scraper.get_chapter_list(&info.base_url).await.unwrap();
+    assert!(list.is_oneshot());
+    let content = scraper.download_chapter(&info.base_url).await.unwrap();
+    assert_eq!(
+        content,
+        "蝉の声が止んだ午後だった。\n押し入れの奥から、色あせた封筒が出てきた。"
+    );
+}
+
+#[tokio::test]
+async fn test_pixiv_series() {
+    let mut pages = Fixtures::new();
+    serve(
+        &mut pages,
+        "https://www.pixiv.net/ajax/novel/series/9876543",
+        "pixiv/series.json",
+    );
+    serve(
+        &mut pages,
+        "https://www.pixiv.net/ajax/novel/series_content/9876543?limit=30&last_order=0&order_by=asc",
+        "pixiv/series_content.json",
+    );
+    serve(
+        &mut pages,
+        "https://www.pixiv.net/ajax/novel/20000001",
+        "pixiv/novel.json",
+    );
+    let scraper = PixivScraper::new(ScrapingConfig::default()).with_fixtures(Arc::new(pages));
+
+    let url = "https://www.pixiv.net/novel/series/9876543";
+    let info = scraper.get_novel_info(url).await.unwrap();
+    assert_eq!(info.title, "月影の騎士団");
+    assert_eq!(info.base_url, url);
+    assert_eq!(info.novel_id, "9876543");
+    assert_eq!(
+        info.synopsis,
+        "騎士団に入った少年の成長譚。\n毎週更新予定。"
+    );
+    assert_eq!(info.tags, vec!["オリジナル", "ファンタジー", "騎士"]);
+
+    // Series chapters come back in content order
+    let list = scraper.get_chapter_list(url).await.unwrap();
+    assert_eq!(
+        chapter_summary(&list),
+        vec![
+            (
+                1,
+                "第一話　入団".into(),
+                "20000001".into(),
+                "20000001".into()
+            ),
+            (
+                2,
+                "第二話　初陣".into(),
+                "20000002".into(),
+                "20000002".into()
+            ),
+        ]
+    );
+
+    let content = scraper.download_chapter("20000001").await.unwrap();
+    assert_eq!(
+        content,
+        "春の朝、少年は王都の門をくぐった。\n\n[chapter:入団試験]\n「次の者、前へ」"
+    );
+
+    // A page without a fixture never reaches the network
+    assert!(scraper.download_chapter("20000002").await.is_err());
+}
+
+#[tokio::test]
+async fn test_pixiv_oneshot() {
+    let mut pages = Fixtures::new();
+    serve(
+        &mut pages,
+        "https://www.pixiv.net/ajax/novel/20000001",
+        "pixiv/novel.json",
+    );
+    let scraper = PixivScraper::new(ScrapingConfig::default()).with_fixtures(Arc::new(pages));
+
+    let url = "https://www.pixiv.net/novel/show.php?id=20000001";
+    let info = scraper.get_novel_info(url).await.unwrap();
+    assert_eq!(info.title, "第一話　入団");
+    assert_eq!(info.novel_id, "20000001");
+    assert_eq!(info.synopsis, "騎士団への入団試験の日。");
+    assert_eq!(info.tags, vec!["オリジナル", "騎士"]);
+
+    let list = scraper.get_chapter_list(url).await.unwrap();
+    assert!(list.is_oneshot());
+}